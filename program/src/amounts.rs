@@ -0,0 +1,185 @@
+//! Unit-safe amount types for presale and supply math.
+//!
+//! On-chain state keeps raw `u64`/`u16` fields so the account layouts stay unchanged;
+//! handlers wrap values in these newtypes at the boundary and only convert between
+//! units through the explicit functions below.
+
+/// Micro-USD per whole USD (prices and contributions use 6 decimals)
+pub const MICRO_USD_PER_USD: u64 = 1_000_000;
+
+/// Basis points in 100%
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Decimals of a micro-USD amount
+pub const MICRO_USD_DECIMALS: u32 = 6;
+
+/// Value of one whole stablecoin: stablecoins are counted at par
+pub const STABLECOIN_PAR: MicroUsd = MicroUsd(MICRO_USD_PER_USD);
+
+/// Largest decimal scale accepted from an oracle (10^38 is the largest power of ten in a u128)
+pub const MAX_DECIMAL_SCALE: u32 = 38;
+
+/// USD amount with 6 decimals precision (1 USD = 1_000_000)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct MicroUsd(pub u64);
+
+/// Token amount in base units of the mint (respecting the mint's decimals)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TokenAmount(pub u64);
+
+/// Ratio in basis points (10_000 = 100%)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Bps(pub u16);
+
+/// Largest integer whose square is at most `value` (rounds down, no floats)
+pub fn integer_sqrt(value: u128) -> u128 {
+    if value < 2 {
+        return value;
+    }
+    // Newton's method from an estimate at or above the root converges downward
+    let mut root = 1u128 << (128 - value.leading_zeros()).div_ceil(2);
+    loop {
+        let next = (root + value / root) / 2;
+        if next >= root {
+            return root;
+        }
+        root = next;
+    }
+}
+
+fn scale(decimals: u8) -> Option<u128> {
+    10u128.checked_pow(decimals as u32)
+}
+
+fn apply_bps(value: u64, bps: Bps) -> Option<u64> {
+    let result = (value as u128)
+        .checked_mul(bps.0 as u128)?
+        .checked_div(BPS_DENOMINATOR as u128)?;
+    u64::try_from(result).ok()
+}
+
+impl MicroUsd {
+    /// Zero dollars
+    pub const ZERO: Self = Self(0);
+
+    /// Add two amounts
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        self.0.checked_add(other.0).map(Self)
+    }
+
+    /// Subtract an amount
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.0.checked_sub(other.0).map(Self)
+    }
+
+    /// Subtract an amount, stopping at zero
+    pub fn saturating_sub(self, other: Self) -> Self {
+        Self(self.0.saturating_sub(other.0))
+    }
+
+    /// Token base units bought with this amount at `price` micro-USD per whole token
+    pub fn to_tokens(self, price: MicroUsd, decimals: u8) -> Option<TokenAmount> {
+        if price.0 == 0 {
+            return None;
+        }
+        let tokens = (self.0 as u128)
+            .checked_mul(scale(decimals)?)?
+            .checked_div(price.0 as u128)?;
+        u64::try_from(tokens).ok().map(TokenAmount)
+    }
+
+    /// Value at par of `amount` base units of a stablecoin with `decimals`
+    pub fn from_stablecoin(amount: u64, decimals: u8) -> Option<Self> {
+        TokenAmount(amount).to_micro_usd(STABLECOIN_PAR, decimals)
+    }
+
+    /// Base units of a stablecoin with `decimals` worth this amount at par, rounded down
+    pub fn to_stablecoin(self, decimals: u8) -> Option<u64> {
+        self.to_tokens(STABLECOIN_PAR, decimals).map(|tokens| tokens.0)
+    }
+
+    /// Split into two halves; the second half carries the odd micro-dollar
+    pub fn split_half(self) -> (Self, Self) {
+        let half = self.0 / 2;
+        (Self(half), Self(self.0 - half))
+    }
+
+    /// Portion of this amount given by `bps`
+    pub fn apply_bps(self, bps: Bps) -> Option<Self> {
+        apply_bps(self.0, bps).map(Self)
+    }
+
+    /// Amount of the decimal `mantissa * 10^-scale` USD, rounded half up to the
+    /// nearest micro-dollar. None for negative values, scales above
+    /// `MAX_DECIMAL_SCALE` and amounts that do not fit a u64.
+    pub fn from_decimal(mantissa: i128, scale: u32) -> Option<Self> {
+        if mantissa < 0 || scale > MAX_DECIMAL_SCALE {
+            return None;
+        }
+        let mantissa = mantissa as u128;
+        let value = if scale <= MICRO_USD_DECIMALS {
+            mantissa.checked_mul(10u128.pow(MICRO_USD_DECIMALS - scale))?
+        } else {
+            let divisor = 10u128.pow(scale - MICRO_USD_DECIMALS);
+            let quotient = mantissa / divisor;
+            let remainder = mantissa % divisor;
+            // Compare against the half without adding to the mantissa, which could overflow
+            if remainder >= divisor - divisor / 2 {
+                quotient.checked_add(1)?
+            } else {
+                quotient
+            }
+        };
+        u64::try_from(value).ok().map(Self)
+    }
+}
+
+impl TokenAmount {
+    /// Zero tokens
+    pub const ZERO: Self = Self(0);
+
+    /// Add two amounts
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        self.0.checked_add(other.0).map(Self)
+    }
+
+    /// Subtract an amount
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.0.checked_sub(other.0).map(Self)
+    }
+
+    /// Portion of this amount given by `bps`
+    pub fn apply_bps(self, bps: Bps) -> Option<Self> {
+        apply_bps(self.0, bps).map(Self)
+    }
+
+    /// Micro-USD value of this amount at `price` micro-USD per whole token
+    pub fn to_micro_usd(self, price: MicroUsd, decimals: u8) -> Option<MicroUsd> {
+        let value = (self.0 as u128)
+            .checked_mul(price.0 as u128)?
+            .checked_div(scale(decimals)?)?;
+        u64::try_from(value).ok().map(MicroUsd)
+    }
+}
+
+impl Bps {
+    /// 100%
+    pub const MAX: Self = Self(BPS_DENOMINATOR as u16);
+
+    /// Signed change from `from` to `to` in basis points (positive for growth)
+    pub fn change_between(from: MicroUsd, to: MicroUsd) -> Option<i64> {
+        if from.0 == 0 {
+            return None;
+        }
+        let diff = (to.0 as i128).checked_sub(from.0 as i128)?;
+        let bps = diff
+            .checked_mul(BPS_DENOMINATOR as i128)?
+            .checked_div(from.0 as i128)?;
+        i64::try_from(bps).ok()
+    }
+
+    /// Basis points as a plain signed value for threshold comparisons
+    pub fn as_i64(self) -> i64 {
+        self.0 as i64
+    }
+}
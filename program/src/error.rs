@@ -244,6 +244,198 @@ pub enum VCoinError {
     /// No consensus between oracles
     #[error("No consensus between oracles")]
     NoOracleConsensus,
+
+    /// Presale can not be finalized yet
+    #[error("Presale has not reached its end time or hard cap")]
+    PresaleNotEnded,
+
+    /// KYC credential required
+    #[error("A valid KYC credential is required for this presale")]
+    KycRequired,
+
+    /// Invalid KYC attestation
+    #[error("Invalid KYC attestation")]
+    InvalidKycAttestation,
+
+    /// Purchase cooldown active
+    #[error("Wallet must wait before purchasing again")]
+    PurchaseCooldownActive,
+
+    /// Launch window cap exceeded
+    #[error("Purchase exceeds the per-wallet cap for the opening window")]
+    LaunchWindowCapExceeded,
+
+    /// Refunds not settled
+    #[error("Presale refunds are not settled yet")]
+    RefundsNotSettled,
+
+    /// Stablecoin limits account missing
+    #[error("The stablecoin limits account must be supplied for this stablecoin")]
+    StablecoinLimitsMissing,
+
+    /// Stablecoin window cap exceeded
+    #[error("Purchase exceeds the rolling volume cap for this stablecoin")]
+    StablecoinVolumeCapExceeded,
+
+    /// Rolling 24h raise cap exceeded
+    #[error("Purchase exceeds the rolling 24h raise cap")]
+    DailyRaiseCapExceeded,
+
+    /// Invalid Merkle proof
+    #[error("Invalid airdrop Merkle proof")]
+    InvalidMerkleProof,
+
+    /// Airdrop already claimed
+    #[error("Airdrop already claimed for this index")]
+    AirdropAlreadyClaimed,
+
+    /// Airdrop exhausted
+    #[error("Claim exceeds the airdrop's remaining total")]
+    AirdropExhausted,
+
+    /// Price bounds not configured
+    #[error("Oracle controller has no price floor/ceiling configured")]
+    PriceBoundsNotConfigured,
+
+    /// Vesting grant revoked
+    #[error("Vesting grant has already been revoked")]
+    VestingGrantRevoked,
+
+    /// Release destination mismatch
+    #[error("Release destination is not the token account bound to the beneficiary")]
+    InvalidReleaseDestination,
+
+    /// Vesting registry full
+    #[error("Vesting registry has reached its pool limit")]
+    VestingPoolLimitReached,
+
+    /// Vesting escrow underfunded
+    #[error("Vesting escrow does not cover the unreleased allocated tokens")]
+    VestingUndercollateralized,
+
+    /// Vesting already paused
+    #[error("Vesting schedule is already paused")]
+    VestingAlreadyPaused,
+
+    /// Vesting not paused
+    #[error("Vesting schedule is not paused")]
+    VestingNotPaused,
+
+    /// Withdrawal not announced
+    #[error("Withdrawal requires a matured NoticeWithdraw for the same stablecoin and destination")]
+    WithdrawNoticeRequired,
+
+    /// Launch prerequisites missing
+    #[error("Launch checklist is incomplete")]
+    LaunchChecklistIncomplete,
+
+    /// Oracle source already added
+    #[error("Oracle source is already configured on this controller")]
+    OracleSourceAlreadyExists,
+
+    /// Stablecoin already supported
+    #[error("Stablecoin is already supported by this presale")]
+    StablecoinAlreadySupported,
+
+    /// Stablecoin limit reached
+    #[error("Maximum number of supported stablecoins reached")]
+    StablecoinLimitReached,
+
+    /// Oracle source not found
+    #[error("Oracle account is not a source of this controller")]
+    OracleSourceNotFound,
+
+    /// Bug-bounty guardian quorum not met
+    #[error("Not enough bug-bounty guardians signed")]
+    BountyGuardianQuorumNotMet,
+
+    /// Bug-bounty escrow underfunded
+    #[error("Bug-bounty escrow does not cover the award on top of committed awards")]
+    BountyInsufficientFunds,
+
+    /// Bug-bounty award already paid
+    #[error("Bug-bounty award has already been paid")]
+    BountyAwardAlreadyPaid,
+
+    /// Oracle feed registry full
+    #[error("Oracle feed registry has reached its feed limit")]
+    ManagedFeedLimitReached,
+
+    /// Oracle feed already registered
+    #[error("Oracle feed registry already has a feed for this asset")]
+    ManagedFeedAlreadyExists,
+
+    /// Blocked by the emergency mode
+    #[error("Instruction is not allowed in the current emergency mode")]
+    BlockedByEmergencyMode,
+
+    /// Stablecoin peg guard account missing
+    #[error("The stablecoin peg guard account must be supplied for this stablecoin")]
+    StablecoinPegGuardMissing,
+
+    /// Stablecoin outside its peg band
+    #[error("Stablecoin is trading outside its peg band")]
+    StablecoinDepegged,
+
+    /// Yield venue still under review
+    #[error("Yield venue does not accept deposits yet")]
+    YieldVenueNotActive,
+
+    /// Yield venue deposit cap exceeded
+    #[error("Deposit exceeds the yield venue cap or the treasury's refund liabilities")]
+    YieldVenueCapExceeded,
+
+    /// Oracle controller source limit reached
+    #[error("Oracle controller already has the maximum number of sources")]
+    OracleSourceLimitReached,
+
+    /// Autonomous mint/burn rate limit reached
+    #[error("Autonomous supply rate limit reached for this window")]
+    SupplyRateLimitExceeded,
+
+    /// Presale account has no room for another buyer
+    #[error("Presale buyer capacity reached; expand the presale account")]
+    PresaleBuyerCapacityReached,
+
+    /// Autonomous mint/burn attempted inside its cooldown
+    #[error("Autonomous supply operation is still cooling down")]
+    SupplyCooldownActive,
+
+    /// Initial allocation table does not split the initial supply
+    #[error("Initial allocations must split the initial supply exactly across distinct escrows")]
+    InvalidInitialAllocation,
+
+    /// Allocation plan is invalid or a draw exceeds a category's share
+    #[error("Allocation plan shares must sum to 100% and draws cannot exceed a category's share")]
+    AllocationPlanExceeded,
+
+    /// Guardians declared a critical failure for the presale
+    #[error("Guardians declared a forced refund; the presale can no longer launch")]
+    ForcedRefundDeclared,
+
+    /// Fee sponsorship has nothing left to reimburse
+    #[error("Fee sponsorship budget is exhausted")]
+    FeeSponsorshipExhausted,
+
+    /// Too few multisig keys signed
+    #[error("Multisig authority did not reach its signature threshold")]
+    MultisigThresholdNotMet,
+
+    /// Authority is a timelock that never queued this call
+    #[error("Operation was not queued on the timelock")]
+    TimelockOperationNotQueued,
+
+    /// Queued call executed before its timelock delay
+    #[error("Timelock delay has not elapsed")]
+    TimelockDelayNotElapsed,
+
+    /// Authority is an access control and no signer holds the required role
+    #[error("No signer holds the role this instruction requires")]
+    MissingRole,
+
+    /// Instruction is kept only so later tags keep their numbers
+    #[error("Instruction is retired")]
+    InstructionRetired,
 }
 
 impl From<VCoinError> for ProgramError {
@@ -0,0 +1,88 @@
+//! Rent for the accounts the program creates, so deployment scripts can fund
+//! payers before sending the initialization instructions.
+//!
+//! Sizes come from the same `get_size` functions the handlers allocate with, so
+//! an estimate priced with the cluster's `Rent` is exactly what the payer is
+//! debited. Token accounts the instructions expect to exist already (escrows,
+//! treasuries) are not included.
+
+use solana_program::rent::Rent;
+
+use crate::state::{
+    AutonomousSupplyController, MultiOracleController, PresaleState, VestingGrant, VestingRegistry,
+    VestingState, MAX_ORACLE_SOURCES, PRESALE_INITIAL_BUYER_CAPACITY,
+    PRESALE_MAX_BUYER_CAPACITY,
+};
+
+/// One account an instruction creates and the rent-exempt balance it is funded with
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccountCost {
+    /// What the account holds
+    pub account: &'static str,
+    /// Number of accounts of this kind
+    pub count: usize,
+    /// Data size of each account in bytes
+    pub size: usize,
+    /// Rent-exempt balance of each account in lamports
+    pub lamports: u64,
+}
+
+/// Accounts a deployment step creates
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CostEstimate {
+    pub accounts: Vec<AccountCost>,
+}
+
+impl CostEstimate {
+    fn with(mut self, rent: &Rent, account: &'static str, count: usize, size: usize) -> Self {
+        if count > 0 {
+            self.accounts.push(AccountCost {
+                account,
+                count,
+                size,
+                lamports: rent.minimum_balance(size),
+            });
+        }
+        self
+    }
+
+    /// Lamports the payer needs for every account in the estimate
+    pub fn total_lamports(&self) -> u64 {
+        self.accounts
+            .iter()
+            .map(|cost| cost.lamports.saturating_mul(cost.count as u64))
+            .fold(0, u64::saturating_add)
+    }
+
+    /// Combine the estimates of several steps paid by the same payer
+    pub fn merge(mut self, other: CostEstimate) -> Self {
+        self.accounts.extend(other.accounts);
+        self
+    }
+}
+
+/// `InitializePresale` followed by the `ExpandPresaleAccount` top-ups needed to
+/// hold `buyer_capacity` buyers (within the initial and maximum capacities)
+pub fn presale(rent: &Rent, buyer_capacity: usize) -> CostEstimate {
+    let buyer_capacity = buyer_capacity.clamp(PRESALE_INITIAL_BUYER_CAPACITY, PRESALE_MAX_BUYER_CAPACITY);
+    CostEstimate::default().with(rent, "presale", 1, PresaleState::get_size_for_buyers(buyer_capacity))
+}
+
+/// `InitializeVesting` for one pool plus `AddVestingBeneficiaries` grants for
+/// `beneficiaries`; the registry is only created with an authority's first pool
+pub fn vesting(rent: &Rent, beneficiaries: usize, first_pool: bool) -> CostEstimate {
+    CostEstimate::default()
+        .with(rent, "vesting registry", usize::from(first_pool), VestingRegistry::get_size())
+        .with(rent, "vesting", 1, VestingState::get_size())
+        .with(rent, "vesting grant", beneficiaries, VestingGrant::get_size())
+}
+
+/// `InitializeAutonomousController`
+pub fn autonomous_controller(rent: &Rent) -> CostEstimate {
+    CostEstimate::default().with(rent, "autonomous supply controller", 1, AutonomousSupplyController::get_size())
+}
+
+/// `InitializeOracleController` or `CreateManagedFeed`'s controller
+pub fn oracle_controller(rent: &Rent) -> CostEstimate {
+    CostEstimate::default().with(rent, "oracle controller", 1, MultiOracleController::get_size(MAX_ORACLE_SOURCES))
+}
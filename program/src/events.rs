@@ -0,0 +1,501 @@
+//! Structured program events.
+//!
+//! Events are Borsh-encoded and written with `sol_log_data`, so monitoring can
+//! decode them from the `Program data:` log lines instead of parsing `msg!` text.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{log::sol_log_data, pubkey::Pubkey};
+
+use crate::state::{BurnKind, CircuitBreakerTrigger, OracleHealthLevel, PurchaseSnapshot, Role, SupplyOperation, SupplyParameters, TimelockedOperation};
+
+/// Events emitted by the VCoin program
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub enum VCoinEvent {
+    /// A locked treasury dropped below the required coverage of outstanding refunds
+    TreasuryAlarmRaised {
+        /// Presale the treasury belongs to
+        presale: Pubkey,
+        /// Stablecoin held by the treasury
+        stablecoin_mint: Pubkey,
+        /// Locked treasury token account
+        treasury: Pubkey,
+        /// Treasury balance when the alarm was raised (micro-USD)
+        balance: u64,
+        /// Refunds still owed from the treasury
+        liabilities: u64,
+        /// Time of the check
+        timestamp: i64,
+    },
+    /// A previously underfunded locked treasury is covered again
+    TreasuryAlarmCleared {
+        /// Presale the treasury belongs to
+        presale: Pubkey,
+        /// Stablecoin held by the treasury
+        stablecoin_mint: Pubkey,
+        /// Locked treasury token account
+        treasury: Pubkey,
+        /// Treasury balance when the alarm was cleared (micro-USD)
+        balance: u64,
+        /// Refunds still owed from the treasury
+        liabilities: u64,
+        /// Time of the check
+        timestamp: i64,
+    },
+    /// The post-launch refund window opened
+    RefundWindowOpened {
+        /// Presale the refunds belong to
+        presale: Pubkey,
+        /// Time the window closes
+        closes_at: i64,
+        /// Time the opening was observed
+        timestamp: i64,
+    },
+    /// 75% of the refund window has elapsed
+    RefundWindowClosingSoon {
+        /// Presale the refunds belong to
+        presale: Pubkey,
+        /// Time the window closes
+        closes_at: i64,
+        /// Time the boundary was observed
+        timestamp: i64,
+    },
+    /// The refund window closed; unclaimed refunds are forfeited
+    RefundWindowClosed {
+        /// Presale the refunds belong to
+        presale: Pubkey,
+        /// Time the window closed
+        closed_at: i64,
+        /// Time the closing was observed
+        timestamp: i64,
+    },
+    /// The authority announced a treasury withdrawal
+    WithdrawNoticed {
+        /// Presale the treasury belongs to
+        presale: Pubkey,
+        /// Stablecoin whose locked treasury will be withdrawn
+        stablecoin_mint: Pubkey,
+        /// Token account the funds will be sent to
+        destination: Pubkey,
+        /// Treasury balance when the notice was given
+        balance: u64,
+        /// Earliest time the withdrawal can execute
+        executable_at: i64,
+        /// Time of the notice
+        timestamp: i64,
+    },
+    /// An oracle controller's circuit breaker tripped
+    CircuitBreakerTripped {
+        /// Oracle controller
+        controller: Pubkey,
+        /// What tripped the breaker
+        trigger: CircuitBreakerTrigger,
+        /// Trips since the breaker last cleared, including this one
+        trips: u8,
+        /// Cooldown before the next consensus update (in seconds)
+        cooldown: u32,
+        /// Time of the trip
+        timestamp: i64,
+    },
+    /// A consensus update is attempted in probation after the cooldown
+    CircuitBreakerProbation {
+        /// Oracle controller
+        controller: Pubkey,
+        /// Trips since the breaker last cleared
+        trips: u8,
+        /// Time of the attempt
+        timestamp: i64,
+    },
+    /// An oracle controller's circuit breaker cleared
+    CircuitBreakerRecovered {
+        /// Oracle controller
+        controller: Pubkey,
+        /// Authority that reset the breaker (None after a successful probation)
+        resolved_by: Option<Pubkey>,
+        /// Time of the recovery
+        timestamp: i64,
+    },
+    /// A presale purchase was executed; contribution records only keep the latest
+    /// purchase, so every purchase's conditions are also logged here
+    PurchaseRecorded {
+        /// Presale the purchase was made in
+        presale: Pubkey,
+        /// Buyer
+        buyer: Pubkey,
+        /// Stablecoin paid with
+        stablecoin_mint: Pubkey,
+        /// Rate and oracle conditions of the purchase
+        purchase: PurchaseSnapshot,
+    },
+    /// Tokens were deposited into a bug-bounty escrow
+    BugBountyFunded {
+        /// Bounty pool
+        bounty: Pubkey,
+        /// Token account the tokens came from
+        source: Pubkey,
+        /// Amount deposited
+        amount: u64,
+        /// Escrow balance after the deposit
+        balance: u64,
+        /// Time of the deposit
+        timestamp: i64,
+    },
+    /// Guardians committed a bounty award for a disclosure
+    BugBountyAwardCommitted {
+        /// Bounty pool
+        bounty: Pubkey,
+        /// Hash of the disclosure report
+        disclosure_hash: [u8; 32],
+        /// Researcher receiving the award
+        researcher: Pubkey,
+        /// Awarded amount
+        amount: u64,
+        /// Guardians who approved the award
+        approved_by: Vec<Pubkey>,
+        /// Time of the commitment
+        timestamp: i64,
+    },
+    /// A committed bounty award was paid out
+    BugBountyAwardPaid {
+        /// Bounty pool
+        bounty: Pubkey,
+        /// Hash of the disclosure report
+        disclosure_hash: [u8; 32],
+        /// Researcher who received the award
+        researcher: Pubkey,
+        /// Amount paid
+        amount: u64,
+        /// Time of the payout
+        timestamp: i64,
+    },
+    /// Holders can swap v1 tokens for v2 tokens
+    MintMigrationOpened {
+        /// Mint holders migrate from
+        old_mint: Pubkey,
+        /// Mint holders migrate to
+        new_mint: Pubkey,
+        /// v1 supply, fixed from now on
+        old_supply: u64,
+        /// Time the migration opened
+        timestamp: i64,
+    },
+    /// A holder swapped v1 tokens for v2 tokens
+    TokensMigrated {
+        /// Mint holders migrate from
+        old_mint: Pubkey,
+        /// Holder who swapped
+        holder: Pubkey,
+        /// v1 tokens burned and v2 tokens minted
+        amount: u64,
+        /// Total migrated so far
+        total_migrated: u64,
+        /// Time of the swap
+        timestamp: i64,
+    },
+    /// An oracle source failed again; sources are deactivated after
+    /// `MAX_CONSECUTIVE_ORACLE_FAILURES` failures in a row
+    OracleSourceFailing {
+        /// Oracle controller
+        controller: Pubkey,
+        /// Failing oracle account
+        oracle: Pubkey,
+        /// Failures in a row, including this one
+        consecutive_failures: u8,
+        /// Whether the source is now inactive
+        deactivated: bool,
+        /// Time of the failure
+        timestamp: i64,
+    },
+    /// An oracle controller's health score crossed the degraded or critical threshold
+    OracleHealthChanged {
+        /// Oracle controller
+        controller: Pubkey,
+        /// Level before the update
+        previous: OracleHealthLevel,
+        /// Level after the update
+        current: OracleHealthLevel,
+        /// Health score after the update (0-100)
+        health_score: u8,
+        /// Time of the update
+        timestamp: i64,
+    },
+    /// A consensus update published a fallback instead of a fresh consensus
+    FallbackPriceUsed {
+        /// Oracle controller
+        controller: Pubkey,
+        /// Published price
+        price: u64,
+        /// Whether the price is the authority's emergency price rather than the
+        /// last valid consensus
+        emergency: bool,
+        /// Age of the reused consensus (0 for emergency prices)
+        consensus_age: i64,
+        /// Time of the update
+        timestamp: i64,
+    },
+    /// An oracle controller was created and listed in a feed registry
+    ManagedFeedCreated {
+        /// Feed registry
+        registry: Pubkey,
+        /// Asset the controller prices
+        asset_id: String,
+        /// New oracle controller
+        controller: Pubkey,
+        /// Time of creation
+        timestamp: i64,
+    },
+    /// Locked treasury stablecoins were lent to an approved yield venue
+    TreasuryYieldDeployed {
+        /// Presale the treasury belongs to
+        presale: Pubkey,
+        /// Yield venue
+        venue: Pubkey,
+        /// Stablecoin amount deposited
+        amount: u64,
+        /// Principal deployed after the deposit
+        deployed_principal: u64,
+        /// Time of the deposit
+        timestamp: i64,
+    },
+    /// Lent stablecoins were recalled to the locked treasury
+    TreasuryYieldRecalled {
+        /// Presale the treasury belongs to
+        presale: Pubkey,
+        /// Yield venue
+        venue: Pubkey,
+        /// Principal returned to the locked treasury
+        principal: u64,
+        /// Yield routed to the insurance fund
+        yield_amount: u64,
+        /// Principal still deployed
+        deployed_principal: u64,
+        /// Time of the recall
+        timestamp: i64,
+    },
+    /// New supply controller parameters were queued behind the timelock
+    SupplyParametersQueued {
+        /// Autonomous supply controller
+        controller: Pubkey,
+        /// Queued parameters
+        parameters: SupplyParameters,
+        /// First time an evaluation uses them
+        effective_at: i64,
+    },
+    /// Queued supply controller parameters came into force
+    SupplyParametersApplied {
+        /// Autonomous supply controller
+        controller: Pubkey,
+        /// Parameters now in force
+        parameters: SupplyParameters,
+        /// Time they were applied
+        timestamp: i64,
+    },
+    /// The presale account is running out of buyer slots; `ExpandPresaleAccount` should be sent
+    PresaleBuyerCapacityLow {
+        /// Presale account
+        presale: Pubkey,
+        /// Unique buyers so far
+        num_buyers: u32,
+        /// Buyers the account currently has room for
+        capacity: u32,
+        /// Time of the purchase that crossed the mark
+        timestamp: i64,
+    },
+    /// The supply treasury was paid out by the controller's distribution
+    SupplyTreasuryDistributed {
+        /// Autonomous supply controller
+        controller: Pubkey,
+        /// Tokens sent to staking rewards
+        staking_rewards: u64,
+        /// Tokens sent to liquidity
+        liquidity: u64,
+        /// Tokens sent to operations
+        operations: u64,
+        /// Time of the distribution
+        timestamp: i64,
+    },
+    /// A keeper cranked the supply controller into a mint or burn
+    SupplyControllerCranked {
+        /// Autonomous supply controller
+        controller: Pubkey,
+        /// Key that sent the crank
+        keeper: Pubkey,
+        /// Mint or burn executed
+        operation: SupplyOperation,
+        /// Tokens minted or burned
+        amount: u64,
+        /// Tokens paid to the keeper from the supply treasury
+        reward: u64,
+        /// Time of the crank
+        timestamp: i64,
+    },
+    /// Tokens left a mint's liquidity escrow
+    LiquidityEscrowReleased {
+        /// Mint of the escrow
+        mint: Pubkey,
+        /// Token account the tokens were sent to
+        destination: Pubkey,
+        /// Tokens released
+        amount: u64,
+        /// Time of the release
+        timestamp: i64,
+    },
+    /// A supply controller's recorded supply no longer matched the mint's
+    /// and was reset to it
+    SupplyDriftDetected {
+        /// Autonomous supply controller
+        controller: Pubkey,
+        /// Mint whose supply was read
+        mint: Pubkey,
+        /// Supply the controller had recorded
+        recorded_supply: u64,
+        /// Actual supply of the mint
+        mint_supply: u64,
+        /// Time the drift was found
+        timestamp: i64,
+    },
+    /// Withheld transfer fees were swept into the burn treasury
+    WithheldFeesSwept {
+        /// Autonomous supply controller
+        controller: Pubkey,
+        /// Mint the fees were withheld in
+        mint: Pubkey,
+        /// Tokens swept by this call
+        amount: u64,
+        /// Tokens swept into the burn treasury so far
+        total_swept: u64,
+        /// Time of the sweep
+        timestamp: i64,
+    },
+    /// Tokens were burned and added to the mint's burn log
+    TokensBurned {
+        /// Mint the tokens were burned from
+        mint: Pubkey,
+        /// Autonomous or manual
+        kind: BurnKind,
+        /// Token account burned from
+        source: Pubkey,
+        /// Tokens burned
+        amount: u64,
+        /// Supply controller price at the burn (micro-USD)
+        price: u64,
+        /// Tokens burned since the log was created
+        total_burned: u64,
+        /// Time of the burn
+        timestamp: i64,
+    },
+    /// The mint's guardians declared a critical failure before launch and opened
+    /// full refunds
+    ForcedRefundDeclared {
+        /// Presale state account
+        presale: Pubkey,
+        /// Guardians who approved the declaration
+        guardians: Vec<Pubkey>,
+        /// Hash of the off-chain incident report
+        reason_hash: [u8; 32],
+        /// Time of the declaration
+        timestamp: i64,
+    },
+    /// A keeper was reimbursed for paying the fee of another user's claim
+    ClaimFeeSponsored {
+        /// Mint whose fee sponsorship paid
+        mint: Pubkey,
+        /// Keeper that paid the transaction fee
+        keeper: Pubkey,
+        /// Buyer or beneficiary whose claim was sponsored
+        claimant: Pubkey,
+        /// Lamports reimbursed
+        lamports: u64,
+        /// Budget left after this claim
+        remaining_budget: u64,
+        /// Time of the claim
+        timestamp: i64,
+    },
+    /// Guardians consolidated a non-canonical locked treasury account into the
+    /// treasury's canonical ATA
+    TreasurySwept {
+        /// Presale the treasury belongs to
+        presale: Pubkey,
+        /// Stablecoin swept
+        stablecoin_mint: Pubkey,
+        /// Non-canonical token account emptied
+        source: Pubkey,
+        /// Canonical treasury ATA credited
+        destination: Pubkey,
+        /// Tokens moved
+        amount: u64,
+        /// Guardians who approved the sweep
+        guardians: Vec<Pubkey>,
+        /// Time of the sweep
+        timestamp: i64,
+    },
+    /// A sensitive admin call was queued behind a timelock
+    TimelockOperationQueued {
+        /// Timelock holding the call
+        timelock: Pubkey,
+        /// Identifier of the queued call
+        operation_id: u64,
+        /// The queued call
+        operation: TimelockedOperation,
+        /// Earliest time the call may execute
+        executable_at: i64,
+    },
+    /// A queued call ran after its timelock delay
+    TimelockOperationExecuted {
+        /// Timelock that held the call
+        timelock: Pubkey,
+        /// Identifier of the call
+        operation_id: u64,
+        /// The executed call
+        operation: TimelockedOperation,
+        /// Time of execution
+        timestamp: i64,
+    },
+    /// A queued call was cancelled before it ran
+    TimelockOperationCancelled {
+        /// Timelock that held the call
+        timelock: Pubkey,
+        /// Identifier of the call
+        operation_id: u64,
+        /// The cancelled call
+        operation: TimelockedOperation,
+        /// Emergency or program authority that cancelled it
+        cancelled_by: Pubkey,
+        /// Time of the cancellation
+        timestamp: i64,
+    },
+    /// An access control admin gave a key a role
+    RoleGranted {
+        /// Access control account
+        access_control: Pubkey,
+        /// Key given the role
+        member: Pubkey,
+        /// Role given
+        role: Role,
+        /// Admin that granted it
+        granted_by: Pubkey,
+        /// Time of the grant
+        timestamp: i64,
+    },
+    /// An access control admin took a role from a key
+    RoleRevoked {
+        /// Access control account
+        access_control: Pubkey,
+        /// Key that lost the role
+        member: Pubkey,
+        /// Role taken
+        role: Role,
+        /// Admin that revoked it
+        revoked_by: Pubkey,
+        /// Time of the revocation
+        timestamp: i64,
+    },
+}
+
+impl VCoinEvent {
+    /// Write the event to the program log
+    pub fn emit(&self) {
+        if let Ok(data) = self.try_to_vec() {
+            sol_log_data(&[&data]);
+        }
+    }
+}
@@ -6,7 +6,8 @@ use solana_program::{
 };
 use spl_token_2022::ID as TOKEN_2022_PROGRAM_ID;
 use borsh::{BorshDeserialize, BorshSerialize, to_vec};
-use crate::state::OracleType;
+use crate::state::{AccessControl, AccessRole, AggregationStrategy, AirdropClaimBitmap, AirdropDistributor, AllocationEscrow, AllocationPlan, AllocationShare, AutonomousSupplyController, BurnLog, CircuitBreakerIncident, ComplianceArtifactKind, DepegAction, EmergencyState, InitialAllocation, MetadataDisplayHints, ChainlinkStreamsConfig, OperationClass, ChainlinkStreamsReport, OracleType, PresaleRoundKind, QuorumPolicy, RedstoneConfig, Role, SaleAnalytics, SignedPriceReport, StalenessPolicy, BugBountyAward, BugBountyEscrow, FeeSponsorship, MintMigration, MultiOracleController, Multisig, OracleFeedRegistry, SimulationState, StablecoinLimits, StablecoinPegGuard, SupplyActionLedger, SupplyDistribution, SupplyParameters, SupplyPriceSource, Timelock, TimelockedOperation, VestingGrant, VestingRegistry, VestingSchedule, VestingState, YieldVenue};
+use spl_associated_token_account::get_associated_token_address_with_program_id;
 
 /// Instruction types supported by the program
 #[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
@@ -18,8 +19,18 @@ pub enum VCoinInstruction {
     /// 1. `[]` The mint account
     /// 2. `[]` The token program
     /// 3. `[]` The system program
-    /// 4. `[]` The rent sysvar
-    /// 5. `[]` The metadata account
+    /// 4. `[]` The metadata account
+    /// 5. `[writable]` The emergency state PDA (`["emergency_state", mint]`)
+    ///
+    /// With an allocation plan this follows:
+    /// 6. `[writable]` The allocation plan PDA (`["allocation_plan", mint]`)
+    ///
+    /// With an allocation table, the initial supply is minted into program-owned
+    /// escrows instead of the authority's token account, and these follow the
+    /// plan PDA (or the emergency state without a plan):
+    /// - `[]` The associated token account program
+    /// - For each allocation, in order: `[]` the escrow owner PDA, then
+    ///   `[writable]` its Token-2022 ATA (created if missing)
     InitializeToken {
         /// Name of the token
         name: String,
@@ -33,6 +44,14 @@ pub enum VCoinInstruction {
         transfer_fee_basis_points: Option<u16>,
         /// Maximum fee rate as percentage of the transfer amount (optional, default 1)
         maximum_fee_rate: Option<u8>,
+        /// Authority that can pause and resume the token (defaults to the authority)
+        emergency_authority: Option<Pubkey>,
+        /// Escrows splitting the initial supply; empty mints it all to the
+        /// authority's token account
+        allocations: Vec<InitialAllocation>,
+        /// Percentages of the initial supply for presale, team, treasury and
+        /// liquidity, which later initializations draw against; empty records no plan
+        allocation_plan: Vec<AllocationShare>,
     },
     /// Initialize a presale
     /// 
@@ -43,7 +62,9 @@ pub enum VCoinInstruction {
     /// 3. `[writable]` The development treasury account (receives 50% of funds immediately)
     /// 4. `[writable]` The locked treasury account (holds 50% for potential refunds)
     /// 5. `[]` The system program
-    /// 6. `[]` Rent sysvar
+    /// 6. `[]` The mint's emergency state PDA
+    /// 7. `[writable]` The mint's allocation plan PDA; when the mint has a plan the
+    ///    tokens the hard cap sells are drawn from its presale share
     InitializePresale {
         /// Start time of the presale
         start_time: i64,
@@ -59,6 +80,16 @@ pub enum VCoinInstruction {
         min_purchase: u64,
         /// Maximum purchase amount in USD (as u64 with 6 decimals precision)
         max_purchase: u64,
+        /// Minimum seconds between purchases from the same wallet (0 disables)
+        purchase_cooldown: i64,
+        /// Length in seconds of the opening window with a per-wallet cap (0 disables)
+        launch_window_duration: i64,
+        /// Maximum total contribution per wallet during the opening window
+        launch_window_max_per_wallet: u64,
+        /// Accept only the remaining capacity when a purchase crosses the hard cap
+        allow_partial_fill: bool,
+        /// Maximum USD raised per rolling 24h window (0 disables)
+        daily_raise_cap: u64,
     },
     /// Buy tokens during presale using stablecoins
     /// 
@@ -74,13 +105,28 @@ pub enum VCoinInstruction {
     /// 8. `[writable]` The locked treasury stablecoin account (receives 50%)
     /// 9. `[]` The stablecoin token program
     /// 10. `[]` The stablecoin mint account
-    /// 11. `[]` The clock sysvar
+    /// 11. `[]` The mint's emergency state PDA
+    /// 12. Optional trailing accounts, in any order:
+    ///     - `[writable]` The presale round account; when present the round's
+    ///       price, limits and whitelist apply to the purchase
+    ///     - `[]` The buyer's KYC credential PDA (required when KYC mode is on)
+    ///     - `[writable]` The stablecoin limits PDA (required when the
+    ///       authority has set limits for the stablecoin)
+    ///     - `[]` The presale's price controller (required when the presale
+    ///       is priced dynamically)
+    ///     - `[]` The stablecoin peg guard PDA and its oracle controller
+    ///       (required when the authority has set a peg guard for the stablecoin)
+    ///     - `[writable]` The sale analytics PDA (required once the authority
+    ///       has created it)
     BuyTokensWithStablecoin {
         /// Amount in stablecoin token units
         amount: u64,
     },
     /// Buy tokens directly
     /// 
+    /// Retired: it minted tokens without collecting payment, so it is always rejected
+    /// with `InstructionRetired`. Purchases go through `BuyTokensWithStablecoin`.
+    ///
     /// Accounts expected:
     /// 0. `[signer]` The buyer
     /// 1. `[writable]` The presale state account
@@ -90,7 +136,6 @@ pub enum VCoinInstruction {
     /// 5. `[]` The token program (SPL Token-2022)
     /// 6. `[]` The system program
     /// 7. `[writable]` The treasury account
-    /// 8. `[]` The clock sysvar
     BuyTokens {
         /// Amount in USD (as u64 with 6 decimals precision)
         amount_usd: u64,
@@ -101,47 +146,72 @@ pub enum VCoinInstruction {
     /// 0. `[signer]` The authority
     /// 1. `[writable]` The presale state account
     /// 2. `[]` The stablecoin mint to add
-    AddSupportedStablecoin,
+    /// 3. `[]` The mint's emergency state PDA
+    AddSupportedStablecoin {
+        /// Succeed without changes if the stablecoin is already supported
+        /// (for re-runnable deployment scripts)
+        skip_if_exists: bool,
+    },
     /// Mark token as launched and set refund availability
     /// 
+    /// Every item of the presale's launch checklist must be completed first.
+    ///
     /// Accounts expected:
     /// 0. `[signer]` The authority
     /// 1. `[writable]` The presale state account
-    /// 2. `[]` The clock sysvar
+    /// 2. `[]` The mint's emergency state PDA
     LaunchToken,
     /// Claim refund after the refund availability date (3 months post-launch)
     /// 
+    /// The buyer need not sign if they enabled keeper refunds with `SetKeeperRefund`
+    /// and the destination is their recorded stablecoin ATA.
+    ///
     /// Accounts expected:
-    /// 0. `[signer]` The buyer claiming refund
+    /// 0. `[signer]` The buyer claiming refund (unsigned for a keeper refund)
     /// 1. `[writable]` The presale state account
     /// 2. `[writable]` The buyer's stablecoin token account (destination)
     /// 3. `[writable]` The locked treasury stablecoin account (source)
     /// 4. `[]` The locked treasury authority (PDA)
     /// 5. `[]` The stablecoin token program
     /// 6. `[]` The stablecoin mint
-    /// 7. `[]` The clock sysvar
+    /// 7. `[]` The mint's emergency state PDA
+    ///
+    /// A keeper paying the fee of a sponsored claim appends:
+    /// 8. `[writable]` The fee sponsorship of the presale mint
+    /// 9. `[signer, writable]` The sponsorship's keeper
     ClaimRefund,
     /// Withdraw remaining locked funds after refund period ends
     /// 
+    /// Same as `ExecuteWithdraw`: requires a matured `NoticeWithdraw`.
+    ///
     /// Accounts expected:
-    /// 0. `[signer]` The authority
+    /// 0. `[signer]` The authority (or the mint's timelock, writable and unsigned)
     /// 1. `[writable]` The presale state account
     /// 2. `[writable]` The locked treasury stablecoin account (source)
     /// 3. `[writable]` The destination treasury stablecoin account
     /// 4. `[]` The locked treasury authority (PDA)
     /// 5. `[]` The stablecoin token program
     /// 6. `[]` The stablecoin mint
-    /// 7. `[]` The clock sysvar
+    /// 7. `[]` The mint's emergency state PDA
     WithdrawLockedFunds,
-    /// Initialize vesting
+    /// Initialize a vesting pool
+    ///
+    /// A mint can have several independent pools (team, advisors, ecosystem),
+    /// each with its own schedule and escrow. Every pool is listed in the
+    /// authority's registry for the mint, created along with the first pool.
     /// 
     /// Accounts expected:
-    /// 0. `[signer]` The authority
+    /// 0. `[signer, writable]` The authority (pays for the registry account)
     /// 1. `[writable]` The vesting state account
     /// 2. `[]` The mint account
     /// 3. `[]` The system program
-    /// 4. `[]` Rent sysvar
+    /// 4. `[]` The mint's emergency state PDA
+    /// 5. `[writable]` The vesting registry (PDA: ["vesting_registry", mint, authority])
+    /// 6. `[writable]` The mint's allocation plan PDA; when the mint has a plan the
+    ///    pool's tokens are drawn from its team share
     InitializeVesting {
+        /// Pool label, unique within the registry
+        label: String,
         /// Total tokens to be vested
         total_tokens: u64,
         /// Vesting start timestamp
@@ -154,48 +224,77 @@ pub enum VCoinInstruction {
     /// Add vesting beneficiary
     /// 
     /// Accounts expected:
-    /// 0. `[signer]` The authority
+    /// 0. `[signer, writable]` The authority (pays for the grant account)
     /// 1. `[writable]` The vesting state account
+    /// 2. `[writable]` The grant account (PDA: ["vesting_grant", vesting, beneficiary])
+    /// 3. `[]` The system program
+    /// 4. `[]` The pool's escrow token account, which must cover every unreleased allocation
+    /// 5. `[]` The mint's emergency state PDA
     AddVestingBeneficiary {
         /// Beneficiary public key
         beneficiary: Pubkey,
         /// Amount of tokens for this beneficiary
         amount: u64,
+        /// Custom start, cliff, duration and interval (None uses the shared schedule)
+        schedule: Option<VestingSchedule>,
     },
     /// Release vested tokens
+    ///
+    /// Permissionless: anyone may crank a release, since tokens can only go to
+    /// the beneficiary's bound account. A third-party cranker receives the
+    /// vesting account's crank tip, if one is set and funded.
     /// 
     /// Accounts expected:
-    /// 0. `[signer]` The authority
+    /// 0. `[signer, writable]` The cranker (any account, including the beneficiary)
     /// 1. `[writable]` The vesting state account
-    /// 2. `[writable]` The mint account
-    /// 3. `[writable]` The beneficiary's token account
-    /// 4. `[]` The token program (SPL Token-2022)
-    /// 5. `[]` The clock sysvar
+    /// 2. `[writable]` The beneficiary's grant account
+    /// 3. `[writable]` The mint account
+    /// 4. `[writable]` The beneficiary's bound token account (their ATA unless
+    ///    changed with `SetBeneficiaryDestination`)
+    /// 5. `[]` The token program (SPL Token-2022)
+    /// 6. `[writable]` The pool's escrow token account
+    /// 7. `[]` The escrow authority (PDA: ["vesting_escrow", vesting])
+    /// 8. `[]` The mint's emergency state PDA
+    ///
+    /// A keeper paying the fee of a sponsored release appends:
+    /// 9. `[writable]` The fee sponsorship of the vesting mint
+    /// 10. `[signer, writable]` The sponsorship's keeper
     ReleaseVestedTokens {
         /// Beneficiary public key
         beneficiary: Pubkey,
+        /// Release at most this many tokens, leaving the rest releasable later
+        /// (None releases everything currently releasable). Only honoured when
+        /// the beneficiary or the authority cranks; third parties release it all
+        max_amount: Option<u64>,
     },
     /// Update token metadata
     /// 
     /// Accounts expected:
-    /// 0. `[signer]` The authority
+    /// 0. `[signer, writable]` The authority (pays for growing the metadata account)
     /// 1. `[writable]` The metadata account (custom program storage)
     /// 2. `[]` The mint account
     /// 3. `[]` The token program (SPL Token-2022)
+    /// 4. `[]` The mint's emergency state PDA
+    /// 5. `[]` (Optional) The system program, needed when the account must grow
     UpdateTokenMetadata {
         /// New name (optional)
         name: Option<String>,
         /// New symbol (optional)
         symbol: Option<String>,
-        /// New URI (optional)
+        /// New URI (optional): empty, or an `https://`, `ipfs://` or `ar://` URI of
+        /// at most `MAX_METADATA_URI_LEN` printable ASCII bytes
         uri: Option<String>,
+        /// SHA-256 of the document at the new URI; replaces the stored hash
+        /// whenever a URI is given (requires `uri`)
+        uri_hash: Option<[u8; 32]>,
     },
     /// Set transfer fee
     /// 
     /// Accounts expected:
-    /// 0. `[signer]` The fee authority
+    /// 0. `[signer]` The fee authority (or the mint's timelock, writable and unsigned)
     /// 1. `[writable]` The mint account
     /// 2. `[]` The token program (SPL Token-2022)
+    /// 3. `[]` The mint's emergency state PDA
     SetTransferFee {
         /// Transfer fee basis points
         transfer_fee_basis_points: u16,
@@ -204,33 +303,43 @@ pub enum VCoinInstruction {
     },
     /// End presale
     /// 
+    /// Anyone may end the presale once `end_time` has passed; ending it early
+    /// requires the authority.
+    ///
     /// Accounts expected:
-    /// 0. `[signer]` The authority
+    /// 0. `[signer]` The caller (the authority when ending before `end_time`)
     /// 1. `[writable]` The presale state account
+    /// 2. `[]` The mint's emergency state PDA
     EndPresale,
     /// Initialize Autonomous Supply Controller
     /// 
     /// Accounts expected:
-    /// 0. `[signer]` The initializer (temporary authority, just for setup)
+    /// 0. `[signer]` The initializer, which becomes the controller's parameter authority
     /// 1. `[writable]` The controller state account
     /// 2. `[]` The mint account
-    /// 3. `[]` The price oracle account
+    /// 3. `[]` The price oracle account, or a MultiOracleController owned by this
+    ///    program when `price_source` is `MultiOracleController`
     /// 4. `[]` The system program
     /// 5. `[]` The token program
-    /// 6. `[]` The rent sysvar
+    /// 6. `[]` The mint's emergency state PDA
     InitializeAutonomousController {
         /// Initial token price (with 6 decimals precision)
         initial_price: u64,
-        /// Maximum token supply (with appropriate decimals)
+        /// Maximum token supply autonomous mints may reach (with appropriate
+        /// decimals); at least the current supply and the 1B minimum supply
         max_supply: u64,
+        /// How the price oracle account is read
+        price_source: SupplyPriceSource,
     },
     /// Update Price from Oracle
     /// 
     /// Accounts expected:
     /// 0. `[]` The controller state account
-    /// 1. `[]` The primary price oracle account
-    /// 2. `[]` The clock sysvar
-    /// 3. `[]` (Optional) The backup price oracle account
+    /// 1. `[]` The primary price oracle account, or the controller's multi-oracle
+    ///    controller (`price_oracle`), whose consensus TWAP is used
+    /// 2. `[]` The mint's emergency state PDA
+    /// 3. `[]` (Optional) The backup price oracle account; ignored for a multi-oracle
+    ///    controller
     UpdateOraclePrice,
     /// Execute Autonomous Mint
     /// 
@@ -238,10 +347,13 @@ pub enum VCoinInstruction {
     /// 0. `[writable]` The controller state account
     /// 1. `[writable]` The mint account
     /// 2. `[]` The mint authority PDA
-    /// 3. `[writable]` The destination account to receive newly minted tokens
+    /// 3. `[writable]` The supply treasury token account receiving the new tokens,
+    ///    owned by the supply treasury PDA (["supply_treasury", mint])
     /// 4. `[]` The token program
-    /// 5. `[]` The clock sysvar
-    /// 6. `[]` The price oracle account
+    /// 5. `[]` The mint's emergency state PDA
+    /// 6. `[]` The price oracle account; a multi-oracle controller is read for the
+    ///    current consensus price
+    /// 7. `[writable]` The supply action ledger (PDA: ["supply_ledger", controller])
     ExecuteAutonomousMint,
     /// Execute Autonomous Burn
     /// 
@@ -252,8 +364,11 @@ pub enum VCoinInstruction {
     /// 3. `[writable]` The burn treasury token account to burn tokens from (must be owned by burn treasury PDA)
     /// 4. `[]` The burn treasury PDA (derived from mint)
     /// 5. `[]` The token program
-    /// 6. `[]` The clock sysvar
-    /// 7. `[]` The price oracle account
+    /// 6. `[]` The mint's emergency state PDA
+    /// 7. `[]` The price oracle account; a multi-oracle controller is read for the
+    ///    current consensus price
+    /// 8. `[writable]` The supply action ledger (PDA: ["supply_ledger", controller])
+    /// 9. `[writable]` The mint's burn log (PDA: ["burn_log", mint])
     ExecuteAutonomousBurn,
     /// Permanently Disable Program Upgrades
     /// 
@@ -268,11 +383,11 @@ pub enum VCoinInstruction {
     /// 
     /// Accounts expected:
     /// 0. `[signer]` The depositor (token holder)
-    /// 1. `[writable]` The depositor's token account
-    /// 2. `[writable]` The burn treasury token account
-    /// 3. `[]` The controller state account
-    /// 4. `[]` The mint account
-    /// 5. `[]` The token program
+    /// 1. `[]` The mint account
+    /// 2. `[writable]` The depositor's token account
+    /// 3. `[writable]` The burn treasury token account
+    /// 4. `[]` The token program
+    /// 5. `[]` The mint's emergency state PDA
     DepositToBurnTreasury {
         /// Amount of tokens to deposit
         amount: u64,
@@ -281,13 +396,12 @@ pub enum VCoinInstruction {
     /// 
     /// Accounts expected:
     /// 0. `[signer]` The payer for account creation
-    /// 1. `[]` The controller state account
-    /// 2. `[]` The mint account
-    /// 3. `[]` The burn treasury PDA
-    /// 4. `[writable]` The burn treasury token account (to be created)
-    /// 5. `[]` The token program
-    /// 6. `[]` The system program
-    /// 7. `[]` The rent sysvar
+    /// 1. `[]` The mint account
+    /// 2. `[]` The burn treasury PDA
+    /// 3. `[writable]` The burn treasury token account (to be created)
+    /// 4. `[]` The token program
+    /// 5. `[]` The system program
+    /// 6. `[]` The mint's emergency state PDA
     InitializeBurnTreasury,
     /// Expand Presale Account
     /// 
@@ -295,29 +409,33 @@ pub enum VCoinInstruction {
     /// 0. `[signer]` The authority
     /// 1. `[writable]` The presale state account
     /// 2. `[]` The system program
-    /// 3. `[]` The rent sysvar
+    /// 3. `[]` The mint's emergency state PDA
     ExpandPresaleAccount {
         /// Additional number of buyers to allocate space for
         additional_buyers: u32,
     },
     /// Claim Refund from Development Treasury
     /// 
+    /// The dev share is paid from the presale's dev refund vault, so a refund does not
+    /// need the authority to sign. The vault is funded by transferring stablecoin into
+    /// the vault authority's token accounts.
+    ///
     /// Accounts expected:
     /// 0. `[signer]` The buyer claiming refund
     /// 1. `[writable]` The presale state account
     /// 2. `[writable]` The buyer's stablecoin token account (destination)
-    /// 3. `[writable]` The development treasury stablecoin account (source)
-    /// 4. `[signer]` The authority (presale owner who must approve dev refunds)
+    /// 3. `[writable]` The dev refund vault stablecoin account (source)
+    /// 4. `[]` The dev refund vault authority (PDA: ["dev_refund_vault", presale])
     /// 5. `[]` The stablecoin token program
     /// 6. `[]` The stablecoin mint
-    /// 7. `[]` The clock sysvar
+    /// 7. `[]` The mint's emergency state PDA
     ClaimDevFundRefund,
     /// Emergency Pause Program Operations
     /// 
     /// Allows authority to quickly pause critical functions during emergency
     /// Accounts expected:
     /// 0. `[signer]` The emergency authority
-    /// 1. `[writable]` The emergency state account
+    /// 1. `[writable]` The mint's emergency state account (created by `InitializeToken`)
     EmergencyPause {
         /// Optional reason for the pause
         reason: Option<String>,
@@ -335,12 +453,12 @@ pub enum VCoinInstruction {
     /// 
     /// Emergency instruction to rescue stuck tokens from any account
     /// Accounts expected:
-    /// 0. `[signer]` The emergency authority
+    /// 0. `[signer]` The emergency authority (or the mint's timelock, writable and unsigned)
     /// 1. `[writable]` The source token account to rescue from
     /// 2. `[writable]` The destination token account 
     /// 3. `[]` Source account authority (PDA derived from program)
-    /// 4. `[]` The token program
-    /// 5. `[]` The mint account
+    /// 4. `[]` The token program owning the source account (SPL Token or Token-2022)
+    /// 5. `[]` The mint of the source account
     RescueTokens {
         /// Amount of tokens to rescue
         amount: u64,
@@ -360,9 +478,9 @@ pub enum VCoinInstruction {
     /// Initialize Multi-Oracle Controller
     /// 
     /// Accounts expected:
-    /// 0. `[signer]` The authority
-    /// 1. `[writable]` The oracle controller account
-    /// 2. `[]` Rent sysvar
+    /// 0. `[signer, writable]` The authority, paying for the controller account
+    /// 1. `[writable]` The oracle controller PDA (`["oracle_controller", asset_id]`)
+    /// 2. `[]` System program
     InitializeOracleController {
         /// Asset ID for the oracle controller (e.g., "BTC/USD")
         asset_id: String,
@@ -387,6 +505,9 @@ pub enum VCoinInstruction {
         max_staleness_seconds: u32,
         /// Whether this is a required oracle
         is_required: bool,
+        /// Succeed without changes if the oracle is already a source; the existing
+        /// configuration is kept (for re-runnable deployment scripts)
+        skip_if_exists: bool,
     },
     
     /// Update Oracle Consensus
@@ -394,8 +515,7 @@ pub enum VCoinInstruction {
     /// Accounts expected:
     /// 0. `[signer]` The caller (can be any account, often a keeper)
     /// 1. `[writable]` The oracle controller account
-    /// 2. `[]` Clock sysvar
-    /// 3+. `[]` The oracle accounts (variable number, passed as remaining accounts)
+    /// 2. `[]` The oracle accounts (variable number, passed as remaining accounts)
     UpdateOracleConsensus,
     
     /// Set Emergency Price
@@ -403,7 +523,6 @@ pub enum VCoinInstruction {
     /// Accounts expected:
     /// 0. `[signer]` The authority
     /// 1. `[writable]` The oracle controller account
-    /// 2. `[]` Clock sysvar
     SetEmergencyPrice {
         /// Emergency price to set
         emergency_price: u64,
@@ -430,228 +549,4234 @@ pub enum VCoinInstruction {
     /// Accounts expected:
     /// 0. `[signer]` The authority
     /// 1. `[writable]` The controller state account
-    /// 2. `[]` The clock sysvar
+    /// 2. `[]` The mint's emergency state PDA
     UpdatePriceDirectly {
         /// The new price value (with 6 decimals precision)
         new_price: u64,
     },
-}
-
-/// Parameters for initializing a token
-#[derive(BorshSerialize, BorshDeserialize, Clone)]
-pub struct InitializeTokenParams {
-    /// Authority that can manage the token
-    pub authority: Pubkey,
-    /// The mint account
-    pub mint: Pubkey,
-    /// Metadata account
-    pub metadata: Pubkey,
-    /// Name of the token
-    pub name: String,
-    /// Symbol of the token
-    pub symbol: String,
-    /// Number of decimals
-    pub decimals: u8,
-    /// Initial supply
-    pub initial_supply: u64,
-    /// Transfer fee basis points (optional, default 500 = 5%)
-    pub transfer_fee_basis_points: Option<u16>,
-    /// Maximum fee rate as percentage of the transfer amount (optional, default 1)
-    pub maximum_fee_rate: Option<u8>,
-}
-
-/// Parameters for initializing a presale
-#[derive(BorshSerialize, BorshDeserialize, Clone)]
-pub struct InitializePresaleParams {
-    /// Authority that can manage the presale
-    pub authority: Pubkey,
-    /// The presale state account
-    pub presale: Pubkey,
-    /// The mint account
-    pub mint: Pubkey,
-    /// The treasury account
-    pub treasury: Pubkey,
-    /// Start time of the presale
-    pub start_time: i64,
-    /// End time of the presale
-    pub end_time: i64,
-    /// Token price in USD (as u64 with 6 decimals precision)
-    pub token_price: u64,
-    /// Hard cap for the presale
-    pub hard_cap: u64,
-    /// Soft cap for the presale
-    pub soft_cap: u64,
-    /// Minimum purchase amount in USD (as u64 with 6 decimals precision)
-    pub min_purchase: u64,
-    /// Maximum purchase amount in USD (as u64 with 6 decimals precision)
-    pub max_purchase: u64,
-}
-
-/// Parameters for buying tokens
-#[derive(BorshSerialize, BorshDeserialize, Clone)]
-pub struct BuyTokensParams {
-    /// The buyer's public key
-    pub buyer: Pubkey,
-    /// The presale state account
-    pub presale: Pubkey,
-    /// The mint account
-    pub mint: Pubkey,
-    /// The buyer's token account
-    pub buyer_token_account: Pubkey,
-    /// Authority that can mint tokens
-    pub authority: Pubkey,
-    /// The treasury account
-    pub treasury: Pubkey,
-    /// Amount in USD (as u64 with 6 decimals precision)
-    pub amount_usd: u64,
-}
-
-/// Parameters for initializing vesting
-#[derive(BorshSerialize, BorshDeserialize, Clone)]
-pub struct InitializeVestingParams {
-    /// Authority that can manage vesting
-    pub authority: Pubkey,
-    /// The vesting state account
-    pub vesting: Pubkey,
-    /// The mint account
-    pub mint: Pubkey,
-    /// Total tokens to be vested
-    pub total_tokens: u64,
-    /// Vesting start timestamp
-    pub start_time: i64,
-    /// Release interval in seconds
-    pub release_interval: i64,
-    /// Number of releases
-    pub num_releases: u8,
-}
-
-/// Types of state that can be recovered in emergency
-#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
-pub enum RecoveryStateType {
-    /// Presale state
-    Presale,
-    /// Vesting state 
-    Vesting,
-    /// Controller state
-    Controller,
-    /// Token metadata
-    TokenMetadata,
-    /// Emergency state
-    EmergencyState,
-}
-
-impl VCoinInstruction {
-    /// Creates a new InitializeToken instruction
-    pub fn initialize_token(
-        program_id: &Pubkey,
-        params: &InitializeTokenParams,
-    ) -> Result<Instruction, std::io::Error> {
-        let instr = Self::InitializeToken {
-            name: params.name.clone(),
-            symbol: params.symbol.clone(),
-            decimals: params.decimals,
-            initial_supply: params.initial_supply,
-            transfer_fee_basis_points: params.transfer_fee_basis_points,
-            maximum_fee_rate: params.maximum_fee_rate,
-        };
-        let data = to_vec(&instr)?;
-
-        let accounts = vec![
-            AccountMeta::new_readonly(params.authority, true),      // Authority (signer)
-            AccountMeta::new(params.mint, false),                  // Mint account
-            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false), // Token program
-            AccountMeta::new_readonly(system_program::id(), false),  // System program
-            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false), // Rent sysvar
-            AccountMeta::new(params.metadata, false),               // Metadata account
-        ];
-
-        Ok(Instruction {
-            program_id: *program_id,
-            accounts,
-            data,
-        })
-    }
-
-    /// Creates a new InitializePresale instruction
-    pub fn initialize_presale(
-        program_id: &Pubkey,
-        params: &InitializePresaleParams,
-    ) -> Result<Instruction, std::io::Error> {
-        let instr = Self::InitializePresale {
-            start_time: params.start_time,
-            end_time: params.end_time,
-            token_price: params.token_price,
-            hard_cap: params.hard_cap,
-            soft_cap: params.soft_cap,
-            min_purchase: params.min_purchase,
-            max_purchase: params.max_purchase,
-        };
-        let data = to_vec(&instr)?;
-
-        let accounts = vec![
-            AccountMeta::new_readonly(params.authority, true),      // Authority (signer)
-            AccountMeta::new(params.presale, false),               // Presale state account
-            AccountMeta::new_readonly(params.mint, false),         // Mint account
-            AccountMeta::new(params.treasury, false),              // Treasury account
-            AccountMeta::new_readonly(system_program::id(), false), // System program
-            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false), // Rent sysvar
-        ];
 
-        Ok(Instruction {
-            program_id: *program_id,
-            accounts,
-            data,
-        })
-    }
+    /// Finalize presale
+    ///
+    /// Permissionless: callable by anyone once the end time has passed or the
+    /// hard cap has been reached.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The caller (any account)
+    /// 1. `[writable]` The presale state account
+    /// 2. `[]` The mint's emergency state PDA
+    FinalizePresale,
 
-    /// Creates a new BuyTokens instruction
-    pub fn buy_tokens(
-        program_id: &Pubkey,
-        params: &BuyTokensParams,
-    ) -> Result<Instruction, std::io::Error> {
-        let instr = Self::BuyTokens {
-            amount_usd: params.amount_usd,
-        };
-        let data = to_vec(&instr)?;
+    /// Initialize a presale round
+    ///
+    /// Rounds run sequentially under one presale and must not overlap. Each
+    /// round has its own price, caps and optional whitelist; purchases also
+    /// count towards the presale's aggregate raise.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The presale authority (pays for the round account)
+    /// 1. `[writable]` The presale state account
+    /// 2. `[writable]` The round account (PDA: ["presale_round", presale, round_index])
+    /// 3. `[]` The system program
+    /// 4. `[]` The mint's emergency state PDA
+    InitializePresaleRound {
+        /// Round index, must equal the number of rounds created so far
+        round_index: u8,
+        /// Stage of the round
+        kind: PresaleRoundKind,
+        /// Start time of the round
+        start_time: i64,
+        /// End time of the round
+        end_time: i64,
+        /// Token price in USD (as u64 with 6 decimals precision)
+        token_price: u64,
+        /// Hard cap for the round
+        hard_cap: u64,
+        /// Minimum purchase amount in USD (as u64 with 6 decimals precision)
+        min_purchase: u64,
+        /// Maximum purchase amount in USD (as u64 with 6 decimals precision)
+        max_purchase: u64,
+        /// Buyers allowed in this round (empty for an open round)
+        whitelist: Vec<Pubkey>,
+    },
 
-        let accounts = vec![
-            AccountMeta::new(params.buyer, true),                  // Buyer (signer)
-            AccountMeta::new(params.presale, false),               // Presale state account
-            AccountMeta::new(params.mint, false),                  // Mint account
-            AccountMeta::new(params.buyer_token_account, false),   // Buyer's token account
-            AccountMeta::new(params.authority, true),              // Authority (signer)
-            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false), // Token program
-            AccountMeta::new_readonly(system_program::id(), false), // System program
-            AccountMeta::new(params.treasury, false),              // Treasury account
-            AccountMeta::new_readonly(sysvar::clock::id(), false),  // Clock sysvar
-        ];
+    /// Configure KYC gating for a presale
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The presale authority
+    /// 1. `[writable]` The presale state account
+    /// 2. `[]` The mint's emergency state PDA
+    SetKycConfig {
+        /// Whether purchases require a KYC credential
+        kyc_required: bool,
+        /// Ed25519 key that signs KYC attestations
+        attestor: Pubkey,
+    },
 
-        Ok(Instruction {
-            program_id: *program_id,
-            accounts,
-            data,
-        })
-    }
+    /// Issue a KYC credential to a buyer
+    ///
+    /// The instruction immediately preceding this one must be an ed25519
+    /// program instruction verifying the attestor's signature over
+    /// `KycCredential::attestation_message(presale, buyer, expires_at)`.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The buyer (pays for the credential account)
+    /// 1. `[]` The presale state account
+    /// 2. `[writable]` The credential account (PDA: ["kyc_credential", presale, buyer])
+    /// 3. `[]` The instructions sysvar
+    /// 4. `[]` The system program
+    /// 5. `[]` The mint's emergency state PDA
+    IssueKycCredential {
+        /// Expiration timestamp of the credential
+        expires_at: i64,
+    },
 
-    /// Creates a new InitializeVesting instruction
-    pub fn initialize_vesting(
-        program_id: &Pubkey,
-        params: &InitializeVestingParams,
-    ) -> Result<Instruction, std::io::Error> {
-        let instr = Self::InitializeVesting {
-            total_tokens: params.total_tokens,
-            start_time: params.start_time,
-            release_interval: params.release_interval,
-            num_releases: params.num_releases,
-        };
-        let data = to_vec(&instr)?;
+    /// Close a finished presale and reclaim rent
+    ///
+    /// Only allowed once the presale has ended and every refund is settled:
+    /// all contributions refunded, or the refund windows closed after launch.
+    /// Account data is zeroed and lamports go to the rent destination.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The presale authority
+    /// 1. `[writable]` The presale state account
+    /// 2. `[writable]` The rent destination
+    /// 3. `[]` The mint's emergency state PDA
+    /// 4. `[writable]` Presale round and KYC credential accounts of this presale to close
+    ///    (variable number, passed as remaining accounts)
+    ClosePresaleAccounts,
 
-        let accounts = vec![
-            AccountMeta::new_readonly(params.authority, true),      // Authority (signer)
-            AccountMeta::new(params.vesting, false),               // Vesting state account
+    /// Commit the hash of an off-chain compliance artifact
+    ///
+    /// Appends a timestamped hash to the presale's compliance record so the
+    /// document can later be shown to have existed at that time.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The presale authority (pays for record growth)
+    /// 1. `[]` The presale state account
+    /// 2. `[writable]` The compliance record (PDA: ["compliance", presale])
+    /// 3. `[]` The system program
+    /// 4. `[]` The mint's emergency state PDA
+    CommitComplianceHash {
+        /// Kind of artifact
+        kind: ComplianceArtifactKind,
+        /// Hash of the artifact
+        hash: [u8; 32],
+    },
+    /// Set per-stablecoin purchase limits and a rolling-window volume cap for a presale
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The presale authority (pays for the limits account)
+    /// 1. `[writable]` The presale state account
+    /// 2. `[]` The stablecoin mint account
+    /// 3. `[writable]` The stablecoin limits account (PDA: ["stablecoin_limits", presale, stablecoin_mint])
+    /// 4. `[]` The system program
+    /// 5. `[]` The mint's emergency state PDA
+    SetStablecoinLimits {
+        /// Minimum purchase in this stablecoin (0 keeps the presale/round minimum)
+        min_purchase: u64,
+        /// Maximum purchase in this stablecoin (0 keeps the presale/round maximum)
+        max_purchase: u64,
+        /// Rolling window length in seconds for the volume cap
+        window_duration: i64,
+        /// Maximum volume per rolling window (0 disables the cap)
+        window_cap: u64,
+        /// Timestamp after which the cap stops applying (0 means it never expires)
+        cap_expires_at: i64,
+    },
+    /// Create a Merkle-distributor airdrop funded from an escrow token account
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The airdrop authority (pays for the distributor account)
+    /// 1. `[writable]` The distributor account (PDA: ["airdrop", mint, merkle_root])
+    /// 2. `[]` The mint account
+    /// 3. `[]` The escrow token account, owned by the distributor PDA
+    /// 4. `[]` The system program
+    /// 5. `[]` The mint's emergency state PDA
+    InitializeAirdrop {
+        /// Root of the Merkle tree of `(index, claimant, amount)` leaves
+        merkle_root: [u8; 32],
+        /// Total amount that may be claimed
+        total: u64,
+    },
+    /// Claim an airdrop allocation with a Merkle proof
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The claimant (pays for the claim bitmap chunk if new)
+    /// 1. `[writable]` The distributor account
+    /// 2. `[writable]` The claim bitmap account (PDA: ["airdrop_claims", distributor, index / 8192 as u32 LE])
+    /// 3. `[writable]` The escrow token account
+    /// 4. `[writable]` The claimant's token account
+    /// 5. `[]` The mint account
+    /// 6. `[]` The token program (SPL Token-2022)
+    /// 7. `[]` The system program
+    /// 8. `[]` The mint's emergency state PDA
+    ClaimAirdrop {
+        /// Leaf index in the Merkle tree
+        index: u64,
+        /// Amount allocated to the claimant
+        amount: u64,
+        /// Sibling hashes from the leaf up to the root
+        proof: Vec<[u8; 32]>,
+    },
+    /// Set the price floor and ceiling an oracle controller enforces for its consumers
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The controller authority
+    /// 1. `[writable]` The oracle controller account
+    SetControllerPriceBounds {
+        /// Lowest usable price (0 together with a 0 ceiling clears the bounds)
+        price_floor: u64,
+        /// Highest usable price
+        price_ceiling: u64,
+    },
+    /// Link (or unlink) an oracle controller that prices the presale dynamically
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The presale authority
+    /// 1. `[writable]` The presale state account
+    /// 2. `[]` The mint's emergency state PDA
+    /// 3. `[]` The oracle controller account (only when `controller` is Some)
+    SetPresalePriceController {
+        /// Controller to read the price from, or None to return to the fixed price
+        controller: Option<Pubkey>,
+    },
+    /// Dry-run the presale checks for a prospective purchase without moving funds.
+    /// The result is written as a Borsh-encoded `PurchaseValidation` to the return data;
+    /// the instruction itself succeeds even when the purchase would be rejected.
+    ///
+    /// Accounts expected:
+    /// 0. `[]` The prospective buyer
+    /// 1. `[]` The presale state account
+    /// 2. `[]` The mint account
+    /// 3. `[]` The stablecoin mint account
+    /// 4. The same optional trailing accounts as `BuyTokensWithStablecoin`
+    ValidatePurchase {
+        /// Amount in stablecoin token units
+        amount: u64,
+    },
+    /// Revoke a beneficiary's vesting grant (e.g. on an employee departure)
+    ///
+    /// Tokens vested up to now stay claimable through `ReleaseVestedTokens`;
+    /// the unvested remainder returns to the pool for new grants, or is removed
+    /// from the pool entirely when `burn_unvested` is set.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The authority
+    /// 1. `[writable]` The vesting state account
+    /// 2. `[writable]` The beneficiary's grant account
+    /// 3. `[]` The mint's emergency state PDA
+    RevokeVestingBeneficiary {
+        /// Beneficiary public key
+        beneficiary: Pubkey,
+        /// Remove the unvested tokens from the pool instead of returning them
+        burn_unvested: bool,
+    },
+    /// Set the locked treasury coverage threshold for the presale's treasury alarm
+    ///
+    /// Purchases, refunds and withdrawals compare the locked treasury balance with the
+    /// refunds it still owes; crossing the threshold raises or clears the alarm and
+    /// emits a `TreasuryAlarmRaised`/`TreasuryAlarmCleared` event.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The presale authority
+    /// 1. `[writable]` The presale state account
+    /// 2. `[]` The mint's emergency state PDA
+    SetTreasuryAlarm {
+        /// Required coverage of outstanding refunds in basis points (0 disables the alarm)
+        coverage_bps: u16,
+    },
+    /// Propose moving a vesting grant to a new key (e.g. a rotated or lost wallet)
+    ///
+    /// The grant only moves once the new key signs `AcceptBeneficiaryTransfer`.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The current beneficiary, or the vesting authority as an override
+    /// 1. `[]` The vesting state account
+    /// 2. `[writable]` The beneficiary's grant account
+    /// 3. `[]` The mint's emergency state PDA
+    ProposeBeneficiaryTransfer {
+        /// Current beneficiary public key
+        beneficiary: Pubkey,
+        /// Key to take over the remaining grant (None cancels a pending proposal)
+        new_beneficiary: Option<Pubkey>,
+    },
+    /// Accept a proposed vesting grant transfer
+    ///
+    /// The grant moves to the new beneficiary's grant PDA and the old grant
+    /// account is closed, its rent going to the new beneficiary.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The proposed new beneficiary (pays for the new grant account)
+    /// 1. `[]` The vesting state account
+    /// 2. `[writable]` The current grant account
+    /// 3. `[writable]` The new grant account (PDA: ["vesting_grant", vesting, new_beneficiary])
+    /// 4. `[]` The system program
+    /// 5. `[]` The mint's emergency state PDA
+    AcceptBeneficiaryTransfer {
+        /// Current beneficiary public key
+        beneficiary: Pubkey,
+    },
+    /// Add several vesting beneficiaries at once on the shared schedule
+    ///
+    /// The batch is validated as a whole; if any entry is rejected none are added.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The authority (pays for the grant accounts)
+    /// 1. `[writable]` The vesting state account
+    /// 2. `[]` The system program
+    /// 3. `[]` The pool's escrow token account, which must cover every unreleased allocation
+    /// 4. `[]` The mint's emergency state PDA
+    /// 5. `[writable]` One grant account per entry, in entry order
+    ///    (PDA: ["vesting_grant", vesting, beneficiary])
+    AddVestingBeneficiaries {
+        /// Beneficiary public keys and token amounts (at most `MAX_BENEFICIARIES_PER_BATCH`)
+        entries: Vec<(Pubkey, u64)>,
+    },
+    /// Return a beneficiary's unlock timeline without modifying state
+    ///
+    /// The result is written as a Borsh-encoded `VestingSchedulePreview` to the
+    /// return data, holding up to `MAX_PREVIEW_TRANCHES` tranches from `start_index`.
+    ///
+    /// Accounts expected:
+    /// 0. `[]` The vesting state account
+    /// 1. `[]` The beneficiary's grant account
+    PreviewVestingSchedule {
+        /// Beneficiary public key
+        beneficiary: Pubkey,
+        /// Index of the first tranche to return
+        start_index: u32,
+    },
+    /// Bind a beneficiary's releases to a token account other than their ATA
+    ///
+    /// Releases are only accepted into the bound account, so the authority cannot
+    /// route a beneficiary's tokens elsewhere.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The beneficiary
+    /// 1. `[]` The vesting state account
+    /// 2. `[writable]` The beneficiary's grant account
+    /// 3. `[]` The destination token account (for the vested mint)
+    /// 4. `[]` The mint's emergency state PDA
+    SetBeneficiaryDestination,
+    /// Set the lamport tip paid to third parties cranking `ReleaseVestedTokens`
+    ///
+    /// Tips are paid from lamports deposited into the vesting account above its
+    /// rent-exempt minimum; releases still succeed when nothing is left to tip.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The authority
+    /// 1. `[writable]` The vesting state account
+    /// 2. `[]` The mint's emergency state PDA
+    SetReleaseCrankTip {
+        /// Tip per release in lamports (at most `MAX_RELEASE_CRANK_TIP`, 0 disables)
+        lamports: u64,
+    },
+    /// Deposit tokens into a vesting pool's escrow
+    ///
+    /// Beneficiaries can only be added while the escrow holds every allocated,
+    /// unreleased token, so grants are always fully collateralized. Anyone may fund.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The funder (owner of the source account)
+    /// 1. `[]` The vesting state account
+    /// 2. `[writable]` The source token account
+    /// 3. `[writable]` The pool's escrow token account (ATA of the ["vesting_escrow", vesting] PDA)
+    /// 4. `[]` The mint account
+    /// 5. `[]` The token program (SPL Token-2022)
+    /// 6. `[]` The mint's emergency state PDA
+    FundVesting {
+        /// Amount of tokens to deposit
+        amount: u64,
+    },
+    /// Choose whether grant receipts minted from now on can be traded
+    ///
+    /// Receipts already minted keep the transferability they were minted with.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The authority
+    /// 1. `[writable]` The vesting state account
+    /// 2. `[]` The mint's emergency state PDA
+    SetReceiptTransferability {
+        /// Whether new receipts are transferable
+        transferable: bool,
+    },
+    /// Mint a Token-2022 NFT receipt for the signer's grant
+    ///
+    /// The receipt mint stores the grant parameters in its token metadata so
+    /// wallets can display the position. It is non-transferable unless the
+    /// authority enabled transferable receipts; a transferable receipt can be
+    /// redeemed by its holder to take over the grant.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The beneficiary (pays for the receipt accounts)
+    /// 1. `[]` The vesting state account
+    /// 2. `[writable]` The beneficiary's grant account
+    /// 3. `[writable]` The receipt mint (PDA: ["grant_receipt", grant])
+    /// 4. `[writable]` The beneficiary's receipt token account (ATA)
+    /// 5. `[]` The token program (SPL Token-2022)
+    /// 6. `[]` The associated token account program
+    /// 7. `[]` The system program
+    /// 8. `[]` The mint's emergency state PDA
+    MintGrantReceipt,
+    /// Take over a grant by presenting its transferable receipt
+    ///
+    /// The grant moves to the holder exactly as in `AcceptBeneficiaryTransfer`.
+    /// The receipt itself stays with the holder.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The receipt holder (pays for the new grant account)
+    /// 1. `[]` The vesting state account
+    /// 2. `[writable]` The current grant account
+    /// 3. `[writable]` The new grant account (PDA: ["vesting_grant", vesting, holder])
+    /// 4. `[]` The holder's receipt token account
+    /// 5. `[]` The system program
+    /// 6. `[]` The mint's emergency state PDA
+    RedeemGrantReceipt {
+        /// Current beneficiary public key
+        beneficiary: Pubkey,
+    },
+    /// Return the deployed program's version and capabilities without modifying state
+    ///
+    /// The result is written as a Borsh-encoded `ProgramVersion` to the return data.
+    ///
+    /// Accounts expected: none
+    GetVersion,
+    /// Freeze time accrual for one grant or a whole vesting account
+    ///
+    /// Tokens vested before the pause stay releasable. Once resumed, every later
+    /// unlock is shifted by the time spent paused.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The authority
+    /// 1. `[writable]` The vesting state account
+    /// 2. `[]` The mint's emergency state PDA
+    /// 3. `[writable]` The beneficiary's grant account (only when pausing a single grant)
+    PauseVesting {
+        /// Beneficiary whose grant to pause; None pauses the whole vesting account
+        beneficiary: Option<Pubkey>,
+    },
+    /// Resume time accrual paused by `PauseVesting`
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The authority
+    /// 1. `[writable]` The vesting state account
+    /// 2. `[]` The mint's emergency state PDA
+    /// 3. `[writable]` The beneficiary's grant account (only when resuming a single grant)
+    ResumeVesting {
+        /// Beneficiary whose grant to resume; None resumes the whole vesting account
+        beneficiary: Option<Pubkey>,
+    },
+    /// Emit any refund window events whose boundary has passed
+    ///
+    /// `ClaimRefund` and `WithdrawLockedFunds` announce boundaries too; this lets a
+    /// keeper announce them when nobody else interacts with the presale. Anyone may call it.
+    ///
+    /// Accounts expected:
+    /// 0. `[writable]` The presale state account
+    /// 1. `[]` The mint's emergency state PDA
+    AnnounceRefundWindow,
+    /// Report how much a beneficiary could release right now, without modifying state
+    ///
+    /// The result is written as a Borsh-encoded `ClaimableAmount` to the return data
+    /// and logged, so UIs can show pending amounts by simulating this instruction.
+    ///
+    /// Accounts expected:
+    /// 0. `[]` The vesting state account
+    /// 1. `[]` The beneficiary's grant account
+    GetClaimableAmount {
+        /// Beneficiary public key
+        beneficiary: Pubkey,
+    },
+    /// Allow (or stop allowing) anyone to trigger the buyer's `ClaimRefund`
+    ///
+    /// Refunds triggered by others can only be paid into the buyer's ATA for the
+    /// stablecoin they contributed, recorded when enabling.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The buyer
+    /// 1. `[writable]` The presale state account
+    /// 2. `[]` The buyer's associated token account for the contributed stablecoin
+    /// 3. `[]` The mint's emergency state PDA
+    SetKeeperRefund {
+        /// Whether keeper-triggered refunds are allowed
+        enabled: bool,
+    },
+    /// Announce a locked treasury withdrawal, starting its public notice delay
+    ///
+    /// Emits `WithdrawNoticed`; `ExecuteWithdraw` succeeds `WITHDRAW_NOTICE_DELAY`
+    /// seconds later for the same stablecoin and destination. A new notice
+    /// replaces the pending one and restarts the delay.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The authority
+    /// 1. `[writable]` The presale state account
+    /// 2. `[]` The locked treasury stablecoin account
+    /// 3. `[]` The destination treasury stablecoin account
+    /// 4. `[]` The stablecoin mint
+    /// 5. `[]` The mint's emergency state PDA
+    NoticeWithdraw,
+    /// Withdraw remaining locked funds after the refund period and a matured notice
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The authority (or the mint's timelock, writable and unsigned)
+    /// 1. `[writable]` The presale state account
+    /// 2. `[writable]` The locked treasury stablecoin account (source)
+    /// 3. `[writable]` The destination treasury stablecoin account
+    /// 4. `[]` The locked treasury authority (PDA)
+    /// 5. `[]` The stablecoin token program
+    /// 6. `[]` The stablecoin mint
+    /// 7. `[]` The mint's emergency state PDA
+    ExecuteWithdraw,
+    /// Tick an item of the presale's launch checklist
+    ///
+    /// Items backed by program accounts are verified against them; liquidity is
+    /// confirmed (or waived) by the authority.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The authority
+    /// 1. `[writable]` The presale state account
+    /// 2. `[]` The mint's emergency state PDA
+    /// 3. Item accounts:
+    ///    - `MetadataFinalized`: `[]` the token metadata account
+    ///    - `VestingFunded`: `[]` the vesting state account, `[]` its escrow token account
+    ///    - `ControllerInitialized`: `[]` the autonomous supply controller account
+    ///    - `LiquiditySeeded` / `LiquidityWaived`: none
+    CompleteLaunchChecklistItem {
+        /// Item to tick
+        item: LaunchChecklistItem,
+    },
+    /// Set the Pyth feed id of the controller's asset, which `PythPull` sources
+    /// are validated against
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The controller authority
+    /// 1. `[writable]` The oracle controller account
+    SetPythFeedId {
+        /// Feed id, or None to clear it (only when no `PythPull` source is configured)
+        feed_id: Option<[u8; 32]>,
+    },
+    /// Smooth the consensus price published by an oracle controller with an
+    /// exponential moving average; the unsmoothed price is kept as `spot_price`
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The controller authority
+    /// 1. `[writable]` The oracle controller account
+    SetConsensusEma {
+        /// Weight of each new spot price in basis points (0 turns smoothing off)
+        alpha_bps: u16,
+    },
+    /// Set the RedStone feed and authorized signers that `Redstone` sources of an
+    /// oracle controller are verified against
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The controller authority
+    /// 1. `[writable]` The oracle controller account
+    SetRedstoneConfig {
+        /// Feed configuration, or None to clear it (only when no `Redstone` source is configured)
+        config: Option<RedstoneConfig>,
+    },
+    /// Remove an oracle source from an oracle controller
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The controller authority
+    /// 1. `[writable]` The oracle controller account
+    RemoveOracleSource {
+        /// Oracle account of the source
+        oracle: Pubkey,
+    },
+    /// Include or exclude an oracle source from consensus without removing it
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The controller authority
+    /// 1. `[writable]` The oracle controller account
+    SetOracleSourceActive {
+        /// Oracle account of the source
+        oracle: Pubkey,
+        /// Whether the source takes part in consensus
+        is_active: bool,
+    },
+    /// Change the consensus parameters of an oracle source; None leaves a value unchanged
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The controller authority
+    /// 1. `[writable]` The oracle controller account
+    UpdateOracleSourceConfig {
+        /// Oracle account of the source
+        oracle: Pubkey,
+        /// Weight for consensus calculation (0-100)
+        weight: Option<u8>,
+        /// Maximum allowed price deviation from consensus (in basis points)
+        max_deviation_bps: Option<u16>,
+        /// Maximum allowed staleness in seconds
+        max_staleness_seconds: Option<u32>,
+        /// Whether the source must be present for consensus
+        is_required: Option<bool>,
+    },
+    /// Set the Chainlink Data Streams feed and DON signers that submitted reports
+    /// are verified against
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The controller authority
+    /// 1. `[writable]` The oracle controller account
+    SetChainlinkStreamsConfig {
+        /// Feed configuration, or None to clear it (only when no `ChainlinkStreams` source is configured)
+        config: Option<ChainlinkStreamsConfig>,
+    },
+    /// Verify a Chainlink Data Streams report and store it as the controller's
+    /// latest report; only reports newer than the stored one are accepted
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The keeper (pays for the report account on first submission)
+    /// 1. `[]` The oracle controller account
+    /// 2. `[writable]` The controller's report account (PDA: ["chainlink_streams", controller])
+    /// 3. `[]` System program
+    SubmitChainlinkStreamsReport {
+        /// Full report as returned by the Data Streams API
+        report: Vec<u8>,
+    },
+    /// Set the rule deciding whether enough sources contributed to an oracle
+    /// controller's consensus
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The controller authority
+    /// 1. `[writable]` The oracle controller account
+    SetQuorumPolicy {
+        /// Quorum policy
+        policy: QuorumPolicy,
+    },
+    /// Return an oracle controller's circuit-breaker state and recent incidents
+    /// without modifying state
+    ///
+    /// The result is written as a Borsh-encoded `CircuitBreakerHistory` to the return data.
+    ///
+    /// Accounts expected:
+    /// 0. `[]` The oracle controller account
+    GetCircuitBreakerIncidents,
+    /// Configure how an oracle controller's circuit breaker recovers
+    ///
+    /// With auto-recovery on, the first consensus update after the cooldown runs in
+    /// probation: success clears the breaker, failure trips it again. Each trip
+    /// before the breaker clears doubles the cooldown, up to `max_cooldown_seconds`.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The controller authority
+    /// 1. `[writable]` The oracle controller account
+    SetCircuitBreakerRecovery {
+        /// Whether the breaker clears itself after a successful probation update
+        auto_recovery: bool,
+        /// Cooldown after the first trip (in seconds)
+        cooldown_seconds: u32,
+        /// Cap on the escalated cooldown (in seconds)
+        max_cooldown_seconds: u32,
+    },
+    /// Set the oldest consensus an oracle controller serves to purchases, supply
+    /// adjustments and informational reads
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The controller authority
+    /// 1. `[writable]` The oracle controller account
+    SetStalenessPolicy {
+        /// Maximum consensus age per kind of consumer
+        policy: StalenessPolicy,
+    },
+    /// Create the bug-bounty pool of a mint with the guardians who approve awards
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The bounty authority (pays for the bounty account)
+    /// 1. `[writable]` The bounty account (PDA: ["bug_bounty", mint])
+    /// 2. `[]` The mint account
+    /// 3. `[]` The escrow token account, owned by the bounty PDA
+    /// 4. `[]` The system program
+    /// 5. `[]` The mint's emergency state PDA
+    InitializeBugBounty {
+        /// Guardians who approve awards
+        guardians: Vec<Pubkey>,
+        /// Guardian signatures required to commit an award
+        guardian_threshold: u8,
+    },
+    /// Deposit tokens from a treasury token account into the bug-bounty escrow
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The owner of the treasury token account
+    /// 1. `[writable]` The bounty account
+    /// 2. `[writable]` The treasury token account
+    /// 3. `[writable]` The escrow token account
+    /// 4. `[]` The mint account
+    /// 5. `[]` The token program (SPL Token-2022)
+    /// 6. `[]` The mint's emergency state PDA
+    FundBugBounty {
+        /// Amount of tokens to deposit
+        amount: u64,
+    },
+    /// Commit a bounty award for a disclosure, reserving it in the escrow
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The payer for the award account
+    /// 1. `[writable]` The bounty account
+    /// 2. `[writable]` The award account (PDA: ["bug_bounty_award", bounty, disclosure_hash])
+    /// 3. `[]` The escrow token account
+    /// 4. `[]` The system program
+    /// 5. `[]` The mint's emergency state PDA
+    /// 6. `[signer]` Approving guardians, at least the bounty's threshold
+    ///    (variable number, passed as remaining accounts)
+    CommitBugBountyAward {
+        /// Hash of the disclosure report
+        disclosure_hash: [u8; 32],
+        /// Researcher receiving the award
+        researcher: Pubkey,
+        /// Awarded amount
+        amount: u64,
+    },
+    /// Pay a committed bounty award to the researcher's token account (anyone may call)
+    ///
+    /// Accounts expected:
+    /// 0. `[writable]` The bounty account
+    /// 1. `[writable]` The award account
+    /// 2. `[writable]` The escrow token account
+    /// 3. `[writable]` The researcher's token account
+    /// 4. `[]` The mint account
+    /// 5. `[]` The token program (SPL Token-2022)
+    /// 6. `[]` The mint's emergency state PDA
+    PayBugBountyAward,
+    /// Open a 1:1 migration from a v1 mint to a v2 mint
+    ///
+    /// Revokes the v1 mint authority, so no more v1 tokens can be minted. The v2
+    /// mint must have no supply, the same decimals and the program's
+    /// ["mint_authority", v2 mint] PDA as its mint authority.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The v1 freeze authority (pays for the migration account)
+    /// 1. `[writable]` The migration account (PDA: ["mint_migration", v1 mint])
+    /// 2. `[writable]` The v1 mint
+    /// 3. `[]` The v2 mint
+    /// 4. `[]` The swap escrow: a v1 token account owned by the migration PDA
+    /// 5. `[]` The v1 mint authority PDA (["mint_authority", v1 mint]), used if it holds the mint authority
+    /// 6. `[]` The token program (SPL Token-2022)
+    /// 7. `[]` The system program
+    /// 8. `[]` The v1 mint's emergency state PDA
+    OpenMintMigration,
+    /// Swap v1 tokens for the same amount of v2 tokens
+    ///
+    /// The v1 tokens are moved into the swap escrow and burned there; v2 tokens
+    /// are minted for what the escrow received.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The holder
+    /// 1. `[writable]` The migration account
+    /// 2. `[writable]` The holder's v1 token account
+    /// 3. `[writable]` The swap escrow
+    /// 4. `[writable]` The v1 mint
+    /// 5. `[writable]` The v2 token account to credit
+    /// 6. `[writable]` The v2 mint
+    /// 7. `[]` The v2 mint authority PDA (["mint_authority", v2 mint])
+    /// 8. `[]` The token program (SPL Token-2022)
+    /// 9. `[]` The v1 mint's emergency state PDA
+    MigrateTokens {
+        /// Amount of v1 tokens to swap
+        amount: u64,
+    },
+    /// Point the v1 supply controller at the v2 mint and its PDAs
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The migration authority
+    /// 1. `[writable]` The migration account
+    /// 2. `[writable]` The autonomous supply controller of the v1 mint
+    /// 3. `[]` The v1 mint's emergency state PDA
+    RepointSupplyController,
+    /// Choose how an oracle controller combines its sources' prices
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The controller authority
+    /// 1. `[writable]` The oracle controller account
+    SetAggregationStrategy {
+        /// Weighted mean, median, confidence-weighted median or trimmed mean
+        strategy: AggregationStrategy,
+    },
+    /// Script the clock and price sequence of a simulation
+    /// (programs built with the `simulation` feature only)
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The simulation authority (pays on first use)
+    /// 1. `[writable]` The simulation account (PDA: ["simulation"])
+    /// 2. `[writable]` The simulated price feed (PDA: ["simulated_price"])
+    /// 3. `[]` The system program
+    ConfigureSimulation {
+        /// Unix time seen by instructions that carry the simulation account
+        unix_timestamp: i64,
+        /// Prices (with 6 decimals precision) published one per step
+        prices: Vec<u64>,
+    },
+    /// Advance the simulated clock and publish the next scripted price
+    /// (programs built with the `simulation` feature only)
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The simulation authority
+    /// 1. `[writable]` The simulation account
+    /// 2. `[writable]` The simulated price feed
+    StepSimulation {
+        /// Seconds to advance the clock by
+        seconds: u32,
+    },
+    /// Create an oracle controller for an asset and list it in the authority's
+    /// feed registry, created along with the first feed
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The authority (pays for the registry and controller accounts)
+    /// 1. `[writable]` The feed registry (PDA: ["oracle_feed_registry", authority])
+    /// 2. `[writable]` The oracle controller (PDA: ["managed_feed", registry, asset_id])
+    /// 3. `[]` The system program
+    CreateManagedFeed {
+        /// Asset ID for the oracle controller (e.g., "SOL/USD"), unique within the registry
+        asset_id: String,
+        /// Minimum required oracles for consensus
+        min_required_oracles: u8,
+    },
+    /// Enter maintenance mode: refunds, vesting releases and claims keep working
+    /// while admin, supply and purchase instructions are blocked.
+    /// `EmergencyResume` returns to normal operation.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The emergency authority
+    /// 1. `[writable]` The mint's emergency state account (created by `InitializeToken`)
+    EnterMaintenanceMode {
+        /// Optional reason for the maintenance window
+        reason: Option<String>,
+    },
+    /// Create or update the depeg protection for one stablecoin of a presale.
+    /// Purchases in the stablecoin then read its USD price from the oracle controller
+    /// and are rejected or haircut when it trades outside the band.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The presale authority (pays for the guard account)
+    /// 1. `[writable]` The presale state account
+    /// 2. `[]` The stablecoin mint account
+    /// 3. `[writable]` The peg guard account (PDA: ["stablecoin_peg", presale, stablecoin_mint])
+    /// 4. `[]` The oracle controller reporting the stablecoin's USD price
+    /// 5. `[]` The system program
+    /// 6. `[]` The mint's emergency state PDA
+    SetStablecoinPegGuard {
+        /// Largest deviation from the peg accepted at face value, in basis points
+        band_bps: u16,
+        /// Action taken outside the band
+        action: DepegAction,
+    },
+    /// Set the ed25519 publisher keys whose signed prices `SignedMessage` sources accept
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The controller authority
+    /// 1. `[writable]` The oracle controller account
+    SetSignedPricePublishers {
+        /// Publisher keys; may only be emptied when no `SignedMessage` source is configured
+        publishers: Vec<Pubkey>,
+    },
+    /// Store a publisher-signed price as the controller's latest signed price;
+    /// only prices published after the stored one are accepted
+    ///
+    /// The instruction immediately preceding this one must be an ed25519
+    /// program instruction verifying the publisher's signature over
+    /// `SignedPriceReport::message(controller, price, confidence, publish_time)`.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The keeper (pays for the price account on first submission)
+    /// 1. `[]` The oracle controller account
+    /// 2. `[writable]` The controller's signed price account (PDA: ["signed_price", controller])
+    /// 3. `[]` The instructions sysvar
+    /// 4. `[]` System program
+    SubmitSignedPrice {
+        /// Registered publisher that signed the price
+        publisher: Pubkey,
+        /// Price in USD with 6 decimals
+        price: u64,
+        /// Confidence interval in USD with 6 decimals
+        confidence: u64,
+        /// Time the publisher observed the price
+        publish_time: i64,
+    },
+    /// Approve (or re-approve) the lending reserve the locked treasury may lend one
+    /// stablecoin to. Deposits open `YIELD_VENUE_REVIEW_DELAY` after the approval;
+    /// lowering the cap of an unchanged venue keeps it open.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The presale authority (pays for the venue account)
+    /// 1. `[]` The presale state account
+    /// 2. `[]` The stablecoin mint account
+    /// 3. `[writable]` The venue account (PDA: ["yield_venue", presale, stablecoin_mint])
+    /// 4. `[]` The lending program (SPL token-lending interface)
+    /// 5. `[]` The lending market
+    /// 6. `[]` The reserve, owned by the lending program
+    /// 7. `[]` The reserve collateral token account owned by the locked treasury authority
+    /// 8. `[]` The insurance fund's stablecoin account that receives the yield
+    /// 9. `[]` The system program
+    /// 10. `[]` The mint's emergency state PDA
+    ApproveYieldVenue {
+        /// Maximum principal deployed at once (0 stops new deposits)
+        deposit_cap: u64,
+    },
+    /// Lend idle locked treasury stablecoins to the approved venue. The treasury must
+    /// keep enough to pay every outstanding refund.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The presale authority
+    /// 1. `[]` The presale state account
+    /// 2. `[writable]` The venue account
+    /// 3. `[writable]` The locked treasury stablecoin account
+    /// 4. `[]` The locked treasury authority PDA
+    /// 5. `[writable]` The venue's collateral account
+    /// 6. `[writable]` The reserve
+    /// 7. `[writable]` The reserve liquidity supply
+    /// 8. `[writable]` The reserve collateral mint
+    /// 9. `[]` The lending market
+    /// 10. `[]` The lending market authority
+    /// 11. `[]` The lending program
+    /// 12. `[]` The clock sysvar
+    /// 13. `[]` The token program
+    /// 14. `[]` The mint's emergency state PDA
+    DeployTreasuryYield {
+        /// Stablecoin amount to deposit
+        amount: u64,
+    },
+    /// Redeem venue collateral back into the locked treasury, routing the yield to the
+    /// insurance fund. The presale authority may recall at any time; anyone may once
+    /// the treasury no longer covers its outstanding refunds.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The caller
+    /// 1. `[]` The presale state account
+    /// 2. `[writable]` The venue account
+    /// 3. `[writable]` The locked treasury stablecoin account
+    /// 4. `[]` The locked treasury authority PDA
+    /// 5. `[writable]` The venue's collateral account
+    /// 6. `[writable]` The reserve
+    /// 7. `[writable]` The reserve collateral mint
+    /// 8. `[writable]` The reserve liquidity supply
+    /// 9. `[]` The lending market
+    /// 10. `[]` The lending market authority
+    /// 11. `[]` The lending program
+    /// 12. `[]` The clock sysvar
+    /// 13. `[]` The token program
+    /// 14. `[]` The stablecoin mint account
+    /// 15. `[writable]` The insurance fund's stablecoin account
+    RecallTreasuryYield {
+        /// Collateral to redeem, or None for all of it
+        collateral_amount: Option<u64>,
+    },
+    /// Queue new mint/burn thresholds and rates for an autonomous supply controller
+    ///
+    /// The parameters apply from the first evaluation at least
+    /// `SUPPLY_PARAMETERS_TIMELOCK` after they were queued; queueing again
+    /// replaces the pending parameters and restarts the delay.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The controller authority (or the mint's timelock, writable and unsigned)
+    /// 1. `[writable]` The controller state account
+    /// 2. `[]` The mint's emergency state PDA
+    UpdateControllerParameters {
+        /// Parameters to queue, or None to cancel the pending ones
+        parameters: Option<SupplyParameters>,
+    },
+    /// Set the per-window caps on autonomous mints and burns
+    ///
+    /// The current window's counts carry over, so changing the limit never
+    /// frees up capacity already used.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The controller authority
+    /// 1. `[writable]` The controller state account
+    /// 2. `[]` The mint's emergency state PDA
+    SetSupplyRateLimit {
+        /// Window length in seconds (1 hour to 1 year)
+        window_seconds: i64,
+        /// Most tokens minted, and separately burned, per window
+        max_amount_per_window: u64,
+        /// Most mints and burns together per window
+        max_operations_per_window: u8,
+    },
+    /// Create the presale's `SaleAnalytics` account; every later purchase must
+    /// present it and adds to its per-day aggregates
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The presale authority (pays for the analytics account)
+    /// 1. `[writable]` The presale state account
+    /// 2. `[writable]` The analytics account (PDA: ["sale_analytics", presale])
+    /// 3. `[]` The system program
+    /// 4. `[]` The mint's emergency state PDA
+    InitializeSaleAnalytics,
+    /// Set how `DistributeSupplyTreasury` splits autonomously minted tokens
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The controller authority
+    /// 1. `[writable]` The controller state account
+    /// 2. `[]` The staking rewards token account
+    /// 3. `[]` The liquidity token account
+    /// 4. `[]` The operations token account
+    /// 5. `[]` The mint's emergency state PDA
+    SetSupplyDistribution {
+        /// Destinations and their shares, adding up to 10000 bps
+        distribution: SupplyDistribution,
+    },
+    /// Pay the supply treasury's balance out by the controller's distribution;
+    /// anyone may send it
+    ///
+    /// Accounts expected:
+    /// 0. `[]` The controller state account
+    /// 1. `[]` The mint account
+    /// 2. `[writable]` The supply treasury token account
+    /// 3. `[]` The supply treasury PDA (["supply_treasury", mint])
+    /// 4. `[writable]` The staking rewards token account
+    /// 5. `[writable]` The liquidity token account
+    /// 6. `[writable]` The operations token account
+    /// 7. `[]` The token program
+    /// 8. `[]` The mint's emergency state PDA
+    DistributeSupplyTreasury,
+    /// Propose handing the token metadata to a new authority (e.g. a marketing
+    /// multisig); the authority only changes once the new key signs
+    /// `AcceptMetadataAuthority`. Mint, freeze and fee authorities are unaffected.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The current metadata authority
+    /// 1. `[writable]` The metadata account
+    /// 2. `[]` The mint's emergency state PDA
+    UpdateMetadataAuthority {
+        /// Key to take over the metadata (None cancels a pending proposal)
+        new_authority: Option<Pubkey>,
+    },
+    /// Accept a proposed metadata authority handover
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The proposed metadata authority
+    /// 1. `[writable]` The metadata account
+    /// 2. `[]` The mint's emergency state PDA
+    AcceptMetadataAuthority,
+    /// Create the ledger every autonomous mint and burn of a supply controller is
+    /// recorded in; anyone may pay for it, and mints and burns fail until it exists
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The payer
+    /// 1. `[]` The controller state account
+    /// 2. `[writable]` The ledger account (PDA: ["supply_ledger", controller])
+    /// 3. `[]` The system program
+    /// 4. `[]` The mint's emergency state PDA
+    InitializeSupplyLedger,
+    /// Set the cooldowns between consecutive autonomous mints and between
+    /// consecutive burns; each is measured from the last operation of its kind
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The controller authority
+    /// 1. `[writable]` The controller state account
+    /// 2. `[]` The mint's emergency state PDA
+    SetSupplyCooldowns {
+        /// Seconds between consecutive mints (at most 1 year)
+        mint_cooldown_seconds: u32,
+        /// Seconds between consecutive burns (at most 1 year)
+        burn_cooldown_seconds: u32,
+    },
+    /// Set or clear the wallet display hints in the token metadata
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The metadata authority (pays for growing the metadata account)
+    /// 1. `[writable]` The metadata account
+    /// 2. `[]` The mint account
+    /// 3. `[]` The mint's emergency state PDA
+    /// 4. `[]` (Optional) The system program, needed when the account must grow
+    SetMetadataDisplayHints {
+        /// Display decimals and ticker aliases, or None to clear them
+        display_hints: Option<MetadataDisplayHints>,
+    },
+    /// Refresh a multi-oracle controller's price, run whichever autonomous mint or
+    /// burn it calls for, and pay the keeper `SUPPLY_KEEPER_REWARD_BPS` of the
+    /// amount from the supply treasury; anyone may send it
+    ///
+    /// A crank that finds nothing to do succeeds without a reward.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The keeper
+    /// 1. `[writable]` The keeper's token account receiving the reward
+    /// 2. `[writable]` The controller state account
+    /// 3. `[writable]` The mint account
+    /// 4. `[]` The mint authority PDA
+    /// 5. `[writable]` The supply treasury token account
+    /// 6. `[]` The supply treasury PDA (["supply_treasury", mint])
+    /// 7. `[writable]` The burn treasury token account
+    /// 8. `[]` The burn treasury PDA
+    /// 9. `[]` The token program
+    /// 10. `[]` The multi-oracle controller the supply controller is priced from
+    /// 11. `[writable]` The supply action ledger (PDA: ["supply_ledger", controller])
+    /// 12. `[writable]` The mint's burn log (PDA: ["burn_log", mint])
+    /// 13. `[]` The mint's emergency state PDA
+    CrankSupplyController,
+    /// Send tokens from a mint's liquidity escrow, e.g. to seed a pool; only the
+    /// authority that initialized the token may release them
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The token authority
+    /// 1. `[]` The mint account
+    /// 2. `[]` The liquidity escrow PDA (["liquidity_escrow", mint])
+    /// 3. `[writable]` The liquidity escrow token account
+    /// 4. `[writable]` The destination token account
+    /// 5. `[]` The token program
+    /// 6. `[]` The emergency state PDA (`["emergency_state", mint]`)
+    ReleaseLiquidityEscrow {
+        /// Tokens to release
+        amount: u64,
+    },
+    /// Reset a supply controller's `current_supply` to the mint's actual supply.
+    /// Anyone may send it; `SupplyDriftDetected` is emitted when they differed
+    ///
+    /// Accounts expected:
+    /// 0. `[writable]` The supply controller account
+    /// 1. `[]` The mint account
+    /// 2. `[]` The mint's emergency state PDA
+    SyncSupply,
+    /// Harvest withheld transfer fees into the mint and sweep them into the burn
+    /// treasury, so autonomous burns are funded by protocol fees
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The mint's withdraw-withheld authority; anyone may send the
+    ///    instruction once that authority is the fee sweep PDA
+    ///    (["fee_sweep_authority", mint]), which is then passed unsigned
+    /// 1. `[writable]` The supply controller account
+    /// 2. `[writable]` The mint account
+    /// 3. `[writable]` The burn treasury token account
+    /// 4. `[]` The token program (SPL Token-2022)
+    /// 5. `[]` The mint's emergency state PDA
+    /// 6. `[writable]` Token accounts to harvest withheld fees from (optional,
+    ///    variable number, passed as remaining accounts)
+    SweepWithheldFeesToBurn,
+    /// Create the log every burn of a mint's tokens is recorded in; anyone may pay
+    /// for it, and autonomous burns fail until it exists
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The payer
+    /// 1. `[]` The mint account
+    /// 2. `[writable]` The burn log account (PDA: ["burn_log", mint])
+    /// 3. `[]` The system program
+    /// 4. `[]` The mint's emergency state PDA
+    InitializeBurnLog,
+    /// Burn tokens from the holder's own account and record them in the burn log,
+    /// priced at the supply controller's current price
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The token account owner
+    /// 1. `[writable]` The token account to burn from
+    /// 2. `[writable]` The mint account
+    /// 3. `[]` The token program
+    /// 4. `[writable]` The supply controller of the mint
+    /// 5. `[writable]` The mint's burn log (PDA: ["burn_log", mint])
+    /// 6. `[]` The mint's emergency state PDA
+    BurnTokens {
+        /// Tokens to burn
+        amount: u64,
+    },
+    /// Declare a critical failure before launch on behalf of the mint's guardian
+    /// set (its bug-bounty guardians). Purchases stop for good, the presale can no
+    /// longer launch, and locked and dev-treasury refunds open immediately,
+    /// skipping the 1-year dev refund delay. The decision is kept on the presale
+    /// and emitted as `ForcedRefundDeclared`.
+    ///
+    /// Accounts expected:
+    /// 0. `[writable]` The presale state account
+    /// 1. `[]` The bug bounty account of the presale mint (PDA: ["bug_bounty", mint])
+    /// 2. `[signer]` Approving guardians, at least the bounty's threshold
+    ///    (variable number, passed as remaining accounts)
+    /// 3. `[]` The dev refund vault's stablecoin token accounts, optional; the
+    ///    declaration logs how far they fall short of the dev halves owed
+    DeclareForcedRefund {
+        /// Hash of the off-chain incident report
+        reason_hash: [u8; 32],
+    },
+    /// Set aside lamports that reimburse a keeper for the transaction fees of other
+    /// users' `ClaimRefund` and `ReleaseVestedTokens`, so claimants need no SOL.
+    /// The authority funds the budget, which is held by the sponsorship account.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The authority (pays the rent and the budget)
+    /// 1. `[writable]` The fee sponsorship account (PDA: ["fee_sponsorship", mint])
+    /// 2. `[]` The mint account
+    /// 3. `[]` The system program
+    /// 4. `[]` The mint's emergency state PDA
+    InitializeFeeSponsorship {
+        /// Fee payer to reimburse
+        keeper: Pubkey,
+        /// Most lamports reimbursed per claim
+        per_claim_lamports: u64,
+        /// Lamports deposited for reimbursements
+        budget_lamports: u64,
+    },
+    /// Change a fee sponsorship's keeper and per-claim cap, and add to its budget
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The sponsorship authority
+    /// 1. `[writable]` The fee sponsorship account
+    /// 2. `[]` The system program
+    /// 3. `[]` The mint's emergency state PDA
+    UpdateFeeSponsorship {
+        /// Fee payer to reimburse
+        keeper: Pubkey,
+        /// Most lamports reimbursed per claim
+        per_claim_lamports: u64,
+        /// Lamports added to the budget
+        additional_budget_lamports: u64,
+    },
+    /// Create an M-of-N multisig that can be set as an authority in place of a
+    /// single key. `EmergencyPause`, `EnterMaintenanceMode`, `EmergencyResume`,
+    /// `UpdateControllerParameters`, `SetSupplyRateLimit`, `SetSupplyCooldowns`,
+    /// `NoticeWithdraw` and `ExecuteWithdraw`/`WithdrawLockedFunds` accept the
+    /// multisig, unsigned, as their authority when at least `threshold` of its keys
+    /// sign and are listed after the instruction's own accounts. The keys cannot
+    /// be changed; rotate by creating a new multisig and moving the authority to it.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The payer
+    /// 1. `[writable]` The new multisig account (PDA: ["multisig", base])
+    /// 2. `[signer]` The base key, a fresh key the address is derived from
+    /// 3. `[]` The system program
+    InitializeMultisig {
+        /// Registered keys
+        signers: Vec<Pubkey>,
+        /// Signatures required
+        threshold: u8,
+    },
+    /// Move a stablecoin balance that landed in a non-canonical token account of
+    /// the presale's locked treasury (e.g. after a stablecoin mint migration) into
+    /// the treasury's canonical ATA for that mint. Needs a quorum of the mint's
+    /// guardians (its bug-bounty guardians) and emits `TreasurySwept`.
+    ///
+    /// Accounts expected:
+    /// 0. `[writable]` The presale state account
+    /// 1. `[]` The bug bounty account of the presale mint (PDA: ["bug_bounty", mint])
+    /// 2. `[]` The locked treasury authority (PDA: ["locked_treasury", presale])
+    /// 3. `[writable]` The non-canonical treasury token account to sweep
+    /// 4. `[writable]` The locked treasury authority's ATA for the same mint
+    /// 5. `[]` The stablecoin mint
+    /// 6. `[]` The stablecoin token program
+    /// 7. `[signer]` Approving guardians, at least the bounty's threshold
+    ///    (variable number, passed as remaining accounts)
+    SweepTreasuryToCanonical,
+    /// Create a mint's timelock. Once it is set as the authority of
+    /// `SetTransferFee` (fee authority), `UpdateControllerParameters`,
+    /// `WithdrawLockedFunds`/`ExecuteWithdraw` or `RescueTokens` (emergency
+    /// authority), those calls must be queued with `QueueTimelockOperation` and
+    /// only run, with the timelock as their writable, unsigned authority, after
+    /// `delay` seconds.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The mint's emergency or program authority (or a multisig, whose keys follow the accounts below)
+    /// 1. `[signer, writable]` The payer
+    /// 2. `[writable]` The timelock account (PDA: ["timelock", mint])
+    /// 3. `[]` The emergency state PDA (`["emergency_state", mint]`)
+    /// 4. `[]` The mint account
+    /// 5. `[]` The system program
+    InitializeTimelock {
+        /// Authority that queues operations
+        proposer: Pubkey,
+        /// Seconds between queueing and execution (1 to 30 days)
+        delay: i64,
+    },
+    /// Queue a sensitive call on a timelock; emits `TimelockOperationQueued`
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The proposer (or a multisig, whose keys follow the accounts below)
+    /// 1. `[writable]` The timelock account
+    /// 2. `[]` The mint's emergency state PDA
+    QueueTimelockOperation {
+        /// Call to queue
+        operation: TimelockedOperation,
+    },
+    /// Drop a queued call before it runs; emits `TimelockOperationCancelled`
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The mint's emergency or program authority (or a multisig, whose keys follow the accounts below)
+    /// 1. `[writable]` The timelock account
+    /// 2. `[]` The emergency state PDA (`["emergency_state", mint]`)
+    CancelTimelockOperation {
+        /// Identifier of the queued call
+        operation_id: u64,
+    },
+    /// Create a mint's access control with its first admin. Set as a stored
+    /// authority, it approves an instruction when a member holding the role
+    /// `VCoinInstruction::required_role` names signs after the instruction's own
+    /// accounts, so fees, oracles, emergencies and the treasury can be run by
+    /// different keys.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The mint's emergency or program authority (or a multisig, whose keys follow the accounts below)
+    /// 1. `[signer, writable]` The payer
+    /// 2. `[writable]` The access control account (PDA: ["access_control", mint])
+    /// 3. `[]` The emergency state PDA (`["emergency_state", mint]`)
+    /// 4. `[]` The mint account
+    /// 5. `[]` The system program
+    InitializeAccessControl {
+        /// First key holding the `Admin` role
+        admin: Pubkey,
+    },
+    /// Give a key a role; emits `RoleGranted`
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` An admin of the access control (or a multisig, whose keys follow the accounts below)
+    /// 1. `[writable]` The access control account
+    /// 2. `[]` The mint's emergency state PDA
+    GrantRole {
+        /// Key to give the role
+        member: Pubkey,
+        /// Role to give
+        role: Role,
+    },
+    /// Take a role from a key; the last admin cannot be removed. Emits `RoleRevoked`.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` An admin of the access control (or a multisig, whose keys follow the accounts below)
+    /// 1. `[writable]` The access control account
+    /// 2. `[]` The mint's emergency state PDA
+    RevokeRole {
+        /// Key to take the role from
+        member: Pubkey,
+        /// Role to take
+        role: Role,
+    },
+}
+
+/// Maximum tranches in one `VestingSchedulePreview` (keeps it under the 1 KiB return data limit)
+pub const MAX_PREVIEW_TRANCHES: usize = 60;
+
+/// Result of `PreviewVestingSchedule`, returned via the transaction return data
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct VestingSchedulePreview {
+    /// Beneficiary the timeline belongs to
+    pub beneficiary: Pubkey,
+    /// Number of tranches in the full timeline
+    pub total_tranches: u32,
+    /// Index of the first tranche in `tranches`
+    pub start_index: u32,
+    /// (unlock_time, amount) tranches in unlock order
+    pub tranches: Vec<(i64, u64)>,
+}
+
+/// Result of `GetCircuitBreakerIncidents`, returned via the transaction return data
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct CircuitBreakerHistory {
+    /// Oracle controller the incidents belong to
+    pub controller: Pubkey,
+    /// Whether the circuit breaker is currently active
+    pub active: bool,
+    /// Reason for the current activation
+    pub reason: Option<String>,
+    /// Recent incidents, oldest first
+    pub incidents: Vec<CircuitBreakerIncident>,
+}
+
+/// Presale rounds with per-round pricing and whitelists
+pub const FEATURE_PRESALE_ROUNDS: u64 = 1 << 0;
+/// Read-only purchase checks (`ValidatePurchase`)
+pub const FEATURE_PURCHASE_VALIDATION: u64 = 1 << 1;
+/// Treasury coverage alarms and structured events
+pub const FEATURE_TREASURY_ALARM: u64 = 1 << 2;
+/// KYC credentials and compliance commitments
+pub const FEATURE_COMPLIANCE: u64 = 1 << 3;
+/// Merkle airdrops
+pub const FEATURE_AIRDROP: u64 = 1 << 4;
+/// Multiple labelled vesting pools per mint, with per-beneficiary grant PDAs
+pub const FEATURE_VESTING_POOLS: u64 = 1 << 5;
+/// Escrow-backed, permissionless vesting releases with crank tips
+pub const FEATURE_PERMISSIONLESS_RELEASE: u64 = 1 << 6;
+/// Token-2022 NFT receipts for vesting grants
+pub const FEATURE_GRANT_RECEIPTS: u64 = 1 << 7;
+/// Oracle controllers accept Pyth pull-oracle price updates
+pub const FEATURE_PYTH_PULL: u64 = 1 << 8;
+/// Oracle controllers accept signed RedStone payloads
+pub const FEATURE_REDSTONE: u64 = 1 << 9;
+/// Oracle controllers accept keeper-submitted Chainlink Data Streams reports
+pub const FEATURE_CHAINLINK_STREAMS: u64 = 1 << 10;
+/// Oracle controllers keep a queryable circuit-breaker incident history
+pub const FEATURE_CIRCUIT_BREAKER_HISTORY: u64 = 1 << 11;
+/// Oracle circuit breakers can recover automatically with escalating cooldowns
+pub const FEATURE_CIRCUIT_BREAKER_RECOVERY: u64 = 1 << 12;
+/// Guardian-approved bug-bounty escrow
+pub const FEATURE_BUG_BOUNTY: u64 = 1 << 13;
+/// 1:1 holder migration to a v2 mint
+pub const FEATURE_MINT_MIGRATION: u64 = 1 << 14;
+/// Scripted clock and prices for scenario tests; set only in builds with the
+/// `simulation` cargo feature, which must never be deployed
+pub const FEATURE_SIMULATION: u64 = 1 << 15;
+
+/// Features enabled in this build, reported by `GetVersion`
+pub const ENABLED_FEATURES: u64 = FEATURE_PRESALE_ROUNDS
+    | FEATURE_PURCHASE_VALIDATION
+    | FEATURE_TREASURY_ALARM
+    | FEATURE_COMPLIANCE
+    | FEATURE_AIRDROP
+    | FEATURE_VESTING_POOLS
+    | FEATURE_PERMISSIONLESS_RELEASE
+    | FEATURE_GRANT_RECEIPTS
+    | FEATURE_PYTH_PULL
+    | FEATURE_REDSTONE
+    | FEATURE_CHAINLINK_STREAMS
+    | FEATURE_CIRCUIT_BREAKER_HISTORY
+    | FEATURE_CIRCUIT_BREAKER_RECOVERY
+    | FEATURE_BUG_BOUNTY
+    | FEATURE_MINT_MIGRATION
+    | if cfg!(feature = "simulation") { FEATURE_SIMULATION } else { 0 };
+
+/// Result of `GetVersion`, returned via the transaction return data
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct ProgramVersion {
+    /// Crate version the program was built from (semver)
+    pub version: String,
+    /// (account type, layout version) for every program account type
+    pub state_layouts: Vec<(String, u16)>,
+    /// Bitmask of `FEATURE_*` flags enabled in this build
+    pub features: u64,
+}
+
+impl ProgramVersion {
+    /// Version information for this build
+    pub fn current() -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            state_layouts: crate::state::STATE_LAYOUT_VERSIONS
+                .iter()
+                .map(|(account, version)| (account.to_string(), *version))
+                .collect(),
+            features: ENABLED_FEATURES,
+        }
+    }
+
+    /// Whether the deployed program supports a `FEATURE_*` flag
+    pub fn supports(&self, feature: u64) -> bool {
+        self.features & feature == feature
+    }
+}
+
+/// Result of `GetClaimableAmount`, returned via the transaction return data
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct ClaimableAmount {
+    /// Beneficiary the grant belongs to
+    pub beneficiary: Pubkey,
+    /// Tokens vested so far
+    pub vested: u64,
+    /// Tokens already released
+    pub released: u64,
+    /// Tokens `ReleaseVestedTokens` would release now
+    pub claimable: u64,
+    /// Time the amounts were computed at
+    pub timestamp: i64,
+}
+
+/// Result of `ValidatePurchase`, returned via the transaction return data
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct PurchaseValidation {
+    /// Whether `BuyTokensWithStablecoin` would accept the purchase right now
+    pub is_valid: bool,
+    /// Program error code the purchase would fail with (0 when valid)
+    pub error_code: u64,
+    /// Amount that would be charged in micro-USD (below the requested amount on a partial fill)
+    pub accepted_amount: u64,
+    /// Tokens the buyer would receive
+    pub tokens_to_receive: u64,
+    /// Price per whole token the purchase would use
+    pub token_price: u64,
+    /// Raise capacity left in the presale (or round) before this purchase
+    pub remaining_cap: u64,
+}
+
+/// Parameters for initializing a token
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct InitializeTokenParams {
+    /// Authority that can manage the token
+    pub authority: Pubkey,
+    /// The mint account
+    pub mint: Pubkey,
+    /// Metadata account
+    pub metadata: Pubkey,
+    /// Name of the token
+    pub name: String,
+    /// Symbol of the token
+    pub symbol: String,
+    /// Number of decimals
+    pub decimals: u8,
+    /// Initial supply
+    pub initial_supply: u64,
+    /// Transfer fee basis points (optional, default 500 = 5%)
+    pub transfer_fee_basis_points: Option<u16>,
+    /// Maximum fee rate as percentage of the transfer amount (optional, default 1)
+    pub maximum_fee_rate: Option<u8>,
+    /// Authority that can pause and resume the token (defaults to the authority)
+    pub emergency_authority: Option<Pubkey>,
+    /// Escrows splitting the initial supply (empty mints it to the authority)
+    pub allocations: Vec<InitialAllocation>,
+    /// Shares of the initial supply by category (empty records no plan)
+    pub allocation_plan: Vec<AllocationShare>,
+}
+
+/// Parameters for initializing a presale
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct InitializePresaleParams {
+    /// Authority that can manage the presale
+    pub authority: Pubkey,
+    /// The presale state account
+    pub presale: Pubkey,
+    /// The mint account
+    pub mint: Pubkey,
+    /// The treasury account
+    pub treasury: Pubkey,
+    /// Start time of the presale
+    pub start_time: i64,
+    /// End time of the presale
+    pub end_time: i64,
+    /// Token price in USD (as u64 with 6 decimals precision)
+    pub token_price: u64,
+    /// Hard cap for the presale
+    pub hard_cap: u64,
+    /// Soft cap for the presale
+    pub soft_cap: u64,
+    /// Minimum purchase amount in USD (as u64 with 6 decimals precision)
+    pub min_purchase: u64,
+    /// Maximum purchase amount in USD (as u64 with 6 decimals precision)
+    pub max_purchase: u64,
+    /// Minimum seconds between purchases from the same wallet (0 disables)
+    pub purchase_cooldown: i64,
+    /// Length in seconds of the opening window with a per-wallet cap (0 disables)
+    pub launch_window_duration: i64,
+    /// Maximum total contribution per wallet during the opening window
+    pub launch_window_max_per_wallet: u64,
+    /// Accept only the remaining capacity when a purchase crosses the hard cap
+    pub allow_partial_fill: bool,
+    /// Maximum USD raised per rolling 24h window (0 disables)
+    pub daily_raise_cap: u64,
+}
+
+/// Parameters for initializing a presale round
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct InitializePresaleRoundParams {
+    /// Authority of the parent presale
+    pub authority: Pubkey,
+    /// The presale state account
+    pub presale: Pubkey,
+    /// Mint sold by the presale
+    pub mint: Pubkey,
+    /// Round index, must equal the number of rounds created so far
+    pub round_index: u8,
+    /// Stage of the round
+    pub kind: PresaleRoundKind,
+    /// Start time of the round
+    pub start_time: i64,
+    /// End time of the round
+    pub end_time: i64,
+    /// Token price in USD (as u64 with 6 decimals precision)
+    pub token_price: u64,
+    /// Hard cap for the round
+    pub hard_cap: u64,
+    /// Minimum purchase amount in USD (as u64 with 6 decimals precision)
+    pub min_purchase: u64,
+    /// Maximum purchase amount in USD (as u64 with 6 decimals precision)
+    pub max_purchase: u64,
+    /// Buyers allowed in this round (empty for an open round)
+    pub whitelist: Vec<Pubkey>,
+}
+
+/// Parameters for buying tokens
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct BuyTokensParams {
+    /// The buyer's public key
+    pub buyer: Pubkey,
+    /// The presale state account
+    pub presale: Pubkey,
+    /// The mint account
+    pub mint: Pubkey,
+    /// The buyer's token account
+    pub buyer_token_account: Pubkey,
+    /// Authority that can mint tokens
+    pub authority: Pubkey,
+    /// The treasury account
+    pub treasury: Pubkey,
+    /// Amount in USD (as u64 with 6 decimals precision)
+    pub amount_usd: u64,
+}
+
+/// Parameters for initializing vesting
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct InitializeVestingParams {
+    /// Authority that can manage vesting
+    pub authority: Pubkey,
+    /// The vesting state account
+    pub vesting: Pubkey,
+    /// The mint account
+    pub mint: Pubkey,
+    /// Pool label, unique within the authority's registry
+    pub label: String,
+    /// Total tokens to be vested
+    pub total_tokens: u64,
+    /// Vesting start timestamp
+    pub start_time: i64,
+    /// Release interval in seconds
+    pub release_interval: i64,
+    /// Number of releases
+    pub num_releases: u8,
+}
+
+/// Parameters for updating token metadata
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct UpdateTokenMetadataParams {
+    /// Authority of the metadata (pays for growing the account)
+    pub authority: Pubkey,
+    /// Metadata account
+    pub metadata: Pubkey,
+    /// The mint account
+    pub mint: Pubkey,
+    /// New name (optional)
+    pub name: Option<String>,
+    /// New symbol (optional)
+    pub symbol: Option<String>,
+    /// New URI (optional)
+    pub uri: Option<String>,
+    /// SHA-256 of the document at the new URI (requires `uri`)
+    pub uri_hash: Option<[u8; 32]>,
+}
+
+/// Parameters for changing an oracle source's consensus settings; None leaves a
+/// value unchanged
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct UpdateOracleSourceConfigParams {
+    /// Controller authority
+    pub authority: Pubkey,
+    /// The oracle controller account
+    pub controller: Pubkey,
+    /// Oracle account of the source
+    pub oracle: Pubkey,
+    /// Weight for consensus calculation (0-100)
+    pub weight: Option<u8>,
+    /// Maximum allowed price deviation from consensus (in basis points)
+    pub max_deviation_bps: Option<u16>,
+    /// Maximum allowed staleness in seconds
+    pub max_staleness_seconds: Option<u32>,
+    /// Whether the source must be present for consensus
+    pub is_required: Option<bool>,
+}
+
+/// Lending reserve accounts a yield venue does not record
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct ReserveAccounts {
+    /// The reserve liquidity supply
+    pub liquidity_supply: Pubkey,
+    /// The reserve collateral mint
+    pub collateral_mint: Pubkey,
+    /// The lending market authority
+    pub lending_market_authority: Pubkey,
+}
+
+/// Items of the launch checklist
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LaunchChecklistItem {
+    /// Token metadata exists for the mint
+    MetadataFinalized,
+    /// Liquidity has been seeded
+    LiquiditySeeded,
+    /// Launching without seeding liquidity
+    LiquidityWaived,
+    /// A vesting pool for the mint is fully funded
+    VestingFunded,
+    /// The supply controller for the mint is initialized
+    ControllerInitialized,
+}
+
+/// Types of state that can be recovered in emergency
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub enum RecoveryStateType {
+    /// Presale state
+    Presale,
+    /// Vesting state 
+    Vesting,
+    /// Controller state
+    Controller,
+    /// Token metadata
+    TokenMetadata,
+    /// Emergency state
+    EmergencyState,
+}
+
+/// Accounts of `PauseVesting` / `ResumeVesting`; the grant is only passed for a single beneficiary
+fn vesting_pause_accounts(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    vesting: &Pubkey,
+    mint: &Pubkey,
+    beneficiary: Option<Pubkey>,
+) -> Vec<AccountMeta> {
+    let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*authority, true),       // Authority (signer)
+        AccountMeta::new(*vesting, false),                 // Vesting state account
+        AccountMeta::new_readonly(emergency_state, false), // Emergency state PDA
+    ];
+    if let Some(beneficiary) = beneficiary {
+        let (grant, _) = VestingGrant::find_address(program_id, vesting, &beneficiary);
+        accounts.push(AccountMeta::new(grant, false)); // Grant PDA
+    }
+    accounts
+}
+
+/// Escrow token account of a vesting pool: the escrow authority PDA's Token-2022 ATA
+fn vesting_escrow(program_id: &Pubkey, vesting: &Pubkey, mint: &Pubkey) -> Pubkey {
+    let (escrow_authority, _) = VestingState::find_escrow_authority(program_id, vesting);
+    get_associated_token_address_with_program_id(&escrow_authority, mint, &TOKEN_2022_PROGRAM_ID)
+}
+
+/// Escrow token account of a mint's bug-bounty pool: the bounty PDA's Token-2022 ATA
+fn bug_bounty_escrow(bounty: &Pubkey, mint: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(bounty, mint, &TOKEN_2022_PROGRAM_ID)
+}
+
+impl VCoinInstruction {
+    /// Creates a new InitializeToken instruction
+    pub fn initialize_token(
+        program_id: &Pubkey,
+        params: &InitializeTokenParams,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::InitializeToken {
+            name: params.name.clone(),
+            symbol: params.symbol.clone(),
+            decimals: params.decimals,
+            initial_supply: params.initial_supply,
+            transfer_fee_basis_points: params.transfer_fee_basis_points,
+            maximum_fee_rate: params.maximum_fee_rate,
+            emergency_authority: params.emergency_authority,
+            allocations: params.allocations.clone(),
+            allocation_plan: params.allocation_plan.clone(),
+        };
+        let data = to_vec(&instr)?;
+        let (emergency_state, _) = EmergencyState::find_address(program_id, &params.mint);
+
+        let mut accounts = vec![
+            AccountMeta::new_readonly(params.authority, true),      // Authority (signer)
+            AccountMeta::new(params.mint, false),                  // Mint account
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false), // Token program
+            AccountMeta::new_readonly(system_program::id(), false),  // System program
+            AccountMeta::new(params.metadata, false),               // Metadata account
+            AccountMeta::new(emergency_state, false),               // Emergency state PDA
+        ];
+        if !params.allocation_plan.is_empty() {
+            let (plan, _) = AllocationPlan::find_address(program_id, &params.mint);
+            accounts.push(AccountMeta::new(plan, false)); // Allocation plan PDA
+        }
+        if !params.allocations.is_empty() {
+            accounts.push(AccountMeta::new_readonly(spl_associated_token_account::id(), false)); // ATA program
+            for allocation in &params.allocations {
+                let (owner, _) = allocation.escrow.find_owner(program_id, &params.mint);
+                let escrow = get_associated_token_address_with_program_id(&owner, &params.mint, &TOKEN_2022_PROGRAM_ID);
+                accounts.push(AccountMeta::new_readonly(owner, false)); // Escrow owner PDA
+                accounts.push(AccountMeta::new(escrow, false));         // Escrow token account
+            }
+        }
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new InitializePresale instruction
+    pub fn initialize_presale(
+        program_id: &Pubkey,
+        params: &InitializePresaleParams,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::InitializePresale {
+            start_time: params.start_time,
+            end_time: params.end_time,
+            token_price: params.token_price,
+            hard_cap: params.hard_cap,
+            soft_cap: params.soft_cap,
+            min_purchase: params.min_purchase,
+            max_purchase: params.max_purchase,
+            purchase_cooldown: params.purchase_cooldown,
+            launch_window_duration: params.launch_window_duration,
+            launch_window_max_per_wallet: params.launch_window_max_per_wallet,
+            allow_partial_fill: params.allow_partial_fill,
+            daily_raise_cap: params.daily_raise_cap,
+        };
+        let data = to_vec(&instr)?;
+        let (allocation_plan, _) = AllocationPlan::find_address(program_id, &params.mint);
+        let (emergency_state, _) = EmergencyState::find_address(program_id, &params.mint);
+
+        let accounts = vec![
+            AccountMeta::new_readonly(params.authority, true),      // Authority (signer)
+            AccountMeta::new(params.presale, false),               // Presale state account
+            AccountMeta::new_readonly(params.mint, false),         // Mint account
+            AccountMeta::new(params.treasury, false),              // Treasury account
+            AccountMeta::new_readonly(system_program::id(), false), // System program
+            AccountMeta::new_readonly(emergency_state, false),     // Emergency state PDA
+            AccountMeta::new(allocation_plan, false),              // Allocation plan PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new BuyTokens instruction (retired, see `VCoinInstruction::BuyTokens`)
+    pub fn buy_tokens(
+        program_id: &Pubkey,
+        params: &BuyTokensParams,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::BuyTokens {
+            amount_usd: params.amount_usd,
+        };
+        let data = to_vec(&instr)?;
+
+        let accounts = vec![
+            AccountMeta::new(params.buyer, true),                  // Buyer (signer)
+            AccountMeta::new(params.presale, false),               // Presale state account
+            AccountMeta::new(params.mint, false),                  // Mint account
+            AccountMeta::new(params.buyer_token_account, false),   // Buyer's token account
+            AccountMeta::new(params.authority, true),              // Authority (signer)
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false), // Token program
+            AccountMeta::new_readonly(system_program::id(), false), // System program
+            AccountMeta::new(params.treasury, false),              // Treasury account
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new InitializeVesting instruction
+    pub fn initialize_vesting(
+        program_id: &Pubkey,
+        params: &InitializeVestingParams,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::InitializeVesting {
+            label: params.label.clone(),
+            total_tokens: params.total_tokens,
+            start_time: params.start_time,
+            release_interval: params.release_interval,
+            num_releases: params.num_releases,
+        };
+        let data = to_vec(&instr)?;
+        let (registry, _) = VestingRegistry::find_address(program_id, &params.mint, &params.authority);
+        let (allocation_plan, _) = AllocationPlan::find_address(program_id, &params.mint);
+        let (emergency_state, _) = EmergencyState::find_address(program_id, &params.mint);
+
+        let accounts = vec![
+            AccountMeta::new(params.authority, true),               // Authority (signer, payer)
+            AccountMeta::new(params.vesting, false),               // Vesting state account
+            AccountMeta::new_readonly(params.mint, false),         // Mint account
+            AccountMeta::new_readonly(system_program::id(), false), // System program
+            AccountMeta::new_readonly(emergency_state, false),      // Emergency state PDA
+            AccountMeta::new(registry, false),                     // Vesting registry PDA
+            AccountMeta::new(allocation_plan, false),              // Allocation plan PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new AddVestingBeneficiary instruction
+    pub fn add_vesting_beneficiary(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        vesting: &Pubkey,
+        mint: &Pubkey,
+        beneficiary: &Pubkey,
+        amount: u64,
+        schedule: Option<VestingSchedule>,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::AddVestingBeneficiary {
+            beneficiary: *beneficiary,
+            amount,
+            schedule,
+        };
+        let data = to_vec(&instr)?;
+        let (grant, _) = VestingGrant::find_address(program_id, vesting, beneficiary);
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
+        let accounts = vec![
+            AccountMeta::new(*authority, true),                   // Authority (signer, payer)
+            AccountMeta::new(*vesting, false),                    // Vesting state account
+            AccountMeta::new(grant, false),                       // Grant PDA
+            AccountMeta::new_readonly(system_program::id(), false), // System program
+            AccountMeta::new_readonly(vesting_escrow(program_id, vesting, mint), false), // Escrow token account
+            AccountMeta::new_readonly(emergency_state, false),                           // Emergency state PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new RevokeVestingBeneficiary instruction
+    pub fn revoke_vesting_beneficiary(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        vesting: &Pubkey,
+        mint: &Pubkey,
+        beneficiary: &Pubkey,
+        burn_unvested: bool,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::RevokeVestingBeneficiary {
+            beneficiary: *beneficiary,
+            burn_unvested,
+        };
+        let data = to_vec(&instr)?;
+        let (grant, _) = VestingGrant::find_address(program_id, vesting, beneficiary);
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
+        let accounts = vec![
+            AccountMeta::new_readonly(*authority, true),          // Authority (signer)
+            AccountMeta::new(*vesting, false),                    // Vesting state account
+            AccountMeta::new(grant, false),                       // Grant PDA
+            AccountMeta::new_readonly(emergency_state, false),    // Emergency state PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new ReleaseVestedTokens instruction
+    pub fn release_vested_tokens(
+        program_id: &Pubkey,
+        cranker: &Pubkey,
+        vesting: &Pubkey,
+        mint: &Pubkey,
+        beneficiary: &Pubkey,
+        beneficiary_token_account: &Pubkey,
+        max_amount: Option<u64>,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::ReleaseVestedTokens {
+            beneficiary: *beneficiary,
+            max_amount,
+        };
+        let data = to_vec(&instr)?;
+        let (grant, _) = VestingGrant::find_address(program_id, vesting, beneficiary);
+        let (escrow_authority, _) = VestingState::find_escrow_authority(program_id, vesting);
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
+        let accounts = vec![
+            AccountMeta::new(*cranker, true),                      // Cranker (signer)
+            AccountMeta::new(*vesting, false),                     // Vesting state account
+            AccountMeta::new(grant, false),                        // Grant PDA
+            AccountMeta::new(*mint, false),                        // Mint account
+            AccountMeta::new(*beneficiary_token_account, false),   // Beneficiary's token account
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false), // Token program
+            AccountMeta::new(vesting_escrow(program_id, vesting, mint), false), // Escrow token account
+            AccountMeta::new_readonly(escrow_authority, false),    // Escrow authority PDA
+            AccountMeta::new_readonly(emergency_state, false),     // Emergency state PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new UpdateTokenMetadata instruction
+    pub fn update_token_metadata(
+        program_id: &Pubkey,
+        params: &UpdateTokenMetadataParams,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::UpdateTokenMetadata {
+            name: params.name.clone(),
+            symbol: params.symbol.clone(),
+            uri: params.uri.clone(),
+            uri_hash: params.uri_hash,
+        };
+        let data = to_vec(&instr)?;
+
+        let (emergency_state, _) = EmergencyState::find_address(program_id, &params.mint);
+
+        let accounts = vec![
+            AccountMeta::new(params.authority, true),              // Authority (signer, payer)
+            AccountMeta::new(params.metadata, false),              // Metadata account
             AccountMeta::new_readonly(params.mint, false),         // Mint account
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false), // Token program
+            AccountMeta::new_readonly(emergency_state, false),       // Emergency state PDA
+            AccountMeta::new_readonly(system_program::id(), false),  // System program
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new SetTransferFee instruction
+    pub fn set_transfer_fee(
+        program_id: &Pubkey,
+        fee_authority: &Pubkey,
+        mint: &Pubkey,
+        transfer_fee_basis_points: u16,
+        maximum_fee: u64,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::SetTransferFee {
+            transfer_fee_basis_points,
+            maximum_fee,
+        };
+        let data = to_vec(&instr)?;
+
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
+        let accounts = vec![
+            AccountMeta::new_readonly(*fee_authority, true),      // Fee authority (signer)
+            AccountMeta::new(*mint, false),                       // Mint account
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false), // Token program
+            AccountMeta::new_readonly(emergency_state, false),       // Emergency state PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new EndPresale instruction
+    pub fn end_presale(
+        program_id: &Pubkey,
+        caller: &Pubkey,
+        presale: &Pubkey,
+        mint: &Pubkey,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::EndPresale;
+        let data = to_vec(&instr)?;
+
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
+        let accounts = vec![
+            AccountMeta::new_readonly(*caller, true),              // Caller (signer)
+            AccountMeta::new(*presale, false),                     // Presale state account
+            AccountMeta::new_readonly(emergency_state, false),     // Emergency state PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new InitializeAutonomousController instruction
+    pub fn initialize_autonomous_controller(
+        program_id: &Pubkey,
+        initial_price: u64,
+        max_supply: u64,
+        price_source: SupplyPriceSource,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::InitializeAutonomousController {
+            initial_price,
+            max_supply,
+            price_source,
+        };
+        let data = to_vec(&instr)?;
+
+        let accounts = vec![
+            AccountMeta::new_readonly(Pubkey::default(), true), // Temporary authority (signer)
+            AccountMeta::new(Pubkey::default(), false),          // Controller state account
+            AccountMeta::new_readonly(Pubkey::default(), false), // Mint account
+            AccountMeta::new_readonly(Pubkey::default(), false), // Price oracle account
+            AccountMeta::new_readonly(system_program::id(), false), // System program
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false), // Token program
+            AccountMeta::new_readonly(Pubkey::default(), false),     // Emergency state PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new UpdateOraclePrice instruction
+    pub fn update_oracle_price(
+        program_id: &Pubkey,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::UpdateOraclePrice;
+        let data = to_vec(&instr)?;
+
+        let accounts = vec![
+            AccountMeta::new_readonly(Pubkey::default(), false), // Controller state account
+            AccountMeta::new_readonly(Pubkey::default(), false), // Primary price oracle account
+            AccountMeta::new_readonly(Pubkey::default(), false), // Emergency state PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new ExecuteAutonomousMint instruction
+    pub fn execute_autonomous_mint(
+        program_id: &Pubkey,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::ExecuteAutonomousMint;
+        let data = to_vec(&instr)?;
+
+        let accounts = vec![
+            AccountMeta::new(Pubkey::default(), false),          // Controller state account
+            AccountMeta::new(Pubkey::default(), false),          // Mint account
+            AccountMeta::new_readonly(Pubkey::default(), false), // Mint authority PDA
+            AccountMeta::new(Pubkey::default(), false),          // Supply treasury token account
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false), // Token program
+            AccountMeta::new_readonly(Pubkey::default(), false),     // Emergency state PDA
+            AccountMeta::new_readonly(Pubkey::default(), false), // Price oracle account
+            AccountMeta::new(Pubkey::default(), false),          // Supply action ledger
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new ExecuteAutonomousBurn instruction
+    pub fn execute_autonomous_burn(
+        program_id: &Pubkey,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::ExecuteAutonomousBurn;
+        let data = to_vec(&instr)?;
+
+        let accounts = vec![
+            AccountMeta::new(Pubkey::default(), false),          // Controller state account
+            AccountMeta::new(Pubkey::default(), false),          // Mint account
+            AccountMeta::new_readonly(Pubkey::default(), false), // Mint authority PDA
+            AccountMeta::new(Pubkey::default(), false),          // Burn treasury token account
+            AccountMeta::new_readonly(Pubkey::default(), false), // Burn treasury PDA
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false), // Token program
+            AccountMeta::new_readonly(Pubkey::default(), false),     // Emergency state PDA
+            AccountMeta::new_readonly(Pubkey::default(), false), // Price oracle account
+            AccountMeta::new(Pubkey::default(), false),          // Supply action ledger
+            AccountMeta::new(Pubkey::default(), false),          // Burn log
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new PermanentlyDisableUpgrades instruction
+    pub fn permanently_disable_upgrades(
+        program_id: &Pubkey,
+        current_upgrade_authority: &Pubkey,
+        program_account: &Pubkey,
+        program_data_account: &Pubkey,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::PermanentlyDisableUpgrades;
+        let data = to_vec(&instr)?;
+
+        let accounts = vec![
+            AccountMeta::new_readonly(*current_upgrade_authority, true), // Current upgrade authority (signer)
+            AccountMeta::new_readonly(*program_account, false),            // Program account
+            AccountMeta::new_readonly(*program_data_account, false),         // Program data account
+            AccountMeta::new_readonly(system_program::id(), false),            // System program
+            AccountMeta::new_readonly(solana_program::bpf_loader::id(), false), // BPF Upgradeable Loader program
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new DepositToBurnTreasury instruction
+    pub fn deposit_to_burn_treasury(
+        program_id: &Pubkey,
+        depositor: &Pubkey,
+        depositor_token_account: &Pubkey,
+        burn_treasury_token_account: &Pubkey,
+        mint: &Pubkey,
+        amount: u64,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::DepositToBurnTreasury {
+            amount,
+        };
+        let data = to_vec(&instr)?;
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
+        let accounts = vec![
+            AccountMeta::new(*depositor, true),                  // Depositor (signer)
+            AccountMeta::new_readonly(*mint, false),               // Mint account
+            AccountMeta::new(*depositor_token_account, false),   // Depositor's token account
+            AccountMeta::new(*burn_treasury_token_account, false), // Burn treasury token account
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false), // Token program
+            AccountMeta::new_readonly(emergency_state, false),     // Emergency state PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new InitializeBurnTreasury instruction
+    pub fn initialize_burn_treasury(
+        program_id: &Pubkey,
+        payer: &Pubkey,
+        mint: &Pubkey,
+        burn_treasury_token_account: &Pubkey,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::InitializeBurnTreasury;
+        let data = to_vec(&instr)?;
+        let (burn_treasury, _) = Pubkey::find_program_address(&[b"burn_treasury", mint.as_ref()], program_id);
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
+        let accounts = vec![
+            AccountMeta::new(*payer, true),                  // Payer (signer)
+            AccountMeta::new_readonly(*mint, false),               // Mint account
+            AccountMeta::new_readonly(burn_treasury, false),       // Burn treasury PDA
+            AccountMeta::new(*burn_treasury_token_account, false), // Burn treasury token account
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false), // Token program
+            AccountMeta::new_readonly(system_program::id(), false), // System program
+            AccountMeta::new_readonly(emergency_state, false),     // Emergency state PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new ExpandPresaleAccount instruction
+    pub fn expand_presale_account(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        presale: &Pubkey,
+        mint: &Pubkey,
+        additional_buyers: u32,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::ExpandPresaleAccount {
+            additional_buyers,
+        };
+        let data = to_vec(&instr)?;
+
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
+        let accounts = vec![
+            AccountMeta::new_readonly(*authority, true),           // Authority (signer)
+            AccountMeta::new(*presale, false),                     // Presale state account
+            AccountMeta::new_readonly(system_program::id(), false), // System program
+            AccountMeta::new_readonly(emergency_state, false),      // Emergency state PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new ClaimDevFundRefund instruction
+    #[allow(clippy::too_many_arguments)]
+    pub fn claim_dev_fund_refund(
+        program_id: &Pubkey,
+        buyer: &Pubkey,
+        presale: &Pubkey,
+        mint: &Pubkey,
+        buyer_stablecoin_token_account: &Pubkey,
+        dev_refund_vault_stablecoin_account: &Pubkey,
+        stablecoin_token_program: &Pubkey,
+        stablecoin_mint: &Pubkey,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::ClaimDevFundRefund;
+        let data = to_vec(&instr)?;
+
+        let (dev_refund_vault_authority, _) =
+            Pubkey::find_program_address(&[b"dev_refund_vault", presale.as_ref()], program_id);
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
+        let accounts = vec![
+            AccountMeta::new(*buyer, true),                  // Buyer (signer)
+            AccountMeta::new(*presale, false),               // Presale state account
+            AccountMeta::new(*buyer_stablecoin_token_account, false),   // Buyer's stablecoin token account (destination)
+            AccountMeta::new(*dev_refund_vault_stablecoin_account, false),   // Dev refund vault stablecoin account (source)
+            AccountMeta::new_readonly(dev_refund_vault_authority, false),   // Dev refund vault authority PDA
+            AccountMeta::new_readonly(*stablecoin_token_program, false),   // Stablecoin token program
+            AccountMeta::new_readonly(*stablecoin_mint, false),   // Stablecoin mint
+            AccountMeta::new_readonly(emergency_state, false),    // Emergency state PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new EmergencyPause instruction
+    pub fn emergency_pause(
+        program_id: &Pubkey,
+        reason: Option<String>,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::EmergencyPause {
+            reason,
+        };
+        let data = to_vec(&instr)?;
+
+        let accounts = vec![
+            AccountMeta::new_readonly(Pubkey::default(), true), // Emergency authority (signer)
+            AccountMeta::new(Pubkey::default(), false),          // Emergency state account
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new EmergencyResume instruction
+    pub fn emergency_resume(
+        program_id: &Pubkey,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::EmergencyResume;
+        let data = to_vec(&instr)?;
+
+        let accounts = vec![
+            AccountMeta::new_readonly(Pubkey::default(), true), // Emergency authority (signer)
+            AccountMeta::new(Pubkey::default(), false),          // Emergency state account
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new RescueTokens instruction
+    pub fn rescue_tokens(
+        program_id: &Pubkey,
+        amount: u64,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::RescueTokens {
+            amount,
+        };
+        let data = to_vec(&instr)?;
+
+        let accounts = vec![
+            AccountMeta::new_readonly(Pubkey::default(), true), // Emergency authority (signer)
+            AccountMeta::new(Pubkey::default(), false),          // Source token account
+            AccountMeta::new(Pubkey::default(), false),          // Destination token account
+            AccountMeta::new_readonly(Pubkey::default(), false), // Source account authority (PDA derived from program)
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false), // Token program
+            AccountMeta::new_readonly(Pubkey::default(), false), // Mint account
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new RecoverState instruction
+    pub fn recover_state(
+        program_id: &Pubkey,
+        state_type: RecoveryStateType,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::RecoverState {
+            state_type,
+        };
+        let data = to_vec(&instr)?;
+
+        let accounts = vec![
+            AccountMeta::new_readonly(Pubkey::default(), true), // Emergency authority (signer)
+            AccountMeta::new(Pubkey::default(), false),          // State account to recover
+            AccountMeta::new_readonly(system_program::id(), false), // System program
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates InitializeOracleController instruction for the asset's controller PDA
+    pub fn initialize_oracle_controller(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        asset_id: String,
+        min_required_oracles: u8,
+    ) -> Result<Instruction, std::io::Error> {
+        let (controller, _) = MultiOracleController::find_address(program_id, &asset_id);
+        let accounts = vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(controller, false),
+            AccountMeta::new_readonly(solana_program::system_program::ID, false),
+        ];
+        
+        let data = Self::InitializeOracleController {
+            asset_id,
+            min_required_oracles,
+        }.try_to_vec()?;
+        
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+    
+    /// Creates AddOracleSource instruction
+    pub fn add_oracle_source(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        controller: &Pubkey,
+        oracle: &Pubkey,
+        oracle_type: OracleType,
+        weight: u8,
+        max_deviation_bps: u16,
+        max_staleness_seconds: u32,
+        is_required: bool,
+        skip_if_exists: bool,
+    ) -> Result<Instruction, std::io::Error> {
+        let accounts = vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(*controller, false),
+            AccountMeta::new_readonly(*oracle, false),
+        ];
+        
+        let data = Self::AddOracleSource {
+            oracle_type,
+            weight,
+            max_deviation_bps,
+            max_staleness_seconds,
+            is_required,
+            skip_if_exists,
+        }.try_to_vec()?;
+        
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+    
+    /// Creates UpdateOracleConsensus instruction
+    pub fn update_oracle_consensus(
+        program_id: &Pubkey,
+        caller: &Pubkey,
+        controller: &Pubkey,
+        oracle_accounts: &[Pubkey],
+    ) -> Result<Instruction, std::io::Error> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(*caller, true),
+            AccountMeta::new(*controller, false),
+        ];
+        
+        // Add oracle accounts
+        for oracle in oracle_accounts {
+            accounts.push(AccountMeta::new_readonly(*oracle, false));
+        }
+        
+        let data = Self::UpdateOracleConsensus.try_to_vec()?;
+        
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+    
+    /// Creates UpdateOracleConsensus instruction from the controller's account data,
+    /// passing each active source's oracle account once, in source order
+    pub fn update_oracle_consensus_for_controller(
+        program_id: &Pubkey,
+        caller: &Pubkey,
+        controller: &Pubkey,
+        controller_data: &[u8],
+    ) -> Result<Instruction, std::io::Error> {
+        let controller_state = MultiOracleController::deserialize(&mut &controller_data[..])?;
+        if !controller_state.is_initialized {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "oracle controller is not initialized",
+            ));
+        }
+        
+        Self::update_oracle_consensus(program_id, caller, controller, &controller_state.consensus_accounts())
+    }
+    
+    /// Creates SetEmergencyPrice instruction
+    pub fn set_emergency_price(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        controller: &Pubkey,
+        emergency_price: u64,
+        expiration_seconds: u32,
+    ) -> Result<Instruction, std::io::Error> {
+        let accounts = vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(*controller, false),
+        ];
+        
+        let data = Self::SetEmergencyPrice {
+            emergency_price,
+            expiration_seconds,
+        }.try_to_vec()?;
+        
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+    
+    /// Creates ClearEmergencyPrice instruction
+    pub fn clear_emergency_price(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        controller: &Pubkey,
+    ) -> Result<Instruction, std::io::Error> {
+        let accounts = vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(*controller, false),
+        ];
+        
+        let data = Self::ClearEmergencyPrice.try_to_vec()?;
+        
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+    
+    /// Creates ResetCircuitBreaker instruction
+    pub fn reset_circuit_breaker(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        controller: &Pubkey,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::ResetCircuitBreaker;
+        let data = to_vec(&instr)?;
+        
+        let accounts = vec![
+            AccountMeta::new_readonly(*authority, true), // Authority (signer)
+            AccountMeta::new(*controller, false),        // Oracle controller
+        ];
+        
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+    
+    /// Creates UpdatePriceDirectly instruction
+    pub fn update_price_directly(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        controller: &Pubkey,
+        mint: &Pubkey,
+        new_price: u64,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::UpdatePriceDirectly { new_price };
+        let data = to_vec(&instr)?;
+        
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+        let accounts = vec![
+            AccountMeta::new_readonly(*authority, true),        // Authority (signer)
+            AccountMeta::new(*controller, false),               // Controller state account
+            AccountMeta::new_readonly(emergency_state, false),  // Emergency state PDA
+        ];
+        
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new FinalizePresale instruction
+    pub fn finalize_presale(
+        program_id: &Pubkey,
+        caller: &Pubkey,
+        presale: &Pubkey,
+        mint: &Pubkey,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::FinalizePresale;
+        let data = to_vec(&instr)?;
+
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
+        let accounts = vec![
+            AccountMeta::new_readonly(*caller, true),              // Caller (signer)
+            AccountMeta::new(*presale, false),                     // Presale state account
+            AccountMeta::new_readonly(emergency_state, false),     // Emergency state PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new InitializePresaleRound instruction
+    pub fn initialize_presale_round(
+        program_id: &Pubkey,
+        params: &InitializePresaleRoundParams,
+    ) -> Result<Instruction, std::io::Error> {
+        let (round, _) = Pubkey::find_program_address(
+            &[b"presale_round", params.presale.as_ref(), &[params.round_index]],
+            program_id,
+        );
+
+        let instr = Self::InitializePresaleRound {
+            round_index: params.round_index,
+            kind: params.kind,
+            start_time: params.start_time,
+            end_time: params.end_time,
+            token_price: params.token_price,
+            hard_cap: params.hard_cap,
+            min_purchase: params.min_purchase,
+            max_purchase: params.max_purchase,
+            whitelist: params.whitelist.clone(),
+        };
+        let data = to_vec(&instr)?;
+
+        let (emergency_state, _) = EmergencyState::find_address(program_id, &params.mint);
+
+        let accounts = vec![
+            AccountMeta::new(params.authority, true),               // Authority (signer, payer)
+            AccountMeta::new(params.presale, false),                // Presale state account
+            AccountMeta::new(round, false),                         // Presale round PDA
+            AccountMeta::new_readonly(system_program::id(), false), // System program
+            AccountMeta::new_readonly(emergency_state, false),      // Emergency state PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new SetKycConfig instruction
+    pub fn set_kyc_config(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        presale: &Pubkey,
+        mint: &Pubkey,
+        kyc_required: bool,
+        attestor: &Pubkey,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::SetKycConfig {
+            kyc_required,
+            attestor: *attestor,
+        };
+        let data = to_vec(&instr)?;
+
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
+        let accounts = vec![
+            AccountMeta::new_readonly(*authority, true),           // Authority (signer)
+            AccountMeta::new(*presale, false),                     // Presale state account
+            AccountMeta::new_readonly(emergency_state, false),     // Emergency state PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new IssueKycCredential instruction
+    ///
+    /// Must be sent directly after an ed25519 program instruction carrying the
+    /// attestor's signature over the attestation message.
+    pub fn issue_kyc_credential(
+        program_id: &Pubkey,
+        buyer: &Pubkey,
+        presale: &Pubkey,
+        mint: &Pubkey,
+        expires_at: i64,
+    ) -> Result<Instruction, std::io::Error> {
+        let (credential, _) = Pubkey::find_program_address(
+            &[b"kyc_credential", presale.as_ref(), buyer.as_ref()],
+            program_id,
+        );
+
+        let instr = Self::IssueKycCredential { expires_at };
+        let data = to_vec(&instr)?;
+
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
+        let accounts = vec![
+            AccountMeta::new(*buyer, true),                         // Buyer (signer, payer)
+            AccountMeta::new_readonly(*presale, false),             // Presale state account
+            AccountMeta::new(credential, false),                    // KYC credential PDA
+            AccountMeta::new_readonly(sysvar::instructions::id(), false), // Instructions sysvar
+            AccountMeta::new_readonly(system_program::id(), false), // System program
+            AccountMeta::new_readonly(emergency_state, false),      // Emergency state PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new ClosePresaleAccounts instruction
+    pub fn close_presale_accounts(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        presale: &Pubkey,
+        mint: &Pubkey,
+        rent_destination: &Pubkey,
+        record_accounts: &[Pubkey],
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::ClosePresaleAccounts;
+        let data = to_vec(&instr)?;
+
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
+        let mut accounts = vec![
+            AccountMeta::new_readonly(*authority, true),           // Authority (signer)
+            AccountMeta::new(*presale, false),                     // Presale state account
+            AccountMeta::new(*rent_destination, false),            // Rent destination
+            AccountMeta::new_readonly(emergency_state, false),     // Emergency state PDA
+        ];
+        accounts.extend(record_accounts.iter().map(|record| AccountMeta::new(*record, false)));
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new CommitComplianceHash instruction
+    pub fn commit_compliance_hash(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        presale: &Pubkey,
+        mint: &Pubkey,
+        kind: ComplianceArtifactKind,
+        hash: [u8; 32],
+    ) -> Result<Instruction, std::io::Error> {
+        let (compliance, _) = Pubkey::find_program_address(&[b"compliance", presale.as_ref()], program_id);
+
+        let instr = Self::CommitComplianceHash { kind, hash };
+        let data = to_vec(&instr)?;
+
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
+        let accounts = vec![
+            AccountMeta::new(*authority, true),                     // Authority (signer, payer)
+            AccountMeta::new_readonly(*presale, false),             // Presale state account
+            AccountMeta::new(compliance, false),                    // Compliance record PDA
+            AccountMeta::new_readonly(system_program::id(), false), // System program
+            AccountMeta::new_readonly(emergency_state, false),      // Emergency state PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new SetStablecoinLimits instruction setting `limits` for its
+    /// presale and stablecoin (the recorded window volume is ignored)
+    pub fn set_stablecoin_limits(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        mint: &Pubkey,
+        limits: &StablecoinLimits,
+    ) -> Result<Instruction, std::io::Error> {
+        let (limits_address, _) = Pubkey::find_program_address(
+            &[b"stablecoin_limits", limits.presale.as_ref(), limits.stablecoin_mint.as_ref()],
+            program_id,
+        );
+
+        let instr = Self::SetStablecoinLimits {
+            min_purchase: limits.min_purchase,
+            max_purchase: limits.max_purchase,
+            window_duration: limits.window.window_duration,
+            window_cap: limits.window_cap,
+            cap_expires_at: limits.cap_expires_at,
+        };
+        let data = to_vec(&instr)?;
+
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
+        let accounts = vec![
+            AccountMeta::new(*authority, true),                     // Authority (signer, payer)
+            AccountMeta::new(limits.presale, false),                // Presale state account
+            AccountMeta::new_readonly(limits.stablecoin_mint, false), // Stablecoin mint
+            AccountMeta::new(limits_address, false),                // Stablecoin limits PDA
+            AccountMeta::new_readonly(system_program::id(), false), // System program
+            AccountMeta::new_readonly(emergency_state, false),      // Emergency state PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new InitializeAirdrop instruction
+    pub fn initialize_airdrop(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        mint: &Pubkey,
+        escrow: &Pubkey,
+        merkle_root: [u8; 32],
+        total: u64,
+    ) -> Result<Instruction, std::io::Error> {
+        let (distributor, _) =
+            Pubkey::find_program_address(&[b"airdrop", mint.as_ref(), &merkle_root], program_id);
+
+        let instr = Self::InitializeAirdrop { merkle_root, total };
+        let data = to_vec(&instr)?;
+
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
+        let accounts = vec![
+            AccountMeta::new(*authority, true),                     // Authority (signer, payer)
+            AccountMeta::new(distributor, false),                   // Distributor PDA
+            AccountMeta::new_readonly(*mint, false),                // Mint
+            AccountMeta::new_readonly(*escrow, false),              // Escrow token account
+            AccountMeta::new_readonly(system_program::id(), false), // System program
+            AccountMeta::new_readonly(emergency_state, false),      // Emergency state PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new ClaimAirdrop instruction against `distributor`
+    pub fn claim_airdrop(
+        program_id: &Pubkey,
+        claimant: &Pubkey,
+        distributor: &AirdropDistributor,
+        claimant_token_account: &Pubkey,
+        index: u64,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<Instruction, std::io::Error> {
+        let (distributor_address, _) = Pubkey::find_program_address(
+            &[b"airdrop", distributor.mint.as_ref(), &distributor.merkle_root],
+            program_id,
+        );
+        let chunk_index = AirdropClaimBitmap::chunk_for(index).unwrap_or(u32::MAX);
+        let (claim_bitmap, _) = Pubkey::find_program_address(
+            &[b"airdrop_claims", distributor_address.as_ref(), &chunk_index.to_le_bytes()],
+            program_id,
+        );
+
+        let instr = Self::ClaimAirdrop { index, amount, proof };
+        let data = to_vec(&instr)?;
+
+        let (emergency_state, _) = EmergencyState::find_address(program_id, &distributor.mint);
+
+        let accounts = vec![
+            AccountMeta::new(*claimant, true),                      // Claimant (signer, payer)
+            AccountMeta::new(distributor_address, false),           // Distributor PDA
+            AccountMeta::new(claim_bitmap, false),                  // Claim bitmap PDA
+            AccountMeta::new(distributor.escrow, false),            // Escrow token account
+            AccountMeta::new(*claimant_token_account, false),       // Claimant token account
+            AccountMeta::new_readonly(distributor.mint, false),     // Mint
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false), // Token-2022 program
+            AccountMeta::new_readonly(system_program::id(), false), // System program
+            AccountMeta::new_readonly(emergency_state, false),      // Emergency state PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new SetControllerPriceBounds instruction
+    pub fn set_controller_price_bounds(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        controller: &Pubkey,
+        price_floor: u64,
+        price_ceiling: u64,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::SetControllerPriceBounds { price_floor, price_ceiling };
+        let data = to_vec(&instr)?;
+
+        let accounts = vec![
+            AccountMeta::new_readonly(*authority, true), // Controller authority (signer)
+            AccountMeta::new(*controller, false),        // Oracle controller account
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new SetPresalePriceController instruction
+    pub fn set_presale_price_controller(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        presale: &Pubkey,
+        mint: &Pubkey,
+        controller: Option<Pubkey>,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::SetPresalePriceController { controller };
+        let data = to_vec(&instr)?;
+
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
+        let mut accounts = vec![
+            AccountMeta::new_readonly(*authority, true), // Presale authority (signer)
+            AccountMeta::new(*presale, false),           // Presale state account
+            AccountMeta::new_readonly(emergency_state, false), // Emergency state PDA
+        ];
+        if let Some(controller) = controller {
+            accounts.push(AccountMeta::new_readonly(controller, false)); // Oracle controller account
+        }
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new ValidatePurchase instruction
+    pub fn validate_purchase(
+        program_id: &Pubkey,
+        buyer: &Pubkey,
+        presale: &Pubkey,
+        mint: &Pubkey,
+        stablecoin_mint: &Pubkey,
+        optional_accounts: &[Pubkey],
+        amount: u64,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::ValidatePurchase { amount };
+        let data = to_vec(&instr)?;
+
+        let mut accounts = vec![
+            AccountMeta::new_readonly(*buyer, false),           // Prospective buyer
+            AccountMeta::new_readonly(*presale, false),         // Presale state account
+            AccountMeta::new_readonly(*mint, false),            // Mint account
+            AccountMeta::new_readonly(*stablecoin_mint, false), // Stablecoin mint account
+        ];
+        // Round, KYC credential, stablecoin limits, price controller and peg guard, as for a purchase
+        accounts.extend(optional_accounts.iter().map(|key| AccountMeta::new_readonly(*key, false)));
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new SetTreasuryAlarm instruction
+    pub fn set_treasury_alarm(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        presale: &Pubkey,
+        mint: &Pubkey,
+        coverage_bps: u16,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::SetTreasuryAlarm { coverage_bps };
+        let data = to_vec(&instr)?;
+
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
+        let accounts = vec![
+            AccountMeta::new_readonly(*authority, true), // Presale authority (signer)
+            AccountMeta::new(*presale, false),           // Presale state account
+            AccountMeta::new_readonly(emergency_state, false), // Emergency state PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new ProposeBeneficiaryTransfer instruction
+    pub fn propose_beneficiary_transfer(
+        program_id: &Pubkey,
+        signer: &Pubkey,
+        vesting: &Pubkey,
+        mint: &Pubkey,
+        beneficiary: &Pubkey,
+        new_beneficiary: Option<Pubkey>,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::ProposeBeneficiaryTransfer {
+            beneficiary: *beneficiary,
+            new_beneficiary,
+        };
+        let data = to_vec(&instr)?;
+        let (grant, _) = VestingGrant::find_address(program_id, vesting, beneficiary);
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
+        let accounts = vec![
+            AccountMeta::new_readonly(*signer, true),   // Beneficiary or authority (signer)
+            AccountMeta::new_readonly(*vesting, false), // Vesting state account
+            AccountMeta::new(grant, false),             // Grant PDA
+            AccountMeta::new_readonly(emergency_state, false), // Emergency state PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new AcceptBeneficiaryTransfer instruction
+    pub fn accept_beneficiary_transfer(
+        program_id: &Pubkey,
+        new_beneficiary: &Pubkey,
+        vesting: &Pubkey,
+        mint: &Pubkey,
+        beneficiary: &Pubkey,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::AcceptBeneficiaryTransfer {
+            beneficiary: *beneficiary,
+        };
+        let data = to_vec(&instr)?;
+
+        let (grant, _) = VestingGrant::find_address(program_id, vesting, beneficiary);
+        let (new_grant, _) = VestingGrant::find_address(program_id, vesting, new_beneficiary);
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
+        let accounts = vec![
+            AccountMeta::new(*new_beneficiary, true),               // New beneficiary (signer, payer)
+            AccountMeta::new_readonly(*vesting, false),             // Vesting state account
+            AccountMeta::new(grant, false),                         // Current grant PDA
+            AccountMeta::new(new_grant, false),                     // New grant PDA
+            AccountMeta::new_readonly(system_program::id(), false), // System program
+            AccountMeta::new_readonly(emergency_state, false),      // Emergency state PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new AddVestingBeneficiaries instruction
+    pub fn add_vesting_beneficiaries(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        vesting: &Pubkey,
+        mint: &Pubkey,
+        entries: Vec<(Pubkey, u64)>,
+    ) -> Result<Instruction, std::io::Error> {
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+        let mut accounts = vec![
+            AccountMeta::new(*authority, true),                     // Authority (signer, payer)
+            AccountMeta::new(*vesting, false),                      // Vesting state account
+            AccountMeta::new_readonly(system_program::id(), false), // System program
+            AccountMeta::new_readonly(vesting_escrow(program_id, vesting, mint), false), // Escrow token account
+            AccountMeta::new_readonly(emergency_state, false),                           // Emergency state PDA
+        ];
+        // Grant PDAs, in entry order
+        accounts.extend(entries.iter().map(|(beneficiary, _)| {
+            AccountMeta::new(VestingGrant::find_address(program_id, vesting, beneficiary).0, false)
+        }));
+
+        let instr = Self::AddVestingBeneficiaries { entries };
+        let data = to_vec(&instr)?;
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new PreviewVestingSchedule instruction
+    pub fn preview_vesting_schedule(
+        program_id: &Pubkey,
+        vesting: &Pubkey,
+        beneficiary: &Pubkey,
+        start_index: u32,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::PreviewVestingSchedule {
+            beneficiary: *beneficiary,
+            start_index,
+        };
+        let data = to_vec(&instr)?;
+
+        let (grant, _) = VestingGrant::find_address(program_id, vesting, beneficiary);
+
+        let accounts = vec![
+            AccountMeta::new_readonly(*vesting, false), // Vesting state account
+            AccountMeta::new_readonly(grant, false),    // Grant PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new SetBeneficiaryDestination instruction
+    pub fn set_beneficiary_destination(
+        program_id: &Pubkey,
+        beneficiary: &Pubkey,
+        vesting: &Pubkey,
+        mint: &Pubkey,
+        destination: &Pubkey,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::SetBeneficiaryDestination;
+        let data = to_vec(&instr)?;
+
+        let (grant, _) = VestingGrant::find_address(program_id, vesting, beneficiary);
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
+        let accounts = vec![
+            AccountMeta::new_readonly(*beneficiary, true),  // Beneficiary (signer)
+            AccountMeta::new_readonly(*vesting, false),     // Vesting state account
+            AccountMeta::new(grant, false),                 // Grant PDA
+            AccountMeta::new_readonly(*destination, false), // Destination token account
+            AccountMeta::new_readonly(emergency_state, false), // Emergency state PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new SetReleaseCrankTip instruction
+    pub fn set_release_crank_tip(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        vesting: &Pubkey,
+        mint: &Pubkey,
+        lamports: u64,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::SetReleaseCrankTip { lamports };
+        let data = to_vec(&instr)?;
+
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
+        let accounts = vec![
+            AccountMeta::new_readonly(*authority, true), // Authority (signer)
+            AccountMeta::new(*vesting, false),           // Vesting state account
+            AccountMeta::new_readonly(emergency_state, false), // Emergency state PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new FundVesting instruction
+    pub fn fund_vesting(
+        program_id: &Pubkey,
+        funder: &Pubkey,
+        vesting: &Pubkey,
+        source: &Pubkey,
+        mint: &Pubkey,
+        amount: u64,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::FundVesting { amount };
+        let data = to_vec(&instr)?;
+
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
+        let accounts = vec![
+            AccountMeta::new_readonly(*funder, true),                // Funder (signer)
+            AccountMeta::new_readonly(*vesting, false),              // Vesting state account
+            AccountMeta::new(*source, false),                        // Source token account
+            AccountMeta::new(vesting_escrow(program_id, vesting, mint), false), // Escrow token account
+            AccountMeta::new_readonly(*mint, false),                 // Mint account
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false), // Token program
+            AccountMeta::new_readonly(emergency_state, false),       // Emergency state PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new SetReceiptTransferability instruction
+    pub fn set_receipt_transferability(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        vesting: &Pubkey,
+        mint: &Pubkey,
+        transferable: bool,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::SetReceiptTransferability { transferable };
+        let data = to_vec(&instr)?;
+
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
+        let accounts = vec![
+            AccountMeta::new_readonly(*authority, true), // Authority (signer)
+            AccountMeta::new(*vesting, false),           // Vesting state account
+            AccountMeta::new_readonly(emergency_state, false), // Emergency state PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new MintGrantReceipt instruction
+    pub fn mint_grant_receipt(
+        program_id: &Pubkey,
+        beneficiary: &Pubkey,
+        vesting: &Pubkey,
+        mint: &Pubkey,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::MintGrantReceipt;
+        let data = to_vec(&instr)?;
+
+        let (grant, _) = VestingGrant::find_address(program_id, vesting, beneficiary);
+        let (receipt_mint, _) = VestingGrant::find_receipt_mint(program_id, &grant);
+        let receipt_account = get_associated_token_address_with_program_id(
+            beneficiary,
+            &receipt_mint,
+            &TOKEN_2022_PROGRAM_ID,
+        );
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
+        let accounts = vec![
+            AccountMeta::new(*beneficiary, true),                    // Beneficiary (signer, payer)
+            AccountMeta::new_readonly(*vesting, false),              // Vesting state account
+            AccountMeta::new(grant, false),                          // Grant PDA
+            AccountMeta::new(receipt_mint, false),                   // Receipt mint PDA
+            AccountMeta::new(receipt_account, false),                // Receipt token account
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false), // Token program
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false), // ATA program
+            AccountMeta::new_readonly(system_program::id(), false),  // System program
+            AccountMeta::new_readonly(emergency_state, false),       // Emergency state PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new RedeemGrantReceipt instruction
+    pub fn redeem_grant_receipt(
+        program_id: &Pubkey,
+        holder: &Pubkey,
+        vesting: &Pubkey,
+        mint: &Pubkey,
+        beneficiary: &Pubkey,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::RedeemGrantReceipt {
+            beneficiary: *beneficiary,
+        };
+        let data = to_vec(&instr)?;
+
+        let (grant, _) = VestingGrant::find_address(program_id, vesting, beneficiary);
+        let (new_grant, _) = VestingGrant::find_address(program_id, vesting, holder);
+        let (receipt_mint, _) = VestingGrant::find_receipt_mint(program_id, &grant);
+        let receipt_account = get_associated_token_address_with_program_id(
+            holder,
+            &receipt_mint,
+            &TOKEN_2022_PROGRAM_ID,
+        );
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
+        let accounts = vec![
+            AccountMeta::new(*holder, true),                        // Receipt holder (signer, payer)
+            AccountMeta::new_readonly(*vesting, false),             // Vesting state account
+            AccountMeta::new(grant, false),                         // Current grant PDA
+            AccountMeta::new(new_grant, false),                     // New grant PDA
+            AccountMeta::new_readonly(receipt_account, false),      // Holder's receipt token account
+            AccountMeta::new_readonly(system_program::id(), false), // System program
+            AccountMeta::new_readonly(emergency_state, false),      // Emergency state PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new GetVersion instruction
+    pub fn get_version(program_id: &Pubkey) -> Result<Instruction, std::io::Error> {
+        let instr = Self::GetVersion;
+        let data = to_vec(&instr)?;
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts: vec![],
+            data,
+        })
+    }
+
+    /// Creates a new PauseVesting instruction
+    pub fn pause_vesting(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        vesting: &Pubkey,
+        mint: &Pubkey,
+        beneficiary: Option<Pubkey>,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::PauseVesting { beneficiary };
+        let data = to_vec(&instr)?;
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts: vesting_pause_accounts(program_id, authority, vesting, mint, beneficiary),
+            data,
+        })
+    }
+
+    /// Creates a new ResumeVesting instruction
+    pub fn resume_vesting(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        vesting: &Pubkey,
+        mint: &Pubkey,
+        beneficiary: Option<Pubkey>,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::ResumeVesting { beneficiary };
+        let data = to_vec(&instr)?;
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts: vesting_pause_accounts(program_id, authority, vesting, mint, beneficiary),
+            data,
+        })
+    }
+
+    /// Creates a new AnnounceRefundWindow instruction
+    pub fn announce_refund_window(
+        program_id: &Pubkey,
+        presale: &Pubkey,
+        mint: &Pubkey,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::AnnounceRefundWindow;
+        let data = to_vec(&instr)?;
+
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
+        let accounts = vec![
+            AccountMeta::new(*presale, false), // Presale state account
+            AccountMeta::new_readonly(emergency_state, false), // Emergency state PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new GetClaimableAmount instruction
+    pub fn get_claimable_amount(
+        program_id: &Pubkey,
+        vesting: &Pubkey,
+        beneficiary: &Pubkey,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::GetClaimableAmount {
+            beneficiary: *beneficiary,
+        };
+        let data = to_vec(&instr)?;
+
+        let (grant, _) = VestingGrant::find_address(program_id, vesting, beneficiary);
+
+        let accounts = vec![
+            AccountMeta::new_readonly(*vesting, false), // Vesting state account
+            AccountMeta::new_readonly(grant, false),    // Grant PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new SetKeeperRefund instruction
+    pub fn set_keeper_refund(
+        program_id: &Pubkey,
+        buyer: &Pubkey,
+        presale: &Pubkey,
+        mint: &Pubkey,
+        buyer_stablecoin_account: &Pubkey,
+        enabled: bool,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::SetKeeperRefund { enabled };
+        let data = to_vec(&instr)?;
+
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
+        let accounts = vec![
+            AccountMeta::new_readonly(*buyer, true),                     // Buyer (signer)
+            AccountMeta::new(*presale, false),                           // Presale state account
+            AccountMeta::new_readonly(*buyer_stablecoin_account, false), // Buyer's stablecoin ATA
+            AccountMeta::new_readonly(emergency_state, false),           // Emergency state PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new NoticeWithdraw instruction
+    pub fn notice_withdraw(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        presale: &Pubkey,
+        mint: &Pubkey,
+        locked_treasury: &Pubkey,
+        destination: &Pubkey,
+        stablecoin_mint: &Pubkey,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::NoticeWithdraw;
+        let data = to_vec(&instr)?;
+
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
+        let accounts = vec![
+            AccountMeta::new_readonly(*authority, true),        // Authority (signer)
+            AccountMeta::new(*presale, false),                  // Presale state account
+            AccountMeta::new_readonly(*locked_treasury, false), // Locked treasury stablecoin account
+            AccountMeta::new_readonly(*destination, false),     // Destination stablecoin account
+            AccountMeta::new_readonly(*stablecoin_mint, false), // Stablecoin mint
+            AccountMeta::new_readonly(emergency_state, false),  // Emergency state PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new ExecuteWithdraw instruction
+    #[allow(clippy::too_many_arguments)]
+    pub fn execute_withdraw(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        presale: &Pubkey,
+        mint: &Pubkey,
+        locked_treasury: &Pubkey,
+        destination: &Pubkey,
+        stablecoin_token_program: &Pubkey,
+        stablecoin_mint: &Pubkey,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::ExecuteWithdraw;
+        let data = to_vec(&instr)?;
+
+        let (locked_treasury_authority, _) =
+            Pubkey::find_program_address(&[b"locked_treasury", presale.as_ref()], program_id);
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
+        let accounts = vec![
+            AccountMeta::new_readonly(*authority, true),                 // Authority (signer)
+            AccountMeta::new(*presale, false),                           // Presale state account
+            AccountMeta::new(*locked_treasury, false),                   // Locked treasury stablecoin account
+            AccountMeta::new(*destination, false),                       // Destination stablecoin account
+            AccountMeta::new_readonly(locked_treasury_authority, false), // Locked treasury authority PDA
+            AccountMeta::new_readonly(*stablecoin_token_program, false), // Stablecoin token program
+            AccountMeta::new_readonly(*stablecoin_mint, false),          // Stablecoin mint
+            AccountMeta::new_readonly(emergency_state, false),           // Emergency state PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new CompleteLaunchChecklistItem instruction
+    ///
+    /// `item_accounts` are the accounts the item is verified against (see the variant docs).
+    pub fn complete_launch_checklist_item(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        presale: &Pubkey,
+        mint: &Pubkey,
+        item: LaunchChecklistItem,
+        item_accounts: &[Pubkey],
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::CompleteLaunchChecklistItem { item };
+        let data = to_vec(&instr)?;
+
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
+        let mut accounts = vec![
+            AccountMeta::new_readonly(*authority, true), // Authority (signer)
+            AccountMeta::new(*presale, false),           // Presale state account
+            AccountMeta::new_readonly(emergency_state, false), // Emergency state PDA
+        ];
+        accounts.extend(item_accounts.iter().map(|account| AccountMeta::new_readonly(*account, false)));
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new SetPythFeedId instruction
+    pub fn set_pyth_feed_id(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        controller: &Pubkey,
+        feed_id: Option<[u8; 32]>,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::SetPythFeedId { feed_id };
+        let data = to_vec(&instr)?;
+
+        let accounts = vec![
+            AccountMeta::new_readonly(*authority, true), // Controller authority (signer)
+            AccountMeta::new(*controller, false),        // Oracle controller account
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new SetConsensusEma instruction
+    pub fn set_consensus_ema(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        controller: &Pubkey,
+        alpha_bps: u16,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::SetConsensusEma { alpha_bps };
+        let data = to_vec(&instr)?;
+
+        let accounts = vec![
+            AccountMeta::new_readonly(*authority, true), // Controller authority (signer)
+            AccountMeta::new(*controller, false),        // Oracle controller account
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new SetRedstoneConfig instruction
+    pub fn set_redstone_config(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        controller: &Pubkey,
+        config: Option<RedstoneConfig>,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::SetRedstoneConfig { config };
+        let data = to_vec(&instr)?;
+
+        let accounts = vec![
+            AccountMeta::new_readonly(*authority, true), // Controller authority (signer)
+            AccountMeta::new(*controller, false),        // Oracle controller account
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new RemoveOracleSource instruction
+    pub fn remove_oracle_source(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        controller: &Pubkey,
+        oracle: &Pubkey,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::RemoveOracleSource { oracle: *oracle };
+        let data = to_vec(&instr)?;
+
+        let accounts = vec![
+            AccountMeta::new_readonly(*authority, true), // Controller authority (signer)
+            AccountMeta::new(*controller, false),        // Oracle controller account
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new SetOracleSourceActive instruction
+    pub fn set_oracle_source_active(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        controller: &Pubkey,
+        oracle: &Pubkey,
+        is_active: bool,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::SetOracleSourceActive { oracle: *oracle, is_active };
+        let data = to_vec(&instr)?;
+
+        let accounts = vec![
+            AccountMeta::new_readonly(*authority, true), // Controller authority (signer)
+            AccountMeta::new(*controller, false),        // Oracle controller account
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new UpdateOracleSourceConfig instruction
+    pub fn update_oracle_source_config(
+        program_id: &Pubkey,
+        params: &UpdateOracleSourceConfigParams,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::UpdateOracleSourceConfig {
+            oracle: params.oracle,
+            weight: params.weight,
+            max_deviation_bps: params.max_deviation_bps,
+            max_staleness_seconds: params.max_staleness_seconds,
+            is_required: params.is_required,
+        };
+        let data = to_vec(&instr)?;
+
+        let accounts = vec![
+            AccountMeta::new_readonly(params.authority, true), // Controller authority (signer)
+            AccountMeta::new(params.controller, false),        // Oracle controller account
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new SetChainlinkStreamsConfig instruction
+    pub fn set_chainlink_streams_config(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        controller: &Pubkey,
+        config: Option<ChainlinkStreamsConfig>,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::SetChainlinkStreamsConfig { config };
+        let data = to_vec(&instr)?;
+
+        let accounts = vec![
+            AccountMeta::new_readonly(*authority, true), // Controller authority (signer)
+            AccountMeta::new(*controller, false),        // Oracle controller account
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new SubmitChainlinkStreamsReport instruction
+    pub fn submit_chainlink_streams_report(
+        program_id: &Pubkey,
+        keeper: &Pubkey,
+        controller: &Pubkey,
+        report: Vec<u8>,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::SubmitChainlinkStreamsReport { report };
+        let data = to_vec(&instr)?;
+
+        let (report_account, _) = ChainlinkStreamsReport::find_address(program_id, controller);
+        let accounts = vec![
+            AccountMeta::new(*keeper, true),                        // Keeper (signer, payer)
+            AccountMeta::new_readonly(*controller, false),          // Oracle controller account
+            AccountMeta::new(report_account, false),                // Report account
+            AccountMeta::new_readonly(system_program::id(), false), // System program
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new SetQuorumPolicy instruction
+    pub fn set_quorum_policy(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        controller: &Pubkey,
+        policy: QuorumPolicy,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::SetQuorumPolicy { policy };
+        let data = to_vec(&instr)?;
+
+        let accounts = vec![
+            AccountMeta::new_readonly(*authority, true), // Controller authority (signer)
+            AccountMeta::new(*controller, false),        // Oracle controller account
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new GetCircuitBreakerIncidents instruction
+    pub fn get_circuit_breaker_incidents(
+        program_id: &Pubkey,
+        controller: &Pubkey,
+    ) -> Result<Instruction, std::io::Error> {
+        let data = to_vec(&Self::GetCircuitBreakerIncidents)?;
+
+        let accounts = vec![
+            AccountMeta::new_readonly(*controller, false), // Oracle controller account
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new SetCircuitBreakerRecovery instruction
+    pub fn set_circuit_breaker_recovery(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        controller: &Pubkey,
+        auto_recovery: bool,
+        cooldown_seconds: u32,
+        max_cooldown_seconds: u32,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::SetCircuitBreakerRecovery {
+            auto_recovery,
+            cooldown_seconds,
+            max_cooldown_seconds,
+        };
+        let data = to_vec(&instr)?;
+
+        let accounts = vec![
+            AccountMeta::new_readonly(*authority, true), // Controller authority (signer)
+            AccountMeta::new(*controller, false),        // Oracle controller account
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new SetStalenessPolicy instruction
+    pub fn set_staleness_policy(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        controller: &Pubkey,
+        policy: StalenessPolicy,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::SetStalenessPolicy { policy };
+        let data = to_vec(&instr)?;
+
+        let accounts = vec![
+            AccountMeta::new_readonly(*authority, true), // Controller authority (signer)
+            AccountMeta::new(*controller, false),        // Oracle controller account
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new InitializeBugBounty instruction
+    ///
+    /// The escrow is the bounty PDA's Token-2022 ATA, which must exist beforehand.
+    pub fn initialize_bug_bounty(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        mint: &Pubkey,
+        guardians: Vec<Pubkey>,
+        guardian_threshold: u8,
+    ) -> Result<Instruction, std::io::Error> {
+        let (bounty, _) = BugBountyEscrow::find_address(program_id, mint);
+
+        let instr = Self::InitializeBugBounty { guardians, guardian_threshold };
+        let data = to_vec(&instr)?;
+
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
+        let accounts = vec![
+            AccountMeta::new(*authority, true),                                  // Authority (signer, payer)
+            AccountMeta::new(bounty, false),                                     // Bounty PDA
+            AccountMeta::new_readonly(*mint, false),                             // Mint
+            AccountMeta::new_readonly(bug_bounty_escrow(&bounty, mint), false),  // Escrow token account
+            AccountMeta::new_readonly(system_program::id(), false),              // System program
+            AccountMeta::new_readonly(emergency_state, false),                   // Emergency state PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new FundBugBounty instruction
+    pub fn fund_bug_bounty(
+        program_id: &Pubkey,
+        funder: &Pubkey,
+        treasury: &Pubkey,
+        mint: &Pubkey,
+        amount: u64,
+    ) -> Result<Instruction, std::io::Error> {
+        let (bounty, _) = BugBountyEscrow::find_address(program_id, mint);
+
+        let instr = Self::FundBugBounty { amount };
+        let data = to_vec(&instr)?;
+
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
+        let accounts = vec![
+            AccountMeta::new_readonly(*funder, true),                // Treasury owner (signer)
+            AccountMeta::new(bounty, false),                         // Bounty PDA
+            AccountMeta::new(*treasury, false),                      // Treasury token account
+            AccountMeta::new(bug_bounty_escrow(&bounty, mint), false), // Escrow token account
+            AccountMeta::new_readonly(*mint, false),                 // Mint account
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false), // Token program
+            AccountMeta::new_readonly(emergency_state, false),       // Emergency state PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new CommitBugBountyAward instruction
+    pub fn commit_bug_bounty_award(
+        program_id: &Pubkey,
+        payer: &Pubkey,
+        mint: &Pubkey,
+        guardians: &[Pubkey],
+        disclosure_hash: [u8; 32],
+        researcher: &Pubkey,
+        amount: u64,
+    ) -> Result<Instruction, std::io::Error> {
+        let (bounty, _) = BugBountyEscrow::find_address(program_id, mint);
+        let (award, _) = BugBountyAward::find_address(program_id, &bounty, &disclosure_hash);
+
+        let instr = Self::CommitBugBountyAward { disclosure_hash, researcher: *researcher, amount };
+        let data = to_vec(&instr)?;
+
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
+        let mut accounts = vec![
+            AccountMeta::new(*payer, true),                                      // Payer (signer)
+            AccountMeta::new(bounty, false),                                     // Bounty PDA
+            AccountMeta::new(award, false),                                      // Award PDA
+            AccountMeta::new_readonly(bug_bounty_escrow(&bounty, mint), false),  // Escrow token account
+            AccountMeta::new_readonly(system_program::id(), false),              // System program
+            AccountMeta::new_readonly(emergency_state, false),                   // Emergency state PDA
+        ];
+        accounts.extend(guardians.iter().map(|guardian| AccountMeta::new_readonly(*guardian, true))); // Approving guardians
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new PayBugBountyAward instruction, paying into the researcher's ATA
+    pub fn pay_bug_bounty_award(
+        program_id: &Pubkey,
+        mint: &Pubkey,
+        disclosure_hash: [u8; 32],
+        researcher: &Pubkey,
+    ) -> Result<Instruction, std::io::Error> {
+        let (bounty, _) = BugBountyEscrow::find_address(program_id, mint);
+        let (award, _) = BugBountyAward::find_address(program_id, &bounty, &disclosure_hash);
+        let destination = get_associated_token_address_with_program_id(researcher, mint, &TOKEN_2022_PROGRAM_ID);
+
+        let data = to_vec(&Self::PayBugBountyAward)?;
+
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
+        let accounts = vec![
+            AccountMeta::new(bounty, false),                         // Bounty PDA
+            AccountMeta::new(award, false),                          // Award PDA
+            AccountMeta::new(bug_bounty_escrow(&bounty, mint), false), // Escrow token account
+            AccountMeta::new(destination, false),                    // Researcher's token account
+            AccountMeta::new_readonly(*mint, false),                 // Mint account
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false), // Token program
+            AccountMeta::new_readonly(emergency_state, false),       // Emergency state PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new OpenMintMigration instruction
+    ///
+    /// The swap escrow is the migration PDA's Token-2022 ATA for the v1 mint, which
+    /// must exist beforehand.
+    pub fn open_mint_migration(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        old_mint: &Pubkey,
+        new_mint: &Pubkey,
+    ) -> Result<Instruction, std::io::Error> {
+        let (migration, _) = MintMigration::find_address(program_id, old_mint);
+        let escrow = get_associated_token_address_with_program_id(&migration, old_mint, &TOKEN_2022_PROGRAM_ID);
+        let (old_mint_authority, _) =
+            Pubkey::find_program_address(&[b"mint_authority", old_mint.as_ref()], program_id);
+
+        let data = to_vec(&Self::OpenMintMigration)?;
+
+        let (emergency_state, _) = EmergencyState::find_address(program_id, old_mint);
+
+        let accounts = vec![
+            AccountMeta::new(*authority, true),                      // v1 freeze authority (signer, payer)
+            AccountMeta::new(migration, false),                      // Migration PDA
+            AccountMeta::new(*old_mint, false),                      // v1 mint
+            AccountMeta::new_readonly(*new_mint, false),             // v2 mint
+            AccountMeta::new_readonly(escrow, false),                // Swap escrow
+            AccountMeta::new_readonly(old_mint_authority, false),    // v1 mint authority PDA
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false), // Token program
+            AccountMeta::new_readonly(system_program::id(), false),  // System program
+            AccountMeta::new_readonly(emergency_state, false),       // Emergency state PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new MigrateTokens instruction between the holder's ATAs
+    pub fn migrate_tokens(
+        program_id: &Pubkey,
+        holder: &Pubkey,
+        old_mint: &Pubkey,
+        new_mint: &Pubkey,
+        amount: u64,
+    ) -> Result<Instruction, std::io::Error> {
+        let (migration, _) = MintMigration::find_address(program_id, old_mint);
+        let escrow = get_associated_token_address_with_program_id(&migration, old_mint, &TOKEN_2022_PROGRAM_ID);
+        let (new_mint_authority, _) =
+            Pubkey::find_program_address(&[b"mint_authority", new_mint.as_ref()], program_id);
+
+        let instr = Self::MigrateTokens { amount };
+        let data = to_vec(&instr)?;
+
+        let (emergency_state, _) = EmergencyState::find_address(program_id, old_mint);
+
+        let accounts = vec![
+            AccountMeta::new_readonly(*holder, true), // Holder (signer)
+            AccountMeta::new(migration, false),       // Migration PDA
+            AccountMeta::new(get_associated_token_address_with_program_id(holder, old_mint, &TOKEN_2022_PROGRAM_ID), false), // Holder's v1 token account
+            AccountMeta::new(escrow, false),          // Swap escrow
+            AccountMeta::new(*old_mint, false),       // v1 mint
+            AccountMeta::new(get_associated_token_address_with_program_id(holder, new_mint, &TOKEN_2022_PROGRAM_ID), false), // Holder's v2 token account
+            AccountMeta::new(*new_mint, false),       // v2 mint
+            AccountMeta::new_readonly(new_mint_authority, false),    // v2 mint authority PDA
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false), // Token program
+            AccountMeta::new_readonly(emergency_state, false),       // Emergency state PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new RepointSupplyController instruction
+    pub fn repoint_supply_controller(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        old_mint: &Pubkey,
+        controller: &Pubkey,
+    ) -> Result<Instruction, std::io::Error> {
+        let (migration, _) = MintMigration::find_address(program_id, old_mint);
+
+        let data = to_vec(&Self::RepointSupplyController)?;
+
+        let (emergency_state, _) = EmergencyState::find_address(program_id, old_mint);
+
+        let accounts = vec![
+            AccountMeta::new_readonly(*authority, true), // Migration authority (signer)
+            AccountMeta::new(migration, false),          // Migration PDA
+            AccountMeta::new(*controller, false),        // Autonomous supply controller
+            AccountMeta::new_readonly(emergency_state, false), // Emergency state PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new SetAggregationStrategy instruction
+    pub fn set_aggregation_strategy(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        controller: &Pubkey,
+        strategy: AggregationStrategy,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::SetAggregationStrategy { strategy };
+        let data = to_vec(&instr)?;
+
+        let accounts = vec![
+            AccountMeta::new_readonly(*authority, true), // Controller authority (signer)
+            AccountMeta::new(*controller, false),        // Oracle controller account
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new ConfigureSimulation instruction
+    pub fn configure_simulation(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        unix_timestamp: i64,
+        prices: Vec<u64>,
+    ) -> Result<Instruction, std::io::Error> {
+        let (simulation, _) = SimulationState::find_address(program_id);
+        let (price_feed, _) = SimulationState::find_price_feed(program_id);
+
+        let instr = Self::ConfigureSimulation { unix_timestamp, prices };
+        let data = to_vec(&instr)?;
+
+        let accounts = vec![
+            AccountMeta::new(*authority, true),  // Simulation authority (signer, payer)
+            AccountMeta::new(simulation, false), // Simulation PDA
+            AccountMeta::new(price_feed, false), // Simulated price feed PDA
+            AccountMeta::new_readonly(system_program::id(), false), // System program
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new StepSimulation instruction
+    pub fn step_simulation(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        seconds: u32,
+    ) -> Result<Instruction, std::io::Error> {
+        let (simulation, _) = SimulationState::find_address(program_id);
+        let (price_feed, _) = SimulationState::find_price_feed(program_id);
+
+        let instr = Self::StepSimulation { seconds };
+        let data = to_vec(&instr)?;
+
+        let accounts = vec![
+            AccountMeta::new_readonly(*authority, true), // Simulation authority (signer)
+            AccountMeta::new(simulation, false),         // Simulation PDA
+            AccountMeta::new(price_feed, false),         // Simulated price feed PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new CreateManagedFeed instruction
+    pub fn create_managed_feed(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        asset_id: String,
+        min_required_oracles: u8,
+    ) -> Result<Instruction, std::io::Error> {
+        let (registry, _) = OracleFeedRegistry::find_address(program_id, authority);
+        let (controller, _) = OracleFeedRegistry::find_feed_address(program_id, &registry, &asset_id);
+
+        let instr = Self::CreateManagedFeed { asset_id, min_required_oracles };
+        let data = to_vec(&instr)?;
+
+        let accounts = vec![
+            AccountMeta::new(*authority, true),  // Authority (signer, payer)
+            AccountMeta::new(registry, false),   // Feed registry PDA
+            AccountMeta::new(controller, false), // Oracle controller PDA
+            AccountMeta::new_readonly(system_program::id(), false), // System program
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new EnterMaintenanceMode instruction
+    pub fn enter_maintenance_mode(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        mint: &Pubkey,
+        reason: Option<String>,
+    ) -> Result<Instruction, std::io::Error> {
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
+        let instr = Self::EnterMaintenanceMode { reason };
+        let data = to_vec(&instr)?;
+
+        let accounts = vec![
+            AccountMeta::new_readonly(*authority, true), // Emergency authority (signer)
+            AccountMeta::new(emergency_state, false),    // Emergency state PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new SetStablecoinPegGuard instruction
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_stablecoin_peg_guard(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        presale: &Pubkey,
+        mint: &Pubkey,
+        stablecoin_mint: &Pubkey,
+        oracle_controller: &Pubkey,
+        band_bps: u16,
+        action: DepegAction,
+    ) -> Result<Instruction, std::io::Error> {
+        let (guard, _) = StablecoinPegGuard::find_address(program_id, presale, stablecoin_mint);
+
+        let instr = Self::SetStablecoinPegGuard { band_bps, action };
+        let data = to_vec(&instr)?;
+
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
+        let accounts = vec![
+            AccountMeta::new(*authority, true),                     // Authority (signer, payer)
+            AccountMeta::new(*presale, false),                      // Presale state account
+            AccountMeta::new_readonly(*stablecoin_mint, false),     // Stablecoin mint
+            AccountMeta::new(guard, false),                         // Peg guard PDA
+            AccountMeta::new_readonly(*oracle_controller, false),   // Stablecoin oracle controller
+            AccountMeta::new_readonly(system_program::id(), false), // System program
+            AccountMeta::new_readonly(emergency_state, false),      // Emergency state PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new SetSignedPricePublishers instruction
+    pub fn set_signed_price_publishers(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        controller: &Pubkey,
+        publishers: Vec<Pubkey>,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::SetSignedPricePublishers { publishers };
+        let data = to_vec(&instr)?;
+
+        let accounts = vec![
+            AccountMeta::new_readonly(*authority, true), // Controller authority (signer)
+            AccountMeta::new(*controller, false),        // Oracle controller account
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new SubmitSignedPrice instruction
+    ///
+    /// Must be sent directly after an ed25519 program instruction carrying the
+    /// publisher's signature over the price message.
+    pub fn submit_signed_price(
+        program_id: &Pubkey,
+        keeper: &Pubkey,
+        controller: &Pubkey,
+        publisher: &Pubkey,
+        price: u64,
+        confidence: u64,
+        publish_time: i64,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::SubmitSignedPrice {
+            publisher: *publisher,
+            price,
+            confidence,
+            publish_time,
+        };
+        let data = to_vec(&instr)?;
+
+        let (price_account, _) = SignedPriceReport::find_address(program_id, controller);
+        let accounts = vec![
+            AccountMeta::new(*keeper, true),                        // Keeper (signer, payer)
+            AccountMeta::new_readonly(*controller, false),          // Oracle controller account
+            AccountMeta::new(price_account, false),                 // Signed price account
+            AccountMeta::new_readonly(sysvar::instructions::id(), false), // Instructions sysvar
             AccountMeta::new_readonly(system_program::id(), false), // System program
-            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false), // Rent sysvar
         ];
 
         Ok(Instruction {
@@ -661,23 +4786,33 @@ impl VCoinInstruction {
         })
     }
 
-    /// Creates a new AddVestingBeneficiary instruction
-    pub fn add_vesting_beneficiary(
+    /// Creates a new ApproveYieldVenue instruction approving `venue`'s reserve,
+    /// insurance fund and deposit cap (its bookkeeping fields are ignored)
+    pub fn approve_yield_venue(
         program_id: &Pubkey,
         authority: &Pubkey,
-        vesting: &Pubkey,
-        beneficiary: &Pubkey,
-        amount: u64,
+        mint: &Pubkey,
+        venue: &YieldVenue,
     ) -> Result<Instruction, std::io::Error> {
-        let instr = Self::AddVestingBeneficiary {
-            beneficiary: *beneficiary,
-            amount,
-        };
+        let (venue_address, _) = YieldVenue::find_address(program_id, &venue.presale, &venue.stablecoin_mint);
+
+        let instr = Self::ApproveYieldVenue { deposit_cap: venue.deposit_cap };
         let data = to_vec(&instr)?;
 
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
         let accounts = vec![
-            AccountMeta::new_readonly(*authority, true),          // Authority (signer)
-            AccountMeta::new(*vesting, false),                    // Vesting state account
+            AccountMeta::new(*authority, true),                         // Authority (signer, payer)
+            AccountMeta::new_readonly(venue.presale, false),            // Presale state account
+            AccountMeta::new_readonly(venue.stablecoin_mint, false),    // Stablecoin mint
+            AccountMeta::new(venue_address, false),                     // Yield venue PDA
+            AccountMeta::new_readonly(venue.lending_program, false),    // Lending program
+            AccountMeta::new_readonly(venue.lending_market, false),     // Lending market
+            AccountMeta::new_readonly(venue.reserve, false),            // Reserve
+            AccountMeta::new_readonly(venue.collateral_account, false), // Treasury collateral account
+            AccountMeta::new_readonly(venue.insurance_fund, false),     // Insurance fund account
+            AccountMeta::new_readonly(system_program::id(), false), // System program
+            AccountMeta::new_readonly(emergency_state, false),      // Emergency state PDA
         ];
 
         Ok(Instruction {
@@ -687,27 +4822,43 @@ impl VCoinInstruction {
         })
     }
 
-    /// Creates a new ReleaseVestedTokens instruction
-    pub fn release_vested_tokens(
+    /// Creates a new DeployTreasuryYield instruction for an approved venue
+    #[allow(clippy::too_many_arguments)]
+    pub fn deploy_treasury_yield(
         program_id: &Pubkey,
         authority: &Pubkey,
-        vesting: &Pubkey,
         mint: &Pubkey,
-        beneficiary: &Pubkey,
-        beneficiary_token_account: &Pubkey,
+        venue: &YieldVenue,
+        locked_treasury: &Pubkey,
+        reserve: &ReserveAccounts,
+        token_program: &Pubkey,
+        amount: u64,
     ) -> Result<Instruction, std::io::Error> {
-        let instr = Self::ReleaseVestedTokens {
-            beneficiary: *beneficiary,
-        };
+        let (venue_address, _) = YieldVenue::find_address(program_id, &venue.presale, &venue.stablecoin_mint);
+        let (locked_treasury_authority, _) =
+            Pubkey::find_program_address(&[b"locked_treasury", venue.presale.as_ref()], program_id);
+
+        let instr = Self::DeployTreasuryYield { amount };
         let data = to_vec(&instr)?;
 
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
         let accounts = vec![
-            AccountMeta::new_readonly(*authority, true),           // Authority (signer)
-            AccountMeta::new(*vesting, false),                     // Vesting state account
-            AccountMeta::new(*mint, false),                        // Mint account
-            AccountMeta::new(*beneficiary_token_account, false),   // Beneficiary's token account
-            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false), // Token program
-            AccountMeta::new_readonly(sysvar::clock::id(), false), // Clock sysvar
+            AccountMeta::new_readonly(*authority, true),                  // Presale authority (signer)
+            AccountMeta::new_readonly(venue.presale, false),              // Presale state account
+            AccountMeta::new(venue_address, false),                       // Yield venue PDA
+            AccountMeta::new(*locked_treasury, false),                    // Locked treasury stablecoin account
+            AccountMeta::new_readonly(locked_treasury_authority, false),  // Locked treasury authority PDA
+            AccountMeta::new(venue.collateral_account, false),            // Treasury collateral account
+            AccountMeta::new(venue.reserve, false),                       // Reserve
+            AccountMeta::new(reserve.liquidity_supply, false),            // Reserve liquidity supply
+            AccountMeta::new(reserve.collateral_mint, false),             // Reserve collateral mint
+            AccountMeta::new_readonly(venue.lending_market, false),       // Lending market
+            AccountMeta::new_readonly(reserve.lending_market_authority, false), // Lending market authority
+            AccountMeta::new_readonly(venue.lending_program, false),      // Lending program
+            AccountMeta::new_readonly(sysvar::clock::id(), false),        // Clock sysvar
+            AccountMeta::new_readonly(*token_program, false),             // Token program
+            AccountMeta::new_readonly(emergency_state, false),            // Emergency state PDA
         ];
 
         Ok(Instruction {
@@ -717,28 +4868,66 @@ impl VCoinInstruction {
         })
     }
 
-    /// Creates a new UpdateTokenMetadata instruction
-    pub fn update_token_metadata(
+    /// Creates a new RecallTreasuryYield instruction for an approved venue
+    pub fn recall_treasury_yield(
+        program_id: &Pubkey,
+        caller: &Pubkey,
+        venue: &YieldVenue,
+        locked_treasury: &Pubkey,
+        reserve: &ReserveAccounts,
+        token_program: &Pubkey,
+        collateral_amount: Option<u64>,
+    ) -> Result<Instruction, std::io::Error> {
+        let (venue_address, _) = YieldVenue::find_address(program_id, &venue.presale, &venue.stablecoin_mint);
+        let (locked_treasury_authority, _) =
+            Pubkey::find_program_address(&[b"locked_treasury", venue.presale.as_ref()], program_id);
+
+        let instr = Self::RecallTreasuryYield { collateral_amount };
+        let data = to_vec(&instr)?;
+
+        let accounts = vec![
+            AccountMeta::new_readonly(*caller, true),                     // Caller (signer)
+            AccountMeta::new_readonly(venue.presale, false),              // Presale state account
+            AccountMeta::new(venue_address, false),                       // Yield venue PDA
+            AccountMeta::new(*locked_treasury, false),                    // Locked treasury stablecoin account
+            AccountMeta::new_readonly(locked_treasury_authority, false),  // Locked treasury authority PDA
+            AccountMeta::new(venue.collateral_account, false),            // Treasury collateral account
+            AccountMeta::new(venue.reserve, false),                       // Reserve
+            AccountMeta::new(reserve.collateral_mint, false),             // Reserve collateral mint
+            AccountMeta::new(reserve.liquidity_supply, false),            // Reserve liquidity supply
+            AccountMeta::new_readonly(venue.lending_market, false),       // Lending market
+            AccountMeta::new_readonly(reserve.lending_market_authority, false), // Lending market authority
+            AccountMeta::new_readonly(venue.lending_program, false),      // Lending program
+            AccountMeta::new_readonly(sysvar::clock::id(), false),        // Clock sysvar
+            AccountMeta::new_readonly(*token_program, false),             // Token program
+            AccountMeta::new_readonly(venue.stablecoin_mint, false),      // Stablecoin mint
+            AccountMeta::new(venue.insurance_fund, false),                // Insurance fund account
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new UpdateControllerParameters instruction
+    pub fn update_controller_parameters(
         program_id: &Pubkey,
         authority: &Pubkey,
-        metadata: &Pubkey,
+        controller: &Pubkey,
         mint: &Pubkey,
-        name: Option<String>,
-        symbol: Option<String>,
-        uri: Option<String>,
+        parameters: Option<SupplyParameters>,
     ) -> Result<Instruction, std::io::Error> {
-        let instr = Self::UpdateTokenMetadata {
-            name,
-            symbol,
-            uri,
-        };
+        let instr = Self::UpdateControllerParameters { parameters };
         let data = to_vec(&instr)?;
 
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
         let accounts = vec![
-            AccountMeta::new_readonly(*authority, true),           // Authority (signer)
-            AccountMeta::new(*metadata, false),                    // Metadata account
-            AccountMeta::new_readonly(*mint, false),               // Mint account
-            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false), // Token program
+            AccountMeta::new_readonly(*authority, true), // Controller authority (signer)
+            AccountMeta::new(*controller, false),        // Controller state account
+            AccountMeta::new_readonly(emergency_state, false), // Emergency state PDA
         ];
 
         Ok(Instruction {
@@ -748,24 +4937,29 @@ impl VCoinInstruction {
         })
     }
 
-    /// Creates a new SetTransferFee instruction
-    pub fn set_transfer_fee(
+    /// Creates a new SetSupplyRateLimit instruction
+    pub fn set_supply_rate_limit(
         program_id: &Pubkey,
-        fee_authority: &Pubkey,
+        authority: &Pubkey,
+        controller: &Pubkey,
         mint: &Pubkey,
-        transfer_fee_basis_points: u16,
-        maximum_fee: u64,
+        window_seconds: i64,
+        max_amount_per_window: u64,
+        max_operations_per_window: u8,
     ) -> Result<Instruction, std::io::Error> {
-        let instr = Self::SetTransferFee {
-            transfer_fee_basis_points,
-            maximum_fee,
+        let instr = Self::SetSupplyRateLimit {
+            window_seconds,
+            max_amount_per_window,
+            max_operations_per_window,
         };
         let data = to_vec(&instr)?;
 
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
         let accounts = vec![
-            AccountMeta::new_readonly(*fee_authority, true),      // Fee authority (signer)
-            AccountMeta::new(*mint, false),                       // Mint account
-            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false), // Token program
+            AccountMeta::new_readonly(*authority, true), // Controller authority (signer)
+            AccountMeta::new(*controller, false),        // Controller state account
+            AccountMeta::new_readonly(emergency_state, false), // Emergency state PDA
         ];
 
         Ok(Instruction {
@@ -775,18 +4969,26 @@ impl VCoinInstruction {
         })
     }
 
-    /// Creates a new EndPresale instruction
-    pub fn end_presale(
+    /// Creates a new InitializeSaleAnalytics instruction
+    pub fn initialize_sale_analytics(
         program_id: &Pubkey,
         authority: &Pubkey,
         presale: &Pubkey,
+        mint: &Pubkey,
     ) -> Result<Instruction, std::io::Error> {
-        let instr = Self::EndPresale;
+        let (analytics, _) = SaleAnalytics::find_address(program_id, presale);
+
+        let instr = Self::InitializeSaleAnalytics;
         let data = to_vec(&instr)?;
 
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
         let accounts = vec![
-            AccountMeta::new_readonly(*authority, true),           // Authority (signer)
-            AccountMeta::new(*presale, false),                     // Presale state account
+            AccountMeta::new(*authority, true),                     // Authority (signer, payer)
+            AccountMeta::new(*presale, false),                      // Presale state account
+            AccountMeta::new(analytics, false),                     // Sale analytics PDA
+            AccountMeta::new_readonly(system_program::id(), false), // System program
+            AccountMeta::new_readonly(emergency_state, false),      // Emergency state PDA
         ];
 
         Ok(Instruction {
@@ -796,26 +4998,26 @@ impl VCoinInstruction {
         })
     }
 
-    /// Creates a new InitializeAutonomousController instruction
-    pub fn initialize_autonomous_controller(
+    /// Creates a new SetSupplyDistribution instruction
+    pub fn set_supply_distribution(
         program_id: &Pubkey,
-        initial_price: u64,
-        max_supply: u64,
+        authority: &Pubkey,
+        controller: &Pubkey,
+        mint: &Pubkey,
+        distribution: SupplyDistribution,
     ) -> Result<Instruction, std::io::Error> {
-        let instr = Self::InitializeAutonomousController {
-            initial_price,
-            max_supply,
-        };
+        let instr = Self::SetSupplyDistribution { distribution };
         let data = to_vec(&instr)?;
 
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
         let accounts = vec![
-            AccountMeta::new_readonly(Pubkey::default(), true), // Temporary authority (signer)
-            AccountMeta::new(Pubkey::default(), false),          // Controller state account
-            AccountMeta::new_readonly(Pubkey::default(), false), // Mint account
-            AccountMeta::new_readonly(Pubkey::default(), false), // Price oracle account
-            AccountMeta::new_readonly(system_program::id(), false), // System program
-            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false), // Token program
-            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false), // Rent sysvar
+            AccountMeta::new_readonly(*authority, true),                        // Controller authority (signer)
+            AccountMeta::new(*controller, false),                               // Controller state account
+            AccountMeta::new_readonly(distribution.staking_rewards, false),     // Staking rewards token account
+            AccountMeta::new_readonly(distribution.liquidity, false),           // Liquidity token account
+            AccountMeta::new_readonly(distribution.operations, false),          // Operations token account
+            AccountMeta::new_readonly(emergency_state, false),                  // Emergency state PDA
         ];
 
         Ok(Instruction {
@@ -825,17 +5027,32 @@ impl VCoinInstruction {
         })
     }
 
-    /// Creates a new UpdateOraclePrice instruction
-    pub fn update_oracle_price(
+    /// Creates a new DistributeSupplyTreasury instruction
+    pub fn distribute_supply_treasury(
         program_id: &Pubkey,
+        controller: &Pubkey,
+        mint: &Pubkey,
+        treasury_token_account: &Pubkey,
+        distribution: &SupplyDistribution,
+        token_program: &Pubkey,
     ) -> Result<Instruction, std::io::Error> {
-        let instr = Self::UpdateOraclePrice;
+        let (treasury_authority, _) = AutonomousSupplyController::find_supply_treasury(program_id, mint);
+
+        let instr = Self::DistributeSupplyTreasury;
         let data = to_vec(&instr)?;
 
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
         let accounts = vec![
-            AccountMeta::new_readonly(Pubkey::default(), false), // Controller state account
-            AccountMeta::new_readonly(Pubkey::default(), false), // Primary price oracle account
-            AccountMeta::new_readonly(sysvar::clock::id(), false), // Clock sysvar
+            AccountMeta::new_readonly(*controller, false),             // Controller state account
+            AccountMeta::new_readonly(*mint, false),                   // Mint account
+            AccountMeta::new(*treasury_token_account, false),          // Supply treasury token account
+            AccountMeta::new_readonly(treasury_authority, false),      // Supply treasury PDA
+            AccountMeta::new(distribution.staking_rewards, false),     // Staking rewards token account
+            AccountMeta::new(distribution.liquidity, false),           // Liquidity token account
+            AccountMeta::new(distribution.operations, false),          // Operations token account
+            AccountMeta::new_readonly(*token_program, false),          // Token program
+            AccountMeta::new_readonly(emergency_state, false),         // Emergency state PDA
         ];
 
         Ok(Instruction {
@@ -845,21 +5062,23 @@ impl VCoinInstruction {
         })
     }
 
-    /// Creates a new ExecuteAutonomousMint instruction
-    pub fn execute_autonomous_mint(
+    /// Creates a new UpdateMetadataAuthority instruction
+    pub fn update_metadata_authority(
         program_id: &Pubkey,
+        authority: &Pubkey,
+        metadata: &Pubkey,
+        mint: &Pubkey,
+        new_authority: Option<Pubkey>,
     ) -> Result<Instruction, std::io::Error> {
-        let instr = Self::ExecuteAutonomousMint;
+        let instr = Self::UpdateMetadataAuthority { new_authority };
         let data = to_vec(&instr)?;
 
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
         let accounts = vec![
-            AccountMeta::new(Pubkey::default(), false),          // Controller state account
-            AccountMeta::new(Pubkey::default(), false),          // Mint account
-            AccountMeta::new_readonly(Pubkey::default(), false), // Mint authority PDA
-            AccountMeta::new(Pubkey::default(), false),          // Destination account
-            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false), // Token program
-            AccountMeta::new_readonly(sysvar::clock::id(), false), // Clock sysvar
-            AccountMeta::new_readonly(Pubkey::default(), false), // Price oracle account
+            AccountMeta::new_readonly(*authority, true), // Metadata authority (signer)
+            AccountMeta::new(*metadata, false),          // Metadata account
+            AccountMeta::new_readonly(emergency_state, false), // Emergency state PDA
         ];
 
         Ok(Instruction {
@@ -869,22 +5088,22 @@ impl VCoinInstruction {
         })
     }
 
-    /// Creates a new ExecuteAutonomousBurn instruction
-    pub fn execute_autonomous_burn(
+    /// Creates a new AcceptMetadataAuthority instruction
+    pub fn accept_metadata_authority(
         program_id: &Pubkey,
+        new_authority: &Pubkey,
+        metadata: &Pubkey,
+        mint: &Pubkey,
     ) -> Result<Instruction, std::io::Error> {
-        let instr = Self::ExecuteAutonomousBurn;
+        let instr = Self::AcceptMetadataAuthority;
         let data = to_vec(&instr)?;
 
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
         let accounts = vec![
-            AccountMeta::new(Pubkey::default(), false),          // Controller state account
-            AccountMeta::new(Pubkey::default(), false),          // Mint account
-            AccountMeta::new_readonly(Pubkey::default(), false), // Mint authority PDA
-            AccountMeta::new(Pubkey::default(), false),          // Burn treasury token account
-            AccountMeta::new_readonly(Pubkey::default(), false), // Burn treasury PDA
-            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false), // Token program
-            AccountMeta::new_readonly(sysvar::clock::id(), false), // Clock sysvar
-            AccountMeta::new_readonly(Pubkey::default(), false), // Price oracle account
+            AccountMeta::new_readonly(*new_authority, true), // Proposed metadata authority (signer)
+            AccountMeta::new(*metadata, false),              // Metadata account
+            AccountMeta::new_readonly(emergency_state, false), // Emergency state PDA
         ];
 
         Ok(Instruction {
@@ -894,22 +5113,26 @@ impl VCoinInstruction {
         })
     }
 
-    /// Creates a new PermanentlyDisableUpgrades instruction
-    pub fn permanently_disable_upgrades(
+    /// Creates a new InitializeSupplyLedger instruction
+    pub fn initialize_supply_ledger(
         program_id: &Pubkey,
-        current_upgrade_authority: &Pubkey,
-        program_account: &Pubkey,
-        program_data_account: &Pubkey,
+        payer: &Pubkey,
+        controller: &Pubkey,
+        mint: &Pubkey,
     ) -> Result<Instruction, std::io::Error> {
-        let instr = Self::PermanentlyDisableUpgrades;
+        let (ledger, _) = SupplyActionLedger::find_address(program_id, controller);
+
+        let instr = Self::InitializeSupplyLedger;
         let data = to_vec(&instr)?;
 
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
         let accounts = vec![
-            AccountMeta::new_readonly(*current_upgrade_authority, true), // Current upgrade authority (signer)
-            AccountMeta::new_readonly(*program_account, false),            // Program account
-            AccountMeta::new_readonly(*program_data_account, false),         // Program data account
-            AccountMeta::new_readonly(system_program::id(), false),            // System program
-            AccountMeta::new_readonly(solana_program::bpf_loader::id(), false), // BPF Upgradeable Loader program
+            AccountMeta::new(*payer, true),                         // Payer (signer)
+            AccountMeta::new_readonly(*controller, false),          // Controller state account
+            AccountMeta::new(ledger, false),                        // Supply action ledger PDA
+            AccountMeta::new_readonly(system_program::id(), false), // System program
+            AccountMeta::new_readonly(emergency_state, false),      // Emergency state PDA
         ];
 
         Ok(Instruction {
@@ -919,28 +5142,134 @@ impl VCoinInstruction {
         })
     }
 
-    /// Creates a new DepositToBurnTreasury instruction
-    pub fn deposit_to_burn_treasury(
+    /// Creates a new SetSupplyCooldowns instruction
+    pub fn set_supply_cooldowns(
         program_id: &Pubkey,
-        depositor: &Pubkey,
-        depositor_token_account: &Pubkey,
+        authority: &Pubkey,
+        controller: &Pubkey,
+        mint: &Pubkey,
+        mint_cooldown_seconds: u32,
+        burn_cooldown_seconds: u32,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::SetSupplyCooldowns {
+            mint_cooldown_seconds,
+            burn_cooldown_seconds,
+        };
+        let data = to_vec(&instr)?;
+
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
+        let accounts = vec![
+            AccountMeta::new_readonly(*authority, true), // Controller authority (signer)
+            AccountMeta::new(*controller, false),        // Controller state account
+            AccountMeta::new_readonly(emergency_state, false), // Emergency state PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new SetMetadataDisplayHints instruction
+    pub fn set_metadata_display_hints(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        metadata: &Pubkey,
+        mint: &Pubkey,
+        display_hints: Option<MetadataDisplayHints>,
+    ) -> Result<Instruction, std::io::Error> {
+        let instr = Self::SetMetadataDisplayHints { display_hints };
+        let data = to_vec(&instr)?;
+
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
+        let accounts = vec![
+            AccountMeta::new(*authority, true),                     // Metadata authority (signer, payer)
+            AccountMeta::new(*metadata, false),                     // Metadata account
+            AccountMeta::new_readonly(*mint, false),                // Mint account
+            AccountMeta::new_readonly(emergency_state, false),      // Emergency state PDA
+            AccountMeta::new_readonly(system_program::id(), false), // System program
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new CrankSupplyController instruction
+    #[allow(clippy::too_many_arguments)]
+    pub fn crank_supply_controller(
+        program_id: &Pubkey,
+        keeper: &Pubkey,
+        keeper_token_account: &Pubkey,
+        controller: &Pubkey,
+        mint: &Pubkey,
+        supply_treasury_token_account: &Pubkey,
         burn_treasury_token_account: &Pubkey,
-        controller_state_account: &Pubkey,
+        price_oracle: &Pubkey,
+    ) -> Result<Instruction, std::io::Error> {
+        let (mint_authority, _) = Pubkey::find_program_address(&[b"mint_authority", mint.as_ref()], program_id);
+        let (supply_treasury, _) = AutonomousSupplyController::find_supply_treasury(program_id, mint);
+        let (burn_treasury, _) = Pubkey::find_program_address(&[b"burn_treasury", mint.as_ref()], program_id);
+        let (ledger, _) = SupplyActionLedger::find_address(program_id, controller);
+        let (burn_log, _) = BurnLog::find_address(program_id, mint);
+
+        let instr = Self::CrankSupplyController;
+        let data = to_vec(&instr)?;
+
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
+        let accounts = vec![
+            AccountMeta::new_readonly(*keeper, true),                 // Keeper (signer)
+            AccountMeta::new(*keeper_token_account, false),           // Keeper reward token account
+            AccountMeta::new(*controller, false),                     // Controller state account
+            AccountMeta::new(*mint, false),                           // Mint account
+            AccountMeta::new_readonly(mint_authority, false),         // Mint authority PDA
+            AccountMeta::new(*supply_treasury_token_account, false),  // Supply treasury token account
+            AccountMeta::new_readonly(supply_treasury, false),        // Supply treasury PDA
+            AccountMeta::new(*burn_treasury_token_account, false),    // Burn treasury token account
+            AccountMeta::new_readonly(burn_treasury, false),          // Burn treasury PDA
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),  // Token program
+            AccountMeta::new_readonly(*price_oracle, false),          // Multi-oracle controller
+            AccountMeta::new(ledger, false),                          // Supply action ledger
+            AccountMeta::new(burn_log, false),                        // Burn log
+            AccountMeta::new_readonly(emergency_state, false),        // Emergency state PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new ReleaseLiquidityEscrow instruction
+    pub fn release_liquidity_escrow(
+        program_id: &Pubkey,
+        authority: &Pubkey,
         mint: &Pubkey,
+        destination: &Pubkey,
         amount: u64,
     ) -> Result<Instruction, std::io::Error> {
-        let instr = Self::DepositToBurnTreasury {
-            amount,
-        };
+        let (escrow_owner, _) = AllocationEscrow::find_liquidity_escrow(program_id, mint);
+        let escrow = get_associated_token_address_with_program_id(&escrow_owner, mint, &TOKEN_2022_PROGRAM_ID);
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
+        let instr = Self::ReleaseLiquidityEscrow { amount };
         let data = to_vec(&instr)?;
 
         let accounts = vec![
-            AccountMeta::new(*depositor, true),                  // Depositor (signer)
-            AccountMeta::new(*depositor_token_account, false),   // Depositor's token account
-            AccountMeta::new(*burn_treasury_token_account, false), // Burn treasury token account
-            AccountMeta::new(*controller_state_account, false),    // Controller state account
-            AccountMeta::new(*mint, false),                        // Mint account
+            AccountMeta::new_readonly(*authority, true),             // Token authority (signer)
+            AccountMeta::new_readonly(*mint, false),                 // Mint account
+            AccountMeta::new_readonly(escrow_owner, false),          // Liquidity escrow PDA
+            AccountMeta::new(escrow, false),                         // Liquidity escrow token account
+            AccountMeta::new(*destination, false),                   // Destination token account
             AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false), // Token program
+            AccountMeta::new_readonly(emergency_state, false),       // Emergency state PDA
         ];
 
         Ok(Instruction {
@@ -950,26 +5279,52 @@ impl VCoinInstruction {
         })
     }
 
-    /// Creates a new InitializeBurnTreasury instruction
-    pub fn initialize_burn_treasury(
+    /// Creates a new SyncSupply instruction
+    pub fn sync_supply(
         program_id: &Pubkey,
-        payer: &Pubkey,
-        controller_state_account: &Pubkey,
+        controller: &Pubkey,
+        mint: &Pubkey,
+    ) -> Result<Instruction, std::io::Error> {
+        let data = to_vec(&Self::SyncSupply)?;
+
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
+        let accounts = vec![
+            AccountMeta::new(*controller, false),      // Supply controller
+            AccountMeta::new_readonly(*mint, false),   // Mint account
+            AccountMeta::new_readonly(emergency_state, false), // Emergency state PDA
+        ];
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Creates a new SweepWithheldFeesToBurn instruction
+    pub fn sweep_withheld_fees_to_burn(
+        program_id: &Pubkey,
+        withdraw_authority: &Pubkey,
+        controller: &Pubkey,
         mint: &Pubkey,
         burn_treasury_token_account: &Pubkey,
+        sources: &[Pubkey],
     ) -> Result<Instruction, std::io::Error> {
-        let instr = Self::InitializeBurnTreasury;
-        let data = to_vec(&instr)?;
+        let (fee_sweep_authority, _) = AutonomousSupplyController::find_fee_sweep_authority(program_id, mint);
+        let data = to_vec(&Self::SweepWithheldFeesToBurn)?;
+
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
 
-        let accounts = vec![
-            AccountMeta::new(*payer, true),                  // Payer (signer)
-            AccountMeta::new(*controller_state_account, false),    // Controller state account
-            AccountMeta::new(*mint, false),                        // Mint account
-            AccountMeta::new(*burn_treasury_token_account, false), // Burn treasury token account
-            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false), // Token program
-            AccountMeta::new_readonly(system_program::id(), false), // System program
-            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false), // Rent sysvar
+        let mut accounts = vec![
+            AccountMeta::new_readonly(*withdraw_authority, *withdraw_authority != fee_sweep_authority), // Withdraw-withheld authority
+            AccountMeta::new(*controller, false),                     // Controller state account
+            AccountMeta::new(*mint, false),                           // Mint account
+            AccountMeta::new(*burn_treasury_token_account, false),    // Burn treasury token account
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),  // Token program
+            AccountMeta::new_readonly(emergency_state, false),        // Emergency state PDA
         ];
+        accounts.extend(sources.iter().map(|source| AccountMeta::new(*source, false))); // Fee sources
 
         Ok(Instruction {
             program_id: *program_id,
@@ -978,23 +5333,23 @@ impl VCoinInstruction {
         })
     }
 
-    /// Creates a new ExpandPresaleAccount instruction
-    pub fn expand_presale_account(
+    /// Creates a new InitializeBurnLog instruction
+    pub fn initialize_burn_log(
         program_id: &Pubkey,
-        authority: &Pubkey,
-        presale: &Pubkey,
-        additional_buyers: u32,
+        payer: &Pubkey,
+        mint: &Pubkey,
     ) -> Result<Instruction, std::io::Error> {
-        let instr = Self::ExpandPresaleAccount {
-            additional_buyers,
-        };
-        let data = to_vec(&instr)?;
+        let (burn_log, _) = BurnLog::find_address(program_id, mint);
+        let data = to_vec(&Self::InitializeBurnLog)?;
+
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
 
         let accounts = vec![
-            AccountMeta::new_readonly(*authority, true),           // Authority (signer)
-            AccountMeta::new(*presale, false),                     // Presale state account
+            AccountMeta::new(*payer, true),                         // Payer (signer)
+            AccountMeta::new_readonly(*mint, false),                // Mint account
+            AccountMeta::new(burn_log, false),                      // Burn log PDA
             AccountMeta::new_readonly(system_program::id(), false), // System program
-            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false), // Rent sysvar
+            AccountMeta::new_readonly(emergency_state, false),      // Emergency state PDA
         ];
 
         Ok(Instruction {
@@ -1004,29 +5359,28 @@ impl VCoinInstruction {
         })
     }
 
-    /// Creates a new ClaimDevFundRefund instruction
-    pub fn claim_dev_fund_refund(
+    /// Creates a new BurnTokens instruction
+    pub fn burn_tokens(
         program_id: &Pubkey,
-        buyer: &Pubkey,
-        presale: &Pubkey,
-        buyer_stablecoin_token_account: &Pubkey,
-        development_treasury_stablecoin_account: &Pubkey,
-        authority: &Pubkey,
-        stablecoin_token_program: &Pubkey,
-        stablecoin_mint: &Pubkey,
+        owner: &Pubkey,
+        token_account: &Pubkey,
+        mint: &Pubkey,
+        controller: &Pubkey,
+        amount: u64,
     ) -> Result<Instruction, std::io::Error> {
-        let instr = Self::ClaimDevFundRefund;
-        let data = to_vec(&instr)?;
+        let (burn_log, _) = BurnLog::find_address(program_id, mint);
+        let data = to_vec(&Self::BurnTokens { amount })?;
+
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
 
         let accounts = vec![
-            AccountMeta::new(*buyer, true),                  // Buyer (signer)
-            AccountMeta::new(*presale, false),               // Presale state account
-            AccountMeta::new(*buyer_stablecoin_token_account, false),   // Buyer's stablecoin token account (destination)
-            AccountMeta::new(*development_treasury_stablecoin_account, false),   // Development treasury stablecoin account (source)
-            AccountMeta::new(*authority, true),              // Authority (signer)
-            AccountMeta::new_readonly(*stablecoin_token_program, false),   // Stablecoin token program
-            AccountMeta::new_readonly(*stablecoin_mint, false),   // Stablecoin mint
-            AccountMeta::new_readonly(sysvar::clock::id(), false),  // Clock sysvar
+            AccountMeta::new_readonly(*owner, true),                 // Token account owner (signer)
+            AccountMeta::new(*token_account, false),                 // Token account to burn from
+            AccountMeta::new(*mint, false),                          // Mint account
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false), // Token program
+            AccountMeta::new(*controller, false),                    // Supply controller
+            AccountMeta::new(burn_log, false),                       // Burn log
+            AccountMeta::new_readonly(emergency_state, false),       // Emergency state PDA
         ];
 
         Ok(Instruction {
@@ -1036,20 +5390,24 @@ impl VCoinInstruction {
         })
     }
 
-    /// Creates a new EmergencyPause instruction
-    pub fn emergency_pause(
+    /// Creates a new DeclareForcedRefund instruction
+    pub fn declare_forced_refund(
         program_id: &Pubkey,
-        reason: Option<String>,
+        presale: &Pubkey,
+        mint: &Pubkey,
+        guardians: &[Pubkey],
+        dev_refund_vault_accounts: &[Pubkey],
+        reason_hash: [u8; 32],
     ) -> Result<Instruction, std::io::Error> {
-        let instr = Self::EmergencyPause {
-            reason,
-        };
-        let data = to_vec(&instr)?;
+        let (bounty, _) = BugBountyEscrow::find_address(program_id, mint);
+        let data = to_vec(&Self::DeclareForcedRefund { reason_hash })?;
 
-        let accounts = vec![
-            AccountMeta::new_readonly(Pubkey::default(), true), // Emergency authority (signer)
-            AccountMeta::new(Pubkey::default(), false),          // Emergency state account
+        let mut accounts = vec![
+            AccountMeta::new(*presale, false),         // Presale state account
+            AccountMeta::new_readonly(bounty, false),  // Bug bounty PDA (guardian set)
         ];
+        accounts.extend(guardians.iter().map(|guardian| AccountMeta::new_readonly(*guardian, true))); // Guardians (signers)
+        accounts.extend(dev_refund_vault_accounts.iter().map(|account| AccountMeta::new_readonly(*account, false))); // Dev refund vault token accounts
 
         Ok(Instruction {
             program_id: *program_id,
@@ -1058,16 +5416,29 @@ impl VCoinInstruction {
         })
     }
 
-    /// Creates a new EmergencyResume instruction
-    pub fn emergency_resume(
+    /// Creates a new InitializeFeeSponsorship instruction
+    pub fn initialize_fee_sponsorship(
         program_id: &Pubkey,
+        authority: &Pubkey,
+        mint: &Pubkey,
+        keeper: &Pubkey,
+        per_claim_lamports: u64,
+        budget_lamports: u64,
     ) -> Result<Instruction, std::io::Error> {
-        let instr = Self::EmergencyResume;
-        let data = to_vec(&instr)?;
+        let (sponsorship, _) = FeeSponsorship::find_address(program_id, mint);
+        let data = to_vec(&Self::InitializeFeeSponsorship {
+            keeper: *keeper,
+            per_claim_lamports,
+            budget_lamports,
+        })?;
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
 
         let accounts = vec![
-            AccountMeta::new_readonly(Pubkey::default(), true), // Emergency authority (signer)
-            AccountMeta::new(Pubkey::default(), false),          // Emergency state account
+            AccountMeta::new(*authority, true),                     // Authority (signer, funds the budget)
+            AccountMeta::new(sponsorship, false),                   // Fee sponsorship PDA
+            AccountMeta::new_readonly(*mint, false),                // Mint account
+            AccountMeta::new_readonly(system_program::id(), false), // System program
+            AccountMeta::new_readonly(emergency_state, false),      // Emergency state PDA
         ];
 
         Ok(Instruction {
@@ -1077,23 +5448,28 @@ impl VCoinInstruction {
         })
     }
 
-    /// Creates a new RescueTokens instruction
-    pub fn rescue_tokens(
+    /// Creates a new UpdateFeeSponsorship instruction
+    pub fn update_fee_sponsorship(
         program_id: &Pubkey,
-        amount: u64,
+        authority: &Pubkey,
+        mint: &Pubkey,
+        keeper: &Pubkey,
+        per_claim_lamports: u64,
+        additional_budget_lamports: u64,
     ) -> Result<Instruction, std::io::Error> {
-        let instr = Self::RescueTokens {
-            amount,
-        };
-        let data = to_vec(&instr)?;
+        let (sponsorship, _) = FeeSponsorship::find_address(program_id, mint);
+        let data = to_vec(&Self::UpdateFeeSponsorship {
+            keeper: *keeper,
+            per_claim_lamports,
+            additional_budget_lamports,
+        })?;
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
 
         let accounts = vec![
-            AccountMeta::new_readonly(Pubkey::default(), true), // Emergency authority (signer)
-            AccountMeta::new(Pubkey::default(), false),          // Source token account
-            AccountMeta::new(Pubkey::default(), false),          // Destination token account
-            AccountMeta::new_readonly(Pubkey::default(), false), // Source account authority (PDA derived from program)
-            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false), // Token program
-            AccountMeta::new_readonly(Pubkey::default(), false), // Mint account
+            AccountMeta::new(*authority, true),                     // Sponsorship authority (signer)
+            AccountMeta::new(sponsorship, false),                   // Fee sponsorship PDA
+            AccountMeta::new_readonly(system_program::id(), false), // System program
+            AccountMeta::new_readonly(emergency_state, false),      // Emergency state PDA
         ];
 
         Ok(Instruction {
@@ -1103,19 +5479,35 @@ impl VCoinInstruction {
         })
     }
 
-    /// Creates a new RecoverState instruction
-    pub fn recover_state(
+    /// Append the mint's fee sponsorship and its keeper to a `ClaimRefund` or
+    /// `ReleaseVestedTokens` instruction, so the keeper paying its fee is reimbursed
+    pub fn with_fee_sponsorship(
+        mut instruction: Instruction,
+        mint: &Pubkey,
+        keeper: &Pubkey,
+    ) -> Instruction {
+        let (sponsorship, _) = FeeSponsorship::find_address(&instruction.program_id, mint);
+        instruction.accounts.push(AccountMeta::new(sponsorship, false)); // Fee sponsorship PDA
+        instruction.accounts.push(AccountMeta::new(*keeper, true));      // Keeper (signer, fee payer)
+        instruction
+    }
+
+    /// Creates a new InitializeMultisig instruction
+    pub fn initialize_multisig(
         program_id: &Pubkey,
-        state_type: RecoveryStateType,
+        payer: &Pubkey,
+        base: &Pubkey,
+        signers: Vec<Pubkey>,
+        threshold: u8,
     ) -> Result<Instruction, std::io::Error> {
-        let instr = Self::RecoverState {
-            state_type,
-        };
-        let data = to_vec(&instr)?;
+        let data = to_vec(&Self::InitializeMultisig { signers, threshold })?;
+
+        let (multisig, _) = Multisig::find_address(program_id, base);
 
         let accounts = vec![
-            AccountMeta::new_readonly(Pubkey::default(), true), // Emergency authority (signer)
-            AccountMeta::new(Pubkey::default(), false),          // State account to recover
+            AccountMeta::new(*payer, true),                         // Payer (signer)
+            AccountMeta::new(multisig, false),                      // New multisig PDA
+            AccountMeta::new_readonly(*base, true),                 // Base key (signer)
             AccountMeta::new_readonly(system_program::id(), false), // System program
         ];
 
@@ -1126,179 +5518,584 @@ impl VCoinInstruction {
         })
     }
 
-    /// Creates InitializeOracleController instruction
-    pub fn initialize_oracle_controller(
+    /// Turn an instruction whose first account is an authority into one approved by
+    /// a `Multisig` at that address: the multisig no longer signs, and `signers`
+    /// (at least its threshold of keys) are appended as signers
+    pub fn with_multisig_signers(mut instruction: Instruction, signers: &[Pubkey]) -> Instruction {
+        if let Some(authority) = instruction.accounts.first_mut() {
+            authority.is_signer = false;
+        }
+        instruction.accounts.extend(signers.iter().map(|signer| AccountMeta::new_readonly(*signer, true))); // Multisig keys (signers)
+        instruction
+    }
+
+    /// Creates a new SweepTreasuryToCanonical instruction
+    #[allow(clippy::too_many_arguments)]
+    pub fn sweep_treasury_to_canonical(
         program_id: &Pubkey,
-        authority: &Pubkey,
-        controller: &Pubkey,
-        asset_id: String,
-        min_required_oracles: u8,
+        presale: &Pubkey,
+        mint: &Pubkey,
+        source: &Pubkey,
+        stablecoin_mint: &Pubkey,
+        stablecoin_token_program: &Pubkey,
+        guardians: &[Pubkey],
     ) -> Result<Instruction, std::io::Error> {
-        let accounts = vec![
-            AccountMeta::new_readonly(*authority, true),
-            AccountMeta::new(*controller, false),
-            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        let (bounty, _) = BugBountyEscrow::find_address(program_id, mint);
+        let (locked_treasury, _) =
+            Pubkey::find_program_address(&[b"locked_treasury", presale.as_ref()], program_id);
+        let canonical = get_associated_token_address_with_program_id(
+            &locked_treasury,
+            stablecoin_mint,
+            stablecoin_token_program,
+        );
+        let data = to_vec(&Self::SweepTreasuryToCanonical)?;
+
+        let mut accounts = vec![
+            AccountMeta::new(*presale, false),                             // Presale state account
+            AccountMeta::new_readonly(bounty, false),                      // Bug bounty PDA (guardian set)
+            AccountMeta::new_readonly(locked_treasury, false),             // Locked treasury authority PDA
+            AccountMeta::new(*source, false),                              // Non-canonical treasury account
+            AccountMeta::new(canonical, false),                            // Canonical treasury ATA
+            AccountMeta::new_readonly(*stablecoin_mint, false),            // Stablecoin mint
+            AccountMeta::new_readonly(*stablecoin_token_program, false),   // Stablecoin token program
         ];
-        
-        let data = Self::InitializeOracleController {
-            asset_id,
-            min_required_oracles,
-        }.try_to_vec()?;
-        
+        accounts.extend(guardians.iter().map(|guardian| AccountMeta::new_readonly(*guardian, true))); // Guardians (signers)
+
         Ok(Instruction {
             program_id: *program_id,
             accounts,
             data,
         })
     }
-    
-    /// Creates AddOracleSource instruction
-    pub fn add_oracle_source(
+
+    /// Creates a new InitializeTimelock instruction
+    pub fn initialize_timelock(
         program_id: &Pubkey,
         authority: &Pubkey,
-        controller: &Pubkey,
-        oracle: &Pubkey,
-        oracle_type: OracleType,
-        weight: u8,
-        max_deviation_bps: u16,
-        max_staleness_seconds: u32,
-        is_required: bool,
+        payer: &Pubkey,
+        mint: &Pubkey,
+        proposer: Pubkey,
+        delay: i64,
     ) -> Result<Instruction, std::io::Error> {
+        let (timelock, _) = Timelock::find_address(program_id, mint);
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+        let data = to_vec(&Self::InitializeTimelock { proposer, delay })?;
+
         let accounts = vec![
-            AccountMeta::new_readonly(*authority, true),
-            AccountMeta::new(*controller, false),
-            AccountMeta::new_readonly(*oracle, false),
+            AccountMeta::new_readonly(*authority, true),            // Emergency or program authority (signer)
+            AccountMeta::new(*payer, true),                         // Payer (signer)
+            AccountMeta::new(timelock, false),                      // Timelock PDA
+            AccountMeta::new_readonly(emergency_state, false),      // Emergency state PDA
+            AccountMeta::new_readonly(*mint, false),                // Mint account
+            AccountMeta::new_readonly(system_program::id(), false), // System program
         ];
-        
-        let data = Self::AddOracleSource {
-            oracle_type,
-            weight,
-            max_deviation_bps,
-            max_staleness_seconds,
-            is_required,
-        }.try_to_vec()?;
-        
+
         Ok(Instruction {
             program_id: *program_id,
             accounts,
             data,
         })
     }
-    
-    /// Creates UpdateOracleConsensus instruction
-    pub fn update_oracle_consensus(
+
+    /// Creates a new QueueTimelockOperation instruction
+    pub fn queue_timelock_operation(
         program_id: &Pubkey,
-        caller: &Pubkey,
-        controller: &Pubkey,
-        oracle_accounts: &[Pubkey],
+        proposer: &Pubkey,
+        mint: &Pubkey,
+        operation: TimelockedOperation,
     ) -> Result<Instruction, std::io::Error> {
-        let mut accounts = vec![
-            AccountMeta::new_readonly(*caller, true),
-            AccountMeta::new(*controller, false),
-            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        let (timelock, _) = Timelock::find_address(program_id, mint);
+        let data = to_vec(&Self::QueueTimelockOperation { operation })?;
+
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
+        let accounts = vec![
+            AccountMeta::new_readonly(*proposer, true), // Proposer (signer)
+            AccountMeta::new(timelock, false),          // Timelock PDA
+            AccountMeta::new_readonly(emergency_state, false), // Emergency state PDA
         ];
-        
-        // Add oracle accounts
-        for oracle in oracle_accounts {
-            accounts.push(AccountMeta::new_readonly(*oracle, false));
-        }
-        
-        let data = Self::UpdateOracleConsensus.try_to_vec()?;
-        
+
         Ok(Instruction {
             program_id: *program_id,
             accounts,
             data,
         })
     }
-    
-    /// Creates SetEmergencyPrice instruction
-    pub fn set_emergency_price(
+
+    /// Creates a new CancelTimelockOperation instruction
+    pub fn cancel_timelock_operation(
         program_id: &Pubkey,
         authority: &Pubkey,
-        controller: &Pubkey,
-        emergency_price: u64,
-        expiration_seconds: u32,
+        mint: &Pubkey,
+        operation_id: u64,
     ) -> Result<Instruction, std::io::Error> {
+        let (timelock, _) = Timelock::find_address(program_id, mint);
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+        let data = to_vec(&Self::CancelTimelockOperation { operation_id })?;
+
         let accounts = vec![
-            AccountMeta::new_readonly(*authority, true),
-            AccountMeta::new(*controller, false),
-            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(*authority, true),       // Emergency or program authority (signer)
+            AccountMeta::new(timelock, false),                 // Timelock PDA
+            AccountMeta::new_readonly(emergency_state, false), // Emergency state PDA
         ];
-        
-        let data = Self::SetEmergencyPrice {
-            emergency_price,
-            expiration_seconds,
-        }.try_to_vec()?;
-        
+
         Ok(Instruction {
             program_id: *program_id,
             accounts,
             data,
         })
     }
-    
-    /// Creates ClearEmergencyPrice instruction
-    pub fn clear_emergency_price(
+
+    /// Turn an instruction whose first account is an authority into one run by the
+    /// mint's `Timelock` at that position, executing a matured queued call
+    pub fn with_timelock(mut instruction: Instruction, mint: &Pubkey) -> Instruction {
+        let (timelock, _) = Timelock::find_address(&instruction.program_id, mint);
+        if let Some(authority) = instruction.accounts.first_mut() {
+            *authority = AccountMeta::new(timelock, false); // Timelock PDA
+        }
+        instruction
+    }
+
+    /// Creates a new InitializeAccessControl instruction
+    pub fn initialize_access_control(
         program_id: &Pubkey,
         authority: &Pubkey,
-        controller: &Pubkey,
+        payer: &Pubkey,
+        mint: &Pubkey,
+        admin: Pubkey,
     ) -> Result<Instruction, std::io::Error> {
+        let (access_control, _) = AccessControl::find_address(program_id, mint);
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+        let data = to_vec(&Self::InitializeAccessControl { admin })?;
+
         let accounts = vec![
-            AccountMeta::new_readonly(*authority, true),
-            AccountMeta::new(*controller, false),
+            AccountMeta::new_readonly(*authority, true),            // Emergency or program authority (signer)
+            AccountMeta::new(*payer, true),                         // Payer (signer)
+            AccountMeta::new(access_control, false),                // Access control PDA
+            AccountMeta::new_readonly(emergency_state, false),      // Emergency state PDA
+            AccountMeta::new_readonly(*mint, false),                // Mint account
+            AccountMeta::new_readonly(system_program::id(), false), // System program
         ];
-        
-        let data = Self::ClearEmergencyPrice.try_to_vec()?;
-        
+
         Ok(Instruction {
             program_id: *program_id,
             accounts,
             data,
         })
     }
-    
-    /// Creates ResetCircuitBreaker instruction
-    pub fn reset_circuit_breaker(
+
+    /// Creates a new GrantRole instruction
+    pub fn grant_role(
         program_id: &Pubkey,
-        authority: &Pubkey,
-        controller: &Pubkey,
+        admin: &Pubkey,
+        mint: &Pubkey,
+        member: Pubkey,
+        role: Role,
     ) -> Result<Instruction, std::io::Error> {
-        let instr = Self::ResetCircuitBreaker;
-        let data = to_vec(&instr)?;
-        
+        let (access_control, _) = AccessControl::find_address(program_id, mint);
+        let data = to_vec(&Self::GrantRole { member, role })?;
+
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
         let accounts = vec![
-            AccountMeta::new_readonly(*authority, true), // Authority (signer)
-            AccountMeta::new(*controller, false),        // Oracle controller
+            AccountMeta::new_readonly(*admin, true), // Admin (signer)
+            AccountMeta::new(access_control, false), // Access control PDA
+            AccountMeta::new_readonly(emergency_state, false), // Emergency state PDA
         ];
-        
+
         Ok(Instruction {
             program_id: *program_id,
             accounts,
             data,
         })
     }
-    
-    /// Creates UpdatePriceDirectly instruction
-    pub fn update_price_directly(
+
+    /// Creates a new RevokeRole instruction
+    pub fn revoke_role(
         program_id: &Pubkey,
-        authority: &Pubkey,
-        controller: &Pubkey,
-        new_price: u64,
+        admin: &Pubkey,
+        mint: &Pubkey,
+        member: Pubkey,
+        role: Role,
     ) -> Result<Instruction, std::io::Error> {
-        let instr = Self::UpdatePriceDirectly { new_price };
-        let data = to_vec(&instr)?;
-        
+        let (access_control, _) = AccessControl::find_address(program_id, mint);
+        let data = to_vec(&Self::RevokeRole { member, role })?;
+
+        let (emergency_state, _) = EmergencyState::find_address(program_id, mint);
+
         let accounts = vec![
-            AccountMeta::new_readonly(*authority, true),        // Authority (signer)
-            AccountMeta::new(*controller, false),               // Controller state account
-            AccountMeta::new_readonly(sysvar::clock::ID, false), // Clock sysvar
+            AccountMeta::new_readonly(*admin, true), // Admin (signer)
+            AccountMeta::new(access_control, false), // Access control PDA
+            AccountMeta::new_readonly(emergency_state, false), // Emergency state PDA
         ];
-        
+
         Ok(Instruction {
             program_id: *program_id,
             accounts,
             data,
         })
     }
-} 
\ No newline at end of file
+
+    /// Turn an instruction whose first account is an authority into one approved
+    /// through the mint's `AccessControl` at that address: the access control no
+    /// longer signs, and `member`, holding the instruction's required role, is
+    /// appended as a signer
+    pub fn with_role_holder(mut instruction: Instruction, mint: &Pubkey, member: &Pubkey) -> Instruction {
+        let (access_control, _) = AccessControl::find_address(&instruction.program_id, mint);
+        if let Some(authority) = instruction.accounts.first_mut() {
+            *authority = AccountMeta::new_readonly(access_control, false); // Access control PDA
+        }
+        instruction.accounts.push(AccountMeta::new_readonly(*member, true)); // Role holder (signer)
+        instruction
+    }
+
+    /// How the instruction is treated by the emergency modes
+    pub fn operation_class(&self) -> OperationClass {
+        match self {
+            Self::EmergencyPause { .. }
+            | Self::EmergencyResume
+            | Self::EnterMaintenanceMode { .. }
+            | Self::RescueTokens { .. }
+            | Self::RecoverState { .. }
+            | Self::RecallTreasuryYield { .. }
+            | Self::DeclareForcedRefund { .. }
+            | Self::SweepTreasuryToCanonical
+            | Self::CancelTimelockOperation { .. } => OperationClass::EmergencyControl,
+            Self::ClaimRefund
+            | Self::ClaimDevFundRefund
+            | Self::ReleaseVestedTokens { .. }
+            | Self::ClaimAirdrop { .. } => OperationClass::UserProtective,
+            Self::ValidatePurchase { .. }
+            | Self::PreviewVestingSchedule { .. }
+            | Self::GetVersion
+            | Self::GetClaimableAmount { .. }
+            | Self::GetCircuitBreakerIncidents => OperationClass::ReadOnly,
+            _ => OperationClass::Restricted,
+        }
+    }
+
+    /// Slot of the mint's `EmergencyState` account, which the processor holds the
+    /// instruction to before the handler runs. Every instruction is listed, so a new
+    /// one has to declare whether its mint's emergency mode gates it.
+    ///
+    /// Emergency controls and read-only calls run in every mode and take no slot;
+    /// neither do calls that act on no single mint: creating the mint (which creates
+    /// its emergency state), multisigs, the shared oracle controllers, the simulator,
+    /// the upgrade lock and the retired `BuyTokens`.
+    pub fn emergency_state_index(&self) -> Option<usize> {
+        match self {
+            Self::AnnounceRefundWindow => Some(1),
+            Self::LaunchToken
+            | Self::EndPresale
+            | Self::UpdateOraclePrice
+            | Self::UpdatePriceDirectly { .. }
+            | Self::FinalizePresale
+            | Self::SetKycConfig { .. }
+            | Self::SetPresalePriceController { .. }
+            | Self::SetTreasuryAlarm { .. }
+            | Self::SetReleaseCrankTip { .. }
+            | Self::SetReceiptTransferability { .. }
+            | Self::PauseVesting { .. }
+            | Self::ResumeVesting { .. }
+            | Self::CompleteLaunchChecklistItem { .. }
+            | Self::UpdateControllerParameters { .. }
+            | Self::SetSupplyRateLimit { .. }
+            | Self::UpdateMetadataAuthority { .. }
+            | Self::AcceptMetadataAuthority
+            | Self::SetSupplyCooldowns { .. }
+            | Self::SyncSupply
+            | Self::QueueTimelockOperation { .. }
+            | Self::GrantRole { .. }
+            | Self::RevokeRole { .. } => Some(2),
+            Self::AddSupportedStablecoin { .. }
+            | Self::SetTransferFee { .. }
+            | Self::ExpandPresaleAccount { .. }
+            | Self::ClosePresaleAccounts
+            | Self::RevokeVestingBeneficiary { .. }
+            | Self::ProposeBeneficiaryTransfer { .. }
+            | Self::SetKeeperRefund { .. }
+            | Self::RepointSupplyController
+            | Self::SetMetadataDisplayHints { .. }
+            | Self::UpdateFeeSponsorship { .. }
+            | Self::InitializeTimelock { .. }
+            | Self::InitializeAccessControl { .. } => Some(3),
+            Self::InitializeVesting { .. }
+            | Self::UpdateTokenMetadata { .. }
+            | Self::InitializePresaleRound { .. }
+            | Self::CommitComplianceHash { .. }
+            | Self::AddVestingBeneficiaries { .. }
+            | Self::SetBeneficiaryDestination
+            | Self::InitializeSaleAnalytics
+            | Self::InitializeSupplyLedger
+            | Self::InitializeBurnLog
+            | Self::InitializeFeeSponsorship { .. } => Some(4),
+            Self::AddVestingBeneficiary { .. }
+            | Self::ExecuteAutonomousMint
+            | Self::DepositToBurnTreasury { .. }
+            | Self::IssueKycCredential { .. }
+            | Self::SetStablecoinLimits { .. }
+            | Self::InitializeAirdrop { .. }
+            | Self::AcceptBeneficiaryTransfer { .. }
+            | Self::NoticeWithdraw
+            | Self::InitializeBugBounty { .. }
+            | Self::CommitBugBountyAward { .. }
+            | Self::SetSupplyDistribution { .. }
+            | Self::SweepWithheldFeesToBurn => Some(5),
+            Self::InitializePresale { .. }
+            | Self::InitializeAutonomousController { .. }
+            | Self::ExecuteAutonomousBurn
+            | Self::InitializeBurnTreasury
+            | Self::FundVesting { .. }
+            | Self::RedeemGrantReceipt { .. }
+            | Self::FundBugBounty { .. }
+            | Self::PayBugBountyAward
+            | Self::SetStablecoinPegGuard { .. }
+            | Self::ReleaseLiquidityEscrow { .. }
+            | Self::BurnTokens { .. } => Some(6),
+            Self::ClaimRefund
+            | Self::WithdrawLockedFunds
+            | Self::ClaimDevFundRefund
+            | Self::ExecuteWithdraw => Some(7),
+            Self::ReleaseVestedTokens { .. }
+            | Self::ClaimAirdrop { .. }
+            | Self::MintGrantReceipt
+            | Self::OpenMintMigration
+            | Self::DistributeSupplyTreasury => Some(8),
+            Self::MigrateTokens { .. } => Some(9),
+            Self::ApproveYieldVenue { .. } => Some(10),
+            Self::BuyTokensWithStablecoin { .. } => Some(11),
+            Self::CrankSupplyController => Some(13),
+            Self::DeployTreasuryYield { .. } => Some(14),
+            Self::InitializeToken { .. }
+            | Self::BuyTokens { .. }
+            | Self::PermanentlyDisableUpgrades
+            | Self::EmergencyPause { .. }
+            | Self::EmergencyResume
+            | Self::RescueTokens { .. }
+            | Self::RecoverState { .. }
+            | Self::InitializeOracleController { .. }
+            | Self::AddOracleSource { .. }
+            | Self::UpdateOracleConsensus
+            | Self::SetEmergencyPrice { .. }
+            | Self::ClearEmergencyPrice
+            | Self::ResetCircuitBreaker
+            | Self::SetControllerPriceBounds { .. }
+            | Self::ValidatePurchase { .. }
+            | Self::PreviewVestingSchedule { .. }
+            | Self::GetVersion
+            | Self::GetClaimableAmount { .. }
+            | Self::SetPythFeedId { .. }
+            | Self::SetConsensusEma { .. }
+            | Self::SetRedstoneConfig { .. }
+            | Self::RemoveOracleSource { .. }
+            | Self::SetOracleSourceActive { .. }
+            | Self::UpdateOracleSourceConfig { .. }
+            | Self::SetChainlinkStreamsConfig { .. }
+            | Self::SubmitChainlinkStreamsReport { .. }
+            | Self::SetQuorumPolicy { .. }
+            | Self::GetCircuitBreakerIncidents
+            | Self::SetCircuitBreakerRecovery { .. }
+            | Self::SetStalenessPolicy { .. }
+            | Self::SetAggregationStrategy { .. }
+            | Self::ConfigureSimulation { .. }
+            | Self::StepSimulation { .. }
+            | Self::CreateManagedFeed { .. }
+            | Self::EnterMaintenanceMode { .. }
+            | Self::SetSignedPricePublishers { .. }
+            | Self::SubmitSignedPrice { .. }
+            | Self::RecallTreasuryYield { .. }
+            | Self::DeclareForcedRefund { .. }
+            | Self::InitializeMultisig { .. }
+            | Self::SweepTreasuryToCanonical
+            | Self::CancelTimelockOperation { .. } => None,
+        }
+    }
+
+    /// Who must approve the instruction, checked by the processor before the
+    /// handler runs. Every instruction is listed, so a new one has to declare its role.
+    ///
+    /// An `Authority` instruction is approved when its authority signs directly, is a
+    /// `Multisig` whose keys sign, is an `AccessControl` with a signer holding
+    /// `required_role`, or is the mint's `Timelock` where `accepts_timelock` allows it.
+    pub fn access_role(&self) -> AccessRole {
+        match self {
+            Self::InitializeToken { .. }
+            | Self::InitializePresale { .. }
+            | Self::AddSupportedStablecoin { .. }
+            | Self::LaunchToken
+            | Self::WithdrawLockedFunds
+            | Self::InitializeVesting { .. }
+            | Self::AddVestingBeneficiary { .. }
+            | Self::UpdateTokenMetadata { .. }
+            | Self::SetTransferFee { .. }
+            | Self::InitializeAutonomousController { .. }
+            | Self::PermanentlyDisableUpgrades
+            | Self::ExpandPresaleAccount { .. }
+            | Self::EmergencyPause { .. }
+            | Self::EmergencyResume
+            | Self::RescueTokens { .. }
+            | Self::RecoverState { .. }
+            | Self::InitializeOracleController { .. }
+            | Self::AddOracleSource { .. }
+            | Self::SetEmergencyPrice { .. }
+            | Self::ClearEmergencyPrice
+            | Self::ResetCircuitBreaker
+            | Self::UpdatePriceDirectly { .. }
+            | Self::InitializePresaleRound { .. }
+            | Self::SetKycConfig { .. }
+            | Self::ClosePresaleAccounts
+            | Self::CommitComplianceHash { .. }
+            | Self::SetStablecoinLimits { .. }
+            | Self::InitializeAirdrop { .. }
+            | Self::SetControllerPriceBounds { .. }
+            | Self::SetPresalePriceController { .. }
+            | Self::RevokeVestingBeneficiary { .. }
+            | Self::SetTreasuryAlarm { .. }
+            | Self::AddVestingBeneficiaries { .. }
+            | Self::SetReleaseCrankTip { .. }
+            | Self::SetReceiptTransferability { .. }
+            | Self::PauseVesting { .. }
+            | Self::ResumeVesting { .. }
+            | Self::NoticeWithdraw
+            | Self::ExecuteWithdraw
+            | Self::CompleteLaunchChecklistItem { .. }
+            | Self::SetPythFeedId { .. }
+            | Self::SetConsensusEma { .. }
+            | Self::SetRedstoneConfig { .. }
+            | Self::RemoveOracleSource { .. }
+            | Self::SetOracleSourceActive { .. }
+            | Self::UpdateOracleSourceConfig { .. }
+            | Self::SetChainlinkStreamsConfig { .. }
+            | Self::SetQuorumPolicy { .. }
+            | Self::SetCircuitBreakerRecovery { .. }
+            | Self::SetStalenessPolicy { .. }
+            | Self::InitializeBugBounty { .. }
+            | Self::OpenMintMigration
+            | Self::RepointSupplyController
+            | Self::SetAggregationStrategy { .. }
+            | Self::ConfigureSimulation { .. }
+            | Self::StepSimulation { .. }
+            | Self::CreateManagedFeed { .. }
+            | Self::EnterMaintenanceMode { .. }
+            | Self::SetStablecoinPegGuard { .. }
+            | Self::SetSignedPricePublishers { .. }
+            | Self::ApproveYieldVenue { .. }
+            | Self::DeployTreasuryYield { .. }
+            | Self::UpdateControllerParameters { .. }
+            | Self::SetSupplyRateLimit { .. }
+            | Self::InitializeSaleAnalytics
+            | Self::SetSupplyDistribution { .. }
+            | Self::UpdateMetadataAuthority { .. }
+            | Self::AcceptMetadataAuthority
+            | Self::SetSupplyCooldowns { .. }
+            | Self::SetMetadataDisplayHints { .. }
+            | Self::ReleaseLiquidityEscrow { .. }
+            | Self::InitializeFeeSponsorship { .. }
+            | Self::UpdateFeeSponsorship { .. }
+            | Self::InitializeTimelock { .. }
+            | Self::QueueTimelockOperation { .. }
+            | Self::CancelTimelockOperation { .. }
+            | Self::InitializeAccessControl { .. }
+            | Self::GrantRole { .. }
+            | Self::RevokeRole { .. } => AccessRole::Authority,
+            Self::BuyTokensWithStablecoin { .. }
+            | Self::BuyTokens { .. }
+            | Self::DepositToBurnTreasury { .. }
+            | Self::InitializeBurnTreasury
+            | Self::ClaimDevFundRefund
+            | Self::IssueKycCredential { .. }
+            | Self::ClaimAirdrop { .. }
+            | Self::ProposeBeneficiaryTransfer { .. }
+            | Self::AcceptBeneficiaryTransfer { .. }
+            | Self::SetBeneficiaryDestination
+            | Self::FundVesting { .. }
+            | Self::MintGrantReceipt
+            | Self::RedeemGrantReceipt { .. }
+            | Self::SetKeeperRefund { .. }
+            | Self::FundBugBounty { .. }
+            | Self::MigrateTokens { .. }
+            | Self::InitializeSupplyLedger
+            | Self::InitializeBurnLog
+            | Self::BurnTokens { .. }
+            | Self::InitializeMultisig { .. } => AccessRole::Buyer,
+            Self::CommitBugBountyAward { .. }
+            | Self::DeclareForcedRefund { .. }
+            | Self::SweepTreasuryToCanonical => AccessRole::Guardian,
+            // The handler takes a keeper in place of the buyer's signature
+            Self::ClaimRefund
+            | Self::ReleaseVestedTokens { .. }
+            | Self::EndPresale
+            | Self::UpdateOraclePrice
+            | Self::ExecuteAutonomousMint
+            | Self::ExecuteAutonomousBurn
+            | Self::UpdateOracleConsensus
+            | Self::FinalizePresale
+            | Self::ValidatePurchase { .. }
+            | Self::PreviewVestingSchedule { .. }
+            | Self::GetVersion
+            | Self::AnnounceRefundWindow
+            | Self::GetClaimableAmount { .. }
+            | Self::SubmitChainlinkStreamsReport { .. }
+            | Self::GetCircuitBreakerIncidents
+            | Self::PayBugBountyAward
+            | Self::SubmitSignedPrice { .. }
+            | Self::RecallTreasuryYield { .. }
+            | Self::DistributeSupplyTreasury
+            | Self::CrankSupplyController
+            | Self::SyncSupply
+            | Self::SweepWithheldFeesToBurn => AccessRole::Permissionless,
+        }
+    }
+
+    /// Role an `AccessControl` member needs to approve the instruction when the
+    /// access control is its authority
+    pub fn required_role(&self) -> Role {
+        match self {
+            Self::SetTransferFee { .. } => Role::FeeManager,
+            Self::AddOracleSource { .. }
+            | Self::SetEmergencyPrice { .. }
+            | Self::ClearEmergencyPrice
+            | Self::ResetCircuitBreaker
+            | Self::UpdatePriceDirectly { .. }
+            | Self::SetControllerPriceBounds { .. }
+            | Self::SetPythFeedId { .. }
+            | Self::SetConsensusEma { .. }
+            | Self::SetRedstoneConfig { .. }
+            | Self::RemoveOracleSource { .. }
+            | Self::SetOracleSourceActive { .. }
+            | Self::UpdateOracleSourceConfig { .. }
+            | Self::SetChainlinkStreamsConfig { .. }
+            | Self::SetQuorumPolicy { .. }
+            | Self::SetCircuitBreakerRecovery { .. }
+            | Self::SetStalenessPolicy { .. }
+            | Self::SetAggregationStrategy { .. }
+            | Self::SetSignedPricePublishers { .. } => Role::OracleManager,
+            Self::EmergencyPause { .. }
+            | Self::EmergencyResume
+            | Self::EnterMaintenanceMode { .. }
+            | Self::RescueTokens { .. }
+            | Self::RecoverState { .. }
+            | Self::CancelTimelockOperation { .. } => Role::EmergencyGuardian,
+            Self::WithdrawLockedFunds
+            | Self::NoticeWithdraw
+            | Self::ExecuteWithdraw
+            | Self::DeployTreasuryYield { .. }
+            | Self::SetTreasuryAlarm { .. }
+            | Self::ReleaseLiquidityEscrow { .. } => Role::Treasurer,
+            _ => Role::Admin,
+        }
+    }
+
+    /// Whether a mint's `Timelock` may stand in for the authority, executing a
+    /// matured queued call
+    pub fn accepts_timelock(&self) -> bool {
+        matches!(
+            self,
+            Self::SetTransferFee { .. }
+                | Self::UpdateControllerParameters { .. }
+                | Self::WithdrawLockedFunds
+                | Self::ExecuteWithdraw
+                | Self::RescueTokens { .. }
+        )
+    }
+}
@@ -1,8 +1,13 @@
 // Export modules
+pub mod amounts;
 pub mod entrypoint;
 pub mod error;
+pub mod estimate;
+pub mod events;
 pub mod instruction;
+pub(crate) mod price_adapter;
 pub mod processor;
+pub mod simulation;
 pub mod state;
 
 // Re-export for convenience
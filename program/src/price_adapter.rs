@@ -0,0 +1,287 @@
+//! Price adapters for the supported oracle providers.
+//!
+//! An adapter only decodes its provider's account into a `PriceReading`.
+//! `read_price` looks the adapter up in `ADAPTERS` by `OracleType` and applies the
+//! owner, staleness and confidence checks shared by every provider, so supporting
+//! a new provider is one adapter plus one table entry.
+
+use solana_program::{account_info::AccountInfo, msg, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{
+    error::VCoinError,
+    processor::{
+        oracle_freshness, oracle_programs, try_get_chainlink_price, try_get_custom_price, try_get_pyth_price,
+        try_get_chainlink_streams_price, try_get_pyth_pull_price, try_get_redstone_price,
+        try_get_signed_price, try_get_switchboard_price, MAX_CONFIDENCE_INTERVAL_BPS,
+    },
+    state::{ChainlinkStreamsConfig, MultiOracleController, OracleType, RedstoneConfig, PYTH_RECEIVER_PROGRAM_ID},
+};
+
+/// Price read from an oracle account, in USD with 6 decimals
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct PriceReading {
+    pub price: u64,
+    pub confidence: u64,
+    pub publish_time: i64,
+}
+
+impl From<(u64, u64, i64)> for PriceReading {
+    fn from((price, confidence, publish_time): (u64, u64, i64)) -> Self {
+        Self { price, confidence, publish_time }
+    }
+}
+
+/// Inputs an adapter may need besides the oracle account
+pub(crate) struct PriceContext<'a> {
+    /// Current cluster time
+    pub current_time: i64,
+    /// Feed id of the controller's asset (Pyth pull oracles)
+    pub pyth_feed_id: Option<[u8; 32]>,
+    /// Feed and signers of the controller's asset (RedStone oracles)
+    pub redstone: Option<&'a RedstoneConfig>,
+    /// Feed and DON signers of the controller's asset (Chainlink Data Streams)
+    pub chainlink_streams: Option<&'a ChainlinkStreamsConfig>,
+    /// Publishers whose signed prices the controller accepts (signed messages)
+    pub signed_price_publishers: &'a [Pubkey],
+}
+
+impl<'a> PriceContext<'a> {
+    /// Context for reading the sources of `controller`, if any
+    pub fn for_controller(current_time: i64, controller: Option<&'a MultiOracleController>) -> Self {
+        Self {
+            current_time,
+            pyth_feed_id: controller.and_then(|controller| controller.pyth_feed_id),
+            redstone: controller.and_then(|controller| controller.redstone.as_ref()),
+            chainlink_streams: controller.and_then(|controller| controller.chainlink_streams.as_ref()),
+            signed_price_publishers: controller.map_or(&[], |controller| &controller.signed_price_publishers),
+        }
+    }
+}
+
+/// Decodes one oracle provider's price accounts
+pub(crate) trait PriceAdapter: Sync {
+    /// Provider name for logs
+    fn name(&self) -> &'static str;
+
+    /// Programs allowed to own the provider's accounts, or None when the account
+    /// contents are authenticated some other way
+    fn trusted_owners(&self) -> Option<&'static [Pubkey]>;
+
+    /// Read the price from `oracle_info`; shared validation happens in `read_price`
+    fn read(&self, oracle_info: &AccountInfo, context: &PriceContext) -> Result<PriceReading, ProgramError>;
+}
+
+struct PythAdapter;
+
+impl PriceAdapter for PythAdapter {
+    fn name(&self) -> &'static str {
+        "Pyth"
+    }
+
+    fn trusted_owners(&self) -> Option<&'static [Pubkey]> {
+        Some(oracle_programs::PYTH)
+    }
+
+    fn read(&self, oracle_info: &AccountInfo, context: &PriceContext) -> Result<PriceReading, ProgramError> {
+        try_get_pyth_price(oracle_info, context.current_time).map(PriceReading::from)
+    }
+}
+
+struct PythPullAdapter;
+
+impl PriceAdapter for PythPullAdapter {
+    fn name(&self) -> &'static str {
+        "Pyth pull"
+    }
+
+    fn trusted_owners(&self) -> Option<&'static [Pubkey]> {
+        Some(&[PYTH_RECEIVER_PROGRAM_ID])
+    }
+
+    fn read(&self, oracle_info: &AccountInfo, context: &PriceContext) -> Result<PriceReading, ProgramError> {
+        try_get_pyth_pull_price(oracle_info, context.current_time, context.pyth_feed_id).map(PriceReading::from)
+    }
+}
+
+struct SwitchboardAdapter;
+
+impl PriceAdapter for SwitchboardAdapter {
+    fn name(&self) -> &'static str {
+        "Switchboard"
+    }
+
+    fn trusted_owners(&self) -> Option<&'static [Pubkey]> {
+        Some(oracle_programs::SWITCHBOARD)
+    }
+
+    fn read(&self, oracle_info: &AccountInfo, context: &PriceContext) -> Result<PriceReading, ProgramError> {
+        try_get_switchboard_price(oracle_info, context.current_time).map(PriceReading::from)
+    }
+}
+
+struct ChainlinkAdapter;
+
+impl PriceAdapter for ChainlinkAdapter {
+    fn name(&self) -> &'static str {
+        "Chainlink"
+    }
+
+    fn trusted_owners(&self) -> Option<&'static [Pubkey]> {
+        Some(oracle_programs::CHAINLINK)
+    }
+
+    fn read(&self, oracle_info: &AccountInfo, context: &PriceContext) -> Result<PriceReading, ProgramError> {
+        try_get_chainlink_price(oracle_info, context.current_time).map(PriceReading::from)
+    }
+}
+
+struct CustomAdapter;
+
+impl PriceAdapter for CustomAdapter {
+    fn name(&self) -> &'static str {
+        "Custom"
+    }
+
+    fn trusted_owners(&self) -> Option<&'static [Pubkey]> {
+        // Custom oracles are operator-run programs chosen by the controller authority
+        None
+    }
+
+    fn read(&self, oracle_info: &AccountInfo, context: &PriceContext) -> Result<PriceReading, ProgramError> {
+        try_get_custom_price(oracle_info, context.current_time).map(PriceReading::from)
+    }
+}
+
+struct RedstoneAdapter;
+
+impl PriceAdapter for RedstoneAdapter {
+    fn name(&self) -> &'static str {
+        "RedStone"
+    }
+
+    fn trusted_owners(&self) -> Option<&'static [Pubkey]> {
+        // Payloads carry their own signatures; anyone may post them
+        None
+    }
+
+    fn read(&self, oracle_info: &AccountInfo, context: &PriceContext) -> Result<PriceReading, ProgramError> {
+        try_get_redstone_price(oracle_info, context.current_time, context.redstone).map(PriceReading::from)
+    }
+}
+
+struct ChainlinkStreamsAdapter;
+
+impl PriceAdapter for ChainlinkStreamsAdapter {
+    fn name(&self) -> &'static str {
+        "Chainlink Data Streams"
+    }
+
+    fn trusted_owners(&self) -> Option<&'static [Pubkey]> {
+        // Reports are verified on submission and stored by this program
+        Some(&[crate::ID])
+    }
+
+    fn read(&self, oracle_info: &AccountInfo, context: &PriceContext) -> Result<PriceReading, ProgramError> {
+        try_get_chainlink_streams_price(oracle_info, context.current_time, context.chainlink_streams)
+            .map(PriceReading::from)
+    }
+}
+
+struct SignedMessageAdapter;
+
+impl PriceAdapter for SignedMessageAdapter {
+    fn name(&self) -> &'static str {
+        "Signed message"
+    }
+
+    fn trusted_owners(&self) -> Option<&'static [Pubkey]> {
+        // Signatures are verified on submission and prices stored by this program
+        Some(&[crate::ID])
+    }
+
+    fn read(&self, oracle_info: &AccountInfo, context: &PriceContext) -> Result<PriceReading, ProgramError> {
+        try_get_signed_price(oracle_info, context.current_time, context.signed_price_publishers)
+            .map(PriceReading::from)
+    }
+}
+
+/// Adapter registered for each oracle type
+const ADAPTERS: &[(OracleType, &dyn PriceAdapter)] = &[
+    (OracleType::Pyth, &PythAdapter),
+    (OracleType::Switchboard, &SwitchboardAdapter),
+    (OracleType::Chainlink, &ChainlinkAdapter),
+    (OracleType::Custom, &CustomAdapter),
+    (OracleType::PythPull, &PythPullAdapter),
+    (OracleType::Redstone, &RedstoneAdapter),
+    (OracleType::ChainlinkStreams, &ChainlinkStreamsAdapter),
+    (OracleType::SignedMessage, &SignedMessageAdapter),
+];
+
+fn adapter_for(oracle_type: &OracleType) -> Option<&'static dyn PriceAdapter> {
+    ADAPTERS
+        .iter()
+        .find(|(registered, _)| registered == oracle_type)
+        .map(|(_, adapter)| *adapter)
+}
+
+/// Reject oracle accounts not owned by the provider's program
+pub(crate) fn check_oracle_owner(oracle_type: &OracleType, oracle_info: &AccountInfo) -> Result<(), ProgramError> {
+    let adapter = adapter_for(oracle_type).ok_or_else(|| {
+        msg!("No price adapter registered for {:?}", oracle_type);
+        VCoinError::InvalidOracleProvider
+    })?;
+
+    if let Some(owners) = adapter.trusted_owners() {
+        if !owners.contains(oracle_info.owner) {
+            msg!("{} account {} has an unexpected owner {}", adapter.name(), oracle_info.key, oracle_info.owner);
+            return Err(VCoinError::InvalidAccountOwner.into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks every provider's reading must pass
+fn validate_reading(name: &str, reading: &PriceReading, current_time: i64) -> Result<(), ProgramError> {
+    if reading.price == 0 {
+        msg!("{} returned a zero price", name);
+        return Err(VCoinError::InvalidOracleData.into());
+    }
+
+    if reading.publish_time > current_time {
+        msg!("{} price is published in the future", name);
+        return Err(VCoinError::InvalidOracleData.into());
+    }
+
+    if current_time - reading.publish_time > oracle_freshness::MAX_STALENESS {
+        msg!("{} price is stale!", name);
+        return Err(VCoinError::StaleOracleData.into());
+    }
+
+    let confidence_bps = (reading.confidence as u128) * 10_000 / (reading.price as u128);
+    if confidence_bps > MAX_CONFIDENCE_INTERVAL_BPS as u128 {
+        msg!("{} confidence interval too large ({}bps)", name, confidence_bps);
+        return Err(VCoinError::LowConfidencePriceData.into());
+    }
+
+    Ok(())
+}
+
+/// Read and validate a price from an oracle account of `oracle_type`,
+/// returning (price, confidence, publish time)
+pub(crate) fn read_price(
+    oracle_type: &OracleType,
+    oracle_info: &AccountInfo,
+    context: &PriceContext,
+) -> Result<(u64, u64, i64), ProgramError> {
+    let adapter = adapter_for(oracle_type).ok_or_else(|| {
+        msg!("No price adapter registered for {:?}", oracle_type);
+        VCoinError::InvalidOracleProvider
+    })?;
+
+    check_oracle_owner(oracle_type, oracle_info)?;
+    let reading = adapter.read(oracle_info, context)?;
+    validate_reading(adapter.name(), &reading, context.current_time)?;
+
+    Ok((reading.price, reading.confidence, reading.publish_time))
+}
@@ -0,0 +1,230 @@
+//! Deterministic simulation mode for replaying economic scenarios in tests.
+//!
+//! Programs built with the `simulation` feature accept `ConfigureSimulation` and
+//! `StepSimulation`, which script a clock and a price sequence in the
+//! `SimulationState` PDA. Any instruction that also carries the simulation
+//! account reads the scripted time through `current_clock`, and each step
+//! publishes the next price to a Custom-layout oracle account that a controller
+//! can register as a source. Other builds reject both instructions and ignore
+//! the account, so a deployed program cannot have its clock or prices scripted.
+
+use std::sync::atomic::{AtomicI64, Ordering};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program::invoke_signed,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction,
+    sysvar::Sysvar,
+};
+
+use crate::{
+    error::VCoinError,
+    state::{SimulationState, MAX_SIMULATED_PRICES, SIMULATED_PRICE_FEED_SIZE},
+};
+
+/// Stored in `SIMULATED_TIME` when the current instruction is not simulated
+const NOT_SIMULATED: i64 = i64::MIN;
+
+/// Scripted time of the instruction being processed
+static SIMULATED_TIME: AtomicI64 = AtomicI64::new(NOT_SIMULATED);
+
+/// Take the scripted time from the simulation account if the instruction carries
+/// it; called once at the start of every instruction
+pub(crate) fn load_clock(program_id: &Pubkey, accounts: &[AccountInfo]) {
+    let mut simulated_time = NOT_SIMULATED;
+
+    if cfg!(feature = "simulation") {
+        let (simulation_address, _) = SimulationState::find_address(program_id);
+        let simulation = accounts
+            .iter()
+            .find(|account| account.key == &simulation_address && account.owner == program_id)
+            .and_then(|account| SimulationState::deserialize(&mut &account.data.borrow()[..]).ok());
+        if let Some(simulation) = simulation.filter(|simulation| simulation.is_initialized) {
+            simulated_time = simulation.unix_timestamp;
+        }
+    }
+
+    SIMULATED_TIME.store(simulated_time, Ordering::Release);
+}
+
+/// Scripted time of the current instruction, if it is simulated
+pub(crate) fn simulated_time() -> Option<i64> {
+    let simulated_time = SIMULATED_TIME.load(Ordering::Acquire);
+    (simulated_time != NOT_SIMULATED).then_some(simulated_time)
+}
+
+fn require_simulation_build() -> ProgramResult {
+    if !cfg!(feature = "simulation") {
+        msg!("Simulation instructions need a program built with the `simulation` feature");
+        return Err(VCoinError::InvalidInstruction.into());
+    }
+    Ok(())
+}
+
+/// Create the simulation on first use, then replace its clock and price script
+pub fn process_configure_simulation(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    unix_timestamp: i64,
+    prices: Vec<u64>,
+) -> ProgramResult {
+    require_simulation_build()?;
+
+    let account_info_iter = &mut accounts.iter();
+    let authority_info = next_account_info(account_info_iter)?;
+    let simulation_info = next_account_info(account_info_iter)?;
+    let price_feed_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+
+    // Verify authority signed the transaction
+    if !authority_info.is_signer {
+        msg!("Authority must sign transaction");
+        return Err(VCoinError::Unauthorized.into());
+    }
+
+    // Verify system program
+    if system_program_info.key != &solana_program::system_program::ID {
+        msg!("Invalid system program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    if prices.len() > MAX_SIMULATED_PRICES || prices.contains(&0) {
+        msg!("A simulation script holds up to {} non-zero prices", MAX_SIMULATED_PRICES);
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Verify the simulation accounts are the expected PDAs
+    let (simulation_address, simulation_bump) = SimulationState::find_address(program_id);
+    if simulation_address != *simulation_info.key {
+        msg!("Invalid simulation account");
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let (price_feed_address, price_feed_bump) = SimulationState::find_price_feed(program_id);
+    if price_feed_address != *price_feed_info.key {
+        msg!("Invalid simulated price feed");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    if simulation_info.data_len() == 0 {
+        // Create the simulation and its price feed
+        let rent = Rent::get()?;
+        let account_size = SimulationState::get_size();
+        invoke_signed(
+            &system_instruction::create_account(
+                authority_info.key,
+                simulation_info.key,
+                rent.minimum_balance(account_size),
+                account_size as u64,
+                program_id,
+            ),
+            &[
+                authority_info.clone(),
+                simulation_info.clone(),
+                system_program_info.clone(),
+            ],
+            &[&[b"simulation", &[simulation_bump]]],
+        )?;
+        invoke_signed(
+            &system_instruction::create_account(
+                authority_info.key,
+                price_feed_info.key,
+                rent.minimum_balance(SIMULATED_PRICE_FEED_SIZE),
+                SIMULATED_PRICE_FEED_SIZE as u64,
+                program_id,
+            ),
+            &[
+                authority_info.clone(),
+                price_feed_info.clone(),
+                system_program_info.clone(),
+            ],
+            &[&[b"simulated_price", &[price_feed_bump]]],
+        )?;
+    } else {
+        // Only the creator may rescript an existing simulation
+        if simulation_info.owner != program_id {
+            msg!("Simulation account not owned by program");
+            return Err(VCoinError::InvalidAccountOwner.into());
+        }
+        let simulation = SimulationState::deserialize(&mut &simulation_info.data.borrow()[..])?;
+        if simulation.authority != *authority_info.key {
+            msg!("Unauthorized: not the simulation authority");
+            return Err(VCoinError::Unauthorized.into());
+        }
+    }
+
+    let simulation = SimulationState {
+        is_initialized: true,
+        authority: *authority_info.key,
+        unix_timestamp,
+        prices,
+        next_price: 0,
+        price_feed: price_feed_address,
+        bump: simulation_bump,
+    };
+
+    simulation.serialize(&mut *simulation_info.data.borrow_mut())?;
+
+    msg!("Simulation clock set to {} with {} scripted prices",
+         unix_timestamp, simulation.prices.len());
+    Ok(())
+}
+
+/// Advance the simulated clock and publish the next scripted price, if any remain
+pub fn process_step_simulation(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    seconds: u32,
+) -> ProgramResult {
+    require_simulation_build()?;
+
+    let account_info_iter = &mut accounts.iter();
+    let authority_info = next_account_info(account_info_iter)?;
+    let simulation_info = next_account_info(account_info_iter)?;
+    let price_feed_info = next_account_info(account_info_iter)?;
+
+    // Verify authority signed the transaction
+    if !authority_info.is_signer {
+        msg!("Authority must sign transaction");
+        return Err(VCoinError::Unauthorized.into());
+    }
+
+    // Verify simulation account ownership
+    if simulation_info.owner != program_id {
+        msg!("Simulation account not owned by program");
+        return Err(VCoinError::InvalidAccountOwner.into());
+    }
+
+    let mut simulation = SimulationState::deserialize(&mut &simulation_info.data.borrow()[..])?;
+    if !simulation.is_initialized {
+        msg!("Simulation not configured");
+        return Err(VCoinError::NotInitialized.into());
+    }
+    if simulation.authority != *authority_info.key {
+        msg!("Unauthorized: not the simulation authority");
+        return Err(VCoinError::Unauthorized.into());
+    }
+    if simulation.price_feed != *price_feed_info.key {
+        msg!("Invalid simulated price feed");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    match simulation.step(seconds) {
+        Some(price) => {
+            price_feed_info.data.borrow_mut()[..SIMULATED_PRICE_FEED_SIZE]
+                .copy_from_slice(&simulation.price_feed_data(price));
+            msg!("Simulation at {}: published price {}", simulation.unix_timestamp, price);
+        }
+        None => {
+            msg!("Simulation at {}: price script exhausted", simulation.unix_timestamp);
+        }
+    }
+
+    simulation.serialize(&mut *simulation_info.data.borrow_mut())?;
+    Ok(())
+}
@@ -1,9 +1,202 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::pubkey::Pubkey;
 use solana_program::program_error::ProgramError;
+use solana_program::keccak;
+use solana_program::secp256k1_recover::secp256k1_recover;
+use crate::amounts::{Bps, MicroUsd, TokenAmount};
+use crate::error::VCoinError;
 
-/// Maximum number of vesting beneficiaries
-pub const MAX_VESTING_BENEFICIARIES: usize = 100;
+/// Maximum entries in one `AddVestingBeneficiaries` batch (40 bytes of data plus a
+/// grant account each, so the instruction still fits a single-signer transaction)
+pub const MAX_BENEFICIARIES_PER_BATCH: usize = 10;
+
+/// Buyers an `InitializePresale` account has room for; `ExpandPresaleAccount` grows it
+pub const PRESALE_INITIAL_BUYER_CAPACITY: usize = 15_000;
+
+/// Most buyers `ExpandPresaleAccount` will grow a presale account to
+pub const PRESALE_MAX_BUYER_CAPACITY: usize = 5_000_000;
+
+/// Share of the buyer capacity in use at which purchases start prompting an expansion
+pub const PRESALE_CAPACITY_WARNING_BPS: u16 = 9_000;
+
+/// Maximum number of vesting pools in one registry
+pub const MAX_VESTING_POOLS: usize = 16;
+
+/// Maximum length of a vesting pool label
+pub const MAX_VESTING_POOL_LABEL_LEN: usize = 32;
+
+/// Maximum tip paid to a third party for cranking a vesting release (lamports)
+pub const MAX_RELEASE_CRANK_TIP: u64 = 1_000_000;
+
+/// Maximum number of whitelisted buyers per presale round
+pub const MAX_ROUND_WHITELIST: usize = 100;
+
+/// Maximum number of compliance hash commitments per presale
+pub const MAX_COMPLIANCE_COMMITMENTS: usize = 64;
+
+/// Maximum Merkle proof length accepted by `ClaimAirdrop`
+pub const MAX_AIRDROP_PROOF_DEPTH: usize = 32;
+
+/// Number of claim indices tracked by one airdrop claim bitmap account
+pub const AIRDROP_BITMAP_CHUNK_BITS: u64 = 8 * 1024;
+
+/// Maximum number of guardians approving bug-bounty awards
+pub const MAX_BOUNTY_GUARDIANS: usize = 7;
+
+/// Maximum number of keys registered in a `Multisig`
+pub const MAX_MULTISIG_SIGNERS: usize = 11;
+
+/// Operations a `Timelock` holds queued at once
+pub const MAX_TIMELOCK_OPERATIONS: usize = 8;
+
+/// Shortest and longest delay a `Timelock` may impose (1 day to 30 days)
+pub const MIN_TIMELOCK_DELAY: i64 = 24 * 60 * 60;
+pub const MAX_TIMELOCK_DELAY: i64 = 30 * 24 * 60 * 60;
+
+/// Role grants an `AccessControl` holds
+pub const MAX_ROLE_GRANTS: usize = 16;
+
+/// Public notice required between `NoticeWithdraw` and `ExecuteWithdraw` (7 days)
+pub const WITHDRAW_NOTICE_DELAY: i64 = 7 * 24 * 60 * 60;
+
+/// Delay between `UpdateControllerParameters` and the new supply parameters taking effect (7 days)
+pub const SUPPLY_PARAMETERS_TIMELOCK: i64 = 7 * 24 * 60 * 60;
+
+/// Largest share of supply one autonomous mint or burn may move (20%)
+pub const MAX_SUPPLY_RATE_BPS: u16 = 2_000;
+
+/// Autonomous mints and burns a `SupplyActionLedger` keeps before overwriting the oldest
+pub const MAX_SUPPLY_ACTION_RECORDS: usize = 64;
+
+/// Burns a `BurnLog` keeps before overwriting the oldest
+pub const MAX_BURN_LOG_RECORDS: usize = 64;
+
+/// Rate-limit window a new supply controller starts with (1 day)
+pub const DEFAULT_SUPPLY_RATE_LIMIT_WINDOW: i64 = 24 * 60 * 60;
+
+/// Shortest and longest autonomous mint/burn rate-limit windows (1 hour to 1 year)
+pub const MIN_SUPPLY_RATE_LIMIT_WINDOW: i64 = 60 * 60;
+pub const MAX_SUPPLY_RATE_LIMIT_WINDOW: i64 = 365 * 24 * 60 * 60;
+
+/// Cooldown a new supply controller starts with between consecutive mints, and
+/// separately between consecutive burns (1 day)
+pub const DEFAULT_SUPPLY_COOLDOWN: u32 = 24 * 60 * 60;
+
+/// Longest cooldown between consecutive mints or burns (1 year)
+pub const MAX_SUPPLY_COOLDOWN: u32 = 365 * 24 * 60 * 60;
+
+/// Share of a cranked mint or burn paid to the keeper from the supply treasury (0.01%)
+pub const SUPPLY_KEEPER_REWARD_BPS: u16 = 1;
+
+/// Pause records kept in an emergency state account; older records are dropped
+pub const MAX_PAUSE_HISTORY: usize = 16;
+
+/// Consensus prices kept by an oracle controller for TWAP calculations
+pub const PRICE_HISTORY_LEN: usize = 48;
+
+/// Circuit-breaker incidents kept by an oracle controller; older incidents are dropped
+pub const MAX_CIRCUIT_BREAKER_INCIDENTS: usize = 8;
+
+/// Consecutive failures after which an oracle source is deactivated
+pub const MAX_CONSECUTIVE_ORACLE_FAILURES: u8 = 5;
+
+/// Shortest circuit-breaker cooldown an oracle controller may be configured with (1 minute)
+pub const MIN_CIRCUIT_BREAKER_COOLDOWN: u32 = 60;
+
+/// Longest circuit-breaker cooldown escalation may reach (7 days)
+pub const MAX_CIRCUIT_BREAKER_COOLDOWN: u32 = 7 * 24 * 60 * 60;
+
+/// Number of buckets a rolling volume window is split into
+pub const ROLLING_WINDOW_BUCKETS: usize = 24;
+
+/// USD peg of a stablecoin in micro-USD
+pub const STABLECOIN_PEG_PRICE: u64 = 1_000_000;
+
+/// Time between approving a yield venue and its first deposit (7 days)
+pub const YIELD_VENUE_REVIEW_DELAY: i64 = 7 * 24 * 60 * 60;
+
+/// Longest token metadata URI `UpdateTokenMetadata` accepts
+pub const MAX_METADATA_URI_LEN: usize = 200;
+
+/// URI schemes token metadata may point to: web, IPFS and Arweave
+pub const METADATA_URI_SCHEMES: &[&str] = &["https://", "ipfs://", "ar://"];
+
+/// Ticker aliases a metadata account may list for wallets
+pub const MAX_TICKER_ALIASES: usize = 4;
+
+/// Longest ticker alias in bytes
+pub const MAX_TICKER_ALIAS_LEN: usize = 10;
+
+/// Most entries in an `InitializeToken` allocation table
+pub const MAX_INITIAL_ALLOCATIONS: usize = 8;
+
+/// Categories an allocation plan can split the initial supply into
+pub const MAX_ALLOCATION_PLAN_SHARES: usize = 4;
+
+/// Layout version of each program account type, reported by `GetVersion`.
+/// Bump an entry whenever that account's serialized layout changes.
+pub const STATE_LAYOUT_VERSIONS: &[(&str, u16)] = &[
+    ("TokenMetadata", 4),
+    ("PresaleState", 10),
+    ("PresaleContribution", 5),
+    ("PresaleRoundState", 1),
+    ("PresaleStats", 1),
+    ("SaleAnalytics", 1),
+    ("TreasuryAlarm", 1),
+    ("StablecoinLimits", 1),
+    ("StablecoinPegGuard", 1),
+    ("YieldVenue", 1),
+    ("KycCredential", 1),
+    ("ComplianceRecord", 1),
+    ("AirdropDistributor", 1),
+    ("AirdropClaimBitmap", 1),
+    ("BugBountyEscrow", 1),
+    ("BugBountyAward", 1),
+    ("MintMigration", 1),
+    ("SimulationState", 1),
+    ("OracleFeedRegistry", 1),
+    ("VestingState", 3),
+    ("VestingGrant", 3),
+    ("VestingRegistry", 1),
+    ("AutonomousSupplyController", 8),
+    ("SupplyActionLedger", 1),
+    ("AllocationPlan", 1),
+    ("BurnLog", 1),
+    ("FeeSponsorship", 1),
+    ("Multisig", 2),
+    ("Timelock", 1),
+    ("AccessControl", 1),
+    ("EmergencyState", 2),
+    ("MultiOracleController", 12),
+    ("ChainlinkStreamsReport", 1),
+    ("SignedPriceReport", 1),
+];
+
+/// Canonical stablecoin mints on each cluster. Build with the `devnet` feature
+/// for devnet deployments, so `InitializePresale` allows that cluster's mints.
+pub mod stablecoin_mints {
+    use solana_program::{pubkey, pubkey::Pubkey};
+
+    /// USDC on mainnet, devnet and testnet
+    pub const USDC: &[Pubkey] = &[
+        pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"),
+        pubkey!("4zMMC9srt5Ri5X14GAgXhaHii3GnPAEERYPJgZJDncDU"),
+        pubkey!("CpMah17kQEL2wqyMKt3mZBdTnZbkbfx4nqmQMFDP5vwp"),
+    ];
+
+    /// USDT on mainnet, devnet and testnet
+    pub const USDT: &[Pubkey] = &[
+        pubkey!("Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB"),
+        pubkey!("DAwBSXe6w9g37wdE2tCrFbho3QHKZi4PjuBytQCULap2"),
+        pubkey!("BQcdHdAQW1hczDbBi9hiegXAR7A98Q9jx3X3iBBBDiq4"),
+    ];
+
+    /// Stablecoins a new presale allows on the build's cluster
+    #[cfg(not(feature = "devnet"))]
+    pub const PRESALE_DEFAULTS: &[Pubkey] = &[USDC[0], USDT[0]];
+    #[cfg(feature = "devnet")]
+    pub const PRESALE_DEFAULTS: &[Pubkey] = &[USDC[1], USDT[1]];
+}
 
 /// Stablecoin Type for presale contributions
 #[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
@@ -17,20 +210,68 @@ pub enum StablecoinType {
 }
 
 /// Presale contribution record with stablecoin tracking
+///
+/// Contributions are non-transferable: no instruction reassigns `buyer`, and
+/// refunds are only paid to the buyer who signs the claim, or, if the buyer
+/// opted in, to their recorded stablecoin ATA by anyone. Any future transfer
+/// or receipt instruction must keep this default and gate reassignment behind
+/// an explicit presale-level opt-in.
 #[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
 pub struct PresaleContribution {
     /// Buyer's public key
     pub buyer: Pubkey,
-    /// Contribution amount in stablecoin (with token's decimals precision)
+    /// Contribution amount in micro-USD
     pub amount: u64,
+    /// Stablecoin base units paid into the development treasury, repaid by `ClaimDevFundRefund`
+    pub dev_amount: u64,
+    /// Stablecoin base units paid into the locked treasury, repaid by `ClaimRefund`
+    pub locked_amount: u64,
     /// Type of stablecoin used
     pub stablecoin_type: StablecoinType,
     /// Stablecoin mint address
     pub stablecoin_mint: Pubkey,
-    /// Whether this contribution has been refunded
+    /// Whether this contribution has been refunded from the locked treasury
     pub refunded: bool,
+    /// Whether the dev-treasury half has been refunded
+    pub dev_refunded: bool,
     /// Timestamp of contribution
     pub timestamp: i64,
+    /// Buyer's stablecoin ATA that anyone may trigger the refund into
+    /// (None requires the buyer to sign the claim)
+    pub keeper_refund_destination: Option<Pubkey>,
+    /// Rate and oracle conditions of the buyer's latest purchase
+    pub last_purchase: PurchaseSnapshot,
+}
+
+/// Conditions a purchase was executed under, kept so disputes about the rate
+/// applied can be settled from on-chain data
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct PurchaseSnapshot {
+    /// Stablecoin amount accepted (micro-USD)
+    pub amount: u64,
+    /// Token price applied (micro-USD per whole token)
+    pub token_price: u64,
+    /// Tokens minted for the purchase (base units)
+    pub tokens: u64,
+    /// Oracle consensus read for a dynamically priced presale (None for fixed pricing)
+    pub oracle: Option<OracleSnapshot>,
+    /// Time of the purchase
+    pub timestamp: i64,
+}
+
+/// An oracle controller's consensus as read at a point in time
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct OracleSnapshot {
+    /// Oracle controller read
+    pub controller: Pubkey,
+    /// Consensus price before clamping to the controller's bounds (micro-USD)
+    pub price: u64,
+    /// Confidence of the consensus (micro-USD)
+    pub confidence: u64,
+    /// Time of the consensus
+    pub timestamp: i64,
+    /// Whether the controller's emergency price was used instead of the consensus
+    pub is_emergency_price: bool,
 }
 
 /// Represents a supported stablecoin with additional metadata
@@ -109,6 +350,57 @@ pub struct PresaleState {
     pub dev_refund_available_timestamp: i64,
     /// Dev refund period end timestamp (30 days after dev_refund_available_timestamp)
     pub dev_refund_period_end_timestamp: i64,
+    /// Number of presale rounds created under this presale
+    pub num_rounds: u8,
+    /// End timestamp of the most recently created round (rounds may not overlap)
+    pub last_round_end_time: i64,
+    /// Whether purchases require a KYC credential issued by `kyc_attestor`
+    pub kyc_required: bool,
+    /// Ed25519 key whose signed attestations back KYC credentials
+    pub kyc_attestor: Pubkey,
+    /// Minimum seconds between purchases from the same wallet (0 disables)
+    pub purchase_cooldown: i64,
+    /// Length in seconds of the opening window with a per-wallet cap (0 disables)
+    pub launch_window_duration: i64,
+    /// Maximum total contribution per wallet during the opening window
+    pub launch_window_max_per_wallet: u64,
+    /// Whether a purchase crossing the hard cap is partially filled instead of rejected
+    pub allow_partial_fill: bool,
+    /// Maximum USD raised per rolling 24h window, tracked in `stats.hourly_raise` (0 disables)
+    pub daily_raise_cap: u64,
+    /// Stablecoin mints that have a `StablecoinLimits` account purchases must present
+    pub limited_stablecoins: Vec<Pubkey>,
+    /// Running raise statistics for dashboards
+    pub stats: PresaleStats,
+    /// Oracle controller supplying a dynamic token price, bounded by its floor/ceiling
+    /// (None keeps the fixed `token_price`)
+    pub price_controller: Option<Pubkey>,
+    /// Locked treasury coverage threshold and current alarm status
+    pub treasury_alarm: TreasuryAlarm,
+    /// Refund window boundaries already announced with events
+    pub refund_notices: RefundWindowNotices,
+    /// Announced treasury withdrawal waiting out its notice delay
+    pub withdraw_notice: Option<WithdrawNotice>,
+    /// Prerequisites `LaunchToken` requires
+    pub launch_checklist: LaunchChecklist,
+    /// Stablecoin mints that have a `StablecoinPegGuard` account purchases must present
+    pub pegged_stablecoins: Vec<Pubkey>,
+    /// Whether purchases must present the presale's `SaleAnalytics` account
+    pub sale_analytics: bool,
+    /// Critical failure declared by the mint's guardians before launch
+    pub forced_refund: Option<ForcedRefund>,
+}
+
+/// Guardian declaration of a critical failure before launch: purchases stop for
+/// good, the presale can no longer launch and both refund halves open at once
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ForcedRefund {
+    /// Time of the declaration
+    pub declared_at: i64,
+    /// Hash of the off-chain incident report
+    pub reason_hash: [u8; 32],
+    /// Guardians who approved the declaration
+    pub approvals: u8,
 }
 
 impl PresaleState {
@@ -117,19 +409,19 @@ impl PresaleState {
         // Base size excluding Vec<Pubkey> and Vec<PresaleContribution>
         let base_size = std::mem::size_of::<Self>() - std::mem::size_of::<Vec<Pubkey>>() - std::mem::size_of::<Vec<PresaleContribution>>() - std::mem::size_of::<Vec<Pubkey>>();
         
-        // Start with space for 15,000 buyers as requested
-        let buyers_capacity = 15_000;
+        // Start with space for the initial buyer capacity
+        let buyers_capacity = PRESALE_INITIAL_BUYER_CAPACITY;
         let buyers_vec_size = std::mem::size_of::<Pubkey>().checked_mul(buyers_capacity)
             .expect("Calculation error in get_size - buyers_vec_size overflow");
         
-        // Space for up to 15,000 contributions
-        let contributions_capacity = 15_000;
+        // Space for as many contributions
+        let contributions_capacity = PRESALE_INITIAL_BUYER_CAPACITY;
         let contribution_size = std::mem::size_of::<PresaleContribution>();
         let contributions_vec_size = contribution_size.checked_mul(contributions_capacity)
             .expect("Calculation error in get_size - contributions_vec_size overflow");
         
-        // Space for up to 10 allowed stablecoins
-        let stablecoins_capacity = 10;
+        // Space for up to 10 allowed stablecoins, plus as many limited, underfunded and pegged stablecoins
+        let stablecoins_capacity = 10 * 4;
         let stablecoins_vec_size = std::mem::size_of::<Pubkey>().checked_mul(stablecoins_capacity)
             .expect("Calculation error in get_size - stablecoins_vec_size overflow");
         
@@ -137,6 +429,7 @@ impl PresaleState {
         base_size.checked_add(buyers_vec_size)
             .and_then(|size| size.checked_add(contributions_vec_size))
             .and_then(|size| size.checked_add(stablecoins_vec_size))
+            .and_then(|size| size.checked_add(PresaleStats::get_size()))
             .expect("Calculation error in get_size - total size overflow")
     }
     
@@ -154,8 +447,8 @@ impl PresaleState {
         let contributions_vec_size = contribution_size.checked_mul(num_buyers)
             .expect("Calculation error in get_size_for_buyers - contributions_vec_size overflow");
         
-        // Space for up to 10 allowed stablecoins
-        let stablecoins_capacity = 10;
+        // Space for up to 10 allowed stablecoins, plus as many limited, underfunded and pegged stablecoins
+        let stablecoins_capacity = 10 * 4;
         let stablecoins_vec_size = std::mem::size_of::<Pubkey>().checked_mul(stablecoins_capacity)
             .expect("Calculation error in get_size_for_buyers - stablecoins_vec_size overflow");
         
@@ -163,9 +456,46 @@ impl PresaleState {
         base_size.checked_add(buyers_vec_size)
             .and_then(|size| size.checked_add(contributions_vec_size))
             .and_then(|size| size.checked_add(stablecoins_vec_size))
+            .and_then(|size| size.checked_add(PresaleStats::get_size()))
             .expect("Calculation error in get_size_for_buyers - total size overflow")
     }
+
+    /// Buyers an account of `data_len` bytes has room for; the inverse of `get_size_for_buyers`
+    pub fn buyer_capacity(data_len: usize) -> usize {
+        let per_buyer = std::mem::size_of::<Pubkey>() + std::mem::size_of::<PresaleContribution>();
+        data_len.saturating_sub(Self::get_size_for_buyers(0)) / per_buyer
+    }
+
+    /// Whether the buyer count has just reached the warning share of `capacity` or filled it
+    pub fn buyer_capacity_running_low(num_buyers: usize, capacity: usize) -> bool {
+        let warning_at = capacity * PRESALE_CAPACITY_WARNING_BPS as usize / 10_000;
+        num_buyers == warning_at || num_buyers == capacity
+    }
     
+    /// Time by which 75% of the post-launch refund window has elapsed
+    pub fn refund_window_closing_soon_timestamp(&self) -> i64 {
+        let length = self.refund_period_end_timestamp.saturating_sub(self.refund_available_timestamp);
+        self.refund_available_timestamp.saturating_add(length / 4 * 3)
+    }
+
+    /// Refunds still owed from the locked treasury for contributions made in `stablecoin_mint`,
+    /// in its base units (refunds pay back the locked share of each contribution)
+    pub fn outstanding_refunds(&self, stablecoin_mint: &Pubkey) -> Option<u64> {
+        self.contributions
+            .iter()
+            .filter(|contribution| !contribution.refunded && contribution.stablecoin_mint == *stablecoin_mint)
+            .try_fold(0u64, |total, contribution| total.checked_add(contribution.locked_amount))
+    }
+
+    /// Refunds the locked treasury must still be able to pay in `stablecoin_mint`;
+    /// nothing is owed once the post-launch refund window has closed
+    pub fn locked_treasury_liabilities(&self, stablecoin_mint: &Pubkey, current_time: i64) -> Option<u64> {
+        if self.token_launched && current_time > self.refund_period_end_timestamp {
+            return Some(0);
+        }
+        self.outstanding_refunds(stablecoin_mint)
+    }
+
     /// Find a contribution by buyer
     pub fn find_contribution(&self, buyer: &Pubkey) -> Option<(usize, &PresaleContribution)> {
         self.contributions.iter().enumerate().find(|(_, contribution)| &contribution.buyer == buyer)
@@ -175,12 +505,12 @@ impl PresaleState {
     pub fn add_stablecoin(&mut self, stablecoin: SupportedStablecoin) -> Result<(), ProgramError> {
         // Check if already exists
         if self.allowed_stablecoins.iter().any(|coin| coin == &stablecoin.mint) {
-            return Err(ProgramError::InvalidArgument);
+            return Err(VCoinError::StablecoinAlreadySupported.into());
         }
         
         // Enforce limit
         if self.allowed_stablecoins.len() >= 10 {
-            return Err(ProgramError::InvalidArgument);
+            return Err(VCoinError::StablecoinLimitReached.into());
         }
         
         // Add stablecoin
@@ -201,36 +531,13 @@ impl PresaleState {
             return None;
         }
         
-        // Get the mint string for comparison
-        let mint_str = stablecoin_mint.to_string();
-        
-        // Known USDC addresses across networks
-        let usdc_addresses = [
-            // Mainnet
-            "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
-            // Devnet
-            "4zMMC9srt5Ri5X14GAgXhaHii3GnPAEERYPJgZJDncDU",
-            // Testnet
-            "CpMah17kQEL2wqyMKt3mZBdTnZbkbfx4nqmQMFDP5vwp",
-        ];
-        
-        // Known USDT addresses across networks
-        let usdt_addresses = [
-            // Mainnet
-            "Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB",
-            // Devnet
-            "DAwBSXe6w9g37wdE2tCrFbho3QHKZi4PjuBytQCULap2",
-            // Testnet 
-            "BQcdHdAQW1hczDbBi9hiegXAR7A98Q9jx3X3iBBBDiq4",
-        ];
-        
-        // Check if it's a known USDC address
-        if usdc_addresses.contains(&mint_str.as_str()) {
+        // Check if it's a known USDC address on any cluster
+        if stablecoin_mints::USDC.contains(stablecoin_mint) {
             return Some(StablecoinType::USDC);
         }
         
-        // Check if it's a known USDT address
-        if usdt_addresses.contains(&mint_str.as_str()) {
+        // Check if it's a known USDT address on any cluster
+        if stablecoin_mints::USDT.contains(stablecoin_mint) {
             return Some(StablecoinType::USDT);
         }
         
@@ -242,12 +549,12 @@ impl PresaleState {
     pub fn add_stablecoin_raw(&mut self, stablecoin_mint: Pubkey) -> Result<(), ProgramError> {
         // Check if already exists
         if self.allowed_stablecoins.iter().any(|coin| coin == &stablecoin_mint) {
-            return Err(ProgramError::InvalidArgument);
+            return Err(VCoinError::StablecoinAlreadySupported.into());
         }
         
         // Enforce limit
         if self.allowed_stablecoins.len() >= 10 {
-            return Err(ProgramError::InvalidArgument);
+            return Err(VCoinError::StablecoinLimitReached.into());
         }
         
         // Add stablecoin
@@ -257,111 +564,2308 @@ impl PresaleState {
     }
 }
 
-/// Vesting beneficiary
+/// Length of the hourly raise history kept in `PresaleStats`
+pub const PRESALE_STATS_WINDOW: i64 = 24 * 60 * 60;
+
+/// Compact raise statistics kept in the presale account so dashboards can read
+/// them without replaying purchase history (unique buyers are `num_buyers`)
 #[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
-pub struct VestingBeneficiary {
-    /// Beneficiary public key
-    pub beneficiary: Pubkey,
-    /// Total amount of tokens to vest
-    pub total_amount: u64,
-    /// Amount of tokens already released
-    pub released_amount: u64,
+pub struct PresaleStats {
+    /// Amount raised per hour over the last day (ring buffer of hourly buckets)
+    pub hourly_raise: RollingVolume,
+    /// Number of purchases made
+    pub num_purchases: u32,
+    /// Largest single purchase amount
+    pub largest_purchase: u64,
+    /// Streaming estimate of the median purchase amount
+    pub median_purchase: u64,
+    /// Current step size of the median estimator
+    pub median_step: u64,
+    /// Direction of the last median adjustment (-1, 0 or 1)
+    pub median_direction: i8,
 }
 
-impl VestingBeneficiary {
-    /// Calculate the amount of tokens that should be released based on current time
-    pub fn calculate_released_amount(&mut self, current_time: i64, release_interval: i64) -> Result<u64, ProgramError> {
-        // Calculate releasable amount based on elapsed time and release interval
-        let elapsed_intervals = if release_interval > 0 {
-            current_time / release_interval
+impl PresaleStats {
+    /// Create empty statistics
+    pub fn new() -> Self {
+        Self {
+            hourly_raise: RollingVolume::new(PRESALE_STATS_WINDOW),
+            num_purchases: 0,
+            largest_purchase: 0,
+            median_purchase: 0,
+            median_step: 1,
+            median_direction: 0,
+        }
+    }
+
+    /// Get the serialized size of the statistics
+    pub fn get_size() -> usize {
+        // hourly buckets, num_purchases, largest/median/step, direction
+        RollingVolume::get_size() + 4 + 8 * 3 + 1
+    }
+
+    /// Record a purchase made at `current_time`
+    pub fn record_purchase(&mut self, current_time: i64, amount: u64) -> Option<()> {
+        self.hourly_raise.record(current_time, amount)?;
+        self.largest_purchase = self.largest_purchase.max(amount);
+        self.update_median(amount);
+        self.num_purchases = self.num_purchases.saturating_add(1);
+        Some(())
+    }
+
+    /// Frugal streaming median: move the estimate towards each sample, doubling the
+    /// step while samples keep pulling in the same direction and halving it otherwise
+    fn update_median(&mut self, amount: u64) {
+        if self.num_purchases == 0 {
+            self.median_purchase = amount;
+            return;
+        }
+
+        let direction: i8 = match amount.cmp(&self.median_purchase) {
+            std::cmp::Ordering::Greater => 1,
+            std::cmp::Ordering::Less => -1,
+            std::cmp::Ordering::Equal => return,
+        };
+
+        self.median_step = if direction == self.median_direction {
+            self.median_step.saturating_mul(2)
         } else {
-            return Err(ProgramError::InvalidArgument);
+            (self.median_step / 2).max(1)
         };
-        
-        // Calculate amount per interval
-        let total_intervals = release_interval as u64;
-        let amount_per_interval = self.total_amount.checked_div(total_intervals)
-            .ok_or(ProgramError::ArithmeticOverflow)?;
-            
-        // Calculate total releasable amount
-        let total_releasable = amount_per_interval.checked_mul(elapsed_intervals as u64)
-            .ok_or(ProgramError::ArithmeticOverflow)?;
-            
-        // Don't release more than total amount
-        let capped_releasable = std::cmp::min(total_releasable, self.total_amount);
-        
-        // Calculate unreleased amount
-        let unreleased = capped_releasable.checked_sub(self.released_amount)
-            .ok_or(ProgramError::ArithmeticOverflow)?;
-            
-        Ok(unreleased)
+        self.median_direction = direction;
+
+        // Never step past the sample itself
+        self.median_purchase = if direction > 0 {
+            self.median_purchase.saturating_add(self.median_step).min(amount)
+        } else {
+            self.median_purchase.saturating_sub(self.median_step).max(amount)
+        };
+    }
+}
+
+impl Default for PresaleStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// On-chain alarm raised when a locked treasury no longer covers the refunds it owes
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct TreasuryAlarm {
+    /// Required coverage of outstanding refunds in basis points (10_000 = fully covered, 0 disables)
+    pub coverage_bps: u16,
+    /// Stablecoins whose locked treasury is currently below the required coverage
+    pub underfunded_mints: Vec<Pubkey>,
+    /// Time the alarm was last raised
+    pub last_raised_at: i64,
+}
+
+impl TreasuryAlarm {
+    /// Whether any locked treasury is currently underfunded
+    pub fn is_raised(&self) -> bool {
+        !self.underfunded_mints.is_empty()
+    }
+}
+
+/// Liquidity item of the launch checklist
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LaunchLiquidity {
+    /// Not confirmed yet
+    #[default]
+    Pending,
+    /// The authority confirmed liquidity was seeded
+    Seeded,
+    /// The authority chose to launch without seeding liquidity
+    Waived,
+}
+
+/// Prerequisites ticked with `CompleteLaunchChecklistItem` before `LaunchToken`
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct LaunchChecklist {
+    /// Token metadata exists for the presale's mint
+    pub metadata_finalized: bool,
+    /// Liquidity seeded or explicitly waived
+    pub liquidity: LaunchLiquidity,
+    /// A vesting pool for the mint holds all of its tokens in escrow
+    pub vesting_funded: bool,
+    /// The autonomous supply controller for the mint is initialized
+    pub controller_initialized: bool,
+}
+
+impl LaunchChecklist {
+    /// Items still open, by name
+    pub fn missing_items(&self) -> Vec<&'static str> {
+        [
+            ("metadata finalized", self.metadata_finalized),
+            ("liquidity seeded or waived", self.liquidity != LaunchLiquidity::Pending),
+            ("vesting funded", self.vesting_funded),
+            ("controller initialized", self.controller_initialized),
+        ]
+        .into_iter()
+        .filter(|(_, done)| !done)
+        .map(|(item, _)| item)
+        .collect()
+    }
+
+    /// Whether every item is done
+    pub fn is_complete(&self) -> bool {
+        self.missing_items().is_empty()
+    }
+}
+
+/// Treasury withdrawal announced with `NoticeWithdraw`
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct WithdrawNotice {
+    /// Stablecoin whose locked treasury will be withdrawn
+    pub stablecoin_mint: Pubkey,
+    /// Token account the funds will be sent to
+    pub destination: Pubkey,
+    /// Time the notice was given
+    pub noticed_at: i64,
+    /// Earliest time `ExecuteWithdraw` succeeds
+    pub executable_at: i64,
+}
+
+/// Refund window boundaries a presale has announced
+///
+/// Each boundary is announced once, by the first handler interaction after it
+/// (or an `AnnounceRefundWindow` ping).
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct RefundWindowNotices {
+    /// `RefundWindowOpened` was emitted
+    pub opened: bool,
+    /// `RefundWindowClosingSoon` was emitted (75% of the window elapsed)
+    pub closing_soon: bool,
+    /// `RefundWindowClosed` was emitted
+    pub closed: bool,
+}
+
+/// Stage of a presale round
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PresaleRoundKind {
+    /// Seed round
+    Seed,
+    /// Private round
+    Private,
+    /// Public round
+    Public,
+}
+
+/// Per-round presale state, stored in a PDA derived from
+/// `[b"presale_round", presale, round_index]`
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct PresaleRoundState {
+    /// Is initialized
+    pub is_initialized: bool,
+    /// Parent presale account
+    pub presale: Pubkey,
+    /// Sequential round index (0 for the first round)
+    pub round_index: u8,
+    /// Stage of this round
+    pub kind: PresaleRoundKind,
+    /// Round start timestamp
+    pub start_time: i64,
+    /// Round end timestamp
+    pub end_time: i64,
+    /// Token price for this round in USD (as u64 with 6 decimals precision)
+    pub token_price: u64,
+    /// Hard cap for this round
+    pub hard_cap: u64,
+    /// Minimum purchase amount in USD (as u64 with 6 decimals precision)
+    pub min_purchase: u64,
+    /// Maximum purchase amount in USD (as u64 with 6 decimals precision)
+    pub max_purchase: u64,
+    /// Total tokens sold in this round
+    pub total_tokens_sold: u64,
+    /// Total USD raised in this round
+    pub total_usd_raised: u64,
+    /// Number of purchases made in this round
+    pub num_purchases: u32,
+    /// Buyers allowed to purchase in this round (empty means open to everyone)
+    pub whitelist: Vec<Pubkey>,
+}
+
+impl PresaleRoundState {
+    /// Get the serialized size of a round account with the given whitelist length
+    pub fn get_size(whitelist_len: usize) -> usize {
+        // is_initialized, presale, round_index, kind
+        1 + 32 + 1 + 1
+            // start/end time, price, caps, purchase limits, totals
+            + 8 * 8
+            // num_purchases
+            + 4
+            // whitelist length prefix and entries
+            + 4 + 32 * whitelist_len
+    }
+
+    /// Check whether a buyer may purchase in this round
+    pub fn is_whitelisted(&self, buyer: &Pubkey) -> bool {
+        self.whitelist.is_empty() || self.whitelist.contains(buyer)
+    }
+
+    /// Check whether the round accepts purchases at the given time
+    pub fn is_open(&self, current_time: i64) -> bool {
+        current_time >= self.start_time
+            && current_time <= self.end_time
+            && self.total_usd_raised < self.hard_cap
+    }
+}
+
+/// KYC credential for a buyer, stored in a PDA derived from
+/// `[b"kyc_credential", presale, buyer]`
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct KycCredential {
+    /// Is initialized
+    pub is_initialized: bool,
+    /// Presale the credential was issued for
+    pub presale: Pubkey,
+    /// Attested buyer wallet
+    pub buyer: Pubkey,
+    /// Attestor key that signed the credential
+    pub attestor: Pubkey,
+    /// Issuance timestamp
+    pub issued_at: i64,
+    /// Expiration timestamp
+    pub expires_at: i64,
+}
+
+impl KycCredential {
+    /// Get the serialized size of the credential account
+    pub fn get_size() -> usize {
+        // is_initialized, presale, buyer, attestor, issued_at, expires_at
+        1 + 32 + 32 + 32 + 8 + 8
+    }
+
+    /// Message the attestor signs with ed25519: presale || buyer || expires_at (little endian)
+    pub fn attestation_message(presale: &Pubkey, buyer: &Pubkey, expires_at: i64) -> Vec<u8> {
+        let mut message = Vec::with_capacity(72);
+        message.extend_from_slice(presale.as_ref());
+        message.extend_from_slice(buyer.as_ref());
+        message.extend_from_slice(&expires_at.to_le_bytes());
+        message
+    }
+
+    /// Check whether the credential is valid for the given attestor at the given time
+    pub fn is_valid(&self, attestor: &Pubkey, current_time: i64) -> bool {
+        self.is_initialized && self.attestor == *attestor && current_time <= self.expires_at
+    }
+}
+
+/// Kind of off-chain compliance artifact a hash commits to
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ComplianceArtifactKind {
+    /// Hash of a batch of KYC records
+    KycBatch,
+    /// Hash of a legal memo or opinion
+    LegalMemo,
+    /// Any other compliance document
+    Other,
+}
+
+/// A single hash commitment to an off-chain compliance artifact
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct ComplianceCommitment {
+    /// Kind of artifact
+    pub kind: ComplianceArtifactKind,
+    /// SHA-256 (or similar) hash of the artifact
+    pub hash: [u8; 32],
+    /// Timestamp the hash was committed
+    pub committed_at: i64,
+}
+
+/// Compliance commitments for a presale, stored in a PDA derived from
+/// `[b"compliance", presale]`
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct ComplianceRecord {
+    /// Is initialized
+    pub is_initialized: bool,
+    /// Presale the commitments belong to
+    pub presale: Pubkey,
+    /// Committed artifact hashes, oldest first
+    pub commitments: Vec<ComplianceCommitment>,
+}
+
+impl ComplianceRecord {
+    /// Get the serialized size of the record with the given number of commitments
+    pub fn get_size(num_commitments: usize) -> usize {
+        // is_initialized, presale, vec length prefix
+        1 + 32 + 4
+            // kind, hash, committed_at per commitment
+            + (1 + 32 + 8) * num_commitments
+    }
+}
+
+/// Volume recorded in one bucket of a rolling window
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct WindowBucket {
+    /// Bucket number (timestamp / bucket duration) this volume belongs to
+    pub bucket_id: i64,
+    /// Volume recorded in the bucket
+    pub volume: u64,
+}
+
+/// Rolling-window volume counter split into `ROLLING_WINDOW_BUCKETS` buckets,
+/// so the reported volume always covers (roughly) the last `window_duration` seconds
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct RollingVolume {
+    /// Window length in seconds (0 disables the window)
+    pub window_duration: i64,
+    /// Ring of buckets indexed by `bucket_id % ROLLING_WINDOW_BUCKETS`
+    pub buckets: Vec<WindowBucket>,
+}
+
+impl RollingVolume {
+    /// Create an empty window of the given length
+    pub fn new(window_duration: i64) -> Self {
+        Self {
+            window_duration,
+            buckets: vec![WindowBucket { bucket_id: 0, volume: 0 }; ROLLING_WINDOW_BUCKETS],
+        }
+    }
+
+    /// Get the serialized size of a rolling window
+    pub fn get_size() -> usize {
+        // window_duration, vec length prefix, bucket_id and volume per bucket
+        8 + 4 + (8 + 8) * ROLLING_WINDOW_BUCKETS
+    }
+
+    fn bucket_duration(&self) -> i64 {
+        (self.window_duration / ROLLING_WINDOW_BUCKETS as i64).max(1)
+    }
+
+    /// Volume recorded within the window ending at `current_time`
+    pub fn volume_at(&self, current_time: i64) -> u64 {
+        if self.window_duration <= 0 {
+            return 0;
+        }
+
+        let current_bucket = current_time.div_euclid(self.bucket_duration());
+        let oldest_bucket = current_bucket - ROLLING_WINDOW_BUCKETS as i64;
+        self.buckets
+            .iter()
+            .filter(|bucket| bucket.bucket_id > oldest_bucket && bucket.bucket_id <= current_bucket)
+            .fold(0u64, |total, bucket| total.saturating_add(bucket.volume))
+    }
+
+    /// Record volume at `current_time`, recycling the bucket if it belongs to an expired period
+    pub fn record(&mut self, current_time: i64, amount: u64) -> Option<()> {
+        if self.window_duration <= 0 || self.buckets.len() != ROLLING_WINDOW_BUCKETS {
+            return Some(());
+        }
+
+        let current_bucket = current_time.div_euclid(self.bucket_duration());
+        let slot = current_bucket.rem_euclid(ROLLING_WINDOW_BUCKETS as i64) as usize;
+        let bucket = &mut self.buckets[slot];
+        if bucket.bucket_id != current_bucket {
+            bucket.bucket_id = current_bucket;
+            bucket.volume = 0;
+        }
+        bucket.volume = bucket.volume.checked_add(amount)?;
+        Some(())
+    }
+}
+
+/// Days of purchase history a `SaleAnalytics` account keeps
+pub const SALE_ANALYTICS_DAYS: usize = 90;
+
+/// Purchases made on one UTC day
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SaleDay {
+    /// Days since the Unix epoch (`unix_timestamp / 86_400`)
+    pub day: i64,
+    /// USD raised (6 decimals)
+    pub usd_raised: u64,
+    /// Number of purchases
+    pub purchases: u32,
+    /// Buyers whose first purchase fell on this day
+    pub new_buyers: u32,
+}
+
+/// Per-day purchase aggregates, so a presale can be charted from one account without
+/// replaying its transactions; stored in a PDA derived from `[b"sale_analytics", presale]`
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct SaleAnalytics {
+    /// Is initialized
+    pub is_initialized: bool,
+    /// Presale the purchases belong to
+    pub presale: Pubkey,
+    /// Ring of days indexed by `day % SALE_ANALYTICS_DAYS`; older days are overwritten
+    pub days: Vec<SaleDay>,
+}
+
+impl SaleAnalytics {
+    /// Create empty analytics for a presale
+    pub fn new(presale: Pubkey) -> Self {
+        Self {
+            is_initialized: true,
+            presale,
+            days: vec![SaleDay::default(); SALE_ANALYTICS_DAYS],
+        }
+    }
+
+    /// Get the serialized size of the analytics account
+    pub fn get_size() -> usize {
+        // is_initialized, presale, vec length prefix, then day, usd_raised,
+        // purchases and new_buyers per day
+        1 + 32 + 4 + (8 + 8 + 4 + 4) * SALE_ANALYTICS_DAYS
+    }
+
+    /// Address of the analytics account of a presale
+    pub fn find_address(program_id: &Pubkey, presale: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"sale_analytics", presale.as_ref()], program_id)
+    }
+
+    /// Record a purchase of `usd_amount` made at `current_time`, recycling the day's
+    /// slot if it still holds an older day
+    pub fn record_purchase(&mut self, current_time: i64, usd_amount: u64, new_buyer: bool) -> Option<()> {
+        if self.days.len() != SALE_ANALYTICS_DAYS {
+            return None;
+        }
+
+        let day = current_time.div_euclid(86_400);
+        let slot = &mut self.days[day.rem_euclid(SALE_ANALYTICS_DAYS as i64) as usize];
+        if slot.day != day {
+            *slot = SaleDay { day, ..SaleDay::default() };
+        }
+        slot.usd_raised = slot.usd_raised.checked_add(usd_amount)?;
+        slot.purchases = slot.purchases.checked_add(1)?;
+        slot.new_buyers = slot.new_buyers.checked_add(u32::from(new_buyer))?;
+        Some(())
+    }
+
+    /// Days with purchases, oldest first
+    pub fn history(&self) -> Vec<SaleDay> {
+        let mut days: Vec<SaleDay> = self.days.iter().copied().filter(|day| day.purchases > 0).collect();
+        days.sort_by_key(|day| day.day);
+        days
+    }
+}
+
+/// Purchase limits for one stablecoin of a presale, stored in a PDA derived from
+/// `[b"stablecoin_limits", presale, stablecoin_mint]`
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct StablecoinLimits {
+    /// Is initialized
+    pub is_initialized: bool,
+    /// Presale the limits apply to
+    pub presale: Pubkey,
+    /// Stablecoin mint the limits apply to
+    pub stablecoin_mint: Pubkey,
+    /// Minimum purchase amount for this stablecoin (0 keeps the presale/round minimum)
+    pub min_purchase: u64,
+    /// Maximum purchase amount for this stablecoin (0 keeps the presale/round maximum)
+    pub max_purchase: u64,
+    /// Maximum volume accepted in this stablecoin per rolling window (0 disables)
+    pub window_cap: u64,
+    /// Timestamp after which the window cap stops applying (0 means it never expires)
+    pub cap_expires_at: i64,
+    /// Rolling volume accounting for the window cap
+    pub window: RollingVolume,
+}
+
+impl StablecoinLimits {
+    /// Get the serialized size of the limits account
+    pub fn get_size() -> usize {
+        // is_initialized, presale, stablecoin_mint
+        1 + 32 + 32
+            // min/max purchase, window cap, cap expiry
+            + 8 * 4
+            + RollingVolume::get_size()
+    }
+
+    /// Check whether the window cap applies at the given time
+    pub fn cap_active(&self, current_time: i64) -> bool {
+        self.window_cap > 0 && (self.cap_expires_at == 0 || current_time <= self.cap_expires_at)
+    }
+}
+
+/// Lending market the locked treasury may lend one stablecoin to, stored in a PDA
+/// derived from `[b"yield_venue", presale, stablecoin_mint]`.
+///
+/// Venues speak the SPL token-lending reserve interface; deposits wait out
+/// `YIELD_VENUE_REVIEW_DELAY` after approval while recalls are always possible.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct YieldVenue {
+    /// Is initialized
+    pub is_initialized: bool,
+    /// Presale whose locked treasury lends
+    pub presale: Pubkey,
+    /// Stablecoin lent
+    pub stablecoin_mint: Pubkey,
+    /// Lending program
+    pub lending_program: Pubkey,
+    /// Lending market of the reserve
+    pub lending_market: Pubkey,
+    /// Reserve the stablecoin is deposited into
+    pub reserve: Pubkey,
+    /// Collateral token account owned by the locked treasury authority
+    pub collateral_account: Pubkey,
+    /// Stablecoin account of the insurance fund that receives the yield
+    pub insurance_fund: Pubkey,
+    /// Maximum principal deployed at once
+    pub deposit_cap: u64,
+    /// Principal currently deployed
+    pub deployed_principal: u64,
+    /// Yield routed to the insurance fund so far
+    pub total_yield_routed: u64,
+    /// Time of the latest approval
+    pub approved_at: i64,
+    /// Time from which deposits are accepted
+    pub active_at: i64,
+}
+
+impl YieldVenue {
+    /// Get the serialized size of the venue account
+    pub fn get_size() -> usize {
+        // is_initialized, presale, stablecoin_mint, lending_program, lending_market,
+        // reserve, collateral_account, insurance_fund
+        1 + 32 * 7
+            // deposit_cap, deployed_principal, total_yield_routed, approved_at, active_at
+            + 8 * 5
+    }
+
+    /// Address of the venue for one stablecoin of a presale
+    pub fn find_address(program_id: &Pubkey, presale: &Pubkey, stablecoin_mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[b"yield_venue", presale.as_ref(), stablecoin_mint.as_ref()],
+            program_id,
+        )
+    }
+
+    /// Whether `other` lends to the same market through the same accounts
+    pub fn same_market(&self, other: &YieldVenue) -> bool {
+        self.lending_program == other.lending_program
+            && self.lending_market == other.lending_market
+            && self.reserve == other.reserve
+            && self.collateral_account == other.collateral_account
+    }
+
+    /// Principal and yield in `received` liquidity for redeeming `collateral_amount`
+    /// of `collateral_balance`; the principal share follows the collateral share
+    pub fn split_recall(&self, collateral_amount: u64, collateral_balance: u64, received: u64) -> Option<(u64, u64)> {
+        if collateral_amount == 0 || collateral_amount > collateral_balance {
+            return None;
+        }
+        let principal = if collateral_amount == collateral_balance {
+            self.deployed_principal
+        } else {
+            let share = self.deployed_principal as u128 * collateral_amount as u128 / collateral_balance as u128;
+            u64::try_from(share).ok()?
+        };
+        Some((principal, received.saturating_sub(principal)))
+    }
+}
+
+/// What a purchase does when its stablecoin trades outside the peg band
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DepegAction {
+    /// Reject the purchase
+    #[default]
+    Reject,
+    /// Below the band, price the tokens at the stablecoin's market value
+    Haircut,
+}
+
+/// Depeg protection for one stablecoin of a presale, stored in a PDA derived from
+/// `[b"stablecoin_peg", presale, stablecoin_mint]`
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct StablecoinPegGuard {
+    /// Is initialized
+    pub is_initialized: bool,
+    /// Presale the guard applies to
+    pub presale: Pubkey,
+    /// Stablecoin mint the guard applies to
+    pub stablecoin_mint: Pubkey,
+    /// Oracle controller reporting the stablecoin's USD price
+    pub oracle_controller: Pubkey,
+    /// Largest deviation from the peg accepted at face value, in basis points
+    pub band_bps: u16,
+    /// Action taken outside the band
+    pub action: DepegAction,
+}
+
+impl StablecoinPegGuard {
+    /// Get the serialized size of the guard account
+    pub fn get_size() -> usize {
+        // is_initialized, presale, stablecoin_mint, oracle_controller
+        1 + 32 + 32 + 32
+            // band_bps, action
+            + 2 + 1
+    }
+
+    /// Address of the guard for one stablecoin of a presale
+    pub fn find_address(program_id: &Pubkey, presale: &Pubkey, stablecoin_mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[b"stablecoin_peg", presale.as_ref(), stablecoin_mint.as_ref()],
+            program_id,
+        )
+    }
+
+    /// Deviation of a micro-USD price from the peg, in basis points
+    pub fn deviation_bps(price: u64) -> u64 {
+        let deviation = price.abs_diff(STABLECOIN_PEG_PRICE) as u128 * 10_000 / STABLECOIN_PEG_PRICE as u128;
+        u64::try_from(deviation).unwrap_or(u64::MAX)
+    }
+
+    /// USD value credited for `amount` of the stablecoin at `price`, or None if the
+    /// purchase must be rejected. A stablecoin above the band is still credited at
+    /// face value when haircutting, since the raise loses nothing.
+    pub fn credited_amount(&self, amount: u64, price: u64) -> Option<u64> {
+        if Self::deviation_bps(price) <= self.band_bps as u64 {
+            return Some(amount);
+        }
+        match self.action {
+            DepegAction::Reject => None,
+            DepegAction::Haircut if price > STABLECOIN_PEG_PRICE => Some(amount),
+            DepegAction::Haircut => {
+                let credited = amount as u128 * price as u128 / STABLECOIN_PEG_PRICE as u128;
+                u64::try_from(credited).ok()
+            }
+        }
+    }
+}
+
+/// Merkle-distributor airdrop, stored in a PDA derived from
+/// `[b"airdrop", mint, merkle_root]`
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct AirdropDistributor {
+    /// Is initialized
+    pub is_initialized: bool,
+    /// Authority that created the airdrop
+    pub authority: Pubkey,
+    /// Mint of the distributed token
+    pub mint: Pubkey,
+    /// Escrow token account owned by the distributor PDA
+    pub escrow: Pubkey,
+    /// Root of the Merkle tree of `(index, claimant, amount)` leaves
+    pub merkle_root: [u8; 32],
+    /// Total amount that may be claimed
+    pub total: u64,
+    /// Amount claimed so far
+    pub total_claimed: u64,
+    /// Number of claims made
+    pub num_claims: u32,
+    /// Distributor PDA bump seed
+    pub bump: u8,
+}
+
+impl AirdropDistributor {
+    /// Get the serialized size of the distributor account
+    pub fn get_size() -> usize {
+        // is_initialized, authority, mint, escrow, merkle_root
+        1 + 32 + 32 + 32 + 32
+            // total, total_claimed, num_claims, bump
+            + 8 + 8 + 4 + 1
+    }
+
+    /// Leaf hash: keccak(0x00 || index || claimant || amount), little endian integers
+    pub fn leaf(index: u64, claimant: &Pubkey, amount: u64) -> [u8; 32] {
+        keccak::hashv(&[&[0u8], &index.to_le_bytes(), claimant.as_ref(), &amount.to_le_bytes()]).to_bytes()
+    }
+
+    /// Node hash: keccak(0x01 || min(a, b) || max(a, b)), so proofs need no left/right flags
+    pub fn node(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+        let (left, right) = if a <= b { (a, b) } else { (b, a) };
+        keccak::hashv(&[&[1u8], left, right]).to_bytes()
+    }
+
+    /// Check a Merkle proof for a leaf against the distributor root
+    pub fn verify_proof(&self, proof: &[[u8; 32]], leaf: [u8; 32]) -> bool {
+        let computed = proof.iter().fold(leaf, |hash, sibling| Self::node(&hash, sibling));
+        computed == self.merkle_root
+    }
+}
+
+/// Claimed flags for a range of airdrop indices, stored in a PDA derived from
+/// `[b"airdrop_claims", distributor, chunk_index]` (chunk index as u32 little endian)
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct AirdropClaimBitmap {
+    /// Is initialized
+    pub is_initialized: bool,
+    /// Distributor the bitmap belongs to
+    pub distributor: Pubkey,
+    /// Chunk covering indices `[chunk_index * AIRDROP_BITMAP_CHUNK_BITS, ..)`
+    pub chunk_index: u32,
+    /// One bit per claim index in the chunk
+    pub bits: Vec<u8>,
+}
+
+impl AirdropClaimBitmap {
+    /// Create an empty bitmap for a chunk
+    pub fn new(distributor: Pubkey, chunk_index: u32) -> Self {
+        Self {
+            is_initialized: true,
+            distributor,
+            chunk_index,
+            bits: vec![0u8; (AIRDROP_BITMAP_CHUNK_BITS / 8) as usize],
+        }
+    }
+
+    /// Get the serialized size of a bitmap account
+    pub fn get_size() -> usize {
+        // is_initialized, distributor, chunk_index, vec length prefix and bits
+        1 + 32 + 4 + 4 + (AIRDROP_BITMAP_CHUNK_BITS / 8) as usize
+    }
+
+    /// Chunk holding the given claim index
+    pub fn chunk_for(index: u64) -> Option<u32> {
+        u32::try_from(index / AIRDROP_BITMAP_CHUNK_BITS).ok()
+    }
+
+    fn position(index: u64) -> (usize, u8) {
+        let bit = index % AIRDROP_BITMAP_CHUNK_BITS;
+        ((bit / 8) as usize, 1u8 << (bit % 8))
+    }
+
+    /// Check whether a claim index has been claimed
+    pub fn is_claimed(&self, index: u64) -> bool {
+        let (byte, mask) = Self::position(index);
+        self.bits.get(byte).is_some_and(|bits| bits & mask != 0)
+    }
+
+    /// Mark a claim index as claimed
+    pub fn set_claimed(&mut self, index: u64) {
+        let (byte, mask) = Self::position(index);
+        if let Some(bits) = self.bits.get_mut(byte) {
+            *bits |= mask;
+        }
+    }
+}
+
+/// Public bug-bounty pool, stored in a PDA derived from `[b"bug_bounty", mint]`
+///
+/// Tokens in the escrow only leave as awards approved by a quorum of guardians,
+/// and committed awards stay reserved until they are paid.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct BugBountyEscrow {
+    /// Is initialized
+    pub is_initialized: bool,
+    /// Authority that created the bounty pool
+    pub authority: Pubkey,
+    /// Mint bounties are paid in
+    pub mint: Pubkey,
+    /// Escrow token account owned by the bounty PDA
+    pub escrow: Pubkey,
+    /// Guardians who approve awards
+    pub guardians: Vec<Pubkey>,
+    /// Guardian signatures required to commit an award
+    pub guardian_threshold: u8,
+    /// Tokens deposited through `FundBugBounty`
+    pub total_funded: u64,
+    /// Tokens committed to awards that are not paid yet
+    pub total_committed: u64,
+    /// Tokens paid out to researchers
+    pub total_paid: u64,
+    /// Number of awards committed
+    pub num_awards: u32,
+    /// Bounty PDA bump seed
+    pub bump: u8,
+}
+
+impl BugBountyEscrow {
+    /// Get the serialized size of the bounty account with room for every guardian
+    pub fn get_size() -> usize {
+        // is_initialized, authority, mint, escrow
+        1 + 32 + 32 + 32
+            // guardians (length prefix and keys), guardian_threshold
+            + 4 + 32 * MAX_BOUNTY_GUARDIANS + 1
+            // total_funded, total_committed, total_paid, num_awards, bump
+            + 8 + 8 + 8 + 4 + 1
+    }
+
+    /// Address of the bounty pool for `mint`
+    pub fn find_address(program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"bug_bounty", mint.as_ref()], program_id)
+    }
+
+    /// Whether a guardian set has no duplicates and a reachable, non-zero threshold
+    pub fn guardians_are_valid(guardians: &[Pubkey], threshold: u8) -> bool {
+        let unique = guardians.iter().enumerate().all(|(i, guardian)| !guardians[..i].contains(guardian));
+        unique
+            && guardians.len() <= MAX_BOUNTY_GUARDIANS
+            && threshold > 0
+            && threshold as usize <= guardians.len()
+    }
+
+    /// Guardians among `signers`, each counted once
+    pub fn approving_guardians(&self, signers: &[Pubkey]) -> Vec<Pubkey> {
+        self.guardians.iter().filter(|guardian| signers.contains(guardian)).copied().collect()
+    }
+
+    /// Tokens in an escrow holding `balance` that are not reserved for committed awards
+    pub fn uncommitted(&self, balance: u64) -> u64 {
+        balance.saturating_sub(self.total_committed)
+    }
+}
+
+/// Migration of holders from a v1 mint to a v2 mint, stored in a PDA derived
+/// from `[b"mint_migration", old_mint]`
+///
+/// Opening the migration revokes the v1 mint authority, so the v1 supply only
+/// shrinks from then on; every v1 token burned through the swap escrow mints
+/// exactly one v2 token.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct MintMigration {
+    /// Is initialized
+    pub is_initialized: bool,
+    /// Freeze authority of the v1 mint that opened the migration
+    pub authority: Pubkey,
+    /// Mint holders migrate from
+    pub old_mint: Pubkey,
+    /// Mint holders migrate to
+    pub new_mint: Pubkey,
+    /// v1 token account owned by the migration PDA that swapped tokens are burned from
+    pub escrow: Pubkey,
+    /// Decimals shared by both mints
+    pub decimals: u8,
+    /// v1 supply when the migration opened
+    pub old_supply: u64,
+    /// v1 tokens burned and v2 tokens minted so far
+    pub total_migrated: u64,
+    /// Number of swaps made
+    pub num_migrations: u32,
+    /// Supply controller re-pointed to the v2 mint, if any
+    pub supply_controller: Option<Pubkey>,
+    /// Time the migration opened
+    pub opened_at: i64,
+    /// Migration PDA bump seed
+    pub bump: u8,
+}
+
+impl MintMigration {
+    /// Get the serialized size of the migration account
+    pub fn get_size() -> usize {
+        // is_initialized, authority, old_mint, new_mint, escrow, decimals
+        1 + 32 + 32 + 32 + 32 + 1
+            // old_supply, total_migrated, num_migrations, supply_controller, opened_at, bump
+            + 8 + 8 + 4 + 33 + 8 + 1
+    }
+
+    /// Address of the migration away from `old_mint`
+    pub fn find_address(program_id: &Pubkey, old_mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"mint_migration", old_mint.as_ref()], program_id)
+    }
+
+    /// v1 tokens that have not been migrated yet
+    pub fn remaining(&self) -> u64 {
+        self.old_supply.saturating_sub(self.total_migrated)
+    }
+}
+
+/// Maximum prices in one simulation script
+pub const MAX_SIMULATED_PRICES: usize = 64;
+
+/// Size of the simulated price feed, in the Custom oracle layout (price,
+/// confidence and publish time, padded to the 64-byte minimum)
+pub const SIMULATED_PRICE_FEED_SIZE: usize = 64;
+
+/// Scripted clock and price sequence for replaying economic scenarios, stored
+/// in a PDA derived from `[b"simulation"]`
+///
+/// Only programs built with the `simulation` feature act on it: instructions
+/// that carry this account see `unix_timestamp` instead of the cluster clock,
+/// and each `StepSimulation` publishes the next scripted price to the price feed.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct SimulationState {
+    /// Is initialized
+    pub is_initialized: bool,
+    /// Account allowed to script the simulation
+    pub authority: Pubkey,
+    /// Unix time seen by instructions that carry the simulation account
+    pub unix_timestamp: i64,
+    /// Prices (with 6 decimals precision) published one per step
+    pub prices: Vec<u64>,
+    /// Index of the next price to publish
+    pub next_price: u16,
+    /// Custom-layout oracle account the prices are published to
+    /// (PDA: ["simulated_price"])
+    pub price_feed: Pubkey,
+    /// Simulation PDA bump seed
+    pub bump: u8,
+}
+
+impl SimulationState {
+    /// Get the serialized size of a simulation with a full price script
+    pub fn get_size() -> usize {
+        // is_initialized, authority, unix_timestamp, prices, next_price, price_feed, bump
+        1 + 32 + 8 + (4 + 8 * MAX_SIMULATED_PRICES) + 2 + 32 + 1
+    }
+
+    /// Address of the simulation account
+    pub fn find_address(program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"simulation"], program_id)
+    }
+
+    /// Address of the simulated price feed
+    pub fn find_price_feed(program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"simulated_price"], program_id)
+    }
+
+    /// Advance the clock by `seconds` and take the next scripted price, if any remain
+    pub fn step(&mut self, seconds: u32) -> Option<u64> {
+        self.unix_timestamp = self.unix_timestamp.saturating_add(seconds as i64);
+        let price = self.prices.get(self.next_price as usize).copied()?;
+        self.next_price += 1;
+        Some(price)
+    }
+
+    /// Price feed contents publishing `price` at the simulated time, with a zero
+    /// confidence interval
+    pub fn price_feed_data(&self, price: u64) -> [u8; SIMULATED_PRICE_FEED_SIZE] {
+        let mut data = [0u8; SIMULATED_PRICE_FEED_SIZE];
+        data[0..8].copy_from_slice(&price.to_le_bytes());
+        data[16..24].copy_from_slice(&self.unix_timestamp.to_le_bytes());
+        data
+    }
+}
+
+/// Bounty awarded for one disclosure, stored in a PDA derived from
+/// `[b"bug_bounty_award", bounty, disclosure_hash]`
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct BugBountyAward {
+    /// Is initialized
+    pub is_initialized: bool,
+    /// Bounty pool the award is paid from
+    pub bounty: Pubkey,
+    /// Hash of the disclosure report the award is for
+    pub disclosure_hash: [u8; 32],
+    /// Researcher whose token account receives the award
+    pub researcher: Pubkey,
+    /// Awarded amount
+    pub amount: u64,
+    /// Guardians who approved the award
+    pub approved_by: Vec<Pubkey>,
+    /// Time the award was committed
+    pub committed_at: i64,
+    /// Time the award was paid
+    pub paid_at: Option<i64>,
+    /// Award PDA bump seed
+    pub bump: u8,
+}
+
+impl BugBountyAward {
+    /// Get the serialized size of an award account with room for every guardian
+    pub fn get_size() -> usize {
+        // is_initialized, bounty, disclosure_hash, researcher, amount
+        1 + 32 + 32 + 32 + 8
+            // approved_by (length prefix and keys), committed_at, paid_at, bump
+            + 4 + 32 * MAX_BOUNTY_GUARDIANS + 8 + 9 + 1
+    }
+
+    /// Address of the award for `disclosure_hash` in `bounty`
+    pub fn find_address(program_id: &Pubkey, bounty: &Pubkey, disclosure_hash: &[u8; 32]) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"bug_bounty_award", bounty.as_ref(), disclosure_hash], program_id)
+    }
+}
+
+/// Vesting terms for a single beneficiary, overriding the vesting account's shared schedule
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct VestingSchedule {
+    /// Vesting start timestamp
+    pub start_time: i64,
+    /// Seconds after the start before anything vests
+    pub cliff_duration: i64,
+    /// Seconds from the start until the full amount has vested
+    pub duration: i64,
+    /// Vested tokens unlock in steps of this many seconds
+    pub release_interval: i64,
+}
+
+impl VestingSchedule {
+    /// Check the schedule is well formed
+    pub fn is_valid(&self) -> bool {
+        self.duration > 0
+            && self.release_interval > 0
+            && self.release_interval <= self.duration
+            && self.cliff_duration >= 0
+            && self.cliff_duration <= self.duration
+    }
+
+    /// Portion of `total_amount` vested by `current_time`
+    pub fn vested_amount(&self, total_amount: u64, current_time: i64) -> Result<u64, ProgramError> {
+        let elapsed = current_time.saturating_sub(self.start_time);
+        if elapsed < self.cliff_duration || elapsed <= 0 {
+            return Ok(0);
+        }
+        if elapsed >= self.duration {
+            return Ok(total_amount);
+        }
+
+        // Only whole intervals count as vested
+        let unlocked = (elapsed / self.release_interval)
+            .checked_mul(self.release_interval)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        let vested = (total_amount as u128)
+            .checked_mul(unlocked as u128)
+            .and_then(|value| value.checked_div(self.duration as u128))
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        u64::try_from(vested).map_err(|_| ProgramError::ArithmeticOverflow)
+    }
+
+    /// Times at which vested tokens can unlock: the cliff, then every release
+    /// interval after it, and finally the end of the schedule
+    pub fn unlock_times(&self) -> impl Iterator<Item = i64> + '_ {
+        let interval = self.release_interval.max(1);
+        // First whole interval at or after the cliff
+        let first_step = (self.cliff_duration + interval - 1) / interval;
+        let cliff = (self.cliff_duration > 0).then_some(self.cliff_duration);
+        let steps = (first_step.max(1)..)
+            .map(move |step| step.saturating_mul(interval))
+            .take_while(move |offset| *offset < self.duration);
+
+        cliff
+            .into_iter()
+            .chain(steps.filter(move |offset| Some(*offset) != cliff))
+            .chain(std::iter::once(self.duration))
+            .map(move |offset| self.start_time.saturating_add(offset))
+    }
+}
+
+/// Pause bookkeeping for a vesting schedule
+///
+/// While paused the schedule's clock stands still; after resuming, every later
+/// unlock happens as much later as the schedule was paused.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct VestingPause {
+    /// Time the current pause started, while paused
+    pub paused_at: Option<i64>,
+    /// Total seconds spent in completed pauses
+    pub paused_duration: i64,
+}
+
+impl VestingPause {
+    /// Whether the schedule is paused
+    pub fn is_paused(&self) -> bool {
+        self.paused_at.is_some()
+    }
+
+    /// Seconds the schedule has been paused in total as of `time`
+    pub fn delay(&self, time: i64) -> i64 {
+        let current = self.paused_at.map_or(0, |paused_at| time.saturating_sub(paused_at).max(0));
+        self.paused_duration.saturating_add(current)
+    }
+
+    /// Schedule time at `time`: `time` minus every second spent paused
+    pub fn accrual_time(&self, time: i64) -> i64 {
+        time.saturating_sub(self.delay(time))
+    }
+
+    /// End the current pause at `time`, returning its length
+    pub fn resume(&mut self, time: i64) -> Option<i64> {
+        let paused_for = time.saturating_sub(self.paused_at?).max(0);
+        self.paused_duration = self.paused_duration.checked_add(paused_for)?;
+        self.paused_at = None;
+        Some(paused_for)
+    }
+}
+
+/// Vesting grant of a single beneficiary
+///
+/// Each grant lives in its own PDA (["vesting_grant", vesting, beneficiary]), so a
+/// vesting account has no beneficiary cap and a release only loads one grant.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct VestingGrant {
+    /// Is initialized
+    pub is_initialized: bool,
+    /// Vesting account the grant belongs to
+    pub vesting: Pubkey,
+    /// Beneficiary public key
+    pub beneficiary: Pubkey,
+    /// Total amount of tokens to vest
+    pub total_amount: u64,
+    /// Amount of tokens already released
+    pub released_amount: u64,
+    /// Schedule time the grant was revoked at; vesting stops accruing at this point
+    pub revoked_at: Option<i64>,
+    /// Custom terms for this beneficiary; None follows the vesting account's schedule
+    pub schedule: Option<VestingSchedule>,
+    /// Key proposed to take over the grant, pending its acceptance
+    pub pending_beneficiary: Option<Pubkey>,
+    /// Token account releases must be sent to (the beneficiary's ATA unless they registered another)
+    pub destination: Pubkey,
+    /// Token-2022 NFT representing the grant, once minted
+    pub receipt_mint: Option<Pubkey>,
+    /// Whether the receipt can be traded; its holder can then take over the grant
+    pub receipt_transferable: bool,
+    /// Pauses of this grant alone, timed on the vesting account's schedule clock
+    pub pause: VestingPause,
+    /// Time of the grant's most recent release (0 before the first)
+    pub last_release_time: i64,
+}
+
+impl VestingGrant {
+    /// Get the serialized size of the grant account
+    pub fn get_size() -> usize {
+        // is_initialized, vesting, beneficiary, total/released amounts
+        1 + 32 + 32 + 8 + 8
+            // revoked_at, schedule, pending_beneficiary, destination
+            + (1 + 8) + (1 + 32) + (1 + 32) + 32
+            // receipt_mint, receipt_transferable
+            + (1 + 32) + 1
+            // pause, last_release_time
+            + (1 + 8) + 8 + 8
+    }
+
+    /// Address of a beneficiary's grant account for `vesting`
+    pub fn find_address(program_id: &Pubkey, vesting: &Pubkey, beneficiary: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"vesting_grant", vesting.as_ref(), beneficiary.as_ref()], program_id)
+    }
+
+    /// Address of the receipt NFT mint for the grant stored at `grant`
+    pub fn find_receipt_mint(program_id: &Pubkey, grant: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"grant_receipt", grant.as_ref()], program_id)
+    }
+
+    /// Whether the grant is controlled by whoever holds its tradable receipt
+    pub fn has_transferable_receipt(&self) -> bool {
+        self.receipt_mint.is_some() && self.receipt_transferable
+    }
+
+    /// Whether the authority has revoked this grant
+    pub fn is_revoked(&self) -> bool {
+        self.revoked_at.is_some()
+    }
+
+    /// The schedule this grant follows: its own terms, or the vesting account's shared schedule
+    pub fn effective_schedule(&self, shared: &VestingSchedule) -> VestingSchedule {
+        self.schedule.unwrap_or(*shared)
+    }
+
+    /// Calculate the total amount vested by `current_time`, released or not.
+    /// For a revoked grant the schedule is frozen at the revocation time.
+    pub fn vested_amount(&self, current_time: i64, shared: &VestingSchedule) -> Result<u64, ProgramError> {
+        let current_time = match self.revoked_at {
+            Some(revoked_at) => current_time.min(revoked_at),
+            None => current_time,
+        };
+
+        let schedule = self.effective_schedule(shared);
+        if !schedule.is_valid() {
+            return Err(ProgramError::InvalidArgument);
+        }
+        schedule.vested_amount(self.total_amount, current_time)
+    }
+
+    /// Calculate the amount of tokens that should be released based on current time
+    pub fn calculate_released_amount(&mut self, current_time: i64, shared: &VestingSchedule) -> Result<u64, ProgramError> {
+        let capped_releasable = self.vested_amount(current_time, shared)?;
+        
+        // Calculate unreleased amount
+        let unreleased = capped_releasable.checked_sub(self.released_amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+            
+        Ok(unreleased)
+    }
+
+    /// Unlock timeline of the grant as (unlock_time, amount) tranches.
+    /// Revoked grants end at the last tranche unlocked before the revocation.
+    pub fn schedule_preview(&self, shared: &VestingSchedule) -> Result<Vec<(i64, u64)>, ProgramError> {
+        let mut tranches = Vec::new();
+        let mut previously_vested = 0u64;
+        for unlock_time in self.effective_schedule(shared).unlock_times() {
+            let vested = self.vested_amount(unlock_time, shared)?;
+            if vested > previously_vested {
+                tranches.push((unlock_time, vested - previously_vested));
+                previously_vested = vested;
+            }
+        }
+        Ok(tranches)
+    }
+}
+
+/// Vesting state
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct VestingState {
+    /// Is initialized
+    pub is_initialized: bool,
+    /// Authority that can modify the vesting schedule
+    pub authority: Pubkey,
+    /// Mint address for the token
+    pub mint: Pubkey,
+    /// Token account holding this pool's tokens (ATA of the ["vesting_escrow", vesting] PDA)
+    pub escrow: Pubkey,
+    /// Total tokens to be vested
+    pub total_tokens: u64,
+    /// Total tokens allocated to beneficiaries
+    pub total_allocated: u64,
+    /// Total tokens released
+    pub total_released: u64,
+    /// Vesting start timestamp
+    pub start_time: i64,
+    /// Release interval in seconds
+    pub release_interval: i64,
+    /// Number of releases
+    pub num_releases: u8,
+    /// Number of beneficiaries (grant accounts)
+    pub num_beneficiaries: u32,
+    /// Lamports paid to a third party cranking a release, from lamports deposited
+    /// into the vesting account above its rent-exempt minimum (0 disables)
+    pub crank_tip_lamports: u64,
+    /// Whether grant receipts minted from now on can be traded
+    pub receipts_transferable: bool,
+    /// Pauses of the whole vesting account
+    pub pause: VestingPause,
+}
+
+impl VestingState {
+    /// Get the size of the vesting state
+    pub fn get_size() -> usize {
+        // is_initialized, authority, mint, escrow
+        1 + 32 + 32 + 32
+            // total tokens, allocated, released
+            + 8 * 3
+            // start_time, release_interval, num_releases, num_beneficiaries
+            + 8 + 8 + 1 + 4
+            // crank_tip_lamports, receipts_transferable
+            + 8 + 1
+            // pause
+            + (1 + 8) + 8
+    }
+
+    /// Allocated tokens not yet released, which the escrow must hold
+    pub fn outstanding_allocation(&self) -> Option<u64> {
+        self.total_allocated.checked_sub(self.total_released)
+    }
+
+    /// Address of the PDA that owns a pool's escrow token account
+    pub fn find_escrow_authority(program_id: &Pubkey, vesting: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"vesting_escrow", vesting.as_ref()], program_id)
+    }
+
+    /// Schedule time of `grant` at `current_time`, with pauses of both the vesting
+    /// account and the grant taken out
+    pub fn accrual_time(&self, grant: &VestingGrant, current_time: i64) -> i64 {
+        grant.pause.accrual_time(self.pause.accrual_time(current_time))
+    }
+
+    /// Tokens of `grant` releasable at `current_time`: vested (after pauses and
+    /// revocation) minus already released
+    pub fn claimable_at(&self, current_time: i64, grant: &VestingGrant) -> Result<u64, ProgramError> {
+        let vested = grant.vested_amount(self.accrual_time(grant, current_time), &self.shared_schedule())?;
+        vested
+            .checked_sub(grant.released_amount)
+            .ok_or(ProgramError::ArithmeticOverflow)
+    }
+
+    /// Record a release of `amount` tokens from `grant` at `current_time`.
+    ///
+    /// All per-release bookkeeping lives on the grant; the vesting account only
+    /// keeps the pool total, so releases for one beneficiary never affect another's.
+    pub fn record_release(&mut self, grant: &mut VestingGrant, amount: u64, current_time: i64) -> Result<(), ProgramError> {
+        if amount > self.claimable_at(current_time, grant)? {
+            return Err(ProgramError::InsufficientFunds);
+        }
+        grant.released_amount = grant.released_amount
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        grant.last_release_time = current_time;
+        self.total_released = self.total_released
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Time at which `grant` reaches `schedule_time`, assuming no pause ends or
+    /// starts after `current_time`
+    pub fn unlock_time(&self, grant: &VestingGrant, schedule_time: i64, current_time: i64) -> i64 {
+        let pool_time = self.pause.accrual_time(current_time);
+        schedule_time
+            .saturating_add(grant.pause.delay(pool_time))
+            .saturating_add(self.pause.delay(current_time))
+    }
+
+    /// The schedule beneficiaries without custom terms follow:
+    /// `num_releases` equal releases, one every `release_interval` from `start_time`
+    pub fn shared_schedule(&self) -> VestingSchedule {
+        VestingSchedule {
+            start_time: self.start_time,
+            cliff_duration: 0,
+            duration: self.release_interval.saturating_mul(self.num_releases as i64),
+            release_interval: self.release_interval,
+        }
+    }
+}
+
+/// Vesting pool listed in a registry
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct VestingPoolEntry {
+    /// Vesting state account of the pool
+    pub vesting: Pubkey,
+    /// Pool label (e.g. "team", "advisors", "ecosystem")
+    pub label: String,
+}
+
+/// Registry of an authority's vesting pools for a mint
+///
+/// Lives at the PDA ["vesting_registry", mint, authority]; every pool created by
+/// the authority for the mint is listed here, so clients can find all of them.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct VestingRegistry {
+    /// Is initialized
+    pub is_initialized: bool,
+    /// Authority whose pools are listed
+    pub authority: Pubkey,
+    /// Mint the pools vest
+    pub mint: Pubkey,
+    /// Registered pools
+    pub pools: Vec<VestingPoolEntry>,
+}
+
+impl VestingRegistry {
+    /// Get the serialized size of the registry account
+    pub fn get_size() -> usize {
+        // is_initialized, authority, mint
+        1 + 32 + 32
+            // pools: length prefix, then vesting + label per pool
+            + 4 + MAX_VESTING_POOLS * (32 + 4 + MAX_VESTING_POOL_LABEL_LEN)
+    }
+
+    /// Address of an authority's registry for `mint`
+    pub fn find_address(program_id: &Pubkey, mint: &Pubkey, authority: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"vesting_registry", mint.as_ref(), authority.as_ref()], program_id)
+    }
+}
+
+/// Token metadata
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct TokenMetadata {
+    /// Is initialized
+    pub is_initialized: bool,
+    /// Authority that can modify the metadata; holds no token powers
+    pub authority: Pubkey,
+    /// Mint address for the token
+    pub mint: Pubkey,
+    /// Name of the token
+    pub name: String,
+    /// Symbol of the token
+    pub symbol: String,
+    /// URI for the token
+    pub uri: String,
+    /// Last updated timestamp
+    pub last_updated_timestamp: i64,
+    /// SHA-256 of the document at `uri`, so clients can check what they fetched
+    pub uri_hash: Option<[u8; 32]>,
+    /// Key proposed by `UpdateMetadataAuthority`, which takes over once it accepts
+    pub pending_authority: Option<Pubkey>,
+    /// How wallets should present the token, set by `SetMetadataDisplayHints`
+    pub display_hints: Option<MetadataDisplayHints>,
+}
+
+/// Presentation hints for wallet integrations; they never change token amounts
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MetadataDisplayHints {
+    /// Decimals to show balances with, at most the mint's decimals
+    pub display_decimals: Option<u8>,
+    /// Other tickers the token is listed under (ASCII letters and digits)
+    pub ticker_aliases: Vec<String>,
+}
+
+impl MetadataDisplayHints {
+    /// Serialized size of the largest hints
+    pub fn get_size() -> usize {
+        // display_decimals, vec length prefix, then each alias with its length
+        2 + 4 + MAX_TICKER_ALIASES * (4 + MAX_TICKER_ALIAS_LEN)
+    }
+
+    /// Whether the display decimals fit the mint and the aliases are at most
+    /// `MAX_TICKER_ALIASES` distinct alphanumeric tickers of up to
+    /// `MAX_TICKER_ALIAS_LEN` bytes
+    pub fn is_valid(&self, mint_decimals: u8) -> bool {
+        let aliases = &self.ticker_aliases;
+        self.display_decimals.unwrap_or(mint_decimals) <= mint_decimals
+            && aliases.len() <= MAX_TICKER_ALIASES
+            && aliases.iter().all(|alias| {
+                (1..=MAX_TICKER_ALIAS_LEN).contains(&alias.len())
+                    && alias.bytes().all(|byte| byte.is_ascii_alphanumeric())
+            })
+            && aliases.iter().enumerate().all(|(i, alias)| {
+                !aliases[..i].iter().any(|other| other.eq_ignore_ascii_case(alias))
+            })
+    }
+}
+
+/// Program-owned escrow that part of the initial supply is minted into
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AllocationEscrow {
+    /// Escrow of the vesting pool that `InitializeVesting` later creates with the
+    /// `vesting` keypair
+    Vesting { vesting: Pubkey },
+    /// Supply treasury, paid out by `DistributeSupplyTreasury`
+    SupplyTreasury,
+    /// Liquidity escrow, released by `ReleaseLiquidityEscrow`
+    Liquidity,
+}
+
+impl AllocationEscrow {
+    /// PDA owning the escrow's token account (the owner's Token-2022 ATA)
+    pub fn find_owner(&self, program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+        match self {
+            Self::Vesting { vesting } => VestingState::find_escrow_authority(program_id, vesting),
+            Self::SupplyTreasury => AutonomousSupplyController::find_supply_treasury(program_id, mint),
+            Self::Liquidity => Self::find_liquidity_escrow(program_id, mint),
+        }
+    }
+
+    /// PDA owning a mint's liquidity escrow
+    pub fn find_liquidity_escrow(program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"liquidity_escrow", mint.as_ref()], program_id)
+    }
+}
+
+/// Share of the initial supply minted into one escrow
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InitialAllocation {
+    /// Escrow receiving the tokens
+    pub escrow: AllocationEscrow,
+    /// Tokens minted into it
+    pub amount: u64,
+}
+
+impl InitialAllocation {
+    /// Whether `allocations` splits exactly `initial_supply` into at most
+    /// `MAX_INITIAL_ALLOCATIONS` non-zero amounts, each for a different escrow
+    pub fn table_is_valid(allocations: &[Self], initial_supply: u64) -> bool {
+        let total = allocations
+            .iter()
+            .try_fold(0u64, |total, allocation| total.checked_add(allocation.amount));
+        allocations.len() <= MAX_INITIAL_ALLOCATIONS
+            && total == Some(initial_supply)
+            && allocations.iter().all(|allocation| allocation.amount > 0)
+            && allocations.iter().enumerate().all(|(i, allocation)| {
+                !allocations[..i].iter().any(|other| other.escrow == allocation.escrow)
+            })
+    }
+}
+
+/// What part of the initial supply an allocation plan reserves tokens for
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AllocationCategory {
+    /// Tokens sold by `InitializePresale` (up to the hard cap)
+    Presale,
+    /// Team, investor and advisor vesting pools
+    Team,
+    /// Supply treasury
+    Treasury,
+    /// Liquidity escrow
+    Liquidity,
+}
+
+impl AllocationEscrow {
+    /// Plan category an escrow's tokens are drawn from
+    pub fn category(&self) -> AllocationCategory {
+        match self {
+            Self::Vesting { .. } => AllocationCategory::Team,
+            Self::SupplyTreasury => AllocationCategory::Treasury,
+            Self::Liquidity => AllocationCategory::Liquidity,
+        }
+    }
+}
+
+/// Percentage of the initial supply an `InitializeToken` plan gives one category
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AllocationShare {
+    /// Category receiving the share
+    pub category: AllocationCategory,
+    /// Share of the initial supply in basis points
+    pub bps: u16,
+}
+
+/// One category of an allocation plan and how much of it is already spoken for
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PlannedAllocation {
+    /// Category of the share
+    pub category: AllocationCategory,
+    /// Share of the initial supply in basis points
+    pub bps: u16,
+    /// Tokens subsystem initializations have drawn against the share so far
+    pub drawn: u64,
+}
+
+/// How a mint's initial supply is split between presale, team, treasury and
+/// liquidity. Recorded by `InitializeToken`; `InitializePresale`, `InitializeVesting`
+/// and the initial allocation table draw against it, so no category can be given
+/// more than its share.
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Eq)]
+pub struct AllocationPlan {
+    /// Is the plan initialized
+    pub is_initialized: bool,
+    /// Mint the plan belongs to
+    pub mint: Pubkey,
+    /// Supply the shares are percentages of
+    pub initial_supply: u64,
+    /// Shares with their drawn amounts
+    pub allocations: Vec<PlannedAllocation>,
+}
+
+impl AllocationPlan {
+    /// Size of a plan account
+    pub fn get_size() -> usize {
+        1 + 32 + 8 + 4 + MAX_ALLOCATION_PLAN_SHARES * (1 + 2 + 8)
+    }
+
+    /// Plan PDA of a mint
+    pub fn find_address(program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"allocation_plan", mint.as_ref()], program_id)
+    }
+
+    /// Whether `shares` names each category at most once, gives every one a non-zero
+    /// share and adds up to exactly 100%
+    pub fn shares_are_valid(shares: &[AllocationShare]) -> bool {
+        let total = shares.iter().map(|share| share.bps as u64).sum::<u64>();
+        shares.len() <= MAX_ALLOCATION_PLAN_SHARES
+            && total == crate::amounts::BPS_DENOMINATOR
+            && shares.iter().all(|share| share.bps > 0)
+            && shares.iter().enumerate().all(|(i, share)| {
+                !shares[..i].iter().any(|other| other.category == share.category)
+            })
+    }
+
+    /// New plan for `initial_supply` with nothing drawn
+    pub fn new(mint: Pubkey, initial_supply: u64, shares: &[AllocationShare]) -> Self {
+        Self {
+            is_initialized: true,
+            mint,
+            initial_supply,
+            allocations: shares
+                .iter()
+                .map(|share| PlannedAllocation { category: share.category, bps: share.bps, drawn: 0 })
+                .collect(),
+        }
+    }
+
+    /// Tokens the plan gives `category` in total (zero if it has no share)
+    pub fn allowance(&self, category: AllocationCategory) -> u64 {
+        self.allocations
+            .iter()
+            .find(|allocation| allocation.category == category)
+            .and_then(|allocation| TokenAmount(self.initial_supply).apply_bps(Bps(allocation.bps)))
+            .map_or(0, |amount| amount.0)
+    }
+
+    /// Tokens of `category` not yet drawn
+    pub fn remaining(&self, category: AllocationCategory) -> u64 {
+        let drawn = self
+            .allocations
+            .iter()
+            .find(|allocation| allocation.category == category)
+            .map_or(0, |allocation| allocation.drawn);
+        self.allowance(category).saturating_sub(drawn)
+    }
+
+    /// Draws `amount` from `category`, failing if that would exceed its share
+    pub fn draw(&mut self, category: AllocationCategory, amount: u64) -> Result<(), ProgramError> {
+        if amount > self.remaining(category) {
+            return Err(VCoinError::AllocationPlanExceeded.into());
+        }
+        if let Some(allocation) = self.allocations.iter_mut().find(|allocation| allocation.category == category) {
+            allocation.drawn += amount;
+        }
+        Ok(())
+    }
+}
+
+impl TokenMetadata {
+    /// Get the size of the token metadata with string allocations
+    pub fn get_size(name_len: usize, symbol_len: usize, uri_len: usize) -> usize {
+        std::mem::size_of::<Self>() - 24 // Subtract the String pointer sizes
+            + 4 + name_len               // Add the actual string size with length
+            + 4 + symbol_len             // Add the actual string size with length
+            + 4 + uri_len                // Add the actual string size with length
+            + MetadataDisplayHints::get_size() // Room for the largest display hints
+    }
+
+    /// Whether `uri` is empty or a printable-ASCII `https`, `ipfs` or `ar` URI of at
+    /// most `MAX_METADATA_URI_LEN` bytes; other characters must be percent-encoded
+    pub fn uri_is_valid(uri: &str) -> bool {
+        if uri.is_empty() {
+            return true;
+        }
+        uri.len() <= MAX_METADATA_URI_LEN
+            && uri.bytes().all(|byte| byte.is_ascii_graphic())
+            && METADATA_URI_SCHEMES
+                .iter()
+                .any(|scheme| uri.strip_prefix(scheme).is_some_and(|rest| !rest.is_empty()))
+    }
+}
+
+/// Where the autonomous supply controller reads the token price from
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum SupplyPriceSource {
+    /// `price_oracle` is a single price account read directly
+    #[default]
+    DirectOracle,
+    /// `price_oracle` is a MultiOracleController; mint and burn use its consensus price
+    MultiOracleController,
+}
+
+/// Mint and burn thresholds and rates of an autonomous supply controller (basis points)
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SupplyParameters {
+    /// Minimum annual growth for minting
+    pub min_growth_for_mint_bps: u16,
+    /// Minimum annual decline for burning
+    pub min_decline_for_burn_bps: u16,
+    /// Mint rate between the minimum and high growth thresholds
+    pub medium_growth_mint_rate_bps: u16,
+    /// Mint rate from the high growth threshold
+    pub high_growth_mint_rate_bps: u16,
+    /// Burn rate between the minimum and high decline thresholds
+    pub medium_decline_burn_rate_bps: u16,
+    /// Burn rate from the high decline threshold
+    pub high_decline_burn_rate_bps: u16,
+    /// High growth threshold
+    pub high_growth_threshold_bps: u16,
+    /// High decline threshold
+    pub high_decline_threshold_bps: u16,
+    /// Growth required to mint once supply reaches the high supply threshold
+    pub extreme_growth_threshold_bps: u16,
+    /// Extreme decline threshold for post-cap rules
+    pub extreme_decline_threshold_bps: u16,
+    /// Mint rate above the high supply threshold
+    pub post_cap_mint_rate_bps: u16,
+    /// Burn rate above the high supply threshold
+    pub post_cap_burn_rate_bps: u16,
+}
+
+impl SupplyParameters {
+    /// Whether thresholds are non-zero and ordered (minimum, high, extreme; a decline
+    /// of at most 100%) and rates are ordered and at most `MAX_SUPPLY_RATE_BPS`
+    pub fn is_valid(&self) -> bool {
+        let growth_thresholds = 0 < self.min_growth_for_mint_bps
+            && self.min_growth_for_mint_bps <= self.high_growth_threshold_bps
+            && self.high_growth_threshold_bps <= self.extreme_growth_threshold_bps;
+        let decline_thresholds = 0 < self.min_decline_for_burn_bps
+            && self.min_decline_for_burn_bps <= self.high_decline_threshold_bps
+            && self.high_decline_threshold_bps <= self.extreme_decline_threshold_bps
+            && self.extreme_decline_threshold_bps <= 10_000;
+        let rates = [
+            self.medium_growth_mint_rate_bps,
+            self.high_growth_mint_rate_bps,
+            self.medium_decline_burn_rate_bps,
+            self.high_decline_burn_rate_bps,
+            self.post_cap_mint_rate_bps,
+            self.post_cap_burn_rate_bps,
+        ]
+        .iter()
+        .all(|rate| *rate <= MAX_SUPPLY_RATE_BPS)
+            && self.medium_growth_mint_rate_bps <= self.high_growth_mint_rate_bps
+            && self.medium_decline_burn_rate_bps <= self.high_decline_burn_rate_bps;
+
+        growth_thresholds && decline_thresholds && rates
+    }
+}
+
+/// Supply parameters waiting out `SUPPLY_PARAMETERS_TIMELOCK`
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PendingSupplyParameters {
+    /// Parameters to apply
+    pub parameters: SupplyParameters,
+    /// First time an evaluation uses them
+    pub effective_at: i64,
+}
+
+/// Direction of an autonomous supply change
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SupplyOperation {
+    Mint,
+    Burn,
+}
+
+/// Per-window caps on autonomous mints and burns, so a manipulated or faulty
+/// price cannot move supply far before anyone can react
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SupplyRateLimit {
+    /// Length of a window in seconds
+    pub window_seconds: i64,
+    /// Most tokens minted, and separately burned, in one window
+    pub max_amount_per_window: u64,
+    /// Most mints and burns together in one window
+    pub max_operations_per_window: u8,
+    /// Start of the current window
+    pub window_start: i64,
+    /// Tokens minted in the current window
+    pub minted_this_epoch: u64,
+    /// Tokens burned in the current window
+    pub burned_this_epoch: u64,
+    /// Mints and burns in the current window
+    pub operations_this_epoch: u8,
+}
+
+impl SupplyRateLimit {
+    /// Limit with an empty window starting at `current_time`
+    pub fn new(window_seconds: i64, max_amount_per_window: u64, max_operations_per_window: u8, current_time: i64) -> Self {
+        Self {
+            window_seconds,
+            max_amount_per_window,
+            max_operations_per_window,
+            window_start: current_time,
+            minted_this_epoch: 0,
+            burned_this_epoch: 0,
+            operations_this_epoch: 0,
+        }
+    }
+
+    /// Whether the window length is within bounds and the caps allow anything at all
+    pub fn is_valid(&self) -> bool {
+        (MIN_SUPPLY_RATE_LIMIT_WINDOW..=MAX_SUPPLY_RATE_LIMIT_WINDOW).contains(&self.window_seconds)
+            && self.max_amount_per_window > 0
+            && self.max_operations_per_window > 0
+    }
+
+    /// Tokens `operation` may still move in the window containing `current_time`
+    /// (starting a new window once the current one has ended); zero once the
+    /// window's operations are used up
+    pub fn remaining(&mut self, operation: SupplyOperation, current_time: i64) -> u64 {
+        if current_time >= self.window_start.saturating_add(self.window_seconds) {
+            *self = Self::new(self.window_seconds, self.max_amount_per_window, self.max_operations_per_window, current_time);
+        }
+        if self.operations_this_epoch >= self.max_operations_per_window {
+            return 0;
+        }
+        let used = match operation {
+            SupplyOperation::Mint => self.minted_this_epoch,
+            SupplyOperation::Burn => self.burned_this_epoch,
+        };
+        self.max_amount_per_window.saturating_sub(used)
+    }
+
+    /// Count an executed mint or burn against the current window
+    pub fn record(&mut self, operation: SupplyOperation, amount: u64) {
+        let used = match operation {
+            SupplyOperation::Mint => &mut self.minted_this_epoch,
+            SupplyOperation::Burn => &mut self.burned_this_epoch,
+        };
+        *used = used.saturating_add(amount);
+        self.operations_this_epoch = self.operations_this_epoch.saturating_add(1);
+    }
+}
+
+/// Where `DistributeSupplyTreasury` sends autonomously minted tokens, as shares of
+/// the treasury balance adding up to 100%
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SupplyDistribution {
+    /// Staking rewards token account
+    pub staking_rewards: Pubkey,
+    /// Share sent to staking rewards (in basis points)
+    pub staking_rewards_bps: u16,
+    /// Liquidity token account
+    pub liquidity: Pubkey,
+    /// Share sent to liquidity (in basis points)
+    pub liquidity_bps: u16,
+    /// Operations token account; also receives the rounding remainder
+    pub operations: Pubkey,
+    /// Share sent to operations (in basis points)
+    pub operations_bps: u16,
+}
+
+impl SupplyDistribution {
+    /// Whether the shares add up to exactly 100%
+    pub fn is_valid(&self) -> bool {
+        u32::from(self.staking_rewards_bps) + u32::from(self.liquidity_bps) + u32::from(self.operations_bps) == 10_000
+    }
+
+    /// Staking rewards, liquidity and operations amounts of `amount`
+    pub fn split(&self, amount: u64) -> Option<[u64; 3]> {
+        let staking_rewards = TokenAmount(amount).apply_bps(Bps(self.staking_rewards_bps))?.0;
+        let liquidity = TokenAmount(amount).apply_bps(Bps(self.liquidity_bps))?.0;
+        let operations = amount.checked_sub(staking_rewards)?.checked_sub(liquidity)?;
+        Some([staking_rewards, liquidity, operations])
+    }
+}
+
+/// State of the multi-oracle controller an autonomous mint or burn was priced with
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SupplyOracleHealth {
+    /// Controller health score (0-100)
+    pub health_score: u8,
+    /// Sources behind the last consensus
+    pub contributing_oracles: u8,
+    /// Confidence of the last consensus (micro-USD)
+    pub confidence: u64,
+    /// Whether the controller's emergency price was in force
+    pub is_emergency_price: bool,
+}
+
+/// One autonomous mint or burn
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SupplyActionRecord {
+    /// Time of the action
+    pub timestamp: i64,
+    /// Mint or burn
+    pub operation: SupplyOperation,
+    /// Tokens minted or burned
+    pub amount: u64,
+    /// Price the action was evaluated at (micro-USD)
+    pub trigger_price: u64,
+    /// Price at the start of the evaluation year (micro-USD)
+    pub year_start_price: u64,
+    /// Supply after the action
+    pub supply_after: u64,
+    /// Oracle controller state, or None when priced from a direct oracle account
+    pub oracle_health: Option<SupplyOracleHealth>,
+}
+
+/// Ring of the latest autonomous mints and burns of a supply controller, so its
+/// monetary history can be audited on chain; stored in a PDA derived from
+/// `[b"supply_ledger", controller]`
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct SupplyActionLedger {
+    /// Is initialized
+    pub is_initialized: bool,
+    /// Autonomous supply controller recorded
+    pub controller: Pubkey,
+    /// Actions recorded since the ledger was created
+    pub total_actions: u64,
+    /// Up to `MAX_SUPPLY_ACTION_RECORDS` actions; once full, `total_actions % MAX_SUPPLY_ACTION_RECORDS`
+    /// is the oldest and is overwritten next
+    pub records: Vec<SupplyActionRecord>,
+}
+
+impl SupplyActionLedger {
+    /// Create an empty ledger for a controller
+    pub fn new(controller: Pubkey) -> Self {
+        Self {
+            is_initialized: true,
+            controller,
+            total_actions: 0,
+            records: Vec::new(),
+        }
+    }
+
+    /// Get the serialized size of a full ledger
+    pub fn get_size() -> usize {
+        // timestamp, operation, amount, trigger/year-start price, supply_after,
+        // then the optional health score, sources, confidence and emergency flag
+        let record_size = 8 + 1 + 8 * 4 + 1 + (1 + 1 + 8 + 1);
+        // is_initialized, controller, total_actions, vec length prefix
+        1 + 32 + 8 + 4 + record_size * MAX_SUPPLY_ACTION_RECORDS
+    }
+
+    /// Address of a controller's ledger
+    pub fn find_address(program_id: &Pubkey, controller: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"supply_ledger", controller.as_ref()], program_id)
+    }
+
+    /// Append an action, overwriting the oldest once the ledger is full
+    pub fn record(&mut self, record: SupplyActionRecord) {
+        if self.records.len() < MAX_SUPPLY_ACTION_RECORDS {
+            self.records.push(record);
+        } else {
+            let slot = (self.total_actions % MAX_SUPPLY_ACTION_RECORDS as u64) as usize;
+            self.records[slot] = record;
+        }
+        self.total_actions = self.total_actions.saturating_add(1);
+    }
+
+    /// Recorded actions, oldest first
+    pub fn history(&self) -> Vec<SupplyActionRecord> {
+        let mut records = self.records.clone();
+        if records.len() == MAX_SUPPLY_ACTION_RECORDS {
+            records.rotate_left((self.total_actions % MAX_SUPPLY_ACTION_RECORDS as u64) as usize);
+        }
+        records
+    }
+}
+
+/// How tokens recorded in a burn log were burned
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BurnKind {
+    /// Burned from the burn treasury by the supply controller
+    Autonomous,
+    /// Burned by their holder with `BurnTokens`
+    Manual,
+}
+
+/// One burn of a mint's tokens
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BurnRecord {
+    /// Time of the burn
+    pub timestamp: i64,
+    /// Slot of the burn, to find its transaction
+    pub slot: u64,
+    /// Autonomous or manual
+    pub kind: BurnKind,
+    /// Token account the tokens were burned from
+    pub source: Pubkey,
+    /// Tokens burned
+    pub amount: u64,
+    /// Supply controller price at the burn (micro-USD)
+    pub price: u64,
+}
+
+/// Proof of burn for a mint: running totals of every autonomous and manual burn
+/// plus a ring of the latest ones; stored in a PDA derived from `[b"burn_log", mint]`
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct BurnLog {
+    /// Is initialized
+    pub is_initialized: bool,
+    /// Mint whose burns are logged
+    pub mint: Pubkey,
+    /// Tokens burned since the log was created
+    pub total_burned: u64,
+    /// Burns recorded since the log was created
+    pub total_burns: u64,
+    /// Up to `MAX_BURN_LOG_RECORDS` burns; once full, `total_burns % MAX_BURN_LOG_RECORDS`
+    /// is the oldest and is overwritten next
+    pub records: Vec<BurnRecord>,
+}
+
+impl BurnLog {
+    /// Create an empty log for a mint
+    pub fn new(mint: Pubkey) -> Self {
+        Self {
+            is_initialized: true,
+            mint,
+            total_burned: 0,
+            total_burns: 0,
+            records: Vec::new(),
+        }
+    }
+
+    /// Get the serialized size of a full log
+    pub fn get_size() -> usize {
+        // timestamp, slot, kind, source, amount, price
+        let record_size = 8 + 8 + 1 + 32 + 8 + 8;
+        // is_initialized, mint, total_burned, total_burns, vec length prefix
+        1 + 32 + 8 + 8 + 4 + record_size * MAX_BURN_LOG_RECORDS
+    }
+
+    /// Address of a mint's burn log
+    pub fn find_address(program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"burn_log", mint.as_ref()], program_id)
+    }
+
+    /// Append a burn and add it to the totals, overwriting the oldest record once full
+    pub fn record(&mut self, record: BurnRecord) {
+        if self.records.len() < MAX_BURN_LOG_RECORDS {
+            self.records.push(record);
+        } else {
+            let slot = (self.total_burns % MAX_BURN_LOG_RECORDS as u64) as usize;
+            self.records[slot] = record;
+        }
+        self.total_burns = self.total_burns.saturating_add(1);
+        self.total_burned = self.total_burned.saturating_add(record.amount);
+    }
+
+    /// Recorded burns, oldest first
+    pub fn history(&self) -> Vec<BurnRecord> {
+        let mut records = self.records.clone();
+        if records.len() == MAX_BURN_LOG_RECORDS {
+            records.rotate_left((self.total_burns % MAX_BURN_LOG_RECORDS as u64) as usize);
+        }
+        records
+    }
+}
+
+/// Most lamports a fee sponsorship may reimburse per claim (a few signatures plus a priority fee)
+pub const MAX_SPONSORED_CLAIM_LAMPORTS: u64 = 100_000;
+
+/// Lamports set aside to reimburse a keeper that pays the transaction fees of
+/// other users' refund and vesting claims; stored in a PDA derived from
+/// `[b"fee_sponsorship", mint]`, which holds the budget above its rent
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct FeeSponsorship {
+    /// Is initialized
+    pub is_initialized: bool,
+    /// Authority that may change the keeper, cap and budget
+    pub authority: Pubkey,
+    /// Mint whose claims are sponsored
+    pub mint: Pubkey,
+    /// Fee payer reimbursed for sponsored claims
+    pub keeper: Pubkey,
+    /// Most lamports reimbursed for a single claim
+    pub per_claim_lamports: u64,
+    /// Lamports deposited for reimbursements so far
+    pub budget_lamports: u64,
+    /// Lamports reimbursed so far
+    pub spent_lamports: u64,
+    /// Claims reimbursed so far
+    pub claims_sponsored: u64,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl FeeSponsorship {
+    /// Get the account size
+    pub fn get_size() -> usize {
+        // is_initialized, authority, mint, keeper, four counters, bump
+        1 + 32 + 32 + 32 + 8 * 4 + 1
+    }
+
+    /// Address of a mint's fee sponsorship
+    pub fn find_address(program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"fee_sponsorship", mint.as_ref()], program_id)
+    }
+
+    /// Budget not yet reimbursed
+    pub fn remaining_budget(&self) -> u64 {
+        self.budget_lamports.saturating_sub(self.spent_lamports)
+    }
+
+    /// Lamports the next claim is reimbursed: the per-claim cap, or what is left of the budget
+    pub fn next_reimbursement(&self) -> u64 {
+        self.per_claim_lamports.min(self.remaining_budget())
+    }
+
+    /// Count a reimbursed claim against the budget
+    pub fn record_claim(&mut self, lamports: u64) {
+        self.spent_lamports = self.spent_lamports.saturating_add(lamports);
+        self.claims_sponsored = self.claims_sponsored.saturating_add(1);
+    }
+}
+
+/// M-of-N authority: wherever an authority is stored, its address may be set to a
+/// multisig, which then approves an instruction when at least `threshold` of its
+/// keys sign it (passed after the instruction's own accounts)
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct Multisig {
+    /// Is initialized
+    pub is_initialized: bool,
+    /// Key the multisig address is derived from
+    pub base: Pubkey,
+    /// Signatures required
+    pub threshold: u8,
+    /// Registered keys
+    pub signers: Vec<Pubkey>,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl Multisig {
+    /// Get the account size (room for `MAX_MULTISIG_SIGNERS` keys)
+    pub fn get_size() -> usize {
+        // is_initialized, base, threshold, vec length prefix, signers, bump
+        1 + 32 + 1 + 4 + 32 * MAX_MULTISIG_SIGNERS + 1
+    }
+
+    /// Address of the multisig derived from `base`
+    pub fn find_address(program_id: &Pubkey, base: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"multisig", base.as_ref()], program_id)
+    }
+
+    /// Whether `signers` are distinct, at most `MAX_MULTISIG_SIGNERS`, and can reach `threshold`
+    pub fn signers_are_valid(signers: &[Pubkey], threshold: u8) -> bool {
+        let unique = signers.iter().enumerate().all(|(i, signer)| !signers[..i].contains(signer));
+        unique
+            && signers.len() <= MAX_MULTISIG_SIGNERS
+            && threshold > 0
+            && threshold as usize <= signers.len()
+    }
+
+    /// Registered keys among `signers`, each counted once
+    pub fn approving_signers(&self, signers: &[Pubkey]) -> Vec<Pubkey> {
+        self.signers.iter().filter(|signer| signers.contains(signer)).copied().collect()
+    }
+
+    /// Whether `signers` include at least `threshold` registered keys
+    pub fn is_approved(&self, signers: &[Pubkey]) -> bool {
+        self.approving_signers(signers).len() >= self.threshold as usize
     }
 }
 
-/// Vesting state
+/// A sensitive admin call a `Timelock` can queue, with every argument and account
+/// it is bound to; the call later executes only if it matches exactly
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum TimelockedOperation {
+    /// `SetTransferFee` on the timelock's mint
+    SetTransferFee {
+        /// Mint whose transfer fee changes
+        mint: Pubkey,
+        /// Transfer fee basis points
+        transfer_fee_basis_points: u16,
+        /// Maximum fee
+        maximum_fee: u64,
+    },
+    /// `UpdateControllerParameters` on a supply controller
+    UpdateControllerParameters {
+        /// Controller state account
+        controller: Pubkey,
+        /// Parameters to queue, or None to cancel the pending ones
+        parameters: Option<SupplyParameters>,
+    },
+    /// `WithdrawLockedFunds` (or `ExecuteWithdraw`) from a presale's locked treasury
+    WithdrawLockedFunds {
+        /// Presale state account
+        presale: Pubkey,
+        /// Stablecoin withdrawn
+        stablecoin_mint: Pubkey,
+        /// Destination token account
+        destination: Pubkey,
+    },
+    /// `RescueTokens` from a program-owned token account
+    RescueTokens {
+        /// Token account rescued from
+        source: Pubkey,
+        /// Token account credited
+        destination: Pubkey,
+        /// Amount of tokens
+        amount: u64,
+    },
+}
+
+impl TimelockedOperation {
+    /// Largest serialized operation (tag plus `WithdrawLockedFunds`)
+    pub const MAX_SIZE: usize = 1 + 32 * 3;
+}
+
+/// An operation waiting out a timelock's delay
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct QueuedOperation {
+    /// Identifier used to cancel the operation
+    pub id: u64,
+    /// The queued call
+    pub operation: TimelockedOperation,
+    /// Earliest time the call may execute
+    pub executable_at: i64,
+}
+
+impl QueuedOperation {
+    /// Serialized size
+    pub const SIZE: usize = 8 + TimelockedOperation::MAX_SIZE + 8;
+}
+
+/// Delay in front of sensitive admin calls: set a mint's timelock, a PDA derived
+/// from `[b"timelock", mint]`, as the authority of `SetTransferFee`,
+/// `UpdateControllerParameters`, `WithdrawLockedFunds` or `RescueTokens`, and those
+/// calls only run once queued by the proposer and past the delay. The mint's
+/// emergency or program authority can cancel queued calls in the meantime.
 #[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
-pub struct VestingState {
+pub struct Timelock {
     /// Is initialized
     pub is_initialized: bool,
-    /// Authority that can modify the vesting schedule
-    pub authority: Pubkey,
-    /// Mint address for the token
+    /// Mint the timelock belongs to
     pub mint: Pubkey,
-    /// Total tokens to be vested
-    pub total_tokens: u64,
-    /// Total tokens allocated to beneficiaries
-    pub total_allocated: u64,
-    /// Total tokens released
-    pub total_released: u64,
-    /// Vesting start timestamp
-    pub start_time: i64,
-    /// Release interval in seconds
-    pub release_interval: i64,
-    /// Number of releases
-    pub num_releases: u8,
-    /// Last release timestamp
-    pub last_release_time: i64,
-    /// Number of beneficiaries
-    pub num_beneficiaries: u8,
-    /// Beneficiaries
-    pub beneficiaries: Vec<VestingBeneficiary>,
+    /// Authority that queues operations
+    pub proposer: Pubkey,
+    /// Seconds between queueing an operation and its execution
+    pub delay: i64,
+    /// Identifier of the next queued operation
+    pub next_operation_id: u64,
+    /// Operations waiting to execute
+    pub queued: Vec<QueuedOperation>,
+    /// PDA bump seed
+    pub bump: u8,
 }
 
-impl VestingState {
-    /// Get the size of the vesting state
+impl Timelock {
+    /// Get the account size (room for `MAX_TIMELOCK_OPERATIONS` queued operations)
     pub fn get_size() -> usize {
-        let base_size = std::mem::size_of::<Self>() - std::mem::size_of::<Vec<VestingBeneficiary>>();
-        let vec_size = std::mem::size_of::<VestingBeneficiary>() * MAX_VESTING_BENEFICIARIES;
-        base_size + vec_size
+        // is_initialized, mint, proposer, delay, next id, vec length prefix, queue, bump
+        1 + 32 + 32 + 8 + 8 + 4 + QueuedOperation::SIZE * MAX_TIMELOCK_OPERATIONS + 1
+    }
+
+    /// Address of a mint's timelock
+    pub fn find_address(program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"timelock", mint.as_ref()], program_id)
+    }
+
+    /// Queue `operation`, executable `delay` seconds from `current_time`
+    pub fn queue(&mut self, operation: TimelockedOperation, current_time: i64) -> Result<QueuedOperation, ProgramError> {
+        if self.queued.len() >= MAX_TIMELOCK_OPERATIONS {
+            return Err(ProgramError::InvalidArgument);
+        }
+        let executable_at = current_time.checked_add(self.delay).ok_or(VCoinError::CalculationError)?;
+        let queued = QueuedOperation { id: self.next_operation_id, operation, executable_at };
+        self.next_operation_id = self.next_operation_id.saturating_add(1);
+        self.queued.push(queued);
+        Ok(queued)
+    }
+
+    /// Drop a queued operation
+    pub fn cancel(&mut self, id: u64) -> Option<QueuedOperation> {
+        let index = self.queued.iter().position(|queued| queued.id == id)?;
+        Some(self.queued.remove(index))
+    }
+
+    /// Remove and return the earliest queued copy of `operation` once its delay has passed
+    pub fn take_ready(&mut self, operation: &TimelockedOperation, current_time: i64) -> Result<QueuedOperation, VCoinError> {
+        let index = self.queued.iter()
+            .position(|queued| queued.operation == *operation)
+            .ok_or(VCoinError::TimelockOperationNotQueued)?;
+        if current_time < self.queued[index].executable_at {
+            return Err(VCoinError::TimelockDelayNotElapsed);
+        }
+        Ok(self.queued.remove(index))
     }
 }
 
-/// Token metadata
+/// Operational duty an `AccessControl` member can hold
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Role {
+    /// Grants and revokes roles; approves every authority instruction without a narrower role
+    Admin,
+    /// Sets the transfer fee
+    FeeManager,
+    /// Configures oracle controllers and the supply controller's price bounds
+    OracleManager,
+    /// Pauses, resumes, rescues and recovers; cancels timelocked calls
+    EmergencyGuardian,
+    /// Withdraws, deploys and watches the locked treasury; releases the liquidity escrow
+    Treasurer,
+}
+
+/// A role held by one key
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RoleGrant {
+    /// Key holding the role
+    pub member: Pubkey,
+    /// Role held
+    pub role: Role,
+}
+
+/// Role-based authority: set a mint's access control, a PDA derived from
+/// `[b"access_control", mint]`, as a stored authority, and a member holding the
+/// role an instruction requires approves it by signing after the instruction's
+/// own accounts
 #[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
-pub struct TokenMetadata {
+pub struct AccessControl {
     /// Is initialized
     pub is_initialized: bool,
-    /// Authority that can modify the metadata
-    pub authority: Pubkey,
-    /// Mint address for the token
+    /// Mint the access control belongs to
     pub mint: Pubkey,
-    /// Name of the token
-    pub name: String,
-    /// Symbol of the token
-    pub symbol: String,
-    /// URI for the token
-    pub uri: String,
-    /// Last updated timestamp
-    pub last_updated_timestamp: i64,
+    /// Roles held, one entry per key and role
+    pub grants: Vec<RoleGrant>,
+    /// PDA bump seed
+    pub bump: u8,
 }
 
-impl TokenMetadata {
-    /// Get the size of the token metadata with string allocations
-    pub fn get_size(name_len: usize, symbol_len: usize, uri_len: usize) -> usize {
-        std::mem::size_of::<Self>() - 24 // Subtract the String pointer sizes
-            + 4 + name_len               // Add the actual string size with length
-            + 4 + symbol_len             // Add the actual string size with length
-            + 4 + uri_len                // Add the actual string size with length
+impl AccessControl {
+    /// Get the account size (room for `MAX_ROLE_GRANTS` grants)
+    pub fn get_size() -> usize {
+        // is_initialized, mint, vec length prefix, grants, bump
+        1 + 32 + 4 + (32 + 1) * MAX_ROLE_GRANTS + 1
+    }
+
+    /// Address of a mint's access control
+    pub fn find_address(program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"access_control", mint.as_ref()], program_id)
+    }
+
+    /// Whether `member` holds `role`
+    pub fn has_role(&self, member: &Pubkey, role: Role) -> bool {
+        self.grants.iter().any(|grant| grant.member == *member && grant.role == role)
+    }
+
+    /// Give `member` a role it does not hold yet
+    pub fn grant(&mut self, member: Pubkey, role: Role) -> Result<(), ProgramError> {
+        if self.has_role(&member, role) || self.grants.len() >= MAX_ROLE_GRANTS {
+            return Err(ProgramError::InvalidArgument);
+        }
+        self.grants.push(RoleGrant { member, role });
+        Ok(())
+    }
+
+    /// Take a role from `member`; the last admin cannot be removed
+    pub fn revoke(&mut self, member: &Pubkey, role: Role) -> Result<(), ProgramError> {
+        let index = self.grants.iter()
+            .position(|grant| grant.member == *member && grant.role == role)
+            .ok_or(ProgramError::InvalidArgument)?;
+        let admins = self.grants.iter().filter(|grant| grant.role == Role::Admin).count();
+        if role == Role::Admin && admins == 1 {
+            return Err(ProgramError::InvalidArgument);
+        }
+        self.grants.remove(index);
+        Ok(())
     }
 }
 
@@ -386,14 +2890,19 @@ pub struct AutonomousSupplyController {
     pub year_start_timestamp: i64,
     /// Last minting timestamp
     pub last_mint_timestamp: i64,
+    /// Last burning timestamp
+    pub last_burn_timestamp: i64,
     /// Current total supply
     pub current_supply: u64,
     /// Token decimals (from the mint)
     pub token_decimals: u8,
     /// Minimum supply (1B tokens with appropriate token decimals)
     pub min_supply: u64,
-    /// High supply threshold (5B tokens with appropriate token decimals)
+    /// High supply threshold (5B tokens with appropriate token decimals, or
+    /// `max_supply` when that is lower)
     pub high_supply_threshold: u64,
+    /// Supply autonomous mints never take the token past
+    pub max_supply: u64,
     /// Mint authority PDA
     pub mint_authority: Pubkey,
     /// Mint authority PDA bump seed
@@ -426,6 +2935,22 @@ pub struct AutonomousSupplyController {
     pub post_cap_mint_rate_bps: u16,
     /// Post-cap burn rate (in basis points, 200 = 2%)
     pub post_cap_burn_rate_bps: u16,
+    /// How `price_oracle` is read
+    pub price_source: SupplyPriceSource,
+    /// Authority that may change the supply parameters (the initializer)
+    pub authority: Pubkey,
+    /// Parameters queued by `UpdateControllerParameters`
+    pub pending_parameters: Option<PendingSupplyParameters>,
+    /// Caps on mints and burns per window
+    pub rate_limit: SupplyRateLimit,
+    /// Split applied by `DistributeSupplyTreasury` (None until the authority sets one)
+    pub distribution: Option<SupplyDistribution>,
+    /// Seconds required between consecutive mints
+    pub mint_cooldown_seconds: u32,
+    /// Seconds required between consecutive burns
+    pub burn_cooldown_seconds: u32,
+    /// Withheld transfer fees swept into the burn treasury so far
+    pub fees_swept_to_burn: u64,
 }
 
 impl AutonomousSupplyController {
@@ -433,49 +2958,30 @@ impl AutonomousSupplyController {
     pub fn get_size() -> usize {
         std::mem::size_of::<Self>()
     }
+
+    /// PDA owning the token accounts autonomous mints are paid into
+    pub fn find_supply_treasury(program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"supply_treasury", mint.as_ref()], program_id)
+    }
     
+    /// PDA that, once made the mint's withdraw-withheld authority, lets anyone
+    /// sweep transfer fees into the burn treasury
+    pub fn find_fee_sweep_authority(program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"fee_sweep_authority", mint.as_ref()], program_id)
+    }
+
     /// Calculate price growth percentage (returns basis points, 100 = 1%)
     /// Returns positive values for growth, negative for decline
     pub fn calculate_price_growth_bps(&self) -> Option<i64> {
-        if self.year_start_price == 0 {
-            return None; // Prevent division by zero
-        }
-        
-        // Calculate percentage change in basis points (100 = 1%)
-        // This can be positive (growth) or negative (decline)
-        let current = self.current_price as i128;
-        let year_start = self.year_start_price as i128;
-        
-        // Safely calculate price difference
-        let diff = if current >= year_start {
-            // Price increased or stayed the same
-            current.checked_sub(year_start)?
-        } else {
-            // Price decreased, result will be negative
-            let abs_diff = year_start.checked_sub(current)?;
-            // Negate the result, but check for i128::MIN edge case
-            if abs_diff == i128::MAX.checked_add(1)? {
-                return None; // Would overflow when negated
-            }
-            -abs_diff
-        };
-        
-        // Calculate percentage: ((current - year_start) / year_start) * 10000
-        // First multiply to preserve precision, then divide
-        let basis_points = diff.checked_mul(10000)?.checked_div(year_start)?;
-        
-        // Convert to i64 safely
-        if basis_points > i64::MAX as i128 || basis_points < i64::MIN as i128 {
-            return None;
-        }
-        
-        Some(basis_points as i64)
+        // None when the year start price is zero (prevents division by zero)
+        Bps::change_between(MicroUsd(self.year_start_price), MicroUsd(self.current_price))
     }
     
     /// Determine if minting is allowed and how much to mint
     pub fn calculate_mint_amount(&self) -> Option<u64> {
         // Get annual price growth in basis points
         let growth_bps = self.calculate_price_growth_bps()?;
+        let supply = TokenAmount(self.current_supply);
         
         // Only mint on positive growth
         if growth_bps <= 0 {
@@ -485,12 +2991,10 @@ impl AutonomousSupplyController {
         // For tokens above high supply threshold (5B tokens)
         if self.current_supply >= self.high_supply_threshold {
             // Only mint if growth exceeds extreme threshold (30%)
-            if growth_bps >= self.extreme_growth_threshold_bps as i64 {
+            if growth_bps >= Bps(self.extreme_growth_threshold_bps).as_i64() {
                 // Mint at 2% rate only for extreme growth above 5B supply
-                let mint_amount = self.current_supply
-                    .checked_mul(self.post_cap_mint_rate_bps as u64)?
-                    .checked_div(10000)?;
-                return Some(mint_amount);
+                let mint_amount = supply.apply_bps(Bps(self.post_cap_mint_rate_bps))?;
+                return Some(self.clip_to_max_supply(mint_amount.0));
             }
             // Otherwise no minting for high supply
             return Some(0);
@@ -499,49 +3003,49 @@ impl AutonomousSupplyController {
         // For normal supply levels (below 5B tokens)
         
         // Less than minimum growth threshold, no minting
-        if growth_bps < self.min_growth_for_mint_bps as i64 {
+        if growth_bps < Bps(self.min_growth_for_mint_bps).as_i64() {
             return Some(0);
         }
         
         // Between min and high growth thresholds, mint at medium rate
-        if growth_bps >= self.min_growth_for_mint_bps as i64 && 
-           growth_bps < self.high_growth_threshold_bps as i64 {
-            let mint_amount = self.current_supply
-                .checked_mul(self.medium_growth_mint_rate_bps as u64)?
-                .checked_div(10000)?;
-            return Some(mint_amount);
+        if growth_bps < Bps(self.high_growth_threshold_bps).as_i64() {
+            let mint_amount = supply.apply_bps(Bps(self.medium_growth_mint_rate_bps))?;
+            return Some(self.clip_to_max_supply(mint_amount.0));
         }
         
         // High growth threshold or higher, mint at high rate
-        let mint_amount = self.current_supply
-            .checked_mul(self.high_growth_mint_rate_bps as u64)?
-            .checked_div(10000)?;
+        let mint_amount = supply.apply_bps(Bps(self.high_growth_mint_rate_bps))?;
         
-        return Some(mint_amount);
+        Some(self.clip_to_max_supply(mint_amount.0))
+    }
+
+    /// Tokens that can still be minted before reaching `max_supply`
+    pub fn mint_headroom(&self) -> u64 {
+        self.max_supply.saturating_sub(self.current_supply)
+    }
+
+    /// Limit a mint to stay at or below the maximum supply
+    fn clip_to_max_supply(&self, amount: u64) -> u64 {
+        amount.min(self.mint_headroom())
     }
     
     /// Determine if burning is allowed and how much to burn
     pub fn calculate_burn_amount(&self) -> Option<u64> {
         // Get annual price growth in basis points
         let growth_bps = self.calculate_price_growth_bps()?;
+        let supply = TokenAmount(self.current_supply);
+        let min_supply = TokenAmount(self.min_supply);
         
         // Only burn on negative growth
         if growth_bps >= 0 {
             return Some(0);
         }
         
-        // Convert negative growth to positive decline value safely
-        // First ensure the value doesn't exceed u64 range when negated
-        if growth_bps == i64::MIN {
-            // Special case: i64::MIN cannot be negated without overflow
-            return None;
-        }
-        
-        // Safe conversion - we know growth_bps is negative but not MIN
-        let decline_bps = (-growth_bps) as u64;
+        // Convert negative growth to positive decline value
+        let decline_bps = growth_bps.unsigned_abs();
         
         // If already at or near minimum supply (within 5%), no burning allowed
-        if self.current_supply <= self.min_supply.checked_mul(105)?.checked_div(100)? {
+        if supply <= min_supply.apply_bps(Bps(10_500))? {
             return Some(0);
         }
         
@@ -555,24 +3059,20 @@ impl AutonomousSupplyController {
         // Calculate burn amount based on decline thresholds
         let burn_amount = if decline_bps >= self.high_decline_threshold_bps as u64 {
             // High decline - burn at high rate
-            self.current_supply
-                .checked_mul(self.high_decline_burn_rate_bps as u64)?
-                .checked_div(10000)?
+            supply.apply_bps(Bps(self.high_decline_burn_rate_bps))?
         } else {
             // Medium decline - burn at medium rate
-            self.current_supply
-                .checked_mul(self.medium_decline_burn_rate_bps as u64)?
-                .checked_div(10000)?
+            supply.apply_bps(Bps(self.medium_decline_burn_rate_bps))?
         };
         
         // Ensure we don't burn below minimum supply
-        let new_total = self.current_supply.checked_sub(burn_amount)?;
-        if new_total < self.min_supply {
+        let new_total = supply.checked_sub(burn_amount)?;
+        if new_total < min_supply {
             // Limit burn to stay at minimum supply
-            return self.current_supply.checked_sub(self.min_supply);
+            return supply.checked_sub(min_supply).map(|amount| amount.0);
         }
         
-        return Some(burn_amount);
+        Some(burn_amount.0)
     }
     
     /// Check if it's time for the annual evaluation
@@ -602,6 +3102,27 @@ impl AutonomousSupplyController {
         current_time >= target_time
     }
     
+    /// Reward owed to the keeper whose `CrankSupplyController` moved `amount` tokens
+    pub fn keeper_reward(amount: u64) -> Option<u64> {
+        Some(TokenAmount(amount).apply_bps(Bps(SUPPLY_KEEPER_REWARD_BPS))?.0)
+    }
+
+    /// When the cooldown after the last mint or burn ends, or None if there has
+    /// been no such operation yet
+    pub fn cooldown_ends_at(&self, operation: SupplyOperation) -> Option<i64> {
+        let (last, cooldown) = match operation {
+            SupplyOperation::Mint => (self.last_mint_timestamp, self.mint_cooldown_seconds),
+            SupplyOperation::Burn => (self.last_burn_timestamp, self.burn_cooldown_seconds),
+        };
+        (last != 0).then(|| last.saturating_add(i64::from(cooldown)))
+    }
+
+    /// Whether a mint or burn at `current_time` falls inside its cooldown
+    pub fn is_cooling_down(&self, operation: SupplyOperation, current_time: i64) -> bool {
+        self.cooldown_ends_at(operation)
+            .is_some_and(|ends_at| current_time < ends_at)
+    }
+
     /// Update the price from oracle
     pub fn update_price(&mut self, new_price: u64, current_time: i64) {
         // No overflow concerns for simple assignments, but good to document
@@ -614,6 +3135,49 @@ impl AutonomousSupplyController {
         self.year_start_timestamp = current_time;
         self.year_start_price = self.current_price;
     }
+    
+    /// Thresholds and rates currently in force
+    pub fn parameters(&self) -> SupplyParameters {
+        SupplyParameters {
+            min_growth_for_mint_bps: self.min_growth_for_mint_bps,
+            min_decline_for_burn_bps: self.min_decline_for_burn_bps,
+            medium_growth_mint_rate_bps: self.medium_growth_mint_rate_bps,
+            high_growth_mint_rate_bps: self.high_growth_mint_rate_bps,
+            medium_decline_burn_rate_bps: self.medium_decline_burn_rate_bps,
+            high_decline_burn_rate_bps: self.high_decline_burn_rate_bps,
+            high_growth_threshold_bps: self.high_growth_threshold_bps,
+            high_decline_threshold_bps: self.high_decline_threshold_bps,
+            extreme_growth_threshold_bps: self.extreme_growth_threshold_bps,
+            extreme_decline_threshold_bps: self.extreme_decline_threshold_bps,
+            post_cap_mint_rate_bps: self.post_cap_mint_rate_bps,
+            post_cap_burn_rate_bps: self.post_cap_burn_rate_bps,
+        }
+    }
+    
+    /// Put `parameters` in force
+    pub fn set_parameters(&mut self, parameters: SupplyParameters) {
+        self.min_growth_for_mint_bps = parameters.min_growth_for_mint_bps;
+        self.min_decline_for_burn_bps = parameters.min_decline_for_burn_bps;
+        self.medium_growth_mint_rate_bps = parameters.medium_growth_mint_rate_bps;
+        self.high_growth_mint_rate_bps = parameters.high_growth_mint_rate_bps;
+        self.medium_decline_burn_rate_bps = parameters.medium_decline_burn_rate_bps;
+        self.high_decline_burn_rate_bps = parameters.high_decline_burn_rate_bps;
+        self.high_growth_threshold_bps = parameters.high_growth_threshold_bps;
+        self.high_decline_threshold_bps = parameters.high_decline_threshold_bps;
+        self.extreme_growth_threshold_bps = parameters.extreme_growth_threshold_bps;
+        self.extreme_decline_threshold_bps = parameters.extreme_decline_threshold_bps;
+        self.post_cap_mint_rate_bps = parameters.post_cap_mint_rate_bps;
+        self.post_cap_burn_rate_bps = parameters.post_cap_burn_rate_bps;
+    }
+    
+    /// Put the queued parameters in force once their timelock has passed,
+    /// returning them if they were applied
+    pub fn apply_due_parameters(&mut self, current_time: i64) -> Option<SupplyParameters> {
+        let pending = self.pending_parameters.filter(|pending| current_time >= pending.effective_at)?;
+        self.set_parameters(pending.parameters);
+        self.pending_parameters = None;
+        Some(pending.parameters)
+    }
 }
 
 /// Purchase record
@@ -670,6 +3234,39 @@ pub enum EmergencyMode {
     Paused,
     /// Critical failure mode (only specific recovery functions)
     Critical,
+    /// Incident response: refunds, vesting releases and claims keep working,
+    /// every other state-changing instruction is blocked
+    Maintenance,
+}
+
+/// How an instruction is treated by the emergency modes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OperationClass {
+    /// Pausing, resuming and recovery by the emergency authority
+    EmergencyControl,
+    /// Returns funds to users (refunds, vesting releases, claims)
+    UserProtective,
+    /// Changes no state
+    ReadOnly,
+    /// Everything else: admin, supply and purchase paths
+    Restricted,
+}
+
+/// Who must approve an instruction, enforced once at dispatch before the handler's
+/// own checks (which still match the approver against the stored authority)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AccessRole {
+    /// An authority: the first account signs, or is a `Multisig` whose keys sign
+    /// after the instruction's accounts, or is the mint's `Timelock` where accepted
+    Authority,
+    /// A user acting for themselves (buyer, holder, beneficiary, claimant or
+    /// payer): the first account signs
+    Buyer,
+    /// A quorum of the mint's guardians, counted against the bug bounty account
+    /// passed with the instruction
+    Guardian,
+    /// Anyone; the handler decides what, if anything, must sign
+    Permissionless,
 }
 
 /// Record of a pause event
@@ -698,6 +3295,11 @@ impl EmergencyState {
             pause_history: Vec::new(),
         }
     }
+
+    /// Address of a mint's emergency state, created by `InitializeToken`
+    pub fn find_address(program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"emergency_state", mint.as_ref()], program_id)
+    }
     
     /// Check if operations are paused
     pub fn is_paused(&self) -> bool {
@@ -707,19 +3309,55 @@ impl EmergencyState {
         }
     }
     
+    /// Whether an instruction of `class` may run in the current mode
+    pub fn allows(&self, class: OperationClass) -> bool {
+        match self.emergency_mode {
+            EmergencyMode::Normal => true,
+            EmergencyMode::Maintenance => class != OperationClass::Restricted,
+            EmergencyMode::Paused | EmergencyMode::Critical => {
+                matches!(class, OperationClass::EmergencyControl | OperationClass::ReadOnly)
+            }
+        }
+    }
+    
     /// Pause operations
     pub fn pause(&mut self, authority: &Pubkey, reason: Option<String>, timestamp: i64) -> Result<(), ProgramError> {
+        self.enter_mode(EmergencyMode::Paused, authority, reason, timestamp)
+    }
+    
+    /// Enter maintenance mode, where only user-protective instructions run
+    pub fn enter_maintenance(&mut self, authority: &Pubkey, reason: Option<String>, timestamp: i64) -> Result<(), ProgramError> {
+        self.enter_mode(EmergencyMode::Maintenance, authority, reason, timestamp)
+    }
+    
+    fn enter_mode(
+        &mut self,
+        mode: EmergencyMode,
+        authority: &Pubkey,
+        reason: Option<String>,
+        timestamp: i64,
+    ) -> Result<(), ProgramError> {
         // Verify authority
         if authority != &self.emergency_authority && authority != &self.program_authority {
             return Err(ProgramError::InvalidArgument);
         }
         
+        // A pause that is still open keeps its record when the mode changes
+        let already_recorded = self.emergency_mode != EmergencyMode::Normal;
+        
         // Set emergency mode
-        self.emergency_mode = EmergencyMode::Paused;
+        self.emergency_mode = mode;
         self.emergency_activated_at = timestamp;
         self.emergency_reason = reason.clone();
         
-        // Record pause event
+        if already_recorded {
+            return Ok(());
+        }
+        
+        // Record pause event, keeping the account within its allocated history
+        if self.pause_history.len() >= MAX_PAUSE_HISTORY {
+            self.pause_history.remove(0);
+        }
         self.pause_history.push(PauseRecord {
             paused_at: timestamp,
             resumed_at: None,
@@ -729,56 +3367,506 @@ impl EmergencyState {
         
         Ok(())
     }
-    
-    /// Resume operations
-    pub fn resume(&mut self, authority: &Pubkey, timestamp: i64) -> Result<(), ProgramError> {
-        // Verify authority
-        if authority != &self.emergency_authority && authority != &self.program_authority {
-            return Err(ProgramError::InvalidArgument);
+    
+    /// Resume operations
+    pub fn resume(&mut self, authority: &Pubkey, timestamp: i64) -> Result<(), ProgramError> {
+        // Verify authority
+        if authority != &self.emergency_authority && authority != &self.program_authority {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        // Set normal mode
+        self.emergency_mode = EmergencyMode::Normal;
+        
+        // Update the latest pause record if any
+        if let Some(last_record) = self.pause_history.last_mut() {
+            if last_record.resumed_at.is_none() {
+                last_record.resumed_at = Some(timestamp);
+            }
+        }
+        
+        // Clear emergency reason
+        self.emergency_reason = None;
+        
+        Ok(())
+    }
+    
+    /// Calculate required space for the emergency state
+    pub fn get_space(history_capacity: usize) -> usize {
+        // Base size excluding Vec<PauseRecord>
+        let base_size = std::mem::size_of::<Self>() - std::mem::size_of::<Vec<PauseRecord>>();
+        
+        // Add space for pause records
+        let record_size = std::mem::size_of::<PauseRecord>();
+        let history_size = record_size.checked_mul(history_capacity)
+            .expect("Calculation error in EmergencyState::get_space");
+        
+        base_size.checked_add(history_size)
+            .expect("Calculation error in EmergencyState::get_space")
+    }
+}
+
+/// Oracle types supported by the protocol
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum OracleType {
+    /// Pyth Network Oracle
+    Pyth,
+    /// Switchboard Oracle
+    Switchboard,
+    /// Chainlink Oracle (future support)
+    Chainlink,
+    /// Custom Oracle
+    Custom,
+    /// Pyth pull oracle: a `PriceUpdateV2` account posted through the Pyth Receiver program
+    PythPull,
+    /// RedStone oracle: an account holding a signed RedStone payload, checked against
+    /// the controller's `RedstoneConfig`
+    Redstone,
+    /// Chainlink Data Streams: the controller's `ChainlinkStreamsReport` account,
+    /// written by keepers with `SubmitChainlinkStreamsReport`
+    ChainlinkStreams,
+    /// Off-chain price signed by one of the controller's registered publishers: the
+    /// controller's `SignedPriceReport` account, written by keepers with `SubmitSignedPrice`
+    SignedMessage,
+}
+
+/// Pyth Receiver program that verifies and owns pull-oracle price update accounts
+pub const PYTH_RECEIVER_PROGRAM_ID: Pubkey =
+    solana_program::pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+
+/// Wormhole verification performed by the Pyth Receiver on a price update
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PythVerificationLevel {
+    /// Only some guardian signatures were checked
+    Partial {
+        /// Number of signatures checked
+        num_signatures: u8,
+    },
+    /// The full guardian quorum was checked
+    Full,
+}
+
+/// Pyth Receiver `PriceUpdateV2` account, without its 8-byte Anchor discriminator
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct PythPriceUpdate {
+    /// Account allowed to overwrite this update
+    pub write_authority: Pubkey,
+    /// How the update was verified
+    pub verification_level: PythVerificationLevel,
+    /// Pyth price feed id
+    pub feed_id: [u8; 32],
+    /// Price in units of 10^exponent
+    pub price: i64,
+    /// Confidence interval in units of 10^exponent
+    pub conf: u64,
+    /// Price exponent
+    pub exponent: i32,
+    /// Time the price was published
+    pub publish_time: i64,
+    /// Publish time of the previous price
+    pub prev_publish_time: i64,
+    /// Exponential moving average price
+    pub ema_price: i64,
+    /// Exponential moving average confidence
+    pub ema_conf: u64,
+    /// Slot the update was posted in
+    pub posted_slot: u64,
+}
+
+impl PythPriceUpdate {
+    /// Anchor discriminator of `PriceUpdateV2` accounts
+    pub const DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+
+    /// Decode a price update account; fully verified updates leave a trailing byte
+    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() < Self::DISCRIMINATOR.len() || data[..8] != Self::DISCRIMINATOR {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::deserialize(&mut &data[8..]).map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    /// Price and confidence with 6 decimals, or None for non-positive or out of range prices
+    pub fn usd_price(&self) -> Option<(u64, u64)> {
+        if self.price <= 0 {
+            return None;
+        }
+        let shift = 6i32.checked_add(self.exponent)?;
+        let factor = 10u128.checked_pow(shift.unsigned_abs())?;
+        let scale = |value: u128| {
+            let scaled = if shift >= 0 { value.checked_mul(factor) } else { value.checked_div(factor) };
+            scaled.and_then(|v| u64::try_from(v).ok())
+        };
+        Some((scale(self.price as u128)?, scale(self.conf as u128)?))
+    }
+}
+
+/// Maximum number of authorized signers in a RedStone feed configuration
+pub const MAX_REDSTONE_SIGNERS: usize = 8;
+
+/// Decimals of RedStone numeric values
+pub const REDSTONE_VALUE_DECIMALS: u32 = 8;
+
+/// Marker closing every RedStone payload
+const REDSTONE_MARKER: [u8; 9] = [0x00, 0x00, 0x02, 0xed, 0x57, 0x01, 0x1e, 0x00, 0x00];
+
+/// RedStone feed a controller accepts: the asset's data feed id and the signers
+/// whose data packages are trusted
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct RedstoneConfig {
+    /// RedStone data feed id, the feed name right-padded with zeros (e.g. "ETH")
+    pub feed_id: [u8; 32],
+    /// Ethereum addresses of the authorized signers
+    pub signers: Vec<[u8; 20]>,
+    /// Distinct authorized signers that must report the feed
+    pub signer_threshold: u8,
+}
+
+/// Median price of a verified RedStone payload, in USD with 6 decimals
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RedstonePrice {
+    /// Median of the signers' values
+    pub price: u64,
+    /// Half the spread between the lowest and highest value
+    pub confidence: u64,
+    /// Time the data packages were signed (seconds)
+    pub timestamp: i64,
+}
+
+/// Reads RedStone payload fields from the end towards the start
+struct RedstoneReader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> RedstoneReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], ProgramError> {
+        let start = self.data.len().checked_sub(len).ok_or(ProgramError::InvalidAccountData)?;
+        let (rest, taken) = self.data.split_at(start);
+        self.data = rest;
+        Ok(taken)
+    }
+
+    /// Big-endian unsigned integer of `len` bytes
+    fn take_number(&mut self, len: usize) -> Result<u128, ProgramError> {
+        be_number(self.take(len)?)
+    }
+}
+
+fn be_number(bytes: &[u8]) -> Result<u128, ProgramError> {
+    let significant = bytes.iter().position(|byte| *byte != 0).unwrap_or(bytes.len());
+    if bytes.len() - significant > 16 {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(bytes[significant..].iter().fold(0u128, |value, byte| (value << 8) | *byte as u128))
+}
+
+impl RedstoneConfig {
+    /// Largest serialized size of a configuration
+    pub fn get_size() -> usize {
+        32 + 4 + 20 * MAX_REDSTONE_SIGNERS + 1
+    }
+
+    /// Whether the signer set is non-empty, bounded, free of duplicates and meets the threshold
+    pub fn is_valid(&self) -> bool {
+        signer_set_is_valid(&self.feed_id, &self.signers, self.signer_threshold, MAX_REDSTONE_SIGNERS)
+    }
+
+    /// Verify a RedStone payload and return the median value of the configured feed
+    ///
+    /// Every data package must carry a valid signature; packages of unknown signers
+    /// are ignored, and each authorized signer counts once. All packages must share
+    /// one timestamp, as RedStone signs them together.
+    pub fn verify_payload(&self, payload: &[u8]) -> Result<RedstonePrice, ProgramError> {
+        let mut reader = RedstoneReader { data: payload };
+
+        if reader.take(REDSTONE_MARKER.len())? != REDSTONE_MARKER {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let metadata_size = reader.take_number(3)? as usize;
+        reader.take(metadata_size)?;
+        let package_count = reader.take_number(2)?;
+
+        let mut values: Vec<u128> = Vec::new();
+        let mut seen_signers: Vec<[u8; 20]> = Vec::new();
+        let mut timestamp_ms: Option<u128> = None;
+
+        for _ in 0..package_count {
+            let signature = reader.take(65)?;
+            let signed_end = reader.data.len();
+            let point_count = reader.take_number(3)? as usize;
+            let value_size = reader.take_number(4)? as usize;
+            let package_time = reader.take_number(6)?;
+            let points_len = value_size
+                .checked_add(32)
+                .and_then(|size| size.checked_mul(point_count))
+                .ok_or(ProgramError::InvalidAccountData)?;
+            let points = reader.take(points_len)?;
+            let signed_bytes = &payload[reader.data.len()..signed_end];
+
+            if *timestamp_ms.get_or_insert(package_time) != package_time {
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            let signer = recover_eth_address(&keccak::hash(signed_bytes).to_bytes(), &signature[..64], signature[64])?;
+            if !self.signers.contains(&signer) || seen_signers.contains(&signer) {
+                continue;
+            }
+
+            let value = points
+                .chunks_exact(32 + value_size)
+                .find(|point| point[..32] == self.feed_id)
+                .map(|point| be_number(&point[32..]))
+                .transpose()?;
+            if let Some(value) = value {
+                seen_signers.push(signer);
+                values.push(value);
+            }
+        }
+
+        if values.len() < self.signer_threshold as usize {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // The two middle values coincide for an odd count
+        values.sort_unstable();
+        let median = (values[(values.len() - 1) / 2] + values[values.len() / 2]) / 2;
+        let spread = (values[values.len() - 1] - values[0]) / 2;
+
+        let divisor = 10u128.pow(REDSTONE_VALUE_DECIMALS - 6);
+        let to_usd = |value: u128| u64::try_from(value / divisor).map_err(|_| ProgramError::InvalidAccountData);
+        let timestamp = i64::try_from(timestamp_ms.unwrap_or(0) / 1_000).map_err(|_| ProgramError::InvalidAccountData)?;
+
+        Ok(RedstonePrice { price: to_usd(median)?, confidence: to_usd(spread)?, timestamp })
+    }
+}
+
+/// Whether a set of Ethereum signers is non-empty, bounded, free of duplicates and
+/// meets its threshold
+fn signer_set_is_valid(feed_id: &[u8; 32], signers: &[[u8; 20]], threshold: u8, max_signers: usize) -> bool {
+    let unique = signers.iter().enumerate().all(|(i, signer)| !signers[..i].contains(signer));
+    *feed_id != [0u8; 32]
+        && signers.len() <= max_signers
+        && unique
+        && threshold > 0
+        && threshold as usize <= signers.len()
+}
+
+/// Ethereum address that produced an ECDSA signature over `message_hash`
+fn recover_eth_address(message_hash: &[u8; 32], signature: &[u8], recovery_byte: u8) -> Result<[u8; 20], ProgramError> {
+    let recovery_id = match recovery_byte {
+        27 | 28 => recovery_byte - 27,
+        0 | 1 => recovery_byte,
+        _ => return Err(ProgramError::InvalidAccountData),
+    };
+    let public_key = secp256k1_recover(message_hash, recovery_id, signature)
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&keccak::hash(&public_key.to_bytes()).to_bytes()[12..]);
+    Ok(address)
+}
+
+/// Maximum number of authorized DON signers in a Chainlink Data Streams configuration
+pub const MAX_CHAINLINK_STREAMS_SIGNERS: usize = 16;
+
+/// Decimals of Chainlink Data Streams crypto prices
+pub const CHAINLINK_STREAMS_PRICE_DECIMALS: u32 = 18;
+
+/// Chainlink Data Streams feed a controller accepts reports for
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct ChainlinkStreamsConfig {
+    /// Data Streams feed id; must be a v3 (crypto) feed
+    pub feed_id: [u8; 32],
+    /// Ethereum addresses of the DON's signing keys
+    pub signers: Vec<[u8; 20]>,
+    /// Distinct authorized signatures a report needs (f + 1 for the DON)
+    pub signer_threshold: u8,
+}
+
+impl ChainlinkStreamsConfig {
+    /// Largest serialized size of a configuration
+    pub fn get_size() -> usize {
+        32 + 4 + 20 * MAX_CHAINLINK_STREAMS_SIGNERS + 1
+    }
+
+    /// Whether the feed is a v3 feed and the signer set is bounded, unique and meets the threshold
+    pub fn is_valid(&self) -> bool {
+        self.feed_id[..2] == [0, 3]
+            && signer_set_is_valid(&self.feed_id, &self.signers, self.signer_threshold, MAX_CHAINLINK_STREAMS_SIGNERS)
+    }
+
+    /// Verify a full Data Streams report as returned by the Data Streams API
+    ///
+    /// The report is the ABI encoding of `(bytes32[3] reportContext, bytes reportBlob,
+    /// bytes32[] rawRs, bytes32[] rawSs, bytes32 rawVs)`; each signature covers
+    /// `keccak256(keccak256(reportBlob) || reportContext)`. Reports that are expired,
+    /// not yet valid or observed in the future are rejected.
+    pub fn verify_report(
+        &self,
+        controller: Pubkey,
+        full_report: &[u8],
+        current_time: i64,
+    ) -> Result<ChainlinkStreamsReport, ProgramError> {
+        let word = |index: usize| -> Result<&[u8], ProgramError> {
+            full_report.get(index * 32..(index + 1) * 32).ok_or(ProgramError::InvalidAccountData)
+        };
+        // Dynamic ABI values: a length word at `offset`, followed by the contents
+        let dynamic = |offset_word: usize, element_size: usize| -> Result<&[u8], ProgramError> {
+            let offset = be_number(word(offset_word)?)? as usize;
+            let length = be_number(full_report.get(offset..offset + 32).ok_or(ProgramError::InvalidAccountData)?)? as usize;
+            let start = offset + 32;
+            let end = length.checked_mul(element_size).and_then(|len| len.checked_add(start))
+                .ok_or(ProgramError::InvalidAccountData)?;
+            full_report.get(start..end).ok_or(ProgramError::InvalidAccountData)
+        };
+
+        let report_context = full_report.get(..96).ok_or(ProgramError::InvalidAccountData)?;
+        let blob = dynamic(3, 1)?;
+        let rs = dynamic(4, 32)?;
+        let ss = dynamic(5, 32)?;
+        let vs = word(6)?;
+        if rs.len() != ss.len() || rs.len() / 32 > vs.len() {
+            return Err(ProgramError::InvalidAccountData);
         }
-        
-        // Set normal mode
-        self.emergency_mode = EmergencyMode::Normal;
-        
-        // Update the latest pause record if any
-        if let Some(last_record) = self.pause_history.last_mut() {
-            if last_record.resumed_at.is_none() {
-                last_record.resumed_at = Some(timestamp);
+
+        // Signatures: distinct authorized signers must reach the threshold
+        let message_hash = keccak::hashv(&[&keccak::hash(blob).to_bytes(), report_context]).to_bytes();
+        let mut seen_signers: Vec<[u8; 20]> = Vec::new();
+        for (index, (r, s)) in rs.chunks_exact(32).zip(ss.chunks_exact(32)).enumerate() {
+            let signer = recover_eth_address(&message_hash, &[r, s].concat(), vs[index])?;
+            if self.signers.contains(&signer) && !seen_signers.contains(&signer) {
+                seen_signers.push(signer);
             }
         }
-        
-        // Clear emergency reason
-        self.emergency_reason = None;
-        
-        Ok(())
+        if seen_signers.len() < self.signer_threshold as usize {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // v3 report: feedId, validFromTimestamp, observationsTimestamp, nativeFee,
+        // linkFee, expiresAt, benchmarkPrice, bid, ask (one 32-byte word each)
+        if blob.len() != 9 * 32 || blob[..32] != self.feed_id {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let field = |index: usize| &blob[index * 32..(index + 1) * 32];
+        let timestamp = |index: usize| be_number(field(index)).and_then(|value| i64::try_from(value).map_err(|_| ProgramError::InvalidAccountData));
+        let to_usd = |index: usize| -> Result<u64, ProgramError> {
+            // int192 prices: negative values are rejected
+            if field(index)[0] & 0x80 != 0 {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            let value = be_number(field(index))? / 10u128.pow(CHAINLINK_STREAMS_PRICE_DECIMALS - 6);
+            u64::try_from(value).map_err(|_| ProgramError::InvalidAccountData)
+        };
+
+        let report = ChainlinkStreamsReport {
+            is_initialized: true,
+            controller,
+            feed_id: self.feed_id,
+            valid_from_timestamp: timestamp(1)?,
+            observations_timestamp: timestamp(2)?,
+            expires_at: timestamp(5)?,
+            price: to_usd(6)?,
+            bid: to_usd(7)?,
+            ask: to_usd(8)?,
+            submitted_at: current_time,
+        };
+        if report.valid_from_timestamp > current_time
+            || report.observations_timestamp > current_time
+            || report.expires_at < current_time
+            || report.price == 0
+            || report.bid > report.ask
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(report)
     }
-    
-    /// Calculate required space for the emergency state
-    pub fn get_space(history_capacity: usize) -> usize {
-        // Base size excluding Vec<PauseRecord>
-        let base_size = std::mem::size_of::<Self>() - std::mem::size_of::<Vec<PauseRecord>>();
-        
-        // Add space for pause records
-        let record_size = std::mem::size_of::<PauseRecord>();
-        let history_size = record_size.checked_mul(history_capacity)
-            .expect("Calculation error in EmergencyState::get_space");
-        
-        base_size.checked_add(history_size)
-            .expect("Calculation error in EmergencyState::get_space")
+}
+
+/// Latest verified Chainlink Data Streams report of an oracle controller, submitted by a keeper
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct ChainlinkStreamsReport {
+    /// Is initialized
+    pub is_initialized: bool,
+    /// Oracle controller the report was verified for
+    pub controller: Pubkey,
+    /// Data Streams feed id
+    pub feed_id: [u8; 32],
+    /// Earliest time the report is valid
+    pub valid_from_timestamp: i64,
+    /// Time the DON observed the price
+    pub observations_timestamp: i64,
+    /// Time after which the report must not be used
+    pub expires_at: i64,
+    /// Benchmark price in USD with 6 decimals
+    pub price: u64,
+    /// Bid price in USD with 6 decimals
+    pub bid: u64,
+    /// Ask price in USD with 6 decimals
+    pub ask: u64,
+    /// Time the report was submitted
+    pub submitted_at: i64,
+}
+
+impl ChainlinkStreamsReport {
+    /// Get the size of the report account
+    pub fn get_size() -> usize {
+        std::mem::size_of::<Self>()
+    }
+
+    /// Address of a controller's report account
+    pub fn find_address(program_id: &Pubkey, controller: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"chainlink_streams", controller.as_ref()], program_id)
+    }
+
+    /// Half the bid/ask spread, used as the confidence interval
+    pub fn confidence(&self) -> u64 {
+        (self.ask - self.bid) / 2
     }
 }
 
-/// Oracle types supported by the protocol
-#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
-pub enum OracleType {
-    /// Pyth Network Oracle
-    Pyth,
-    /// Switchboard Oracle
-    Switchboard,
-    /// Chainlink Oracle (future support)
-    Chainlink,
-    /// Custom Oracle
-    Custom,
+/// Maximum number of publishers whose signed prices a controller accepts
+pub const MAX_SIGNED_PRICE_PUBLISHERS: usize = 8;
+
+/// Latest publisher-signed price of an oracle controller, submitted by a keeper
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct SignedPriceReport {
+    /// Is initialized
+    pub is_initialized: bool,
+    /// Oracle controller the price was signed for
+    pub controller: Pubkey,
+    /// Ed25519 key of the publisher that signed the price
+    pub publisher: Pubkey,
+    /// Price in USD with 6 decimals
+    pub price: u64,
+    /// Confidence interval in USD with 6 decimals
+    pub confidence: u64,
+    /// Time the publisher observed the price
+    pub publish_time: i64,
+    /// Time the report was submitted
+    pub submitted_at: i64,
+}
+
+impl SignedPriceReport {
+    /// Get the size of the report account
+    pub fn get_size() -> usize {
+        // is_initialized, controller, publisher, price, confidence, publish_time, submitted_at
+        1 + 32 + 32 + 8 * 4
+    }
+
+    /// Address of a controller's signed price account
+    pub fn find_address(program_id: &Pubkey, controller: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"signed_price", controller.as_ref()], program_id)
+    }
+
+    /// Message the publisher signs with ed25519:
+    /// controller || price || confidence || publish_time (little endian)
+    pub fn message(controller: &Pubkey, price: u64, confidence: u64, publish_time: i64) -> Vec<u8> {
+        let mut message = Vec::with_capacity(56);
+        message.extend_from_slice(controller.as_ref());
+        message.extend_from_slice(&price.to_le_bytes());
+        message.extend_from_slice(&confidence.to_le_bytes());
+        message.extend_from_slice(&publish_time.to_le_bytes());
+        message
+    }
 }
 
 /// Oracle source configuration
@@ -825,6 +3913,140 @@ pub struct OracleConsensusResult {
     pub is_fallback_price: bool,
     /// Maximum deviation between oracles (in basis points)
     pub max_deviation_bps: u16,
+    /// Consensus price before EMA smoothing (equals `price` when smoothing is off)
+    pub spot_price: u64,
+}
+
+/// Rule deciding whether enough oracle sources contributed to publish a consensus price
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum QuorumPolicy {
+    /// At least `min_required_oracles` sources, including every active required source
+    #[default]
+    MinOracles,
+    /// At least `n` sources, whether required or not
+    NOfM {
+        /// Sources that must contribute
+        n: u8,
+    },
+    /// Every active required source plus at least `optional` other sources
+    RequiredPlus {
+        /// Optional sources that must contribute besides the required ones
+        optional: u8,
+    },
+    /// The weights of the contributing sources add up to at least `min_weight`
+    MinWeight {
+        /// Total weight needed (source weights are 0-100)
+        min_weight: u16,
+    },
+}
+
+impl QuorumPolicy {
+    /// Whether the policy can be met by at least one source
+    pub fn is_valid(&self) -> bool {
+        match *self {
+            Self::MinOracles | Self::RequiredPlus { .. } => true,
+            Self::NOfM { n } => n > 0,
+            Self::MinWeight { min_weight } => min_weight > 0,
+        }
+    }
+}
+
+/// A source's price as it enters consensus aggregation
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PriceSample {
+    /// Price in USD (with 6 decimals precision)
+    pub price: u64,
+    /// Confidence interval reported by the source (same precision as `price`)
+    pub confidence: u64,
+    /// Source weight (0-100)
+    pub weight: u8,
+}
+
+/// How an oracle controller combines its sources' prices into one consensus price
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum AggregationStrategy {
+    /// Mean weighted by source weight; smooth, but every source moves the result
+    #[default]
+    WeightedMean,
+    /// Middle price, ignoring weights; a minority of sources cannot move it far
+    Median,
+    /// Price at half the total weight, with each source weighted by its weight
+    /// divided by its relative confidence interval
+    ConfidenceWeightedMedian,
+    /// Plain mean after dropping the highest and lowest prices
+    TrimmedMean {
+        /// Share of the sources dropped at each end (in basis points, below 5000)
+        trim_bps: u16,
+    },
+}
+
+impl AggregationStrategy {
+    /// Whether the strategy's parameters are usable
+    pub fn is_valid(&self) -> bool {
+        match *self {
+            Self::WeightedMean | Self::Median | Self::ConfidenceWeightedMedian => true,
+            Self::TrimmedMean { trim_bps } => trim_bps < 5_000,
+        }
+    }
+
+    /// Combine `samples` into one price, or None when there are none
+    pub fn aggregate(&self, samples: &[PriceSample]) -> Option<u64> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable_by_key(|sample| sample.price);
+
+        match *self {
+            Self::WeightedMean => {
+                let total_weight: u128 = sorted.iter().map(|sample| sample.weight as u128).sum();
+                if total_weight == 0 {
+                    // Fall back to a simple average if weights sum to zero
+                    return mean(&sorted);
+                }
+                let weighted_sum: u128 = sorted.iter()
+                    .map(|sample| sample.price as u128 * sample.weight as u128)
+                    .sum();
+                u64::try_from(weighted_sum / total_weight).ok()
+            }
+            Self::Median => {
+                // The two middle samples coincide for an odd count
+                let sum = sorted[(sorted.len() - 1) / 2].price as u128 + sorted[sorted.len() / 2].price as u128;
+                u64::try_from(sum / 2).ok()
+            }
+            Self::ConfidenceWeightedMedian => {
+                // A source claiming a 1bps interval counts 10,000 times its weight;
+                // a zero interval is treated as 1bps
+                let weights: Vec<u128> = sorted.iter()
+                    .map(|sample| {
+                        let confidence_bps = (sample.confidence as u128 * 10_000 / sample.price.max(1) as u128).max(1);
+                        sample.weight as u128 * 10_000 * 10_000 / confidence_bps
+                    })
+                    .collect();
+                let total_weight: u128 = weights.iter().sum();
+                if total_weight == 0 {
+                    return Self::Median.aggregate(&sorted);
+                }
+                let mut cumulative = 0u128;
+                sorted.iter().zip(weights).find_map(|(sample, weight)| {
+                    cumulative += weight;
+                    (cumulative * 2 >= total_weight).then_some(sample.price)
+                })
+            }
+            Self::TrimmedMean { trim_bps } => {
+                let trimmed = sorted.len() * trim_bps as usize / 10_000;
+                if trimmed * 2 >= sorted.len() {
+                    return Self::Median.aggregate(&sorted);
+                }
+                mean(&sorted[trimmed..sorted.len() - trimmed])
+            }
+        }
+    }
+}
+
+fn mean(samples: &[PriceSample]) -> Option<u64> {
+    let sum: u128 = samples.iter().map(|sample| sample.price as u128).sum();
+    u64::try_from(sum / samples.len() as u128).ok()
 }
 
 /// Oracle health status for monitoring
@@ -846,6 +4068,146 @@ pub struct OracleHealthStatus {
     pub avg_deviation_bps: u16,
 }
 
+/// Coarse health of an oracle controller, derived from its health score
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OracleHealthLevel {
+    /// Score at or above the degraded threshold
+    Healthy,
+    /// Score below the degraded threshold
+    Degraded,
+    /// Score at or below the critical threshold
+    Critical,
+}
+
+/// A consensus price and the time it was reached
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct PriceObservation {
+    /// Price in USD (with 6 decimals precision)
+    pub price: u64,
+    /// Time of the consensus
+    pub timestamp: i64,
+}
+
+/// What tripped an oracle controller's circuit breaker
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CircuitBreakerTrigger {
+    /// Too few sources contributed to the consensus
+    QuorumNotMet {
+        /// Sources that returned a valid price
+        sources: u8,
+        /// Quorum policy in force
+        policy: QuorumPolicy,
+        /// Whether the quorum was lost only after outliers were filtered out
+        after_filtering: bool,
+    },
+    /// The consensus moved more than allowed in one update
+    ExtremePriceChange {
+        /// Previous consensus price
+        previous_price: u64,
+        /// Rejected consensus price
+        new_price: u64,
+        /// Change between the two (in basis points)
+        change_bps: u16,
+    },
+}
+
+impl CircuitBreakerTrigger {
+    /// Human-readable reason stored in `circuit_breaker_reason`
+    pub fn reason(&self) -> String {
+        match *self {
+            Self::QuorumNotMet { sources, policy, after_filtering: false } => {
+                format!("Oracle quorum not met ({} sources, {:?})", sources, policy)
+            }
+            Self::QuorumNotMet { sources, policy, after_filtering: true } => {
+                format!("Oracle quorum not met after filtering outliers ({} sources, {:?})", sources, policy)
+            }
+            Self::ExtremePriceChange { change_bps, .. } => {
+                format!("Extreme price change detected ({}bps)", change_bps)
+            }
+        }
+    }
+}
+
+/// One activation of an oracle controller's circuit breaker
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CircuitBreakerIncident {
+    /// What tripped the breaker
+    pub trigger: CircuitBreakerTrigger,
+    /// Time the breaker was activated
+    pub activated_at: i64,
+    /// Time the breaker was reset (None while still active)
+    pub resolved_at: Option<i64>,
+    /// Authority that reset the breaker (None when it recovered automatically)
+    pub resolved_by: Option<Pubkey>,
+}
+
+impl CircuitBreakerIncident {
+    /// Seconds the breaker was (or has so far been) active
+    pub fn duration(&self, current_time: i64) -> i64 {
+        self.resolved_at.unwrap_or(current_time).saturating_sub(self.activated_at).max(0)
+    }
+}
+
+/// Longest consensus age a staleness policy may allow (24 hours)
+pub const MAX_POLICY_STALENESS: u32 = 86_400;
+
+/// Consumer of an oracle controller's price, which decides how fresh it must be
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OracleOperation {
+    /// Pricing a presale purchase
+    Purchase,
+    /// Autonomous mint and burn decisions
+    SupplyAdjustment,
+    /// Informational reads (display, TWAP liveness)
+    Display,
+}
+
+impl OracleOperation {
+    /// Whether the operation moves value and so may not use a fallback consensus
+    pub fn requires_regular_consensus(&self) -> bool {
+        !matches!(self, Self::Display)
+    }
+}
+
+/// Oldest consensus each kind of consumer accepts (in seconds)
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StalenessPolicy {
+    /// Maximum consensus age for presale purchases
+    pub purchase: u32,
+    /// Maximum consensus age for mint and burn decisions
+    pub supply_adjustment: u32,
+    /// Maximum consensus age for informational reads
+    pub display: u32,
+}
+
+impl Default for StalenessPolicy {
+    fn default() -> Self {
+        Self {
+            purchase: 300,          // 5 minutes
+            supply_adjustment: 300, // 5 minutes
+            display: 900,           // 15 minutes
+        }
+    }
+}
+
+impl StalenessPolicy {
+    /// Maximum consensus age allowed for `operation`
+    pub fn max_age(&self, operation: OracleOperation) -> u32 {
+        match operation {
+            OracleOperation::Purchase => self.purchase,
+            OracleOperation::SupplyAdjustment => self.supply_adjustment,
+            OracleOperation::Display => self.display,
+        }
+    }
+
+    /// Whether every threshold is between one second and `MAX_POLICY_STALENESS`
+    pub fn is_valid(&self) -> bool {
+        [self.purchase, self.supply_adjustment, self.display]
+            .iter()
+            .all(|max_age| (1..=MAX_POLICY_STALENESS).contains(max_age))
+    }
+}
+
 /// Multi-Oracle Controller for price feed management
 #[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
 pub struct MultiOracleController {
@@ -877,6 +4239,102 @@ pub struct MultiOracleController {
     pub emergency_price_timestamp: i64,
     /// Emergency price expiration in seconds
     pub emergency_price_expiration: u32,
+    /// Lowest price consumers such as a dynamic presale may use (0 = not configured)
+    pub price_floor: u64,
+    /// Highest price consumers such as a dynamic presale may use (0 = not configured)
+    pub price_ceiling: u64,
+    /// Pyth feed id of the asset; pull-oracle sources must publish this feed
+    pub pyth_feed_id: Option<[u8; 32]>,
+    /// Ring of recent consensus prices (at most `PRICE_HISTORY_LEN`)
+    pub price_history: Vec<PriceObservation>,
+    /// Slot of `price_history` the next observation is written to once the ring is full
+    pub price_history_next: u8,
+    /// Weight of a new spot price in the EMA written to `last_consensus`
+    /// (in basis points; 0 = smoothing off)
+    pub ema_alpha_bps: u16,
+    /// RedStone feed and signers that RedStone sources must match
+    pub redstone: Option<RedstoneConfig>,
+    /// Chainlink Data Streams feed and DON signers that submitted reports must match
+    pub chainlink_streams: Option<ChainlinkStreamsConfig>,
+    /// Rule deciding whether enough sources contributed to a consensus
+    pub quorum_policy: QuorumPolicy,
+    /// Recent circuit-breaker activations, oldest first (at most `MAX_CIRCUIT_BREAKER_INCIDENTS`)
+    pub circuit_breaker_incidents: Vec<CircuitBreakerIncident>,
+    /// Whether a consensus update after the cooldown runs in probation and clears
+    /// the circuit breaker when it succeeds
+    pub circuit_breaker_auto_recovery: bool,
+    /// Cap on the escalated circuit-breaker cooldown (in seconds)
+    pub circuit_breaker_max_cooldown: u32,
+    /// Trips since the circuit breaker last cleared; each one doubles the cooldown
+    pub circuit_breaker_trips: u8,
+    /// Maximum consensus age per kind of consumer
+    pub staleness_policy: StalenessPolicy,
+    /// How contributing prices are combined into the consensus price
+    pub aggregation_strategy: AggregationStrategy,
+    /// Ed25519 keys whose signed prices `SignedMessage` sources accept
+    /// (at most `MAX_SIGNED_PRICE_PUBLISHERS`)
+    pub signed_price_publishers: Vec<Pubkey>,
+}
+
+/// Maximum feeds in one oracle feed registry
+pub const MAX_MANAGED_FEEDS: usize = 16;
+
+/// Maximum asset id length of a managed feed (the asset id is a PDA seed)
+pub const MAX_MANAGED_FEED_ASSET_ID_LEN: usize = 32;
+
+/// Oracle sources a controller account created by the program is sized for
+pub const MAX_ORACLE_SOURCES: usize = 8;
+
+/// Oracle controller listed in a feed registry
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct ManagedFeed {
+    /// Asset the controller prices (e.g. "VCN/USD", "SOL/USD", "USDC/USD")
+    pub asset_id: String,
+    /// Oracle controller (PDA: ["managed_feed", registry, asset_id])
+    pub controller: Pubkey,
+}
+
+/// Registry of an authority's oracle controllers by asset id, stored in a PDA
+/// derived from `[b"oracle_feed_registry", authority]`
+///
+/// Consumers find a project's feed for an asset by reading the registry, or by
+/// deriving the controller address from the registry and the asset id.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct OracleFeedRegistry {
+    /// Is initialized
+    pub is_initialized: bool,
+    /// Authority whose feeds are listed; also the authority of each controller
+    pub authority: Pubkey,
+    /// Registered feeds, in creation order
+    pub feeds: Vec<ManagedFeed>,
+}
+
+impl OracleFeedRegistry {
+    /// Get the serialized size of the registry account
+    pub fn get_size() -> usize {
+        // is_initialized, authority
+        1 + 32
+            // feeds: length prefix, then asset id + controller per feed
+            + 4 + MAX_MANAGED_FEEDS * (4 + MAX_MANAGED_FEED_ASSET_ID_LEN + 32)
+    }
+
+    /// Address of an authority's feed registry
+    pub fn find_address(program_id: &Pubkey, authority: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"oracle_feed_registry", authority.as_ref()], program_id)
+    }
+
+    /// Address of the controller for `asset_id` in `registry`
+    pub fn find_feed_address(program_id: &Pubkey, registry: &Pubkey, asset_id: &str) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"managed_feed", registry.as_ref(), asset_id.as_bytes()], program_id)
+    }
+
+    /// Controller registered for `asset_id`, if any
+    pub fn lookup(&self, asset_id: &str) -> Option<Pubkey> {
+        self.feeds
+            .iter()
+            .find(|feed| feed.asset_id == asset_id)
+            .map(|feed| feed.controller)
+    }
 }
 
 impl MultiOracleController {
@@ -890,10 +4348,33 @@ impl MultiOracleController {
         let sources_size = source_size.checked_mul(oracle_sources_count)
             .expect("Calculation error in MultiOracleController::get_size");
         
+        // Add space for a full price history ring
+        let history_size = std::mem::size_of::<PriceObservation>() * PRICE_HISTORY_LEN;
+        
+        // Add space for RedStone and Data Streams configurations with the most signers
+        let redstone_size = RedstoneConfig::get_size();
+        let streams_size = ChainlinkStreamsConfig::get_size();
+        
+        // Add space for a full circuit-breaker incident history
+        let incidents_size = std::mem::size_of::<CircuitBreakerIncident>() * MAX_CIRCUIT_BREAKER_INCIDENTS;
+        
+        // Add space for a full set of signed price publishers
+        let publishers_size = std::mem::size_of::<Pubkey>() * MAX_SIGNED_PRICE_PUBLISHERS;
+        
         base_size.checked_add(sources_size)
+            .and_then(|size| size.checked_add(history_size))
+            .and_then(|size| size.checked_add(redstone_size))
+            .and_then(|size| size.checked_add(streams_size))
+            .and_then(|size| size.checked_add(incidents_size))
+            .and_then(|size| size.checked_add(publishers_size))
             .expect("Calculation error in MultiOracleController::get_size")
     }
     
+    /// Address of the controller `InitializeOracleController` creates for `asset_id`
+    pub fn find_address(program_id: &Pubkey, asset_id: &str) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"oracle_controller", asset_id.as_bytes()], program_id)
+    }
+    
     /// Create a new oracle controller
     pub fn new(
         authority: Pubkey, 
@@ -919,6 +4400,7 @@ impl MultiOracleController {
                 circuit_breaker_reason: None,
                 is_fallback_price: false,
                 max_deviation_bps: 0,
+                spot_price: 0,
             },
             health: OracleHealthStatus {
                 last_checked: 0,
@@ -932,7 +4414,91 @@ impl MultiOracleController {
             emergency_price: None,
             emergency_price_timestamp: 0,
             emergency_price_expiration: 86400, // 24 hours default
+            price_floor: 0,
+            price_ceiling: 0,
+            pyth_feed_id: None,
+            price_history: Vec::new(),
+            price_history_next: 0,
+            ema_alpha_bps: 0,
+            redstone: None,
+            chainlink_streams: None,
+            quorum_policy: QuorumPolicy::MinOracles,
+            circuit_breaker_incidents: Vec::new(),
+            circuit_breaker_auto_recovery: false,
+            circuit_breaker_max_cooldown: 86400, // 24 hours default
+            circuit_breaker_trips: 0,
+            staleness_policy: StalenessPolicy::default(),
+            aggregation_strategy: AggregationStrategy::WeightedMean,
+            signed_price_publishers: Vec::new(),
+        }
+    }
+
+    /// Consensus price to publish for a new `spot_price`: the EMA of the previous
+    /// consensus and the spot price when smoothing is on and a regular consensus exists
+    pub fn smoothed_price(&self, spot_price: u64) -> Option<u64> {
+        let previous = &self.last_consensus;
+        if self.ema_alpha_bps == 0 || previous.price == 0 || previous.is_fallback_price {
+            return Some(spot_price);
+        }
+        let alpha = Bps(self.ema_alpha_bps.min(Bps::MAX.0));
+        let weighted_spot = MicroUsd(spot_price).apply_bps(alpha)?;
+        let weighted_previous = MicroUsd(previous.price).apply_bps(Bps(Bps::MAX.0 - alpha.0))?;
+        weighted_spot.checked_add(weighted_previous).map(|price| price.0)
+    }
+
+    /// Record a consensus price, overwriting the oldest observation once the ring is full
+    pub fn record_price(&mut self, price: u64, timestamp: i64) {
+        let observation = PriceObservation { price, timestamp };
+        if self.price_history.len() < PRICE_HISTORY_LEN {
+            self.price_history.push(observation);
+        } else {
+            let slot = self.price_history_next as usize % PRICE_HISTORY_LEN;
+            self.price_history[slot] = observation;
+            self.price_history_next = ((slot + 1) % PRICE_HISTORY_LEN) as u8;
+        }
+    }
+
+    /// Time-weighted average of the recorded consensus prices over the `window_seconds`
+    /// ending at `current_time`. Each price counts until the next observation; when the
+    /// history does not reach back to the window start only the covered part is averaged.
+    /// Returns None without observations in or before the window.
+    pub fn get_twap(&self, window_seconds: i64, current_time: i64) -> Option<u64> {
+        if window_seconds <= 0 {
+            return None;
         }
+        let window_start = current_time.checked_sub(window_seconds)?;
+
+        let mut observations: Vec<PriceObservation> = self.price_history.iter()
+            .filter(|observation| observation.timestamp <= current_time)
+            .copied()
+            .collect();
+        observations.sort_by_key(|observation| observation.timestamp);
+
+        let mut weighted_sum: u128 = 0;
+        let mut covered: u128 = 0;
+        for (i, observation) in observations.iter().enumerate() {
+            let start = observation.timestamp.max(window_start);
+            let end = observations.get(i + 1).map_or(current_time, |next| next.timestamp);
+            if end <= start {
+                continue;
+            }
+            let duration = (end - start) as u128;
+            weighted_sum = weighted_sum.checked_add((observation.price as u128).checked_mul(duration)?)?;
+            covered = covered.checked_add(duration)?;
+        }
+
+        if covered == 0 {
+            // Only an observation taken at `current_time` itself falls in the window
+            return observations.last()
+                .filter(|observation| observation.timestamp >= window_start)
+                .map(|observation| observation.price);
+        }
+        u64::try_from(weighted_sum / covered).ok()
+    }
+
+    /// Check whether both price bounds are configured
+    pub fn has_price_bounds(&self) -> bool {
+        self.price_floor > 0 && self.price_ceiling >= self.price_floor
     }
     
     /// Check if emergency price is valid
@@ -957,11 +4523,54 @@ impl MultiOracleController {
         }
     }
     
+    /// Whether the contributing sources, given as (weight, is_required), meet the quorum policy
+    pub fn quorum_met(&self, contributors: &[(u8, bool)]) -> bool {
+        let required_sources = self.oracle_sources.iter()
+            .filter(|source| source.is_active && source.is_required)
+            .count();
+        let required = contributors.iter().filter(|(_, is_required)| *is_required).count();
+        let optional = contributors.len() - required;
+        let weight: u32 = contributors.iter().map(|(weight, _)| *weight as u32).sum();
+        
+        !contributors.is_empty() && match self.quorum_policy {
+            QuorumPolicy::MinOracles => {
+                contributors.len() >= self.min_required_oracles as usize && required >= required_sources
+            },
+            QuorumPolicy::NOfM { n } => contributors.len() >= n as usize,
+            QuorumPolicy::RequiredPlus { optional: needed } => {
+                required >= required_sources && optional >= needed as usize
+            },
+            QuorumPolicy::MinWeight { min_weight } => weight >= min_weight as u32,
+        }
+    }
+    
+    /// Oracle accounts to pass to `UpdateOracleConsensus`: every active source
+    /// once, in source order
+    pub fn consensus_accounts(&self) -> Vec<Pubkey> {
+        let mut accounts: Vec<Pubkey> = Vec::with_capacity(self.oracle_sources.len());
+        for source in self.oracle_sources.iter().filter(|source| source.is_active) {
+            if !accounts.contains(&source.pubkey) {
+                accounts.push(source.pubkey);
+            }
+        }
+        accounts
+    }
+    
+    /// Check whether an oracle account is already a source of this controller
+    pub fn has_oracle_source(&self, pubkey: &Pubkey) -> bool {
+        self.oracle_sources.iter().any(|source| source.pubkey == *pubkey)
+    }
+    
     /// Add a new oracle source
     pub fn add_oracle_source(&mut self, oracle_source: OracleSource) -> Result<(), ProgramError> {
         // Check if oracle already exists
-        if self.oracle_sources.iter().any(|source| source.pubkey == oracle_source.pubkey) {
-            return Err(ProgramError::InvalidArgument);
+        if self.has_oracle_source(&oracle_source.pubkey) {
+            return Err(VCoinError::OracleSourceAlreadyExists.into());
+        }
+        
+        // Controller accounts only have room for so many sources
+        if self.oracle_sources.len() >= MAX_ORACLE_SOURCES {
+            return Err(VCoinError::OracleSourceLimitReached.into());
         }
         
         // Add the oracle
@@ -976,6 +4585,24 @@ impl MultiOracleController {
         Ok(())
     }
     
+    /// Remove an oracle source, returning its configuration
+    pub fn remove_oracle_source(&mut self, pubkey: &Pubkey) -> Result<OracleSource, ProgramError> {
+        let oracle_idx = self.oracle_sources.iter().position(|source| &source.pubkey == pubkey)
+            .ok_or(VCoinError::OracleSourceNotFound)?;
+        let removed = self.oracle_sources.remove(oracle_idx);
+        
+        // Update health status
+        self.health.total_oracles = self.oracle_sources.len() as u8;
+        if removed.is_active {
+            self.health.active_oracles = self.health.active_oracles.saturating_sub(1);
+        }
+        if self.health.active_oracles < self.min_required_oracles {
+            self.health.is_degraded = true;
+        }
+        
+        Ok(removed)
+    }
+    
     /// Update an existing oracle source
     pub fn update_oracle_source(
         &mut self, 
@@ -988,7 +4615,12 @@ impl MultiOracleController {
     ) -> Result<(), ProgramError> {
         // Find the oracle
         let oracle_idx = self.oracle_sources.iter().position(|source| &source.pubkey == pubkey)
-            .ok_or(ProgramError::InvalidArgument)?;
+            .ok_or(VCoinError::OracleSourceNotFound)?;
+        
+        // Ensure weight is within range before changing anything
+        if weight.is_some_and(|weight| weight > 100) {
+            return Err(VCoinError::InvalidPriceOracleParams.into());
+        }
         
         // Update fields if provided
         if let Some(is_active) = is_active {
@@ -1001,13 +4633,12 @@ impl MultiOracleController {
                 }
             }
             self.oracle_sources[oracle_idx].is_active = is_active;
+            if self.health.active_oracles < self.min_required_oracles {
+                self.health.is_degraded = true;
+            }
         }
         
         if let Some(weight) = weight {
-            // Ensure weight is within range
-            if weight > 100 {
-                return Err(ProgramError::InvalidArgument);
-            }
             self.oracle_sources[oracle_idx].weight = weight;
         }
         
@@ -1027,7 +4658,8 @@ impl MultiOracleController {
     }
     
     /// Activate circuit breaker
-    pub fn activate_circuit_breaker(&mut self, reason: String, current_time: i64) {
+    pub fn activate_circuit_breaker(&mut self, trigger: CircuitBreakerTrigger, current_time: i64) {
+        let reason = trigger.reason();
         self.circuit_breaker_active = true;
         self.circuit_breaker_activated_at = current_time;
         self.circuit_breaker_reason = Some(reason.clone());
@@ -1038,12 +4670,34 @@ impl MultiOracleController {
         
         // Mark system as degraded
         self.health.is_degraded = true;
+        
+        // Every trip before the breaker clears doubles the cooldown
+        self.circuit_breaker_trips = self.circuit_breaker_trips.saturating_add(1);
+        
+        // Record the incident, keeping the account within its allocated history
+        if self.circuit_breaker_incidents.len() >= MAX_CIRCUIT_BREAKER_INCIDENTS {
+            self.circuit_breaker_incidents.remove(0);
+        }
+        self.circuit_breaker_incidents.push(CircuitBreakerIncident {
+            trigger,
+            activated_at: current_time,
+            resolved_at: None,
+            resolved_by: None,
+        });
     }
     
-    /// Deactivate circuit breaker
-    pub fn deactivate_circuit_breaker(&mut self) {
+    /// Deactivate circuit breaker, closing the open incidents as resolved by `resolver`
+    /// (None for an automatic recovery)
+    pub fn deactivate_circuit_breaker(&mut self, resolver: Option<Pubkey>, current_time: i64) {
         self.circuit_breaker_active = false;
         self.circuit_breaker_reason = None;
+        self.circuit_breaker_trips = 0;
+        
+        // A breaker tripped again while active leaves several incidents open
+        for incident in self.circuit_breaker_incidents.iter_mut().filter(|incident| incident.resolved_at.is_none()) {
+            incident.resolved_at = Some(current_time);
+            incident.resolved_by = resolver;
+        }
         
         // Update last consensus
         self.last_consensus.circuit_breaker_active = false;
@@ -1055,6 +4709,15 @@ impl MultiOracleController {
         }
     }
     
+    /// Cooldown of the current trip: the base cooldown doubled for every earlier
+    /// trip since the breaker last cleared, capped at `circuit_breaker_max_cooldown`
+    pub fn effective_circuit_breaker_cooldown(&self) -> u32 {
+        let doublings = self.circuit_breaker_trips.saturating_sub(1).min(31) as u32;
+        let escalated = (self.circuit_breaker_cooldown as u64) << doublings;
+        let cap = self.circuit_breaker_max_cooldown.max(self.circuit_breaker_cooldown);
+        escalated.min(cap as u64) as u32
+    }
+    
     /// Check if circuit breaker cooldown period has passed
     pub fn has_circuit_breaker_cooldown_passed(&self, current_time: i64) -> bool {
         if !self.circuit_breaker_active {
@@ -1062,12 +4725,20 @@ impl MultiOracleController {
         }
         
         let cooldown_end = self.circuit_breaker_activated_at
-            .checked_add(self.circuit_breaker_cooldown as i64)
+            .checked_add(self.effective_circuit_breaker_cooldown() as i64)
             .unwrap_or(i64::MAX);
         
         current_time >= cooldown_end
     }
     
+    /// Whether the next consensus update is a probation attempt that clears the
+    /// circuit breaker on success and trips it again on failure
+    pub fn in_circuit_breaker_probation(&self, current_time: i64) -> bool {
+        self.circuit_breaker_active
+            && self.circuit_breaker_auto_recovery
+            && self.has_circuit_breaker_cooldown_passed(current_time)
+    }
+    
     /// Record a new price from an oracle
     pub fn record_oracle_price(
         &mut self, 
@@ -1088,17 +4759,18 @@ impl MultiOracleController {
         Ok(())
     }
     
-    /// Record an oracle failure
-    pub fn record_oracle_failure(&mut self, oracle_pubkey: &Pubkey) -> Result<(), ProgramError> {
+    /// Record an oracle failure, returning the source's consecutive failure count
+    pub fn record_oracle_failure(&mut self, oracle_pubkey: &Pubkey) -> Result<u8, ProgramError> {
         // Find the oracle
         let oracle_idx = self.oracle_sources.iter().position(|source| &source.pubkey == oracle_pubkey)
             .ok_or(ProgramError::InvalidArgument)?;
         
         // Update failure count
-        self.oracle_sources[oracle_idx].consecutive_failures += 1;
+        let failures = self.oracle_sources[oracle_idx].consecutive_failures.saturating_add(1);
+        self.oracle_sources[oracle_idx].consecutive_failures = failures;
         
         // If too many consecutive failures, deactivate
-        if self.oracle_sources[oracle_idx].consecutive_failures >= 5 {
+        if failures >= MAX_CONSECUTIVE_ORACLE_FAILURES {
             if self.oracle_sources[oracle_idx].is_active {
                 self.oracle_sources[oracle_idx].is_active = false;
                 self.health.active_oracles = self.health.active_oracles.saturating_sub(1);
@@ -1110,6 +4782,6 @@ impl MultiOracleController {
             }
         }
         
-        Ok(())
+        Ok(failures)
     }
 } 
\ No newline at end of file
@@ -0,0 +1,240 @@
+//! Which accounts the access check accepts as an authority or a guardian set.
+
+mod common;
+
+use borsh::BorshDeserialize;
+use common::{instruction_fixtures, presale, process, process_data, with_emergency_state, TestAccount};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey, rent::Rent, system_program};
+use vcoin_program::{
+    error::VCoinError,
+    instruction::VCoinInstruction,
+    state::{AccessControl, AccessRole, BugBountyEscrow, EmergencyMode, Multisig, PresaleState, Role, RoleGrant, RollingVolume, StablecoinLimits, Timelock},
+};
+
+fn bounty(mint: Pubkey, guardians: &[Pubkey]) -> BugBountyEscrow {
+    BugBountyEscrow {
+        is_initialized: true,
+        authority: Pubkey::new_unique(),
+        mint,
+        escrow: Pubkey::new_unique(),
+        guardians: guardians.to_vec(),
+        guardian_threshold: guardians.len() as u8,
+        total_funded: 0,
+        total_committed: 0,
+        total_paid: 0,
+        num_awards: 0,
+        bump: 255,
+    }
+}
+
+#[test]
+fn guardian_sets_must_be_the_presale_mints_bounty() {
+    let state = presale();
+    let guardians = [Pubkey::new_unique(), Pubkey::new_unique()];
+    let declare = VCoinInstruction::DeclareForcedRefund { reason_hash: [3; 32] };
+    let accounts_with = |bounty_key: Pubkey, bounty: &BugBountyEscrow| {
+        vec![
+            TestAccount::program(Pubkey::new_unique(), &state, PresaleState::get_size_for_buyers(1)),
+            TestAccount::program(bounty_key, bounty, BugBountyEscrow::get_size()).readonly(),
+            TestAccount::signer(guardians[0]),
+            TestAccount::signer(guardians[1]),
+        ]
+    };
+
+    // The guardians of another mint's bounty cannot stop this presale
+    let other_mint = Pubkey::new_unique();
+    let (other_bounty, _) = BugBountyEscrow::find_address(&vcoin_program::id(), &other_mint);
+    let mut accounts = accounts_with(other_bounty, &bounty(other_mint, &guardians));
+    let outcome = process(&mut accounts, &declare);
+    assert_eq!(outcome.result, Err(ProgramError::InvalidSeeds));
+    assert!(outcome.logged("is not the guardian set of mint"));
+    assert_eq!(accounts[0].state::<PresaleState>().forced_refund, None);
+
+    // Nor can a program account of the same size that is not at the bounty PDA,
+    // even when its bytes name the presale's mint
+    let mut accounts = accounts_with(Pubkey::new_unique(), &bounty(state.mint, &guardians));
+    let outcome = process(&mut accounts, &declare);
+    assert_eq!(outcome.result, Err(ProgramError::InvalidSeeds));
+    assert!(!outcome.logged("Instruction: "));
+
+    let (bounty_address, _) = BugBountyEscrow::find_address(&vcoin_program::id(), &state.mint);
+    let mut accounts = accounts_with(bounty_address, &bounty(state.mint, &guardians));
+    assert_eq!(process(&mut accounts, &declare).result, Ok(()));
+}
+
+#[test]
+fn authority_accounts_must_sit_at_their_own_pda() {
+    let program_id = vcoin_program::id();
+    let mint = Pubkey::new_unique();
+    let keys = [Pubkey::new_unique(), Pubkey::new_unique()];
+    let pause = VCoinInstruction::EmergencyPause { reason: None };
+    let run = |authority: TestAccount| {
+        let mut accounts = vec![authority, TestAccount::signer(keys[0]), TestAccount::signer(keys[1])];
+        process(&mut accounts, &pause)
+    };
+
+    // A multisig only counts at the PDA of its base key
+    let base = Pubkey::new_unique();
+    let (multisig_address, bump) = Multisig::find_address(&program_id, &base);
+    let multisig = Multisig { is_initialized: true, base, threshold: 2, signers: keys.to_vec(), bump };
+    let outcome = run(TestAccount::program(Pubkey::new_unique(), &multisig, Multisig::get_size()));
+    assert_eq!(outcome.result, Err(VCoinError::Unauthorized.into()));
+    assert!(outcome.logged("Authority must sign transaction"));
+    assert!(run(TestAccount::program(multisig_address, &multisig, Multisig::get_size())).logged("Instruction: Emergency Pause"));
+
+    // An access control only counts at its mint's PDA
+    let (access_control_address, bump) = AccessControl::find_address(&program_id, &mint);
+    let access_control = AccessControl {
+        is_initialized: true,
+        mint,
+        grants: vec![RoleGrant { member: keys[0], role: Role::EmergencyGuardian }],
+        bump,
+    };
+    let outcome = run(TestAccount::program(Pubkey::new_unique(), &access_control, AccessControl::get_size()));
+    assert_eq!(outcome.result, Err(VCoinError::Unauthorized.into()));
+    assert!(!outcome.logged("Approved by"));
+    assert!(run(TestAccount::program(access_control_address, &access_control, AccessControl::get_size())).logged("Approved by"));
+
+    // A timelock only counts at its mint's PDA; elsewhere it is an unsigned authority
+    let (timelock_address, bump) = Timelock::find_address(&program_id, &mint);
+    let timelock = Timelock {
+        is_initialized: true,
+        mint,
+        proposer: keys[0],
+        delay: 86_400,
+        next_operation_id: 0,
+        queued: Vec::new(),
+        bump,
+    };
+    let outcome = run(TestAccount::program(Pubkey::new_unique(), &timelock, Timelock::get_size()));
+    assert!(outcome.logged("Authority must sign transaction"));
+    let outcome = run(TestAccount::program(timelock_address, &timelock, Timelock::get_size()));
+    assert!(outcome.logged("A timelock cannot approve this instruction"));
+}
+
+#[test]
+fn multisigs_are_created_at_their_base_keys_pda() {
+    let base = Pubkey::new_unique();
+    let (multisig_address, bump) = Multisig::find_address(&vcoin_program::id(), &base);
+    let keys = vec![Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+    let initialize = VCoinInstruction::InitializeMultisig { signers: keys.clone(), threshold: 2 };
+    let accounts_at = |multisig: Pubkey| {
+        vec![
+            TestAccount::signer(Pubkey::new_unique()),
+            TestAccount::empty(multisig),
+            TestAccount::signer(base).readonly(),
+            TestAccount::new(system_program::ID, Pubkey::default(), Vec::new()).readonly(),
+        ]
+    };
+
+    let mut accounts = accounts_at(Pubkey::new_unique());
+    assert_eq!(process(&mut accounts, &initialize).result, Err(ProgramError::InvalidSeeds));
+
+    let mut accounts = accounts_at(multisig_address);
+    assert_eq!(process(&mut accounts, &initialize).result, Ok(()));
+    assert_eq!(accounts[1].owner, vcoin_program::id());
+    assert_eq!(accounts[1].lamports, Rent::default().minimum_balance(Multisig::get_size()));
+    assert_eq!(
+        accounts[1].state::<Multisig>(),
+        Multisig { is_initialized: true, base, threshold: 2, signers: keys, bump },
+    );
+}
+
+#[test]
+fn every_authority_handler_accepts_an_access_control_role_holder() {
+    let program_id = vcoin_program::id();
+    let mint = Pubkey::new_unique();
+    let holder = Pubkey::new_unique();
+    let (access_control_address, bump) = AccessControl::find_address(&program_id, &mint);
+
+    for (name, data) in instruction_fixtures() {
+        let instruction = VCoinInstruction::try_from_slice(&data).unwrap();
+        // The loader itself needs the upgrade authority's signature
+        if instruction.access_role() != AccessRole::Authority || instruction == VCoinInstruction::PermanentlyDisableUpgrades {
+            continue;
+        }
+        let access_control = AccessControl {
+            is_initialized: true,
+            mint,
+            grants: vec![RoleGrant { member: holder, role: instruction.required_role() }],
+            bump,
+        };
+        let mut accounts = vec![TestAccount::program(access_control_address, &access_control, AccessControl::get_size())];
+        accounts.extend((0..16).map(|_| TestAccount::empty(Pubkey::new_unique())));
+        let mut accounts = with_emergency_state(&instruction, accounts, EmergencyMode::Normal);
+        // The fee handler checks its token program and emergency state first
+        if name == "SetTransferFee" {
+            accounts[2].key = spl_token_2022::ID;
+            accounts[3] = TestAccount::emergency_state(&accounts[1].key, EmergencyMode::Normal);
+        }
+        accounts.push(TestAccount::signer(holder));
+
+        // The dispatch check and then the handler accept the holder's signature;
+        // the empty accounts fail the handler later on
+        let outcome = process_data(&mut accounts, &data);
+        if outcome.logged("built with the `simulation` feature") {
+            continue;
+        }
+        let approvals = outcome.logs.iter().filter(|line| line.contains("Approved by")).count();
+        assert_eq!(approvals, 2, "{name}: {:?}", outcome.logs);
+    }
+}
+
+#[test]
+fn multisig_authorities_update_stablecoin_limits() {
+    let program_id = vcoin_program::id();
+    let keys = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+    let base = Pubkey::new_unique();
+    let (multisig_address, bump) = Multisig::find_address(&program_id, &base);
+    let multisig = Multisig { is_initialized: true, base, threshold: 2, signers: keys.to_vec(), bump };
+    let usdc = Pubkey::new_unique();
+    let mut state = PresaleState { authority: multisig_address, ..presale() };
+    state.allowed_stablecoins.push(usdc);
+    let presale_key = Pubkey::new_unique();
+    let (limits_address, _) = Pubkey::find_program_address(
+        &[b"stablecoin_limits", presale_key.as_ref(), usdc.as_ref()],
+        &program_id,
+    );
+    let limits = StablecoinLimits {
+        is_initialized: true,
+        presale: presale_key,
+        stablecoin_mint: usdc,
+        min_purchase: 0,
+        max_purchase: 0,
+        window_cap: 0,
+        cap_expires_at: 0,
+        window: RollingVolume::new(86_400),
+    };
+    let set_limits = VCoinInstruction::SetStablecoinLimits {
+        min_purchase: 10_000_000,
+        max_purchase: 5_000_000_000,
+        window_duration: 86_400,
+        window_cap: 100_000_000_000,
+        cap_expires_at: 0,
+    };
+    let accounts_signed_by = |signers: &[Pubkey]| {
+        let mut accounts = vec![
+            TestAccount::program(multisig_address, &multisig, Multisig::get_size()).readonly(),
+            TestAccount::program(presale_key, &state, PresaleState::get_size_for_buyers(1)),
+            TestAccount::mint(usdc, 6).readonly(),
+            TestAccount::program(limits_address, &limits, StablecoinLimits::get_size()),
+            TestAccount::new(system_program::ID, Pubkey::default(), Vec::new()).readonly(),
+            TestAccount::emergency_state(&state.mint, EmergencyMode::Normal),
+        ];
+        accounts.extend(signers.iter().map(|key| TestAccount::signer(*key)));
+        accounts
+    };
+
+    // One key of a 2-of-3 multisig cannot change the limits
+    let mut accounts = accounts_signed_by(&keys[..1]);
+    let outcome = process(&mut accounts, &set_limits);
+    assert_eq!(outcome.result, Err(VCoinError::MultisigThresholdNotMet.into()));
+    assert_eq!(accounts[3].state::<StablecoinLimits>(), limits);
+
+    // Two keys can, with no key signing as the authority itself
+    let mut accounts = accounts_signed_by(&keys[1..]);
+    let outcome = process(&mut accounts, &set_limits);
+    assert_eq!(outcome.result, Ok(()));
+    let updated: StablecoinLimits = accounts[3].state();
+    assert_eq!((updated.min_purchase, updated.max_purchase, updated.window_cap), (10_000_000, 5_000_000_000, 100_000_000_000));
+}
@@ -0,0 +1,95 @@
+//! Unit conversions between micro-USD, token base units and basis points.
+
+use vcoin_program::amounts::{integer_sqrt, Bps, MicroUsd, TokenAmount, MAX_DECIMAL_SCALE};
+
+#[test]
+fn purchase_converts_using_mint_decimals() {
+    // $100 at $0.03 per token
+    let amount = MicroUsd(100_000_000);
+    let price = MicroUsd(30_000);
+
+    assert_eq!(amount.to_tokens(price, 6), Some(TokenAmount(3_333_333_333)));
+    assert_eq!(amount.to_tokens(price, 9), Some(TokenAmount(3_333_333_333_333)));
+    assert_eq!(amount.to_tokens(MicroUsd::ZERO, 6), None);
+
+    // Round trip back to micro-USD loses at most the rounding remainder
+    let tokens = amount.to_tokens(price, 9).unwrap();
+    assert_eq!(tokens.to_micro_usd(price, 9), Some(MicroUsd(99_999_999)));
+}
+
+#[test]
+fn stablecoin_amounts_follow_the_stablecoin_decimals() {
+    // $25 paid in 6, 8 and 2 decimal stablecoins
+    assert_eq!(MicroUsd::from_stablecoin(25_000_000, 6), Some(MicroUsd(25_000_000)));
+    assert_eq!(MicroUsd::from_stablecoin(2_500_000_000, 8), Some(MicroUsd(25_000_000)));
+    assert_eq!(MicroUsd::from_stablecoin(2_500, 2), Some(MicroUsd(25_000_000)));
+
+    assert_eq!(MicroUsd(12_500_000).to_stablecoin(8), Some(1_250_000_000));
+    // Below 6 decimals the payout rounds down to whole base units
+    assert_eq!(MicroUsd(12_509_999).to_stablecoin(2), Some(1_250));
+}
+
+#[test]
+fn split_half_keeps_odd_micro_dollar() {
+    assert_eq!(MicroUsd(25_000_001).split_half(), (MicroUsd(12_500_000), MicroUsd(12_500_001)));
+}
+
+#[test]
+fn bps_math_does_not_overflow_large_supplies() {
+    let supply = TokenAmount(u64::MAX / 2);
+    assert_eq!(supply.apply_bps(Bps(1_000)), Some(TokenAmount(u64::MAX / 2 / 10)));
+    assert_eq!(supply.apply_bps(Bps::MAX), Some(supply));
+
+    assert_eq!(Bps::change_between(MicroUsd(1_000_000), MicroUsd(1_100_000)), Some(1_000));
+    assert_eq!(Bps::change_between(MicroUsd(1_000_000), MicroUsd(950_000)), Some(-500));
+    assert_eq!(Bps::change_between(MicroUsd::ZERO, MicroUsd(1)), None);
+}
+
+/// Reference conversion on decimal strings: keep six fractional digits and round
+/// half up on the seventh
+fn micro_usd_reference(mantissa: i128, scale: u32) -> Option<u64> {
+    let digits = format!("{:0>width$}", mantissa, width = scale as usize + 1);
+    let (whole, fraction) = digits.split_at(digits.len() - scale as usize);
+    let fraction = format!("{:0<7}", fraction);
+    let truncated: u128 = format!("{}{}", whole, &fraction[..6]).parse().ok()?;
+    let rounded = if fraction.as_bytes()[6] >= b'5' { truncated + 1 } else { truncated };
+    u64::try_from(rounded).ok()
+}
+
+#[test]
+fn decimal_prices_round_to_nearest_micro_dollar() {
+    // $0.0299995 rounds up, $0.0299994 rounds down
+    assert_eq!(MicroUsd::from_decimal(299_995, 7), Some(MicroUsd(30_000)));
+    assert_eq!(MicroUsd::from_decimal(299_994, 7), Some(MicroUsd(29_999)));
+    // Whole dollars with fewer than six decimals scale up
+    assert_eq!(MicroUsd::from_decimal(3, 0), Some(MicroUsd(3_000_000)));
+
+    assert_eq!(MicroUsd::from_decimal(-1, 6), None);
+    assert_eq!(MicroUsd::from_decimal(1, MAX_DECIMAL_SCALE + 1), None);
+    assert_eq!(MicroUsd::from_decimal(i128::MAX, 0), None);
+
+    let mantissas = [
+        0, 1, 4, 5, 9, 10, 49, 50, 99, 123_456_789, 499_999_999, 500_000_000, 999_999_999_999,
+        u64::MAX as i128, u64::MAX as i128 + 1, 10i128.pow(25) + 5 * 10i128.pow(18), i128::MAX,
+    ];
+    for scale in 0..=MAX_DECIMAL_SCALE {
+        for mantissa in mantissas {
+            assert_eq!(
+                MicroUsd::from_decimal(mantissa, scale).map(|amount| amount.0),
+                micro_usd_reference(mantissa, scale),
+                "{}e-{}", mantissa, scale,
+            );
+        }
+    }
+}
+
+#[test]
+fn integer_sqrt_rounds_down() {
+    assert_eq!(integer_sqrt(0), 0);
+    assert_eq!(integer_sqrt(1), 1);
+    assert_eq!(integer_sqrt(15), 3);
+    assert_eq!(integer_sqrt(16), 4);
+    assert_eq!(integer_sqrt(1_000_000_000_000), 1_000_000);
+    assert_eq!(integer_sqrt(u64::MAX as u128 * u64::MAX as u128), u64::MAX as u128);
+    assert_eq!(integer_sqrt(u128::MAX), u64::MAX as u128);
+}
@@ -0,0 +1,1310 @@
+//! Golden Borsh layouts for every instruction, account struct and event.
+//!
+//! Each canonical instance is serialized and compared against the hex in
+//! `tests/fixtures/borsh_layouts.txt`. A mismatch means the on-chain ABI or
+//! account layout changed; if that is intended, regenerate the fixture with
+//! `UPDATE_GOLDEN=1 cargo test --test borsh_layouts` and review the diff.
+
+use borsh::to_vec;
+use solana_program::pubkey::Pubkey;
+use vcoin_program::events::VCoinEvent;
+use vcoin_program::state::*;
+use vcoin_program::{CircuitBreakerHistory, ClaimableAmount, LaunchChecklistItem, ProgramVersion, PurchaseValidation, RecoveryStateType, VCoinInstruction, VestingSchedulePreview};
+
+const FIXTURE_PATH: &str = "tests/fixtures/borsh_layouts.txt";
+
+fn key(byte: u8) -> Pubkey {
+    Pubkey::new_from_array([byte; 32])
+}
+
+fn redstone_config() -> RedstoneConfig {
+    RedstoneConfig { feed_id: [0x45; 32], signers: vec![[0x11; 20], [0x22; 20]], signer_threshold: 2 }
+}
+
+fn chainlink_streams_config() -> ChainlinkStreamsConfig {
+    let mut feed_id = [0x33; 32];
+    feed_id[..2].copy_from_slice(&[0, 3]);
+    ChainlinkStreamsConfig { feed_id, signers: vec![[0x44; 20], [0x55; 20]], signer_threshold: 2 }
+}
+
+fn oracle_source() -> OracleSource {
+    OracleSource {
+        pubkey: key(7),
+        oracle_type: OracleType::Switchboard,
+        is_active: true,
+        weight: 60,
+        max_deviation_bps: 500,
+        max_staleness_seconds: 900,
+        last_valid_price: 1_250_000,
+        last_update_timestamp: 1_700_000_000,
+        consecutive_failures: 1,
+        is_required: true,
+    }
+}
+
+fn instruction_vectors() -> Vec<(&'static str, VCoinInstruction)> {
+    use VCoinInstruction::*;
+    vec![
+        ("InitializeToken", InitializeToken {
+            name: "VCoin".to_string(),
+            symbol: "VCN".to_string(),
+            decimals: 6,
+            initial_supply: 1_000_000_000,
+            transfer_fee_basis_points: Some(500),
+            maximum_fee_rate: None,
+            emergency_authority: Some(key(9)),
+            allocations: vec![
+                InitialAllocation { escrow: AllocationEscrow::Vesting { vesting: key(4) }, amount: 350_000_000 },
+                InitialAllocation { escrow: AllocationEscrow::SupplyTreasury, amount: 550_000_000 },
+                InitialAllocation { escrow: AllocationEscrow::Liquidity, amount: 100_000_000 },
+            ],
+            allocation_plan: vec![
+                AllocationShare { category: AllocationCategory::Presale, bps: 2_000 },
+                AllocationShare { category: AllocationCategory::Team, bps: 3_500 },
+                AllocationShare { category: AllocationCategory::Treasury, bps: 3_500 },
+                AllocationShare { category: AllocationCategory::Liquidity, bps: 1_000 },
+            ],
+        }),
+        ("InitializePresale", InitializePresale {
+            start_time: 1_700_000_000,
+            end_time: 1_702_592_000,
+            token_price: 30_000,
+            hard_cap: 1_000_000_000_000,
+            soft_cap: 200_000_000_000,
+            min_purchase: 10_000_000,
+            max_purchase: 50_000_000_000,
+            purchase_cooldown: 60,
+            launch_window_duration: 900,
+            launch_window_max_per_wallet: 1_000_000_000,
+            allow_partial_fill: true,
+            daily_raise_cap: 100_000_000_000,
+        }),
+        ("BuyTokensWithStablecoin", BuyTokensWithStablecoin { amount: 25_000_000 }),
+        ("BuyTokens", BuyTokens { amount_usd: 25_000_000 }),
+        ("AddSupportedStablecoin", AddSupportedStablecoin { skip_if_exists: true }),
+        ("LaunchToken", LaunchToken),
+        ("ClaimRefund", ClaimRefund),
+        ("WithdrawLockedFunds", WithdrawLockedFunds),
+        ("InitializeVesting", InitializeVesting {
+            label: "team".to_string(),
+            total_tokens: 500_000_000,
+            start_time: 1_700_000_000,
+            release_interval: 2_592_000,
+            num_releases: 12,
+        }),
+        ("AddVestingBeneficiary", AddVestingBeneficiary {
+            beneficiary: key(1),
+            amount: 1_000_000,
+            schedule: Some(VestingSchedule {
+                start_time: 1_700_000_000,
+                cliff_duration: 31_536_000,
+                duration: 126_144_000,
+                release_interval: 2_592_000,
+            }),
+        }),
+        ("ReleaseVestedTokens", ReleaseVestedTokens { beneficiary: key(1), max_amount: Some(100_000) }),
+        ("UpdateTokenMetadata", UpdateTokenMetadata {
+            name: Some("VCoin".to_string()),
+            symbol: None,
+            uri: Some("https://vcoin.example/meta.json".to_string()),
+            uri_hash: Some([0x6d; 32]),
+        }),
+        ("SetTransferFee", SetTransferFee { transfer_fee_basis_points: 250, maximum_fee: 5_000_000 }),
+        ("EndPresale", EndPresale),
+        ("InitializeAutonomousController", InitializeAutonomousController {
+            initial_price: 30_000,
+            max_supply: 5_000_000_000_000_000,
+            price_source: SupplyPriceSource::MultiOracleController,
+        }),
+        ("UpdateOraclePrice", UpdateOraclePrice),
+        ("ExecuteAutonomousMint", ExecuteAutonomousMint),
+        ("ExecuteAutonomousBurn", ExecuteAutonomousBurn),
+        ("PermanentlyDisableUpgrades", PermanentlyDisableUpgrades),
+        ("DepositToBurnTreasury", DepositToBurnTreasury { amount: 42 }),
+        ("InitializeBurnTreasury", InitializeBurnTreasury),
+        ("ExpandPresaleAccount", ExpandPresaleAccount { additional_buyers: 10_000 }),
+        ("ClaimDevFundRefund", ClaimDevFundRefund),
+        ("EmergencyPause", EmergencyPause { reason: Some("incident".to_string()) }),
+        ("EmergencyResume", EmergencyResume),
+        ("RescueTokens", RescueTokens { amount: 42 }),
+        ("RecoverState", RecoverState { state_type: RecoveryStateType::Controller }),
+        ("InitializeOracleController", InitializeOracleController {
+            asset_id: "VCN/USD".to_string(),
+            min_required_oracles: 2,
+        }),
+        ("AddOracleSource", AddOracleSource {
+            oracle_type: OracleType::Pyth,
+            weight: 60,
+            max_deviation_bps: 500,
+            max_staleness_seconds: 900,
+            is_required: true,
+            skip_if_exists: false,
+        }),
+        ("UpdateOracleConsensus", UpdateOracleConsensus),
+        ("SetEmergencyPrice", SetEmergencyPrice { emergency_price: 31_000, expiration_seconds: 3_600 }),
+        ("ClearEmergencyPrice", ClearEmergencyPrice),
+        ("ResetCircuitBreaker", ResetCircuitBreaker),
+        ("UpdatePriceDirectly", UpdatePriceDirectly { new_price: 32_000 }),
+        ("FinalizePresale", FinalizePresale),
+        ("InitializePresaleRound", InitializePresaleRound {
+            round_index: 1,
+            kind: PresaleRoundKind::Private,
+            start_time: 1_700_000_000,
+            end_time: 1_700_604_800,
+            token_price: 20_000,
+            hard_cap: 100_000_000_000,
+            min_purchase: 1_000_000_000,
+            max_purchase: 10_000_000_000,
+            whitelist: vec![key(2), key(3)],
+        }),
+        ("SetKycConfig", SetKycConfig { kyc_required: true, attestor: key(4) }),
+        ("IssueKycCredential", IssueKycCredential { expires_at: 1_731_536_000 }),
+        ("ClosePresaleAccounts", ClosePresaleAccounts),
+        ("CommitComplianceHash", CommitComplianceHash {
+            kind: ComplianceArtifactKind::LegalMemo,
+            hash: [0xab; 32],
+        }),
+        ("SetStablecoinLimits", SetStablecoinLimits {
+            min_purchase: 10_000_000,
+            max_purchase: 5_000_000_000,
+            window_duration: 86_400,
+            window_cap: 100_000_000_000,
+            cap_expires_at: 1_702_592_000,
+        }),
+        ("InitializeAirdrop", InitializeAirdrop { merkle_root: [0x11; 32], total: 50_000_000_000_000 }),
+        ("ClaimAirdrop", ClaimAirdrop {
+            index: 8_200,
+            amount: 1_000_000_000,
+            proof: vec![[0x22; 32], [0x33; 32]],
+        }),
+        ("SetControllerPriceBounds", SetControllerPriceBounds {
+            price_floor: 500_000,
+            price_ceiling: 5_000_000,
+        }),
+        ("SetPresalePriceController", SetPresalePriceController { controller: Some(key(9)) }),
+        ("ValidatePurchase", ValidatePurchase { amount: 25_000_000 }),
+        ("RevokeVestingBeneficiary", RevokeVestingBeneficiary { beneficiary: key(1), burn_unvested: true }),
+        ("SetTreasuryAlarm", SetTreasuryAlarm { coverage_bps: 10_000 }),
+        ("ProposeBeneficiaryTransfer", ProposeBeneficiaryTransfer { beneficiary: key(1), new_beneficiary: Some(key(2)) }),
+        ("AcceptBeneficiaryTransfer", AcceptBeneficiaryTransfer { beneficiary: key(1) }),
+        ("AddVestingBeneficiaries", AddVestingBeneficiaries {
+            entries: vec![(key(1), 1_000_000), (key(2), 500_000)],
+        }),
+        ("PreviewVestingSchedule", PreviewVestingSchedule { beneficiary: key(1), start_index: 0 }),
+        ("SetBeneficiaryDestination", SetBeneficiaryDestination),
+        ("SetReleaseCrankTip", SetReleaseCrankTip { lamports: 5_000 }),
+        ("FundVesting", FundVesting { amount: 1_500_000 }),
+        ("SetReceiptTransferability", SetReceiptTransferability { transferable: true }),
+        ("MintGrantReceipt", MintGrantReceipt),
+        ("RedeemGrantReceipt", RedeemGrantReceipt { beneficiary: key(10) }),
+        ("GetVersion", GetVersion),
+        ("PauseVesting", PauseVesting { beneficiary: Some(key(10)) }),
+        ("ResumeVesting", ResumeVesting { beneficiary: None }),
+        ("AnnounceRefundWindow", AnnounceRefundWindow),
+        ("GetClaimableAmount", GetClaimableAmount { beneficiary: key(6) }),
+        ("SetKeeperRefund", SetKeeperRefund { enabled: true }),
+        ("NoticeWithdraw", NoticeWithdraw),
+        ("ExecuteWithdraw", ExecuteWithdraw),
+        ("CompleteLaunchChecklistItem", CompleteLaunchChecklistItem { item: LaunchChecklistItem::VestingFunded }),
+        ("SetPythFeedId", SetPythFeedId { feed_id: Some([0xef; 32]) }),
+        ("SetConsensusEma", SetConsensusEma { alpha_bps: 2_000 }),
+        ("SetRedstoneConfig", SetRedstoneConfig { config: Some(redstone_config()) }),
+        ("RemoveOracleSource", RemoveOracleSource { oracle: key(7) }),
+        ("SetOracleSourceActive", SetOracleSourceActive { oracle: key(7), is_active: false }),
+        ("UpdateOracleSourceConfig", UpdateOracleSourceConfig {
+            oracle: key(7),
+            weight: Some(40),
+            max_deviation_bps: None,
+            max_staleness_seconds: Some(600),
+            is_required: Some(false),
+        }),
+        ("SetChainlinkStreamsConfig", SetChainlinkStreamsConfig { config: Some(chainlink_streams_config()) }),
+        ("SubmitChainlinkStreamsReport", SubmitChainlinkStreamsReport { report: vec![0x66; 8] }),
+        ("SetQuorumPolicy", SetQuorumPolicy { policy: QuorumPolicy::RequiredPlus { optional: 1 } }),
+        ("GetCircuitBreakerIncidents", GetCircuitBreakerIncidents),
+        ("SetCircuitBreakerRecovery", SetCircuitBreakerRecovery {
+            auto_recovery: true,
+            cooldown_seconds: 600,
+            max_cooldown_seconds: 86_400,
+        }),
+        ("SetStalenessPolicy", SetStalenessPolicy {
+            policy: StalenessPolicy { purchase: 120, supply_adjustment: 600, display: 3_600 },
+        }),
+        ("InitializeBugBounty", InitializeBugBounty { guardians: vec![key(3), key(4), key(9)], guardian_threshold: 2 }),
+        ("FundBugBounty", FundBugBounty { amount: 10_000_000_000 }),
+        ("CommitBugBountyAward", CommitBugBountyAward {
+            disclosure_hash: [0x5a; 32],
+            researcher: key(6),
+            amount: 2_500_000_000,
+        }),
+        ("PayBugBountyAward", PayBugBountyAward),
+        ("OpenMintMigration", OpenMintMigration),
+        ("MigrateTokens", MigrateTokens { amount: 1_500_000_000 }),
+        ("RepointSupplyController", RepointSupplyController),
+        ("SetAggregationStrategy", SetAggregationStrategy {
+            strategy: AggregationStrategy::TrimmedMean { trim_bps: 2_000 },
+        }),
+        ("ConfigureSimulation", ConfigureSimulation {
+            unix_timestamp: 1_700_000_000,
+            prices: vec![30_000, 36_000, 27_000],
+        }),
+        ("StepSimulation", StepSimulation { seconds: 31_536_000 }),
+        ("CreateManagedFeed", CreateManagedFeed {
+            asset_id: "USDC/USD".to_string(),
+            min_required_oracles: 2,
+        }),
+        ("EnterMaintenanceMode", EnterMaintenanceMode { reason: Some("migration".to_string()) }),
+        ("SetStablecoinPegGuard", SetStablecoinPegGuard { band_bps: 200, action: DepegAction::Reject }),
+        ("SetSignedPricePublishers", SetSignedPricePublishers { publishers: vec![key(8)] }),
+        ("SubmitSignedPrice", SubmitSignedPrice {
+            publisher: key(8),
+            price: 2_000_200_000,
+            confidence: 100_000,
+            publish_time: 1_700_000_001,
+        }),
+        ("ApproveYieldVenue", ApproveYieldVenue { deposit_cap: 50_000_000_000 }),
+        ("DeployTreasuryYield", DeployTreasuryYield { amount: 10_000_000_000 }),
+        ("RecallTreasuryYield", RecallTreasuryYield { collateral_amount: Some(9_500_000_000) }),
+        ("UpdateControllerParameters", UpdateControllerParameters { parameters: Some(supply_parameters()) }),
+        ("SetSupplyRateLimit", SetSupplyRateLimit {
+            window_seconds: 86_400,
+            max_amount_per_window: 200_000_000_000_000,
+            max_operations_per_window: 2,
+        }),
+        ("InitializeSaleAnalytics", InitializeSaleAnalytics),
+        ("SetSupplyDistribution", SetSupplyDistribution { distribution: supply_distribution() }),
+        ("DistributeSupplyTreasury", DistributeSupplyTreasury),
+        ("UpdateMetadataAuthority", UpdateMetadataAuthority { new_authority: Some(key(9)) }),
+        ("AcceptMetadataAuthority", AcceptMetadataAuthority),
+        ("InitializeSupplyLedger", InitializeSupplyLedger),
+        ("SetSupplyCooldowns", SetSupplyCooldowns { mint_cooldown_seconds: 86_400, burn_cooldown_seconds: 43_200 }),
+        ("SetMetadataDisplayHints", SetMetadataDisplayHints {
+            display_hints: Some(MetadataDisplayHints { display_decimals: Some(2), ticker_aliases: vec!["VCOIN".to_string(), "VCN2".to_string()] }),
+        }),
+        ("CrankSupplyController", CrankSupplyController),
+        ("ReleaseLiquidityEscrow", ReleaseLiquidityEscrow { amount: 25_000_000 }),
+        ("SyncSupply", SyncSupply),
+        ("SweepWithheldFeesToBurn", SweepWithheldFeesToBurn),
+        ("InitializeBurnLog", InitializeBurnLog),
+        ("BurnTokens", BurnTokens { amount: 5_000_000 }),
+        ("DeclareForcedRefund", DeclareForcedRefund { reason_hash: [7; 32] }),
+        ("InitializeFeeSponsorship", InitializeFeeSponsorship { keeper: key(4), per_claim_lamports: 10_000, budget_lamports: 1_000_000_000 }),
+        ("UpdateFeeSponsorship", UpdateFeeSponsorship { keeper: key(5), per_claim_lamports: 15_000, additional_budget_lamports: 500_000_000 }),
+        ("InitializeMultisig", InitializeMultisig { signers: vec![key(10), key(11), key(12)], threshold: 2 }),
+        ("SweepTreasuryToCanonical", SweepTreasuryToCanonical),
+        ("InitializeTimelock", InitializeTimelock { proposer: key(2), delay: 172_800 }),
+        ("QueueTimelockOperation", QueueTimelockOperation {
+            operation: TimelockedOperation::SetTransferFee { mint: key(3), transfer_fee_basis_points: 50, maximum_fee: 1_000_000 },
+        }),
+        ("CancelTimelockOperation", CancelTimelockOperation { operation_id: 4 }),
+        ("InitializeAccessControl", InitializeAccessControl { admin: key(2) }),
+        ("GrantRole", GrantRole { member: key(4), role: Role::FeeManager }),
+        ("RevokeRole", RevokeRole { member: key(4), role: Role::Treasurer }),
+    ]
+}
+
+fn supply_distribution() -> SupplyDistribution {
+    SupplyDistribution {
+        staking_rewards: key(6),
+        staking_rewards_bps: 5_000,
+        liquidity: key(7),
+        liquidity_bps: 3_000,
+        operations: key(8),
+        operations_bps: 2_000,
+    }
+}
+
+fn supply_parameters() -> SupplyParameters {
+    SupplyParameters {
+        min_growth_for_mint_bps: 400,
+        min_decline_for_burn_bps: 600,
+        medium_growth_mint_rate_bps: 300,
+        high_growth_mint_rate_bps: 800,
+        medium_decline_burn_rate_bps: 400,
+        high_decline_burn_rate_bps: 900,
+        high_growth_threshold_bps: 1_200,
+        high_decline_threshold_bps: 1_500,
+        extreme_growth_threshold_bps: 2_500,
+        extreme_decline_threshold_bps: 3_500,
+        post_cap_mint_rate_bps: 150,
+        post_cap_burn_rate_bps: 250,
+    }
+}
+
+fn purchase_snapshot() -> PurchaseSnapshot {
+    PurchaseSnapshot {
+        amount: 25_000_000,
+        token_price: 30_000,
+        tokens: 833_333_333,
+        oracle: Some(OracleSnapshot {
+            controller: key(7),
+            price: 29_500,
+            confidence: 50,
+            timestamp: 1_700_000_090,
+            is_emergency_price: false,
+        }),
+        timestamp: 1_700_000_100,
+    }
+}
+
+fn circuit_breaker_incidents() -> Vec<CircuitBreakerIncident> {
+    vec![
+        CircuitBreakerIncident {
+            trigger: CircuitBreakerTrigger::QuorumNotMet {
+                sources: 1,
+                policy: QuorumPolicy::NOfM { n: 2 },
+                after_filtering: true,
+            },
+            activated_at: 1_700_000_000,
+            resolved_at: Some(1_700_003_600),
+            resolved_by: Some(key(1)),
+        },
+        CircuitBreakerIncident {
+            trigger: CircuitBreakerTrigger::ExtremePriceChange {
+                previous_price: 30_000,
+                new_price: 45_000,
+                change_bps: 5_000,
+            },
+            activated_at: 1_700_010_000,
+            resolved_at: None,
+            resolved_by: None,
+        },
+    ]
+}
+
+fn account_vectors() -> Vec<(&'static str, Vec<u8>)> {
+    let mut stats = PresaleStats::new();
+    for (offset, amount) in [(100, 25_000_000), (4_000, 10_000_000), (8_000, 40_000_000)] {
+        stats.record_purchase(1_700_000_000 + offset, amount).unwrap();
+    }
+    let mut sale_analytics = SaleAnalytics::new(key(1));
+    for (offset, amount, new_buyer) in [(100, 25_000_000, true), (90_000, 10_000_000, true), (95_000, 40_000_000, false)] {
+        sale_analytics.record_purchase(1_700_000_000 + offset, amount, new_buyer).unwrap();
+    }
+    let mut burn_log = BurnLog::new(key(3));
+    burn_log.record(BurnRecord {
+        timestamp: 1_700_000_000,
+        slot: 250_000_000,
+        kind: BurnKind::Autonomous,
+        source: key(7),
+        amount: 7_500_000_000,
+        price: 24_000,
+    });
+    burn_log.record(BurnRecord {
+        timestamp: 1_700_086_400,
+        slot: 250_216_000,
+        kind: BurnKind::Manual,
+        source: key(6),
+        amount: 5_000_000,
+        price: 36_000,
+    });
+    let mut allocation_plan = AllocationPlan::new(key(1), 1_000_000_000, &[
+        AllocationShare { category: AllocationCategory::Presale, bps: 2_000 },
+        AllocationShare { category: AllocationCategory::Team, bps: 8_000 },
+    ]);
+    allocation_plan.draw(AllocationCategory::Team, 350_000_000).unwrap();
+    let mut supply_ledger = SupplyActionLedger::new(key(2));
+    supply_ledger.record(SupplyActionRecord {
+        timestamp: 1_700_000_000,
+        operation: SupplyOperation::Mint,
+        amount: 100_000_000_000_000,
+        trigger_price: 36_000,
+        year_start_price: 30_000,
+        supply_after: 1_100_000_000_000_000,
+        oracle_health: Some(SupplyOracleHealth {
+            health_score: 90,
+            contributing_oracles: 2,
+            confidence: 9_800,
+            is_emergency_price: false,
+        }),
+    });
+    supply_ledger.record(SupplyActionRecord {
+        timestamp: 1_731_536_000,
+        operation: SupplyOperation::Burn,
+        amount: 55_000_000_000_000,
+        trigger_price: 27_000,
+        year_start_price: 36_000,
+        supply_after: 1_045_000_000_000_000,
+        oracle_health: None,
+    });
+
+    let presale = PresaleState {
+        is_initialized: true,
+        authority: key(1),
+        mint: key(2),
+        dev_treasury: key(3),
+        locked_treasury: key(4),
+        start_time: 1_700_000_000,
+        end_time: 1_702_592_000,
+        token_price: 30_000,
+        hard_cap: 1_000_000_000_000,
+        soft_cap: 200_000_000_000,
+        min_purchase: 10_000_000,
+        max_purchase: 50_000_000_000,
+        total_tokens_sold: 833_333_333,
+        total_usd_raised: 25_000_000,
+        num_buyers: 1,
+        is_active: true,
+        has_ended: false,
+        token_launched: false,
+        launch_timestamp: 0,
+        refund_available_timestamp: 0,
+        refund_period_end_timestamp: 0,
+        soft_cap_reached: false,
+        allowed_stablecoins: vec![key(5)],
+        contributions: vec![PresaleContribution {
+            buyer: key(6),
+            amount: 25_000_000,
+            dev_amount: 12_500_000,
+            locked_amount: 12_500_000,
+            stablecoin_type: StablecoinType::USDC,
+            stablecoin_mint: key(5),
+            refunded: false,
+            dev_refunded: false,
+            timestamp: 1_700_000_100,
+            keeper_refund_destination: Some(key(9)),
+            last_purchase: purchase_snapshot(),
+        }],
+        buyer_pubkeys: vec![key(6)],
+        dev_funds_refundable: false,
+        dev_refund_available_timestamp: 0,
+        dev_refund_period_end_timestamp: 0,
+        num_rounds: 1,
+        last_round_end_time: 1_700_604_800,
+        kyc_required: true,
+        kyc_attestor: key(7),
+        purchase_cooldown: 60,
+        launch_window_duration: 900,
+        launch_window_max_per_wallet: 1_000_000_000,
+        allow_partial_fill: true,
+        daily_raise_cap: 100_000_000_000,
+        limited_stablecoins: vec![key(5)],
+        stats,
+        price_controller: Some(key(9)),
+        treasury_alarm: TreasuryAlarm {
+            coverage_bps: 10_000,
+            underfunded_mints: vec![key(5)],
+            last_raised_at: 1_700_000_200,
+        },
+        refund_notices: RefundWindowNotices { opened: true, closing_soon: true, closed: false },
+        withdraw_notice: Some(WithdrawNotice {
+            stablecoin_mint: key(5),
+            destination: key(4),
+            noticed_at: 1_710_000_100,
+            executable_at: 1_710_604_900,
+        }),
+        launch_checklist: LaunchChecklist {
+            metadata_finalized: true,
+            liquidity: LaunchLiquidity::Waived,
+            vesting_funded: true,
+            controller_initialized: false,
+        },
+        pegged_stablecoins: vec![key(5)],
+        sale_analytics: true,
+        forced_refund: Some(ForcedRefund {
+            declared_at: 1_700_500_000,
+            reason_hash: [7; 32],
+            approvals: 3,
+        }),
+    };
+
+    let round = PresaleRoundState {
+        is_initialized: true,
+        presale: key(1),
+        round_index: 0,
+        kind: PresaleRoundKind::Seed,
+        start_time: 1_700_000_000,
+        end_time: 1_700_604_800,
+        token_price: 10_000,
+        hard_cap: 50_000_000_000,
+        min_purchase: 1_000_000_000,
+        max_purchase: 5_000_000_000,
+        total_tokens_sold: 100_000_000,
+        total_usd_raised: 1_000_000_000,
+        num_purchases: 1,
+        whitelist: vec![key(6)],
+    };
+
+    let credential = KycCredential {
+        is_initialized: true,
+        presale: key(1),
+        buyer: key(6),
+        attestor: key(7),
+        issued_at: 1_700_000_000,
+        expires_at: 1_731_536_000,
+    };
+
+    let vesting = VestingState {
+        is_initialized: true,
+        authority: key(1),
+        mint: key(2),
+        escrow: key(4),
+        total_tokens: 500_000_000,
+        total_allocated: 1_000_000,
+        total_released: 250_000,
+        start_time: 1_700_000_000,
+        release_interval: 2_592_000,
+        num_releases: 12,
+        num_beneficiaries: 2,
+        crank_tip_lamports: 5_000,
+        receipts_transferable: true,
+        pause: VestingPause { paused_at: Some(1_702_000_000), paused_duration: 86_400 },
+    };
+
+    let vesting_grant = VestingGrant {
+        is_initialized: true,
+        vesting: key(3),
+        beneficiary: key(7),
+        total_amount: 500_000,
+        released_amount: 250_000,
+        revoked_at: Some(1_700_600_000),
+        schedule: Some(VestingSchedule {
+            start_time: 1_700_000_000,
+            cliff_duration: 0,
+            duration: 63_072_000,
+            release_interval: 86_400,
+        }),
+        pending_beneficiary: Some(key(8)),
+        destination: key(9),
+        receipt_mint: Some(key(11)),
+        receipt_transferable: true,
+        pause: VestingPause { paused_at: None, paused_duration: 3_600 },
+        last_release_time: 1_702_592_000,
+    };
+
+    let vesting_registry = VestingRegistry {
+        is_initialized: true,
+        authority: key(1),
+        mint: key(2),
+        pools: vec![
+            VestingPoolEntry { vesting: key(3), label: "team".to_string() },
+            VestingPoolEntry { vesting: key(5), label: "ecosystem".to_string() },
+        ],
+    };
+
+    let metadata = TokenMetadata {
+        is_initialized: true,
+        authority: key(1),
+        mint: key(2),
+        name: "VCoin".to_string(),
+        symbol: "VCN".to_string(),
+        uri: "https://vcoin.example/meta.json".to_string(),
+        last_updated_timestamp: 1_700_000_000,
+        uri_hash: Some([0x6d; 32]),
+        pending_authority: Some(key(9)),
+        display_hints: Some(MetadataDisplayHints {
+            display_decimals: Some(2),
+            ticker_aliases: vec!["VCOIN".to_string()],
+        }),
+    };
+
+    let controller = AutonomousSupplyController {
+        is_initialized: true,
+        mint: key(2),
+        price_oracle: key(7),
+        initial_price: 30_000,
+        year_start_price: 30_000,
+        current_price: 36_000,
+        last_price_update: 1_700_000_000,
+        year_start_timestamp: 1_690_000_000,
+        last_mint_timestamp: 1_695_000_000,
+        last_burn_timestamp: 1_697_000_000,
+        current_supply: 1_000_000_000_000_000,
+        token_decimals: 6,
+        min_supply: 1_000_000_000_000_000,
+        high_supply_threshold: 5_000_000_000_000_000,
+        max_supply: 10_000_000_000_000_000,
+        mint_authority: key(8),
+        mint_authority_bump: 254,
+        burn_treasury: key(9),
+        burn_treasury_bump: 253,
+        min_growth_for_mint_bps: 500,
+        min_decline_for_burn_bps: 500,
+        medium_growth_mint_rate_bps: 500,
+        high_growth_mint_rate_bps: 1_000,
+        medium_decline_burn_rate_bps: 500,
+        high_decline_burn_rate_bps: 1_000,
+        high_growth_threshold_bps: 1_000,
+        high_decline_threshold_bps: 1_000,
+        extreme_growth_threshold_bps: 3_000,
+        extreme_decline_threshold_bps: 3_000,
+        post_cap_mint_rate_bps: 200,
+        post_cap_burn_rate_bps: 200,
+        price_source: SupplyPriceSource::MultiOracleController,
+        authority: key(1),
+        pending_parameters: Some(PendingSupplyParameters {
+            parameters: supply_parameters(),
+            effective_at: 1_700_604_800,
+        }),
+        rate_limit: SupplyRateLimit {
+            window_seconds: 86_400,
+            max_amount_per_window: 200_000_000_000_000,
+            max_operations_per_window: 2,
+            window_start: 1_699_950_000,
+            minted_this_epoch: 100_000_000_000_000,
+            burned_this_epoch: 0,
+            operations_this_epoch: 1,
+        },
+        distribution: Some(supply_distribution()),
+        mint_cooldown_seconds: 86_400,
+        burn_cooldown_seconds: 43_200,
+        fees_swept_to_burn: 2_500_000_000,
+    };
+
+    let emergency = EmergencyState {
+        is_initialized: true,
+        emergency_authority: key(1),
+        program_authority: key(2),
+        emergency_mode: EmergencyMode::Paused,
+        emergency_activated_at: 1_700_000_000,
+        emergency_reason: Some("incident".to_string()),
+        pause_history: vec![PauseRecord {
+            paused_at: 1_700_000_000,
+            resumed_at: None,
+            reason: Some("incident".to_string()),
+            paused_by: key(1),
+        }],
+    };
+
+    let oracle_controller = MultiOracleController {
+        is_initialized: true,
+        authority: key(1),
+        asset_id: "VCN/USD".to_string(),
+        oracle_sources: vec![oracle_source()],
+        min_required_oracles: 2,
+        circuit_breaker_active: false,
+        circuit_breaker_activated_at: 0,
+        circuit_breaker_reason: None,
+        circuit_breaker_cooldown: 3_600,
+        last_consensus: OracleConsensusResult {
+            price: 1_250_000,
+            confidence: 1_000,
+            timestamp: 1_700_000_000,
+            contributing_oracles: 2,
+            circuit_breaker_active: false,
+            circuit_breaker_reason: None,
+            is_fallback_price: false,
+            max_deviation_bps: 120,
+            spot_price: 1_262_000,
+        },
+        health: OracleHealthStatus {
+            last_checked: 1_700_000_000,
+            health_score: 100,
+            active_oracles: 2,
+            total_oracles: 2,
+            is_degraded: false,
+            max_staleness: 30,
+            avg_deviation_bps: 60,
+        },
+        emergency_price: Some(1_200_000),
+        emergency_price_timestamp: 1_700_000_000,
+        emergency_price_expiration: 3_600,
+        price_floor: 500_000,
+        price_ceiling: 5_000_000,
+        pyth_feed_id: Some([0xef; 32]),
+        price_history: vec![
+            PriceObservation { price: 1_240_000, timestamp: 1_699_996_400 },
+            PriceObservation { price: 1_250_000, timestamp: 1_700_000_000 },
+        ],
+        price_history_next: 0,
+        ema_alpha_bps: 2_000,
+        redstone: Some(redstone_config()),
+        chainlink_streams: Some(chainlink_streams_config()),
+        quorum_policy: QuorumPolicy::MinWeight { min_weight: 120 },
+        circuit_breaker_incidents: circuit_breaker_incidents(),
+        circuit_breaker_auto_recovery: true,
+        circuit_breaker_max_cooldown: 86_400,
+        circuit_breaker_trips: 2,
+        staleness_policy: StalenessPolicy { purchase: 120, supply_adjustment: 600, display: 3_600 },
+        aggregation_strategy: AggregationStrategy::ConfidenceWeightedMedian,
+        signed_price_publishers: vec![key(8)],
+    };
+
+    let compliance = ComplianceRecord {
+        is_initialized: true,
+        presale: key(1),
+        commitments: vec![ComplianceCommitment {
+            kind: ComplianceArtifactKind::KycBatch,
+            hash: [0xcd; 32],
+            committed_at: 1_700_000_000,
+        }],
+    };
+
+    let mut window = RollingVolume::new(86_400);
+    window.record(1_700_000_100, 25_000_000).unwrap();
+    let stablecoin_limits = StablecoinLimits {
+        is_initialized: true,
+        presale: key(1),
+        stablecoin_mint: key(5),
+        min_purchase: 10_000_000,
+        max_purchase: 5_000_000_000,
+        window_cap: 100_000_000_000,
+        cap_expires_at: 1_702_592_000,
+        window,
+    };
+
+    let peg_guard = StablecoinPegGuard {
+        is_initialized: true,
+        presale: key(1),
+        stablecoin_mint: key(5),
+        oracle_controller: key(9),
+        band_bps: 200,
+        action: DepegAction::Haircut,
+    };
+
+    let airdrop = AirdropDistributor {
+        is_initialized: true,
+        authority: key(1),
+        mint: key(2),
+        escrow: key(7),
+        merkle_root: [0x11; 32],
+        total: 50_000_000_000_000,
+        total_claimed: 1_000_000_000,
+        num_claims: 1,
+        bump: 254,
+    };
+
+    let mut claim_bitmap = AirdropClaimBitmap::new(key(8), 1);
+    claim_bitmap.set_claimed(8_200);
+
+    let bug_bounty = BugBountyEscrow {
+        is_initialized: true,
+        authority: key(1),
+        mint: key(2),
+        escrow: key(7),
+        guardians: vec![key(3), key(4), key(9)],
+        guardian_threshold: 2,
+        total_funded: 10_000_000_000,
+        total_committed: 2_500_000_000,
+        total_paid: 1_000_000_000,
+        num_awards: 2,
+        bump: 253,
+    };
+
+    let mint_migration = MintMigration {
+        is_initialized: true,
+        authority: key(1),
+        old_mint: key(2),
+        new_mint: key(9),
+        escrow: key(7),
+        decimals: 9,
+        old_supply: 1_000_000_000_000_000_000,
+        total_migrated: 1_500_000_000,
+        num_migrations: 1,
+        supply_controller: Some(key(3)),
+        opened_at: 1_700_300_000,
+        bump: 251,
+    };
+
+    let bug_bounty_award = BugBountyAward {
+        is_initialized: true,
+        bounty: key(8),
+        disclosure_hash: [0x5a; 32],
+        researcher: key(6),
+        amount: 2_500_000_000,
+        approved_by: vec![key(3), key(9)],
+        committed_at: 1_700_100_000,
+        paid_at: Some(1_700_200_000),
+        bump: 252,
+    };
+
+    vec![
+        ("PresaleState", to_vec(&presale).unwrap()),
+        ("PresaleRoundState", to_vec(&round).unwrap()),
+        ("KycCredential", to_vec(&credential).unwrap()),
+        ("ComplianceRecord", to_vec(&compliance).unwrap()),
+        ("StablecoinLimits", to_vec(&stablecoin_limits).unwrap()),
+        ("StablecoinPegGuard", to_vec(&peg_guard).unwrap()),
+        ("SaleAnalytics", to_vec(&sale_analytics).unwrap()),
+        ("SupplyActionLedger", to_vec(&supply_ledger).unwrap()),
+        ("AllocationPlan", to_vec(&allocation_plan).unwrap()),
+        ("BurnLog", to_vec(&burn_log).unwrap()),
+        ("Multisig", to_vec(&Multisig {
+            is_initialized: true,
+            base: key(9),
+            threshold: 2,
+            signers: vec![key(10), key(11), key(12)],
+            bump: 254,
+        }).unwrap()),
+        ("FeeSponsorship", to_vec(&FeeSponsorship {
+            is_initialized: true,
+            authority: key(1),
+            mint: key(3),
+            keeper: key(4),
+            per_claim_lamports: 10_000,
+            budget_lamports: 1_000_000_000,
+            spent_lamports: 120_000,
+            claims_sponsored: 12,
+            bump: 250,
+        }).unwrap()),
+        ("Timelock", to_vec(&Timelock {
+            is_initialized: true,
+            mint: key(3),
+            proposer: key(2),
+            delay: 172_800,
+            next_operation_id: 3,
+            queued: vec![
+                QueuedOperation {
+                    id: 1,
+                    operation: TimelockedOperation::UpdateControllerParameters { controller: key(8), parameters: Some(supply_parameters()) },
+                    executable_at: 1_700_172_800,
+                },
+                QueuedOperation {
+                    id: 2,
+                    operation: TimelockedOperation::WithdrawLockedFunds { presale: key(1), stablecoin_mint: key(5), destination: key(7) },
+                    executable_at: 1_700_259_200,
+                },
+            ],
+            bump: 249,
+        }).unwrap()),
+        ("AccessControl", to_vec(&AccessControl {
+            is_initialized: true,
+            mint: key(3),
+            grants: vec![
+                RoleGrant { member: key(2), role: Role::Admin },
+                RoleGrant { member: key(4), role: Role::OracleManager },
+                RoleGrant { member: key(5), role: Role::EmergencyGuardian },
+            ],
+            bump: 248,
+        }).unwrap()),
+        ("YieldVenue", to_vec(&YieldVenue {
+            is_initialized: true,
+            presale: key(1),
+            stablecoin_mint: key(5),
+            lending_program: key(3),
+            lending_market: key(4),
+            reserve: key(6),
+            collateral_account: key(7),
+            insurance_fund: key(8),
+            deposit_cap: 50_000_000_000,
+            deployed_principal: 10_000_000_000,
+            total_yield_routed: 125_000_000,
+            approved_at: 1_700_000_000,
+            active_at: 1_700_604_800,
+        }).unwrap()),
+        ("AirdropDistributor", to_vec(&airdrop).unwrap()),
+        ("AirdropClaimBitmap", to_vec(&claim_bitmap).unwrap()),
+        ("BugBountyEscrow", to_vec(&bug_bounty).unwrap()),
+        ("BugBountyAward", to_vec(&bug_bounty_award).unwrap()),
+        ("MintMigration", to_vec(&mint_migration).unwrap()),
+        ("OracleFeedRegistry", to_vec(&OracleFeedRegistry {
+            is_initialized: true,
+            authority: key(1),
+            feeds: vec![
+                ManagedFeed { asset_id: "VCN/USD".to_string(), controller: key(3) },
+                ManagedFeed { asset_id: "SOL/USD".to_string(), controller: key(5) },
+            ],
+        }).unwrap()),
+        ("SimulationState", to_vec(&SimulationState {
+            is_initialized: true,
+            authority: key(1),
+            unix_timestamp: 1_731_536_000,
+            prices: vec![30_000, 36_000, 27_000],
+            next_price: 2,
+            price_feed: key(7),
+            bump: 250,
+        }).unwrap()),
+        ("VestingState", to_vec(&vesting).unwrap()),
+        ("VestingGrant", to_vec(&vesting_grant).unwrap()),
+        ("VestingRegistry", to_vec(&vesting_registry).unwrap()),
+        ("TokenMetadata", to_vec(&metadata).unwrap()),
+        ("AutonomousSupplyController", to_vec(&controller).unwrap()),
+        ("EmergencyState", to_vec(&emergency).unwrap()),
+        ("MultiOracleController", to_vec(&oracle_controller).unwrap()),
+        ("ChainlinkStreamsReport", to_vec(&ChainlinkStreamsReport {
+            is_initialized: true,
+            controller: key(1),
+            feed_id: chainlink_streams_config().feed_id,
+            valid_from_timestamp: 1_700_000_000,
+            observations_timestamp: 1_700_000_001,
+            expires_at: 1_700_086_400,
+            price: 2_000_200_000,
+            bid: 2_000_100_000,
+            ask: 2_000_300_000,
+            submitted_at: 1_700_000_002,
+        }).unwrap()),
+        ("SignedPriceReport", to_vec(&SignedPriceReport {
+            is_initialized: true,
+            controller: key(1),
+            publisher: key(8),
+            price: 2_000_200_000,
+            confidence: 100_000,
+            publish_time: 1_700_000_001,
+            submitted_at: 1_700_000_002,
+        }).unwrap()),
+        ("PurchaseRecord", to_vec(&PurchaseRecord {
+            buyer: key(6),
+            amount_usd: 25_000_000,
+            tokens_purchased: 833_333_333,
+            timestamp: 1_700_000_100,
+        }).unwrap()),
+        ("SupportedStablecoin", to_vec(&SupportedStablecoin {
+            mint: key(5),
+            stablecoin_type: StablecoinType::USDT,
+            is_active: true,
+            added_at: 1_700_000_000,
+            name: Some("USDT".to_string()),
+            decimals: 6,
+        }).unwrap()),
+        ("UpgradeState", to_vec(&UpgradeState::Proposed { proposal_time: 1_700_000_000 }).unwrap()),
+        ("VestingSchedulePreview", to_vec(&VestingSchedulePreview {
+            beneficiary: key(6),
+            total_tranches: 2,
+            start_index: 0,
+            tranches: vec![(1_702_592_000, 500_000), (1_705_184_000, 500_000)],
+        }).unwrap()),
+        ("ProgramVersion", to_vec(&ProgramVersion {
+            version: "0.1.0".to_string(),
+            state_layouts: vec![("VestingState".to_string(), 1), ("VestingGrant".to_string(), 1)],
+            features: 0b1010_0101,
+        }).unwrap()),
+        ("ClaimableAmount", to_vec(&ClaimableAmount {
+            beneficiary: key(6),
+            vested: 1_000_000,
+            released: 500_000,
+            claimable: 500_000,
+            timestamp: 1_705_184_000,
+        }).unwrap()),
+        ("CircuitBreakerHistory", to_vec(&CircuitBreakerHistory {
+            controller: key(7),
+            active: false,
+            reason: None,
+            incidents: circuit_breaker_incidents(),
+        }).unwrap()),
+        ("PurchaseValidation", to_vec(&PurchaseValidation {
+            is_valid: true,
+            error_code: 0,
+            accepted_amount: 25_000_000,
+            tokens_to_receive: 833_333_333,
+            token_price: 30_000,
+            remaining_cap: 975_000_000,
+        }).unwrap()),
+    ]
+}
+
+fn event_vectors() -> Vec<(&'static str, VCoinEvent)> {
+    vec![
+        ("TreasuryAlarmRaised", VCoinEvent::TreasuryAlarmRaised {
+            presale: key(2),
+            stablecoin_mint: key(5),
+            treasury: key(8),
+            balance: 400_000_000,
+            liabilities: 500_000_000,
+            timestamp: 1_700_000_200,
+        }),
+        ("TreasuryAlarmCleared", VCoinEvent::TreasuryAlarmCleared {
+            presale: key(2),
+            stablecoin_mint: key(5),
+            treasury: key(8),
+            balance: 600_000_000,
+            liabilities: 500_000_000,
+            timestamp: 1_700_000_300,
+        }),
+        ("RefundWindowOpened", VCoinEvent::RefundWindowOpened {
+            presale: key(2),
+            closes_at: 1_710_000_000,
+            timestamp: 1_707_400_000,
+        }),
+        ("RefundWindowClosingSoon", VCoinEvent::RefundWindowClosingSoon {
+            presale: key(2),
+            closes_at: 1_710_000_000,
+            timestamp: 1_709_350_000,
+        }),
+        ("RefundWindowClosed", VCoinEvent::RefundWindowClosed {
+            presale: key(2),
+            closed_at: 1_710_000_000,
+            timestamp: 1_710_000_100,
+        }),
+        ("WithdrawNoticed", VCoinEvent::WithdrawNoticed {
+            presale: key(2),
+            stablecoin_mint: key(5),
+            destination: key(4),
+            balance: 450_000_000,
+            executable_at: 1_710_604_900,
+            timestamp: 1_710_000_100,
+        }),
+        ("CircuitBreakerTripped", VCoinEvent::CircuitBreakerTripped {
+            controller: key(7),
+            trigger: CircuitBreakerTrigger::ExtremePriceChange {
+                previous_price: 30_000,
+                new_price: 45_000,
+                change_bps: 5_000,
+            },
+            trips: 2,
+            cooldown: 7_200,
+            timestamp: 1_700_010_000,
+        }),
+        ("CircuitBreakerProbation", VCoinEvent::CircuitBreakerProbation {
+            controller: key(7),
+            trips: 2,
+            timestamp: 1_700_017_200,
+        }),
+        ("CircuitBreakerRecovered", VCoinEvent::CircuitBreakerRecovered {
+            controller: key(7),
+            resolved_by: None,
+            timestamp: 1_700_017_200,
+        }),
+        ("PurchaseRecorded", VCoinEvent::PurchaseRecorded {
+            presale: key(2),
+            buyer: key(6),
+            stablecoin_mint: key(5),
+            purchase: purchase_snapshot(),
+        }),
+        ("BugBountyFunded", VCoinEvent::BugBountyFunded {
+            bounty: key(8),
+            source: key(5),
+            amount: 10_000_000_000,
+            balance: 10_000_000_000,
+            timestamp: 1_700_050_000,
+        }),
+        ("BugBountyAwardCommitted", VCoinEvent::BugBountyAwardCommitted {
+            bounty: key(8),
+            disclosure_hash: [0x5a; 32],
+            researcher: key(6),
+            amount: 2_500_000_000,
+            approved_by: vec![key(3), key(9)],
+            timestamp: 1_700_100_000,
+        }),
+        ("BugBountyAwardPaid", VCoinEvent::BugBountyAwardPaid {
+            bounty: key(8),
+            disclosure_hash: [0x5a; 32],
+            researcher: key(6),
+            amount: 2_500_000_000,
+            timestamp: 1_700_200_000,
+        }),
+        ("MintMigrationOpened", VCoinEvent::MintMigrationOpened {
+            old_mint: key(2),
+            new_mint: key(9),
+            old_supply: 1_000_000_000_000_000_000,
+            timestamp: 1_700_300_000,
+        }),
+        ("TokensMigrated", VCoinEvent::TokensMigrated {
+            old_mint: key(2),
+            holder: key(6),
+            amount: 1_500_000_000,
+            total_migrated: 1_500_000_000,
+            timestamp: 1_700_300_100,
+        }),
+            ("OracleSourceFailing", VCoinEvent::OracleSourceFailing {
+            controller: key(1),
+            oracle: key(4),
+            consecutive_failures: 5,
+            deactivated: true,
+            timestamp: 1_700_000_900,
+        }),
+        ("OracleHealthChanged", VCoinEvent::OracleHealthChanged {
+            controller: key(1),
+            previous: OracleHealthLevel::Healthy,
+            current: OracleHealthLevel::Degraded,
+            health_score: 55,
+            timestamp: 1_700_000_900,
+        }),
+        ("FallbackPriceUsed", VCoinEvent::FallbackPriceUsed {
+            controller: key(1),
+            price: 30_000,
+            emergency: false,
+            consensus_age: 1_800,
+            timestamp: 1_700_000_900,
+        }),
+        ("ManagedFeedCreated", VCoinEvent::ManagedFeedCreated {
+            registry: key(4),
+            asset_id: "SOL/USD".to_string(),
+            controller: key(5),
+            timestamp: 1_700_001_000,
+        }),
+        ("TreasuryYieldDeployed", VCoinEvent::TreasuryYieldDeployed {
+            presale: key(1),
+            venue: key(9),
+            amount: 10_000_000_000,
+            deployed_principal: 10_000_000_000,
+            timestamp: 1_700_700_000,
+        }),
+        ("TreasuryYieldRecalled", VCoinEvent::TreasuryYieldRecalled {
+            presale: key(1),
+            venue: key(9),
+            principal: 5_000_000_000,
+            yield_amount: 62_500_000,
+            deployed_principal: 5_000_000_000,
+            timestamp: 1_701_000_000,
+        }),
+        ("SupplyParametersQueued", VCoinEvent::SupplyParametersQueued {
+            controller: key(3),
+            parameters: supply_parameters(),
+            effective_at: 1_700_604_800,
+        }),
+        ("SupplyParametersApplied", VCoinEvent::SupplyParametersApplied {
+            controller: key(3),
+            parameters: supply_parameters(),
+            timestamp: 1_700_700_000,
+        }),
+        ("PresaleBuyerCapacityLow", VCoinEvent::PresaleBuyerCapacityLow {
+            presale: key(1),
+            num_buyers: 13_500,
+            capacity: 15_000,
+            timestamp: 1_700_000_000,
+        }),
+        ("SupplyTreasuryDistributed", VCoinEvent::SupplyTreasuryDistributed {
+            controller: key(3),
+            staking_rewards: 50_000_000_000,
+            liquidity: 30_000_000_000,
+            operations: 20_000_000_000,
+            timestamp: 1_700_000_000,
+        }),
+        ("SupplyControllerCranked", VCoinEvent::SupplyControllerCranked {
+            controller: key(3),
+            keeper: key(4),
+            operation: SupplyOperation::Burn,
+            amount: 55_000_000_000_000,
+            reward: 5_500_000_000,
+            timestamp: 1_700_000_000,
+        }),
+        ("LiquidityEscrowReleased", VCoinEvent::LiquidityEscrowReleased {
+            mint: key(2),
+            destination: key(6),
+            amount: 25_000_000,
+            timestamp: 1_700_000_000,
+        }),
+        ("SupplyDriftDetected", VCoinEvent::SupplyDriftDetected {
+            controller: key(2),
+            mint: key(3),
+            recorded_supply: 1_000_000_000_000_000,
+            mint_supply: 999_950_000_000_000,
+            timestamp: 1_700_000_000,
+        }),
+        ("WithheldFeesSwept", VCoinEvent::WithheldFeesSwept {
+            controller: key(2),
+            mint: key(3),
+            amount: 500_000_000,
+            total_swept: 2_500_000_000,
+            timestamp: 1_700_000_000,
+        }),
+        ("TokensBurned", VCoinEvent::TokensBurned {
+            mint: key(3),
+            kind: BurnKind::Manual,
+            source: key(6),
+            amount: 5_000_000,
+            price: 36_000,
+            total_burned: 7_505_000_000,
+            timestamp: 1_700_000_000,
+        }),
+        ("ForcedRefundDeclared", VCoinEvent::ForcedRefundDeclared {
+            presale: key(1),
+            guardians: vec![key(10), key(11), key(12)],
+            reason_hash: [7; 32],
+            timestamp: 1_700_500_000,
+        }),
+        ("ClaimFeeSponsored", VCoinEvent::ClaimFeeSponsored {
+            mint: key(3),
+            keeper: key(4),
+            claimant: key(6),
+            lamports: 10_000,
+            remaining_budget: 999_880_000,
+            timestamp: 1_700_600_000,
+        }),
+        ("TreasurySwept", VCoinEvent::TreasurySwept {
+            presale: key(1),
+            stablecoin_mint: key(5),
+            source: key(6),
+            destination: key(7),
+            amount: 12_500_000_000,
+            guardians: vec![key(10), key(11)],
+            timestamp: 1_700_700_000,
+        }),
+        ("TimelockOperationQueued", VCoinEvent::TimelockOperationQueued {
+            timelock: key(9),
+            operation_id: 4,
+            operation: TimelockedOperation::RescueTokens { source: key(6), destination: key(7), amount: 2_000_000 },
+            executable_at: 1_700_972_800,
+        }),
+        ("TimelockOperationExecuted", VCoinEvent::TimelockOperationExecuted {
+            timelock: key(9),
+            operation_id: 1,
+            operation: TimelockedOperation::SetTransferFee { mint: key(3), transfer_fee_basis_points: 50, maximum_fee: 1_000_000 },
+            timestamp: 1_700_800_000,
+        }),
+        ("TimelockOperationCancelled", VCoinEvent::TimelockOperationCancelled {
+            timelock: key(9),
+            operation_id: 4,
+            operation: TimelockedOperation::RescueTokens { source: key(6), destination: key(7), amount: 2_000_000 },
+            cancelled_by: key(2),
+            timestamp: 1_700_900_000,
+        }),
+        ("RoleGranted", VCoinEvent::RoleGranted {
+            access_control: key(9),
+            member: key(4),
+            role: Role::FeeManager,
+            granted_by: key(2),
+            timestamp: 1_701_000_000,
+        }),
+        ("RoleRevoked", VCoinEvent::RoleRevoked {
+            access_control: key(9),
+            member: key(4),
+            role: Role::FeeManager,
+            revoked_by: key(2),
+            timestamp: 1_701_100_000,
+        }),
+    ]
+}
+
+fn all_vectors() -> Vec<(String, String)> {
+    let instructions = instruction_vectors()
+        .into_iter()
+        .map(|(name, instruction)| (format!("instruction.{}", name), to_vec(&instruction).unwrap()));
+    let accounts = account_vectors()
+        .into_iter()
+        .map(|(name, bytes)| (format!("account.{}", name), bytes));
+    let events = event_vectors()
+        .into_iter()
+        .map(|(name, event)| (format!("event.{}", name), to_vec(&event).unwrap()));
+
+    instructions
+        .chain(accounts)
+        .chain(events)
+        .map(|(name, bytes)| (name, bytes.iter().map(|b| format!("{:02x}", b)).collect()))
+        .collect()
+}
+
+#[test]
+fn borsh_layouts_match_golden_fixtures() {
+    let vectors = all_vectors();
+
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        let mut contents = String::from("# Generated by tests/borsh_layouts.rs; regenerate with UPDATE_GOLDEN=1\n");
+        for (name, hex) in &vectors {
+            contents.push_str(&format!("{} {}\n", name, hex));
+        }
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(FIXTURE_PATH);
+        std::fs::write(path, contents).unwrap();
+        return;
+    }
+
+    let fixture: Vec<(&str, &str)> = include_str!("fixtures/borsh_layouts.txt")
+        .lines()
+        .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+        .map(|line| line.split_once(' ').expect("fixture line must be `<name> <hex>`"))
+        .collect();
+
+    let mut mismatches = Vec::new();
+    for (name, hex) in &vectors {
+        match fixture.iter().find(|(fixture_name, _)| fixture_name == name) {
+            Some((_, expected)) if expected == hex => {}
+            Some((_, expected)) => mismatches.push(format!("{}\n  expected {}\n  actual   {}", name, expected, hex)),
+            None => mismatches.push(format!("{} missing from fixture\n  actual   {}", name, hex)),
+        }
+    }
+    for (fixture_name, _) in &fixture {
+        if !vectors.iter().any(|(name, _)| name == fixture_name) {
+            mismatches.push(format!("{} in fixture has no canonical instance", fixture_name));
+        }
+    }
+
+    assert!(mismatches.is_empty(), "Borsh layout changed:\n{}", mismatches.join("\n"));
+}
+
+#[test]
+fn instruction_tags_follow_variant_order() {
+    for (index, (name, instruction)) in instruction_vectors().iter().enumerate() {
+        let bytes = to_vec(instruction).unwrap();
+        assert_eq!(bytes[0] as usize, index, "{} is no longer variant {}", name, index);
+    }
+}
@@ -0,0 +1,55 @@
+//! Bug-bounty guardian quorums and escrow reservations.
+
+use solana_program::pubkey::Pubkey;
+use vcoin_program::state::{BugBountyAward, BugBountyEscrow, MAX_BOUNTY_GUARDIANS};
+
+#[test]
+fn awards_need_a_guardian_quorum_and_uncommitted_funds() {
+    let guardians: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+    assert!(BugBountyEscrow::guardians_are_valid(&guardians, 2));
+    assert!(!BugBountyEscrow::guardians_are_valid(&guardians, 0));
+    assert!(!BugBountyEscrow::guardians_are_valid(&guardians, 4));
+    assert!(!BugBountyEscrow::guardians_are_valid(&[guardians[0], guardians[0]], 1));
+    let too_many: Vec<Pubkey> = (0..=MAX_BOUNTY_GUARDIANS).map(|_| Pubkey::new_unique()).collect();
+    assert!(!BugBountyEscrow::guardians_are_valid(&too_many, 2));
+
+    let mut bounty = BugBountyEscrow {
+        is_initialized: true,
+        authority: Pubkey::new_unique(),
+        mint: Pubkey::new_unique(),
+        escrow: Pubkey::new_unique(),
+        guardians: guardians.clone(),
+        guardian_threshold: 2,
+        total_funded: 10_000,
+        total_committed: 0,
+        total_paid: 0,
+        num_awards: 0,
+        bump: 255,
+    };
+
+    // Only guardians count, each once, whatever else signed
+    let signers = [guardians[2], Pubkey::new_unique(), guardians[2], guardians[0]];
+    assert_eq!(bounty.approving_guardians(&signers), vec![guardians[0], guardians[2]]);
+    assert_eq!(bounty.approving_guardians(&[guardians[1]]).len(), 1);
+
+    // Committed awards stay reserved until paid
+    bounty.total_committed = 7_500;
+    assert_eq!(bounty.uncommitted(10_000), 2_500);
+    assert_eq!(bounty.uncommitted(5_000), 0);
+
+    // Accounts are sized for a full guardian set
+    bounty.guardians = too_many[..MAX_BOUNTY_GUARDIANS].to_vec();
+    assert_eq!(borsh::to_vec(&bounty).unwrap().len(), BugBountyEscrow::get_size());
+    let award = BugBountyAward {
+        is_initialized: true,
+        bounty: Pubkey::new_unique(),
+        disclosure_hash: [7; 32],
+        researcher: Pubkey::new_unique(),
+        amount: 2_500,
+        approved_by: bounty.guardians.clone(),
+        committed_at: 1_700_000_000,
+        paid_at: Some(1_700_086_400),
+        bump: 254,
+    };
+    assert_eq!(borsh::to_vec(&award).unwrap().len(), BugBountyAward::get_size());
+}
@@ -0,0 +1,429 @@
+//! Runs the processor natively, the way the runtime would: accounts are laid out
+//! in the loader's input format (so `realloc` works), the clock and rent sysvars
+//! are served, system program CPIs create and fund accounts, and logs, events
+//! and cross-program invocations are recorded for the test to inspect.
+
+#![allow(dead_code)]
+
+use std::cell::RefCell;
+use std::sync::Once;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::AccountInfo,
+    clock::Clock,
+    entrypoint::{self, ProgramResult, MAX_PERMITTED_DATA_INCREASE},
+    instruction::Instruction,
+    program_error::ProgramError,
+    program_stubs::{set_syscall_stubs, SyscallStubs},
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction::SystemInstruction,
+    program_pack::Pack,
+    system_program,
+    sysvar::{
+        self,
+        instructions::{self, construct_instructions_data, store_current_index, BorrowedAccountMeta, BorrowedInstruction},
+    },
+};
+use spl_token_2022::instruction::TokenInstruction;
+use spl_token_2022::state::{Account, AccountState, Mint};
+use vcoin_program::{
+    events::VCoinEvent,
+    processor::Processor,
+    state::{EmergencyMode, EmergencyState, LaunchChecklist, PresaleState, PresaleStats, RefundWindowNotices, TreasuryAlarm, MAX_PAUSE_HISTORY},
+    VCoinInstruction,
+};
+
+/// Time the clock sysvar reports unless a test moves it
+pub const NOW: i64 = 1_700_000_000;
+
+/// An account passed to the processor
+#[derive(Clone, Debug)]
+pub struct TestAccount {
+    pub key: Pubkey,
+    pub owner: Pubkey,
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+impl TestAccount {
+    /// A writable, unsigned account
+    pub fn new(key: Pubkey, owner: Pubkey, data: Vec<u8>) -> Self {
+        Self { key, owner, lamports: 1_000_000_000, data, is_signer: false, is_writable: true }
+    }
+
+    /// A system-owned wallet that signs
+    pub fn signer(key: Pubkey) -> Self {
+        Self { is_signer: true, ..Self::new(key, system_program::ID, Vec::new()) }
+    }
+
+    /// An account owned by this program holding `state`, zero-padded to `size`
+    pub fn program<T: BorshSerialize>(key: Pubkey, state: &T, size: usize) -> Self {
+        let mut data = borsh::to_vec(state).unwrap();
+        assert!(data.len() <= size, "state does not fit its account");
+        data.resize(size, 0);
+        Self::new(key, vcoin_program::id(), data)
+    }
+
+    /// An SPL token mint with `decimals`
+    pub fn mint(key: Pubkey, decimals: u8) -> Self {
+        let mut data = vec![0; Mint::LEN];
+        Mint { decimals, is_initialized: true, ..Mint::default() }.pack_into_slice(&mut data);
+        Self::new(key, spl_token::ID, data)
+    }
+
+    /// An SPL token account of `mint` owned by `owner`, holding `amount`
+    pub fn token(key: Pubkey, mint: Pubkey, owner: Pubkey, amount: u64) -> Self {
+        let mut data = vec![0; Account::LEN];
+        Account { mint, owner, amount, state: AccountState::Initialized, ..Account::default() }.pack_into_slice(&mut data);
+        Self::new(key, spl_token::ID, data)
+    }
+
+    /// The instructions sysvar of a transaction made of `instructions`, executing
+    /// the one at `current`
+    pub fn instructions_sysvar(instructions: &[Instruction], current: u16) -> Self {
+        let borrowed: Vec<BorrowedInstruction> = instructions
+            .iter()
+            .map(|instruction| BorrowedInstruction {
+                program_id: &instruction.program_id,
+                accounts: instruction
+                    .accounts
+                    .iter()
+                    .map(|meta| BorrowedAccountMeta { pubkey: &meta.pubkey, is_signer: meta.is_signer, is_writable: meta.is_writable })
+                    .collect(),
+                data: &instruction.data,
+            })
+            .collect();
+        let mut data = construct_instructions_data(&borrowed);
+        store_current_index(&mut data, current);
+        Self::new(instructions::ID, sysvar::ID, data).readonly()
+    }
+
+    /// The emergency state PDA of `mint`, in `mode`
+    pub fn emergency_state(mint: &Pubkey, mode: EmergencyMode) -> Self {
+        let state = EmergencyState { emergency_mode: mode, ..EmergencyState::new(Pubkey::new_unique(), Pubkey::new_unique()) };
+        let (address, _) = EmergencyState::find_address(&vcoin_program::id(), mint);
+        Self::program(address, &state, EmergencyState::get_space(MAX_PAUSE_HISTORY)).readonly()
+    }
+
+    /// An empty, unsigned account that does not exist yet
+    pub fn empty(key: Pubkey) -> Self {
+        Self { lamports: 0, ..Self::new(key, system_program::ID, Vec::new()) }
+    }
+
+    /// The account, read-only
+    pub fn readonly(self) -> Self {
+        Self { is_writable: false, ..self }
+    }
+
+    /// The account, signing
+    pub fn signing(self) -> Self {
+        Self { is_signer: true, ..self }
+    }
+
+    /// Deserialize the account's state
+    pub fn state<T: BorshDeserialize>(&self) -> T {
+        T::deserialize(&mut &self.data[..]).unwrap()
+    }
+}
+
+/// `accounts` with the emergency state of a fresh mint, in `mode`, at the slot
+/// `instruction` declares; missing accounts before the slot are left empty
+pub fn with_emergency_state(instruction: &VCoinInstruction, mut accounts: Vec<TestAccount>, mode: EmergencyMode) -> Vec<TestAccount> {
+    if let Some(index) = instruction.emergency_state_index() {
+        while accounts.len() < index {
+            accounts.push(TestAccount::empty(Pubkey::new_unique()));
+        }
+        accounts.insert(index, TestAccount::emergency_state(&Pubkey::new_unique(), mode));
+    }
+    accounts
+}
+
+/// An active presale with a fresh authority and mint and no contributions yet
+pub fn presale() -> PresaleState {
+    PresaleState {
+        is_initialized: true,
+        authority: Pubkey::new_unique(),
+        mint: Pubkey::new_unique(),
+        dev_treasury: Pubkey::new_unique(),
+        locked_treasury: Pubkey::new_unique(),
+        start_time: NOW,
+        end_time: NOW + 30 * 86_400,
+        token_price: 30_000,
+        hard_cap: 1_000_000_000_000,
+        soft_cap: 200_000_000_000,
+        min_purchase: 10_000_000,
+        max_purchase: 50_000_000_000,
+        total_tokens_sold: 0,
+        total_usd_raised: 0,
+        num_buyers: 0,
+        is_active: true,
+        has_ended: false,
+        token_launched: false,
+        launch_timestamp: 0,
+        refund_available_timestamp: 0,
+        refund_period_end_timestamp: 0,
+        soft_cap_reached: false,
+        allowed_stablecoins: Vec::new(),
+        contributions: Vec::new(),
+        buyer_pubkeys: Vec::new(),
+        dev_funds_refundable: false,
+        dev_refund_available_timestamp: 0,
+        dev_refund_period_end_timestamp: 0,
+        num_rounds: 0,
+        last_round_end_time: 0,
+        kyc_required: false,
+        kyc_attestor: Pubkey::default(),
+        purchase_cooldown: 0,
+        launch_window_duration: 0,
+        launch_window_max_per_wallet: 0,
+        allow_partial_fill: false,
+        daily_raise_cap: 0,
+        limited_stablecoins: Vec::new(),
+        stats: PresaleStats::new(),
+        price_controller: None,
+        treasury_alarm: TreasuryAlarm {
+            coverage_bps: 10_000,
+            underfunded_mints: Vec::new(),
+            last_raised_at: 0,
+        },
+        refund_notices: RefundWindowNotices::default(),
+        withdraw_notice: None,
+        launch_checklist: LaunchChecklist::default(),
+        pegged_stablecoins: Vec::new(),
+        sale_analytics: false,
+        forced_refund: None,
+    }
+}
+
+/// A cross-program invocation the processor made
+#[derive(Clone, Debug)]
+pub struct Invocation {
+    pub instruction: Instruction,
+    pub signer_seeds: Vec<Vec<Vec<u8>>>,
+}
+
+impl Invocation {
+    /// The amount moved, if this is a token `TransferChecked`
+    pub fn transferred(&self) -> Option<u64> {
+        match TokenInstruction::unpack(&self.instruction.data) {
+            Ok(TokenInstruction::TransferChecked { amount, .. }) => Some(amount),
+            _ => None,
+        }
+    }
+}
+
+/// What an instruction did
+#[derive(Debug)]
+pub struct Outcome {
+    pub result: ProgramResult,
+    pub logs: Vec<String>,
+    pub events: Vec<VCoinEvent>,
+    pub invocations: Vec<Invocation>,
+}
+
+impl Outcome {
+    /// Whether any log line contains `text`
+    pub fn logged(&self, text: &str) -> bool {
+        self.logs.iter().any(|line| line.contains(text))
+    }
+}
+
+thread_local! {
+    static TIME: RefCell<i64> = const { RefCell::new(NOW) };
+    static LOGS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+    static EVENTS: RefCell<Vec<VCoinEvent>> = const { RefCell::new(Vec::new()) };
+    static INVOCATIONS: RefCell<Vec<Invocation>> = const { RefCell::new(Vec::new()) };
+}
+
+struct Stubs;
+
+impl SyscallStubs for Stubs {
+    fn sol_log(&self, message: &str) {
+        LOGS.with(|logs| logs.borrow_mut().push(message.to_string()));
+    }
+
+    fn sol_log_data(&self, fields: &[&[u8]]) {
+        if let Some(event) = fields.first().and_then(|data| VCoinEvent::try_from_slice(data).ok()) {
+            EVENTS.with(|events| events.borrow_mut().push(event));
+        }
+    }
+
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        let clock = Clock { unix_timestamp: TIME.with(|time| *time.borrow()), ..Clock::default() };
+        unsafe { *(var_addr as *mut Clock) = clock };
+        0
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Rent) = Rent::default() };
+        0
+    }
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        INVOCATIONS.with(|invocations| invocations.borrow_mut().push(Invocation {
+            instruction: instruction.clone(),
+            signer_seeds: signers_seeds
+                .iter()
+                .map(|seeds| seeds.iter().map(|seed| seed.to_vec()).collect())
+                .collect(),
+        }));
+        if instruction.program_id == system_program::ID {
+            run_system_instruction(instruction, account_infos)?;
+        }
+        Ok(())
+    }
+}
+
+/// The parts of the system program the processor calls
+fn run_system_instruction(instruction: &Instruction, account_infos: &[AccountInfo]) -> ProgramResult {
+    let find = |key: &Pubkey| {
+        account_infos.iter().find(|info| info.key == key).ok_or(ProgramError::NotEnoughAccountKeys)
+    };
+    match bincode_system_instruction(&instruction.data)? {
+        SystemInstruction::CreateAccount { lamports, space, owner } => {
+            let from = find(&instruction.accounts[0].pubkey)?;
+            let to = find(&instruction.accounts[1].pubkey)?;
+            if to.data_len() > 0 || *to.owner != system_program::ID {
+                return Err(ProgramError::AccountAlreadyInitialized);
+            }
+            **from.try_borrow_mut_lamports()? -= lamports;
+            **to.try_borrow_mut_lamports()? += lamports;
+            to.realloc(space as usize, true)?;
+            to.assign(&owner);
+        }
+        SystemInstruction::Transfer { lamports } => {
+            let from = find(&instruction.accounts[0].pubkey)?;
+            let to = find(&instruction.accounts[1].pubkey)?;
+            **from.try_borrow_mut_lamports()? -= lamports;
+            **to.try_borrow_mut_lamports()? += lamports;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Decode the system instructions the processor builds
+fn bincode_system_instruction(data: &[u8]) -> Result<SystemInstruction, ProgramError> {
+    let u64_at = |offset: usize| {
+        data.get(offset..offset + 8)
+            .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+            .ok_or(ProgramError::InvalidInstructionData)
+    };
+    let tag = data.get(..4).ok_or(ProgramError::InvalidInstructionData)?;
+    Ok(match u32::from_le_bytes(tag.try_into().unwrap()) {
+        0 => SystemInstruction::CreateAccount {
+            lamports: u64_at(4)?,
+            space: u64_at(12)?,
+            owner: Pubkey::try_from(data.get(20..52).ok_or(ProgramError::InvalidInstructionData)?).unwrap(),
+        },
+        2 => SystemInstruction::Transfer { lamports: u64_at(4)? },
+        _ => SystemInstruction::AdvanceNonceAccount,
+    })
+}
+
+/// Move the clock the processor sees
+pub fn set_time(unix_timestamp: i64) {
+    TIME.with(|time| *time.borrow_mut() = unix_timestamp);
+}
+
+/// Run an instruction against `accounts`, writing back what it changed
+pub fn process(accounts: &mut [TestAccount], instruction: &VCoinInstruction) -> Outcome {
+    process_data(accounts, &borsh::to_vec(instruction).unwrap())
+}
+
+/// Run raw instruction data against `accounts`, writing back what it changed
+pub fn process_data(accounts: &mut [TestAccount], instruction_data: &[u8]) -> Outcome {
+    static STUBS: Once = Once::new();
+    STUBS.call_once(|| {
+        set_syscall_stubs(Box::new(Stubs));
+    });
+    LOGS.with(|logs| logs.borrow_mut().clear());
+    EVENTS.with(|events| events.borrow_mut().clear());
+    INVOCATIONS.with(|invocations| invocations.borrow_mut().clear());
+
+    let (mut input, data_offsets) = serialize(accounts, instruction_data);
+    let result = unsafe {
+        let (program_id, account_infos, data) = entrypoint::deserialize(input.as_mut_ptr() as *mut u8);
+        Processor::process(program_id, &account_infos, data)
+    };
+
+    // Read back lamports, owner and data (whose length may have changed)
+    let bytes = unsafe { std::slice::from_raw_parts(input.as_ptr() as *const u8, input.len() * 8) };
+    for (account, offset) in accounts.iter_mut().zip(data_offsets) {
+        let Some(data_offset) = offset else { continue };
+        let u64_at = |offset: usize| u64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap());
+        account.owner = Pubkey::try_from(&bytes[data_offset - 48..data_offset - 16]).unwrap();
+        account.lamports = u64_at(data_offset - 16);
+        let data_len = u64_at(data_offset - 8) as usize;
+        account.data = bytes[data_offset..data_offset + data_len].to_vec();
+    }
+    for index in 0..accounts.len() {
+        if let Some(first) = accounts[..index].iter().position(|earlier| earlier.key == accounts[index].key) {
+            accounts[index] = TestAccount { is_signer: accounts[index].is_signer, is_writable: accounts[index].is_writable, ..accounts[first].clone() };
+        }
+    }
+
+    Outcome {
+        result,
+        logs: LOGS.with(|logs| logs.borrow().clone()),
+        events: EVENTS.with(|events| events.borrow().clone()),
+        invocations: INVOCATIONS.with(|invocations| invocations.borrow().clone()),
+    }
+}
+
+/// Lay out the loader input: accounts (duplicates point at their first
+/// occurrence), instruction data and program id, in an 8-byte aligned buffer.
+/// Also returns where each non-duplicate account's data starts.
+fn serialize(accounts: &[TestAccount], instruction_data: &[u8]) -> (Vec<u64>, Vec<Option<usize>>) {
+    let mut bytes = Vec::new();
+    let mut data_offsets = Vec::new();
+    bytes.extend_from_slice(&(accounts.len() as u64).to_le_bytes());
+    for (index, account) in accounts.iter().enumerate() {
+        if let Some(first) = accounts[..index].iter().position(|earlier| earlier.key == account.key) {
+            bytes.push(first as u8);
+            bytes.extend_from_slice(&[0; 7]);
+            data_offsets.push(None);
+            continue;
+        }
+        bytes.extend_from_slice(&[u8::MAX, account.is_signer as u8, account.is_writable as u8, 0]);
+        bytes.extend_from_slice(&[0; 4]);
+        bytes.extend_from_slice(account.key.as_ref());
+        bytes.extend_from_slice(account.owner.as_ref());
+        bytes.extend_from_slice(&account.lamports.to_le_bytes());
+        bytes.extend_from_slice(&(account.data.len() as u64).to_le_bytes());
+        data_offsets.push(Some(bytes.len()));
+        bytes.extend_from_slice(&account.data);
+        bytes.resize(bytes.len() + MAX_PERMITTED_DATA_INCREASE, 0);
+        bytes.resize(bytes.len().next_multiple_of(8), 0);
+        bytes.extend_from_slice(&0u64.to_le_bytes());
+    }
+    bytes.extend_from_slice(&(instruction_data.len() as u64).to_le_bytes());
+    bytes.extend_from_slice(instruction_data);
+    bytes.extend_from_slice(vcoin_program::id().as_ref());
+
+    let mut input = vec![0u64; bytes.len().div_ceil(8)];
+    unsafe { std::slice::from_raw_parts_mut(input.as_mut_ptr() as *mut u8, bytes.len()) }.copy_from_slice(&bytes);
+    (input, data_offsets)
+}
+
+/// Instruction data for every variant, in variant order, from the Borsh fixtures
+pub fn instruction_fixtures() -> Vec<(&'static str, Vec<u8>)> {
+    include_str!("../fixtures/borsh_layouts.txt")
+        .lines()
+        .filter_map(|line| line.strip_prefix("instruction."))
+        .map(|line| {
+            let (name, hex) = line.split_once(' ').unwrap();
+            let data = (0..hex.len()).step_by(2).map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap()).collect();
+            (name, data)
+        })
+        .collect()
+}
@@ -0,0 +1,171 @@
+//! Every instruction tag reaches the handler of its own variant.
+
+mod common;
+
+use borsh::BorshDeserialize;
+use common::{instruction_fixtures, presale, process, process_data, with_emergency_state, TestAccount, NOW};
+use solana_program::{pubkey::Pubkey, system_program};
+use vcoin_program::{
+    error::VCoinError,
+    instruction::VCoinInstruction,
+    state::{AccessRole, AutonomousSupplyController, BugBountyEscrow, EmergencyMode, PresaleState, SupplyPriceSource, SupplyRateLimit},
+};
+
+/// Accounts that get an instruction of `role` past the access check
+fn accounts_for(role: AccessRole) -> Vec<TestAccount> {
+    let signer = TestAccount::signer(Pubkey::new_unique());
+    if role != AccessRole::Guardian {
+        return vec![signer];
+    }
+    let presale = presale();
+    let mint = presale.mint;
+    let (bounty_address, bump) = BugBountyEscrow::find_address(&vcoin_program::id(), &mint);
+    let bounty = BugBountyEscrow {
+        is_initialized: true,
+        authority: Pubkey::new_unique(),
+        mint,
+        escrow: Pubkey::new_unique(),
+        guardians: vec![signer.key],
+        guardian_threshold: 1,
+        total_funded: 0,
+        total_committed: 0,
+        total_paid: 0,
+        num_awards: 0,
+        bump,
+    };
+    vec![
+        TestAccount::program(Pubkey::new_unique(), &presale, PresaleState::get_size_for_buyers(1)),
+        TestAccount::program(bounty_address, &bounty, BugBountyEscrow::get_size()),
+        signer,
+    ]
+}
+
+#[test]
+fn every_variant_tag_reaches_a_handler() {
+    for (index, (name, data)) in instruction_fixtures().into_iter().enumerate() {
+        assert_eq!(data[0] as usize, index, "{name} is not variant {index}");
+        let instruction = VCoinInstruction::try_from_slice(&data).unwrap();
+        let mut accounts = with_emergency_state(&instruction, accounts_for(instruction.access_role()), EmergencyMode::Normal);
+
+        // The handler may reject the accounts, but the tag must reach the arm of its own variant
+        let outcome = process_data(&mut accounts, &data);
+        let arm = outcome.logs.iter().find_map(|line| line.strip_prefix("Instruction: "));
+        assert_eq!(arm.map(|arm| arm.replace([' ', '-'], "").to_lowercase()), Some(name.to_lowercase()), "tag {index}: {:?}", outcome.logs);
+        assert!(!outcome.logged("Unsupported instruction tag"), "{name} (tag {index}) has no dispatch arm");
+        assert_ne!(outcome.result, Err(VCoinError::InvalidInstructionData.into()), "{name} (tag {index}) did not decode");
+        if !outcome.logged("built with the `simulation` feature") {
+            assert_ne!(outcome.result, Err(VCoinError::InvalidInstruction.into()), "{name} (tag {index}) decoded to another variant");
+        }
+    }
+}
+
+#[test]
+fn buy_tokens_is_retired() {
+    let mut accounts = accounts_for(AccessRole::Buyer);
+    let outcome = process(&mut accounts, &VCoinInstruction::BuyTokens { amount_usd: 100_000_000 });
+    assert!(outcome.logged("purchases pay through BuyTokensWithStablecoin"));
+    assert_eq!(outcome.result, Err(VCoinError::InstructionRetired.into()));
+}
+
+#[test]
+fn update_controller_parameters_is_decoded_from_its_tag() {
+    let instruction = VCoinInstruction::UpdateControllerParameters { parameters: None };
+    assert_eq!(borsh::to_vec(&instruction).unwrap()[0], 97);
+
+    // A controller the program does not own stops the parameter update, not a circuit breaker reset
+    let mut accounts = vec![
+        TestAccount::signer(Pubkey::new_unique()),
+        TestAccount::new(Pubkey::new_unique(), system_program::ID, vec![0; 64]),
+        TestAccount::emergency_state(&Pubkey::new_unique(), EmergencyMode::Normal),
+    ];
+    let outcome = process(&mut accounts, &instruction);
+    assert!(outcome.logged("Instruction: Update Controller Parameters"));
+    assert_eq!(outcome.result, Err(VCoinError::InvalidAccountOwner.into()));
+}
+
+fn supply_controller(authority: Pubkey) -> AutonomousSupplyController {
+    AutonomousSupplyController {
+        is_initialized: true,
+        mint: Pubkey::new_unique(),
+        price_oracle: Pubkey::new_unique(),
+        initial_price: 30_000,
+        year_start_price: 30_000,
+        current_price: 30_000,
+        last_price_update: NOW,
+        year_start_timestamp: NOW,
+        last_mint_timestamp: 0,
+        last_burn_timestamp: 0,
+        current_supply: 2_000_000_000_000_000,
+        token_decimals: 6,
+        min_supply: 1_000_000_000_000_000,
+        high_supply_threshold: 5_000_000_000_000_000,
+        max_supply: 10_000_000_000_000_000,
+        mint_authority: Pubkey::new_unique(),
+        mint_authority_bump: 255,
+        burn_treasury: Pubkey::new_unique(),
+        burn_treasury_bump: 255,
+        min_growth_for_mint_bps: 500,
+        min_decline_for_burn_bps: 500,
+        medium_growth_mint_rate_bps: 500,
+        high_growth_mint_rate_bps: 1_000,
+        medium_decline_burn_rate_bps: 500,
+        high_decline_burn_rate_bps: 1_000,
+        high_growth_threshold_bps: 1_000,
+        high_decline_threshold_bps: 1_000,
+        extreme_growth_threshold_bps: 3_000,
+        extreme_decline_threshold_bps: 3_000,
+        post_cap_mint_rate_bps: 200,
+        post_cap_burn_rate_bps: 200,
+        price_source: SupplyPriceSource::MultiOracleController,
+        authority,
+        pending_parameters: None,
+        rate_limit: SupplyRateLimit::new(86_400, 1_000_000, 1, NOW),
+        distribution: None,
+        mint_cooldown_seconds: 86_400,
+        burn_cooldown_seconds: 86_400,
+        fees_swept_to_burn: 0,
+    }
+}
+
+#[test]
+fn supply_rate_limit_is_decoded_from_its_tag() {
+    let instruction = VCoinInstruction::SetSupplyRateLimit {
+        window_seconds: 7 * 86_400,
+        max_amount_per_window: 5_000_000,
+        max_operations_per_window: 3,
+    };
+    assert_eq!(borsh::to_vec(&instruction).unwrap()[0], 98);
+
+    // The payload sets the limit and leaves the price alone
+    let authority = Pubkey::new_unique();
+    let controller = supply_controller(authority);
+    let mut accounts = vec![
+        TestAccount::signer(authority),
+        TestAccount::program(Pubkey::new_unique(), &controller, AutonomousSupplyController::get_size()),
+        TestAccount::emergency_state(&controller.mint, EmergencyMode::Normal),
+    ];
+    let outcome = process(&mut accounts, &instruction);
+    assert_eq!(outcome.result, Ok(()));
+    let controller: AutonomousSupplyController = accounts[1].state();
+    assert_eq!(controller.rate_limit.window_seconds, 7 * 86_400);
+    assert_eq!(controller.rate_limit.max_amount_per_window, 5_000_000);
+    assert_eq!(controller.rate_limit.max_operations_per_window, 3);
+    assert_eq!(controller.current_price, 30_000);
+}
+
+#[test]
+fn direct_price_updates_are_decoded_from_their_own_tag() {
+    let instruction = VCoinInstruction::UpdatePriceDirectly { new_price: 45_000 };
+    assert_eq!(borsh::to_vec(&instruction).unwrap()[0], 33);
+
+    let controller = supply_controller(Pubkey::new_unique());
+    let (mint_authority, _) = Pubkey::find_program_address(&[b"mint_authority", controller.mint.as_ref()], &vcoin_program::id());
+    let mut accounts = vec![
+        TestAccount::signer(mint_authority),
+        TestAccount::program(Pubkey::new_unique(), &controller, AutonomousSupplyController::get_size()),
+        TestAccount::emergency_state(&controller.mint, EmergencyMode::Normal),
+    ];
+    let outcome = process(&mut accounts, &instruction);
+    assert_eq!(outcome.result, Ok(()));
+    assert_eq!(accounts[1].state::<AutonomousSupplyController>().current_price, 45_000);
+}
@@ -0,0 +1,282 @@
+//! Which instructions each emergency mode lets through.
+
+mod common;
+
+use borsh::BorshDeserialize;
+use common::{instruction_fixtures, process, process_data, with_emergency_state, TestAccount};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+use vcoin_program::{
+    error::VCoinError,
+    instruction::VCoinInstruction,
+    state::{AccessControl, AccessRole, EmergencyMode, EmergencyState, Multisig, OperationClass, Role, RoleGrant, Timelock, TimelockedOperation, MAX_PAUSE_HISTORY, MAX_ROLE_GRANTS, MAX_TIMELOCK_OPERATIONS},
+};
+
+#[test]
+fn maintenance_lets_only_refunds_and_claims_through() {
+    let authority = Pubkey::new_unique();
+    let mut emergency = EmergencyState::new(authority, Pubkey::new_unique());
+
+    let claim = VCoinInstruction::ClaimRefund.operation_class();
+    let sale = VCoinInstruction::EndPresale.operation_class();
+    let resume = VCoinInstruction::EmergencyResume.operation_class();
+    assert_eq!(claim, OperationClass::UserProtective);
+    assert_eq!(sale, OperationClass::Restricted);
+    assert_eq!(resume, OperationClass::EmergencyControl);
+    assert_eq!(VCoinInstruction::GetVersion.operation_class(), OperationClass::ReadOnly);
+    assert!(emergency.allows(sale));
+
+    emergency.enter_maintenance(&authority, Some("migration".to_string()), 1_700_000_000).unwrap();
+    assert_eq!(emergency.emergency_mode, EmergencyMode::Maintenance);
+    assert!(emergency.allows(claim) && emergency.allows(resume));
+    assert!(!emergency.allows(sale));
+
+    // Escalating to a pause blocks claims too, within the same pause record
+    emergency.pause(&authority, Some("incident".to_string()), 1_700_000_060).unwrap();
+    assert!(!emergency.allows(claim) && !emergency.allows(sale));
+    assert!(emergency.allows(resume));
+    assert_eq!(emergency.pause_history.len(), 1);
+    assert_eq!(emergency.pause_history[0].paused_at, 1_700_000_000);
+
+    emergency.resume(&authority, 1_700_000_120).unwrap();
+    assert!(emergency.allows(sale));
+    assert_eq!(emergency.pause_history[0].resumed_at, Some(1_700_000_120));
+
+    // Only emergency or program authorities change the mode
+    assert!(emergency.enter_maintenance(&Pubkey::new_unique(), None, 1_700_000_180).is_err());
+}
+
+#[test]
+fn gated_instructions_need_their_mints_emergency_state() {
+    for (name, data) in instruction_fixtures() {
+        let instruction = VCoinInstruction::try_from_slice(&data).unwrap();
+        let class = instruction.operation_class();
+        let Some(index) = instruction.emergency_state_index() else {
+            continue;
+        };
+        assert!(
+            matches!(class, OperationClass::Restricted | OperationClass::UserProtective),
+            "{name} runs in every mode but takes an emergency state",
+        );
+        let signer = || vec![TestAccount::signer(Pubkey::new_unique())];
+
+        // Without the account at its slot the instruction fails before its handler
+        let mut accounts = signer();
+        accounts.resize_with(index, || TestAccount::empty(Pubkey::new_unique()));
+        let outcome = process_data(&mut accounts, &data);
+        assert_eq!(outcome.result, Err(ProgramError::NotEnoughAccountKeys), "{name}: {:?}", outcome.logs);
+        assert!(outcome.logged("Missing the mint's emergency state account"));
+        assert!(!outcome.logged("Instruction: "), "{name} reached its handler");
+
+        // Paused, nothing but emergency controls and read-only calls runs
+        let mut accounts = with_emergency_state(&instruction, signer(), EmergencyMode::Paused);
+        let outcome = process_data(&mut accounts, &data);
+        assert_eq!(outcome.result, Err(VCoinError::BlockedByEmergencyMode.into()), "{name}: {:?}", outcome.logs);
+        assert!(!outcome.logged("Instruction: "), "{name} reached its handler while paused");
+
+        // In maintenance, refunds and claims still reach their handlers
+        let mut accounts = with_emergency_state(&instruction, signer(), EmergencyMode::Maintenance);
+        let outcome = process_data(&mut accounts, &data);
+        if class == OperationClass::Restricted {
+            assert_eq!(outcome.result, Err(VCoinError::BlockedByEmergencyMode.into()), "{name}: {:?}", outcome.logs);
+            assert!(!outcome.logged("Instruction: "), "{name} reached its handler in maintenance");
+        } else {
+            assert!(outcome.logged("Instruction: "), "{name}: {:?}", outcome.logs);
+        }
+    }
+
+    // An account that is not an emergency state of this program does not count
+    let end = VCoinInstruction::EndPresale;
+    let mut accounts = with_emergency_state(&end, vec![TestAccount::signer(Pubkey::new_unique())], EmergencyMode::Normal);
+    accounts[2].owner = Pubkey::new_unique();
+    let outcome = process(&mut accounts, &end);
+    assert_eq!(outcome.result, Err(VCoinError::InvalidAccountOwner.into()));
+    assert!(!outcome.logged("Instruction: "));
+}
+
+#[test]
+fn guardian_interventions_run_while_paused() {
+    let authority = Pubkey::new_unique();
+    let mut emergency = EmergencyState::new(authority, Pubkey::new_unique());
+    let declare = VCoinInstruction::DeclareForcedRefund { reason_hash: [1; 32] }.operation_class();
+    assert_eq!(declare, OperationClass::EmergencyControl);
+    let sweep = VCoinInstruction::SweepTreasuryToCanonical.operation_class();
+    assert_eq!(sweep, OperationClass::EmergencyControl);
+
+    emergency.pause(&authority, Some("critical failure".to_string()), 1_700_000_000).unwrap();
+    assert!(emergency.allows(declare) && emergency.allows(sweep));
+}
+
+#[test]
+fn a_multisig_pause_needs_its_threshold_of_keys() {
+    let keys = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+    assert!(Multisig::signers_are_valid(&keys, 2));
+    assert!(!Multisig::signers_are_valid(&keys, 4));
+    assert!(!Multisig::signers_are_valid(&[keys[0], keys[0]], 1));
+    let multisig = Multisig { is_initialized: true, base: Pubkey::new_unique(), threshold: 2, signers: keys.to_vec(), bump: 255 };
+    assert!(borsh::to_vec(&multisig).unwrap().len() <= Multisig::get_size());
+
+    // A key signing twice or an outsider does not count
+    assert!(!multisig.is_approved(&[keys[0], keys[0], Pubkey::new_unique()]));
+    assert!(multisig.is_approved(&[keys[2], keys[0]]));
+
+    // The multisig stays the authority account but no longer signs; its keys do
+    let pause = VCoinInstruction::emergency_pause(&Pubkey::new_unique(), None).unwrap();
+    let pause = VCoinInstruction::with_multisig_signers(pause, &keys[..2]);
+    assert!(!pause.accounts[0].is_signer);
+    assert_eq!(pause.accounts.len(), 4);
+    assert!(pause.accounts[2..].iter().all(|account| account.is_signer));
+}
+
+#[test]
+fn timelocked_calls_run_only_after_their_delay() {
+    let mint = Pubkey::new_unique();
+    let mut timelock = Timelock {
+        is_initialized: true,
+        mint,
+        proposer: Pubkey::new_unique(),
+        delay: 2 * 86_400,
+        next_operation_id: 0,
+        queued: Vec::new(),
+        bump: 255,
+    };
+    let fee = TimelockedOperation::SetTransferFee { mint, transfer_fee_basis_points: 50, maximum_fee: 1_000_000 };
+    let rescue = TimelockedOperation::RescueTokens { source: Pubkey::new_unique(), destination: Pubkey::new_unique(), amount: 10 };
+
+    let queued = timelock.queue(fee, 1_700_000_000).unwrap();
+    assert_eq!(queued.executable_at, 1_700_172_800);
+    let rescue_id = timelock.queue(rescue, 1_700_000_000).unwrap().id;
+
+    // The call must match what was queued and wait out the delay
+    let other_fee = TimelockedOperation::SetTransferFee { mint, transfer_fee_basis_points: 100, maximum_fee: 1_000_000 };
+    assert_eq!(timelock.take_ready(&other_fee, 1_800_000_000), Err(VCoinError::TimelockOperationNotQueued));
+    assert_eq!(timelock.take_ready(&fee, 1_700_172_799), Err(VCoinError::TimelockDelayNotElapsed));
+    assert_eq!(timelock.take_ready(&fee, 1_700_172_800), Ok(queued));
+    assert_eq!(timelock.take_ready(&fee, 1_700_172_800), Err(VCoinError::TimelockOperationNotQueued));
+
+    // A cancelled call can no longer run
+    assert_eq!(timelock.cancel(rescue_id).map(|cancelled| cancelled.operation), Some(rescue));
+    assert_eq!(timelock.take_ready(&rescue, 1_800_000_000), Err(VCoinError::TimelockOperationNotQueued));
+
+    while timelock.queued.len() < MAX_TIMELOCK_OPERATIONS {
+        timelock.queue(rescue, 1_700_000_000).unwrap();
+    }
+    assert!(timelock.queue(rescue, 1_700_000_000).is_err());
+    assert!(borsh::to_vec(&timelock).unwrap().len() <= Timelock::get_size());
+
+    // The timelock takes the authority's place, writable and unsigned
+    let program_id = Pubkey::new_unique();
+    let set_fee = VCoinInstruction::set_transfer_fee(&program_id, &Pubkey::new_unique(), &mint, 50, 1_000_000).unwrap();
+    let set_fee = VCoinInstruction::with_timelock(set_fee, &mint);
+    assert_eq!(set_fee.accounts[0].pubkey, Timelock::find_address(&program_id, &mint).0);
+    assert!(set_fee.accounts[0].is_writable && !set_fee.accounts[0].is_signer);
+    assert_eq!(VCoinInstruction::CancelTimelockOperation { operation_id: 0 }.operation_class(), OperationClass::EmergencyControl);
+}
+
+#[test]
+fn each_instruction_declares_who_approves_it() {
+    assert_eq!(VCoinInstruction::EmergencyPause { reason: None }.access_role(), AccessRole::Authority);
+    assert_eq!(VCoinInstruction::BuyTokens { amount_usd: 1 }.access_role(), AccessRole::Buyer);
+    assert_eq!(VCoinInstruction::ClaimDevFundRefund.access_role(), AccessRole::Buyer);
+    assert_eq!(VCoinInstruction::ClaimRefund.access_role(), AccessRole::Permissionless);
+    assert_eq!(VCoinInstruction::DeclareForcedRefund { reason_hash: [1; 32] }.access_role(), AccessRole::Guardian);
+    assert_eq!(VCoinInstruction::SweepTreasuryToCanonical.access_role(), AccessRole::Guardian);
+    assert_eq!(VCoinInstruction::CrankSupplyController.access_role(), AccessRole::Permissionless);
+    assert_eq!(VCoinInstruction::GetVersion.access_role(), AccessRole::Permissionless);
+
+    // Only the timelocked calls let a timelock stand in for their authority
+    let rescue = VCoinInstruction::RescueTokens { amount: 1 };
+    assert!(rescue.accepts_timelock() && rescue.access_role() == AccessRole::Authority);
+    assert!(VCoinInstruction::ExecuteWithdraw.accepts_timelock());
+    assert!(!VCoinInstruction::EmergencyResume.accepts_timelock());
+    assert!(!VCoinInstruction::NoticeWithdraw.accepts_timelock());
+}
+
+#[test]
+fn roles_separate_operational_duties() {
+    let mint = Pubkey::new_unique();
+    let admin = Pubkey::new_unique();
+    let fee_manager = Pubkey::new_unique();
+    let mut access_control = AccessControl {
+        is_initialized: true,
+        mint,
+        grants: vec![RoleGrant { member: admin, role: Role::Admin }],
+        bump: 255,
+    };
+
+    access_control.grant(fee_manager, Role::FeeManager).unwrap();
+    assert!(access_control.grant(fee_manager, Role::FeeManager).is_err());
+    assert!(access_control.has_role(&fee_manager, Role::FeeManager));
+    assert!(!access_control.has_role(&fee_manager, Role::Treasurer));
+    assert!(!access_control.has_role(&admin, Role::FeeManager));
+
+    // The last admin stays, so the roles can always be managed
+    assert!(access_control.revoke(&admin, Role::Admin).is_err());
+    access_control.revoke(&fee_manager, Role::FeeManager).unwrap();
+    assert!(access_control.revoke(&fee_manager, Role::FeeManager).is_err());
+
+    while access_control.grants.len() < MAX_ROLE_GRANTS {
+        access_control.grant(Pubkey::new_unique(), Role::Admin).unwrap();
+    }
+    assert!(access_control.grant(fee_manager, Role::FeeManager).is_err());
+    assert!(borsh::to_vec(&access_control).unwrap().len() <= AccessControl::get_size());
+    access_control.revoke(&admin, Role::Admin).unwrap();
+
+    // Each duty maps to its role; everything else needs an admin
+    assert_eq!(VCoinInstruction::SetTransferFee { transfer_fee_basis_points: 1, maximum_fee: 1 }.required_role(), Role::FeeManager);
+    assert_eq!(VCoinInstruction::EmergencyPause { reason: None }.required_role(), Role::EmergencyGuardian);
+    assert_eq!(VCoinInstruction::ExecuteWithdraw.required_role(), Role::Treasurer);
+    assert_eq!(VCoinInstruction::GrantRole { member: fee_manager, role: Role::Treasurer }.required_role(), Role::Admin);
+
+    // The access control takes the authority's place and the role holder signs after
+    let program_id = Pubkey::new_unique();
+    let set_fee = VCoinInstruction::set_transfer_fee(&program_id, &Pubkey::new_unique(), &mint, 50, 1_000_000).unwrap();
+    let set_fee = VCoinInstruction::with_role_holder(set_fee, &mint, &fee_manager);
+    assert_eq!(set_fee.accounts[0].pubkey, AccessControl::find_address(&program_id, &mint).0);
+    assert!(!set_fee.accounts[0].is_signer);
+    let holder = set_fee.accounts.last().unwrap();
+    assert!(holder.pubkey == fee_manager && holder.is_signer);
+}
+
+#[test]
+fn rescues_only_move_accounts_held_by_a_token_program() {
+    let authority = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let (token_authority, _) = Pubkey::find_program_address(&[b"token_authority", mint.as_ref()], &vcoin_program::id());
+    let emergency = EmergencyState::new(authority, Pubkey::new_unique());
+    let (emergency_address, _) = EmergencyState::find_address(&vcoin_program::id(), &mint);
+    let rescue_accounts = vec![
+        TestAccount::signer(authority),
+        TestAccount::token(Pubkey::new_unique(), mint, token_authority, 1_000),
+        TestAccount::token(Pubkey::new_unique(), mint, Pubkey::new_unique(), 0),
+        TestAccount::new(token_authority, solana_program::system_program::ID, Vec::new()).readonly(),
+        TestAccount::new(spl_token::ID, Pubkey::default(), Vec::new()).readonly(),
+        TestAccount::mint(mint, 6).readonly(),
+        TestAccount::program(emergency_address, &emergency, EmergencyState::get_space(MAX_PAUSE_HISTORY)).readonly(),
+    ];
+    let rescue = VCoinInstruction::RescueTokens { amount: 1_000 };
+
+    // Neither the source nor its mint may be owned by another program
+    for index in [1, 5] {
+        let mut accounts = rescue_accounts.clone();
+        accounts[index].owner = Pubkey::new_unique();
+        let outcome = process(&mut accounts, &rescue);
+        assert_eq!(outcome.result, Err(ProgramError::IncorrectProgramId), "account {}: {:?}", index, outcome.logs);
+        assert!(outcome.logged("is not owned by a token program"));
+        assert!(outcome.invocations.is_empty());
+    }
+
+    // Nor may the emergency state be another mint's, even one naming the caller
+    let other_mint = Pubkey::new_unique();
+    let (other_address, _) = EmergencyState::find_address(&vcoin_program::id(), &other_mint);
+    let mut accounts = rescue_accounts.clone();
+    accounts[6].key = other_address;
+    let outcome = process(&mut accounts, &rescue);
+    assert_eq!(outcome.result, Err(ProgramError::InvalidSeeds));
+    assert!(outcome.logged("is not the emergency state of mint"));
+    assert!(outcome.invocations.is_empty());
+
+    let mut accounts = rescue_accounts;
+    let outcome = process(&mut accounts, &rescue);
+    assert_eq!(outcome.result, Ok(()));
+    assert_eq!(outcome.invocations[0].transferred(), Some(1_000));
+}
@@ -0,0 +1,33 @@
+//! Rent estimates for deployment payers.
+
+use solana_program::rent::Rent;
+use vcoin_program::estimate;
+use vcoin_program::state::{PresaleState, VestingGrant, VestingState, PRESALE_INITIAL_BUYER_CAPACITY};
+
+#[test]
+fn estimates_price_the_sizes_the_handlers_allocate() {
+    let rent = Rent::default();
+
+    // Capacity below the initial allocation still pays for the initial account
+    let presale = estimate::presale(&rent, 10);
+    assert_eq!(presale, estimate::presale(&rent, PRESALE_INITIAL_BUYER_CAPACITY));
+    assert_eq!(presale.accounts[0].size, PresaleState::get_size_for_buyers(PRESALE_INITIAL_BUYER_CAPACITY));
+    assert!(estimate::presale(&rent, 20_000).total_lamports() > presale.total_lamports());
+
+    // The registry is only paid for with the first pool
+    let first = estimate::vesting(&rent, 3, true);
+    let second = estimate::vesting(&rent, 3, false);
+    assert_eq!(first.accounts.len(), 3);
+    assert_eq!(
+        second.total_lamports(),
+        rent.minimum_balance(VestingState::get_size()) + 3 * rent.minimum_balance(VestingGrant::get_size())
+    );
+    assert!(first.total_lamports() > second.total_lamports());
+
+    let controllers = estimate::autonomous_controller(&rent).merge(estimate::oracle_controller(&rent));
+    assert_eq!(controllers.accounts.len(), 2);
+    assert_eq!(
+        controllers.total_lamports(),
+        controllers.accounts.iter().map(|cost| cost.lamports).sum::<u64>()
+    );
+}
@@ -0,0 +1,119 @@
+//! Initial supply allocation tables and plans.
+
+use solana_program::pubkey::Pubkey;
+use solana_program::program_error::ProgramError;
+use vcoin_program::error::VCoinError;
+use vcoin_program::state::{
+    AllocationCategory, AllocationEscrow, AllocationPlan, AllocationShare, AutonomousSupplyController,
+    InitialAllocation, VestingState, MAX_INITIAL_ALLOCATIONS,
+};
+
+#[test]
+fn tables_split_the_whole_supply_across_distinct_escrows() {
+    let vesting = Pubkey::new_unique();
+    let table = [
+        InitialAllocation { escrow: AllocationEscrow::Vesting { vesting }, amount: 350 },
+        InitialAllocation { escrow: AllocationEscrow::SupplyTreasury, amount: 550 },
+        InitialAllocation { escrow: AllocationEscrow::Liquidity, amount: 100 },
+    ];
+    assert!(InitialAllocation::table_is_valid(&table, 1_000));
+
+    // Anything short of or beyond the supply would leave tokens unaccounted for
+    assert!(!InitialAllocation::table_is_valid(&table, 999));
+    assert!(!InitialAllocation::table_is_valid(&table, 1_001));
+
+    // Zero amounts and repeated escrows are rejected; separate vesting pools are distinct
+    let zero = [table[0], InitialAllocation { escrow: AllocationEscrow::Liquidity, amount: 0 }];
+    assert!(!InitialAllocation::table_is_valid(&zero, 350));
+    let repeated = [table[1], table[1]];
+    assert!(!InitialAllocation::table_is_valid(&repeated, 1_100));
+    let pools: Vec<InitialAllocation> = (0..=MAX_INITIAL_ALLOCATIONS)
+        .map(|_| InitialAllocation { escrow: AllocationEscrow::Vesting { vesting: Pubkey::new_unique() }, amount: 1 })
+        .collect();
+    assert!(InitialAllocation::table_is_valid(&pools[..MAX_INITIAL_ALLOCATIONS], MAX_INITIAL_ALLOCATIONS as u64));
+    assert!(!InitialAllocation::table_is_valid(&pools, pools.len() as u64));
+
+    // Overflowing totals never match
+    let huge = [
+        InitialAllocation { escrow: AllocationEscrow::SupplyTreasury, amount: u64::MAX },
+        InitialAllocation { escrow: AllocationEscrow::Liquidity, amount: 2 },
+    ];
+    assert!(!InitialAllocation::table_is_valid(&huge, 1));
+}
+
+#[test]
+fn escrows_are_owned_by_program_addresses() {
+    let program_id = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let vesting = Pubkey::new_unique();
+
+    assert_eq!(
+        AllocationEscrow::Vesting { vesting }.find_owner(&program_id, &mint),
+        VestingState::find_escrow_authority(&program_id, &vesting)
+    );
+    assert_eq!(
+        AllocationEscrow::SupplyTreasury.find_owner(&program_id, &mint),
+        AutonomousSupplyController::find_supply_treasury(&program_id, &mint)
+    );
+    let (liquidity, _) = AllocationEscrow::Liquidity.find_owner(&program_id, &mint);
+    assert_eq!(liquidity, Pubkey::find_program_address(&[b"liquidity_escrow", mint.as_ref()], &program_id).0);
+    assert!(!liquidity.is_on_curve());
+}
+
+fn share(category: AllocationCategory, bps: u16) -> AllocationShare {
+    AllocationShare { category, bps }
+}
+
+#[test]
+fn plans_split_exactly_one_hundred_percent() {
+    let plan = [
+        share(AllocationCategory::Presale, 2_000),
+        share(AllocationCategory::Team, 3_500),
+        share(AllocationCategory::Treasury, 3_500),
+        share(AllocationCategory::Liquidity, 1_000),
+    ];
+    assert!(AllocationPlan::shares_are_valid(&plan));
+    assert!(AllocationPlan::shares_are_valid(&[share(AllocationCategory::Treasury, 10_000)]));
+
+    assert!(!AllocationPlan::shares_are_valid(&plan[..3]));
+    assert!(!AllocationPlan::shares_are_valid(&[share(AllocationCategory::Team, 5_000), share(AllocationCategory::Team, 5_000)]));
+    assert!(!AllocationPlan::shares_are_valid(&[share(AllocationCategory::Team, 10_000), share(AllocationCategory::Presale, 0)]));
+    assert!(!AllocationPlan::shares_are_valid(&[share(AllocationCategory::Team, 10_001)]));
+}
+
+#[test]
+fn draws_cannot_exceed_a_category_share() {
+    let mut plan = AllocationPlan::new(Pubkey::new_unique(), 1_000_000, &[
+        share(AllocationCategory::Presale, 2_500),
+        share(AllocationCategory::Team, 7_500),
+    ]);
+    assert_eq!(plan.allowance(AllocationCategory::Presale), 250_000);
+    assert_eq!(plan.allowance(AllocationCategory::Treasury), 0);
+
+    // Two vesting pools may share the team allocation but not overrun it
+    plan.draw(AllocationCategory::Team, 500_000).unwrap();
+    plan.draw(AllocationCategory::Team, 250_000).unwrap();
+    assert_eq!(plan.remaining(AllocationCategory::Team), 0);
+    assert_eq!(
+        plan.draw(AllocationCategory::Team, 1),
+        Err(ProgramError::from(VCoinError::AllocationPlanExceeded))
+    );
+
+    // A presale whose hard cap sells more than its share is refused without drawing
+    assert!(plan.draw(AllocationCategory::Presale, 250_001).is_err());
+    assert_eq!(plan.remaining(AllocationCategory::Presale), 250_000);
+
+    // Categories the plan leaves out cannot be drawn at all
+    assert!(plan.draw(AllocationCategory::Liquidity, 1).is_err());
+    assert!(plan.draw(AllocationCategory::Liquidity, 0).is_ok());
+
+    assert!(borsh::to_vec(&plan).unwrap().len() <= AllocationPlan::get_size());
+}
+
+#[test]
+fn escrows_draw_from_their_category() {
+    let vesting = Pubkey::new_unique();
+    assert_eq!(AllocationEscrow::Vesting { vesting }.category(), AllocationCategory::Team);
+    assert_eq!(AllocationEscrow::SupplyTreasury.category(), AllocationCategory::Treasury);
+    assert_eq!(AllocationEscrow::Liquidity.category(), AllocationCategory::Liquidity);
+}
@@ -0,0 +1,140 @@
+//! KYC credentials are only issued against the attestor's ed25519 attestation.
+
+mod common;
+
+use common::{presale, process, Outcome, TestAccount, NOW};
+use solana_program::{
+    ed25519_program,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program,
+};
+use vcoin_program::{
+    error::VCoinError,
+    instruction::VCoinInstruction,
+    state::{EmergencyMode, KycCredential, PresaleState},
+};
+
+const EXPIRES_AT: i64 = NOW + 365 * 86_400;
+
+/// An ed25519 program instruction checking one signature by `signer` over
+/// `message`, with the key, signature and message embedded in its own data.
+/// The runtime verifies the signature before the program runs, so its bytes
+/// do not matter here.
+fn ed25519_instruction(signer: &Pubkey, message: &[u8]) -> Instruction {
+    const DATA_START: u16 = 16;
+    let public_key_offset = DATA_START;
+    let signature_offset = public_key_offset + 32;
+    let message_offset = signature_offset + 64;
+
+    let mut data = vec![1, 0];
+    for field in [signature_offset, u16::MAX, public_key_offset, u16::MAX, message_offset, message.len() as u16, u16::MAX] {
+        data.extend_from_slice(&field.to_le_bytes());
+    }
+    data.extend_from_slice(signer.as_ref());
+    data.extend_from_slice(&[7; 64]);
+    data.extend_from_slice(message);
+    Instruction::new_with_bytes(ed25519_program::ID, &data, Vec::new())
+}
+
+struct Kyc {
+    state: PresaleState,
+    presale: Pubkey,
+    buyer: Pubkey,
+}
+
+impl Kyc {
+    fn new() -> Self {
+        let state = PresaleState { kyc_required: true, kyc_attestor: Pubkey::new_unique(), ..presale() };
+        Self { state, presale: Pubkey::new_unique(), buyer: Pubkey::new_unique() }
+    }
+
+    /// What the attestor signs for this buyer
+    fn message(&self) -> Vec<u8> {
+        KycCredential::attestation_message(&self.presale, &self.buyer, EXPIRES_AT)
+    }
+
+    /// Issue the credential in a transaction of `preceding` followed by the issue
+    /// instruction itself
+    fn issue(&self, preceding: Vec<Instruction>) -> (Outcome, Vec<TestAccount>) {
+        let program_id = vcoin_program::id();
+        let (credential, _) = Pubkey::find_program_address(
+            &[b"kyc_credential", self.presale.as_ref(), self.buyer.as_ref()],
+            &program_id,
+        );
+        let instruction = VCoinInstruction::IssueKycCredential { expires_at: EXPIRES_AT };
+        let current = preceding.len() as u16;
+        let mut transaction = preceding;
+        let data = borsh::to_vec(&instruction).unwrap();
+        transaction.push(Instruction::new_with_bytes(program_id, &data, vec![AccountMeta::new(self.buyer, true)]));
+
+        let mut accounts = vec![
+            TestAccount::signer(self.buyer),
+            TestAccount::program(self.presale, &self.state, PresaleState::get_size_for_buyers(1)).readonly(),
+            TestAccount::empty(credential),
+            TestAccount::instructions_sysvar(&transaction, current),
+            TestAccount::new(system_program::ID, Pubkey::default(), Vec::new()).readonly(),
+            TestAccount::emergency_state(&self.state.mint, EmergencyMode::Normal),
+        ];
+        let outcome = process(&mut accounts, &instruction);
+        (outcome, accounts)
+    }
+
+    /// Issue the credential and expect it to be refused
+    fn refused(&self, preceding: Vec<Instruction>, reason: &str) {
+        let (outcome, accounts) = self.issue(preceding);
+        assert_eq!(outcome.result, Err(VCoinError::InvalidKycAttestation.into()));
+        assert!(outcome.logged(reason), "{:?}", outcome.logs);
+        assert!(accounts[2].data.is_empty());
+    }
+}
+
+#[test]
+fn a_valid_attestation_issues_the_credential() {
+    let kyc = Kyc::new();
+    let (outcome, accounts) = kyc.issue(vec![ed25519_instruction(&kyc.state.kyc_attestor, &kyc.message())]);
+    assert_eq!(outcome.result, Ok(()));
+    let credential: KycCredential = accounts[2].state();
+    assert_eq!((credential.buyer, credential.attestor, credential.expires_at), (kyc.buyer, kyc.state.kyc_attestor, EXPIRES_AT));
+}
+
+#[test]
+fn an_attestation_by_another_key_is_refused() {
+    let kyc = Kyc::new();
+    kyc.refused(
+        vec![ed25519_instruction(&Pubkey::new_unique(), &kyc.message())],
+        "does not match the expected signer or message",
+    );
+}
+
+#[test]
+fn a_tampered_message_is_refused() {
+    let kyc = Kyc::new();
+    let mut message = kyc.message();
+    *message.last_mut().unwrap() ^= 1;
+    kyc.refused(
+        vec![ed25519_instruction(&kyc.state.kyc_attestor, &message)],
+        "does not match the expected signer or message",
+    );
+
+    // Nor does an attestation for another buyer carry over
+    let other_buyer = KycCredential::attestation_message(&kyc.presale, &Pubkey::new_unique(), EXPIRES_AT);
+    kyc.refused(
+        vec![ed25519_instruction(&kyc.state.kyc_attestor, &other_buyer)],
+        "does not match the expected signer or message",
+    );
+}
+
+#[test]
+fn the_attestation_must_immediately_precede_the_issue() {
+    let kyc = Kyc::new();
+    let attested = ed25519_instruction(&kyc.state.kyc_attestor, &kyc.message());
+    let unrelated = Instruction::new_with_bytes(system_program::ID, &[], Vec::new());
+    kyc.refused(vec![attested, unrelated], "Preceding instruction is not an ed25519 signature check");
+}
+
+#[test]
+fn a_missing_attestation_is_refused() {
+    let kyc = Kyc::new();
+    kyc.refused(Vec::new(), "Missing ed25519 attestation instruction");
+}
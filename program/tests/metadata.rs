@@ -0,0 +1,68 @@
+//! Token metadata URI rules and display hints.
+
+use solana_program::pubkey::Pubkey;
+use vcoin_program::state::{
+    MetadataDisplayHints, TokenMetadata, MAX_METADATA_URI_LEN, MAX_TICKER_ALIASES, MAX_TICKER_ALIAS_LEN,
+};
+
+#[test]
+fn uris_need_a_known_scheme_and_printable_ascii() {
+    for uri in ["", "https://vcoin.example/meta.json", "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi", "ar://kbHnY8hI3Ifk1uLpgbALxBGZjOiw0L9o2WsAOUkMW6E"] {
+        assert!(TokenMetadata::uri_is_valid(uri), "{}", uri);
+    }
+
+    // Other schemes, bare schemes, whitespace and raw non-ASCII are rejected
+    for uri in ["http://vcoin.example/meta.json", "javascript:alert(1)", "https://", "ipfs://bafy meta", "https://vcoin.example/m\u{e9}ta.json", "HTTPS://vcoin.example"] {
+        assert!(!TokenMetadata::uri_is_valid(uri), "{}", uri);
+    }
+    assert!(TokenMetadata::uri_is_valid("https://vcoin.example/m%C3%A9ta.json"));
+
+    let longest = format!("https://{}", "a".repeat(MAX_METADATA_URI_LEN - "https://".len()));
+    assert!(TokenMetadata::uri_is_valid(&longest));
+    assert!(!TokenMetadata::uri_is_valid(&format!("{}a", longest)));
+}
+
+#[test]
+fn display_hints_fit_the_mint_and_list_distinct_tickers() {
+    let hints = |display_decimals: Option<u8>, aliases: &[&str]| MetadataDisplayHints {
+        display_decimals,
+        ticker_aliases: aliases.iter().map(|alias| alias.to_string()).collect(),
+    };
+    assert!(hints(Some(2), &["VCOIN", "VCN2"]).is_valid(6));
+    assert!(hints(Some(6), &[]).is_valid(6));
+    assert!(hints(None, &["VCOIN"]).is_valid(0));
+
+    // More display decimals than the mint has
+    assert!(!hints(Some(7), &[]).is_valid(6));
+    // Empty, too long, non-alphanumeric or repeated tickers
+    assert!(!hints(None, &[""]).is_valid(6));
+    assert!(!hints(None, &[&"V".repeat(MAX_TICKER_ALIAS_LEN + 1)]).is_valid(6));
+    assert!(!hints(None, &["V-COIN"]).is_valid(6));
+    assert!(!hints(None, &["VCOIN", "vcoin"]).is_valid(6));
+    let too_many: Vec<String> = (0..=MAX_TICKER_ALIASES).map(|i| format!("VCN{}", i)).collect();
+    let too_many: Vec<&str> = too_many.iter().map(String::as_str).collect();
+    assert!(!hints(None, &too_many).is_valid(6));
+}
+
+#[test]
+fn accounts_are_sized_for_a_hash_and_a_pending_authority() {
+    let name = "VCoin".to_string();
+    let symbol = "VCN".to_string();
+    let uri = format!("https://{}", "a".repeat(MAX_METADATA_URI_LEN - "https://".len()));
+    let metadata = TokenMetadata {
+        is_initialized: true,
+        authority: Pubkey::new_unique(),
+        mint: Pubkey::new_unique(),
+        name: name.clone(),
+        symbol: symbol.clone(),
+        uri: uri.clone(),
+        last_updated_timestamp: 1_700_000_000,
+        uri_hash: Some([7; 32]),
+        pending_authority: Some(Pubkey::new_unique()),
+        display_hints: Some(MetadataDisplayHints {
+            display_decimals: Some(u8::MAX),
+            ticker_aliases: vec!["V".repeat(MAX_TICKER_ALIAS_LEN); MAX_TICKER_ALIASES],
+        }),
+    };
+    assert!(borsh::to_vec(&metadata).unwrap().len() <= TokenMetadata::get_size(name.len(), symbol.len(), uri.len()));
+}
@@ -0,0 +1,744 @@
+//! Oracle controller sources, Pyth pull-oracle price update accounts and signed
+//! RedStone payloads, Chainlink Data Streams reports and publisher prices.
+
+mod common;
+
+use common::{process, TestAccount};
+use solana_program::{account_info::AccountInfo, keccak, program_error::ProgramError, pubkey::Pubkey};
+use vcoin_program::error::VCoinError;
+use vcoin_program::processor::get_price_from_oracle;
+use vcoin_program::VCoinInstruction;
+use vcoin_program::state::{
+    AggregationStrategy, ChainlinkStreamsConfig, CircuitBreakerTrigger, ManagedFeed, MultiOracleController, OracleFeedRegistry, OracleOperation, OracleSource, OracleType, PriceSample, PythPriceUpdate,
+    PythVerificationLevel, QuorumPolicy, RedstoneConfig, RedstonePrice, SignedPriceReport, StalenessPolicy, MAX_CIRCUIT_BREAKER_INCIDENTS,
+    MAX_CONSECUTIVE_ORACLE_FAILURES, MAX_MANAGED_FEEDS, MAX_MANAGED_FEED_ASSET_ID_LEN, MAX_ORACLE_SOURCES, MAX_POLICY_STALENESS, PRICE_HISTORY_LEN,
+};
+
+/// Raw `PriceUpdateV2` account bytes as written by the Pyth Receiver
+fn price_update_account(verification: &[u8], feed_id: [u8; 32], price: i64, exponent: i32) -> Vec<u8> {
+    let mut data = PythPriceUpdate::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(Pubkey::new_unique().as_ref());
+    data.extend_from_slice(verification);
+    data.extend_from_slice(&feed_id);
+    data.extend_from_slice(&price.to_le_bytes());
+    data.extend_from_slice(&2_500_000u64.to_le_bytes()); // conf
+    data.extend_from_slice(&exponent.to_le_bytes());
+    data.extend_from_slice(&1_700_000_000i64.to_le_bytes()); // publish_time
+    data.extend_from_slice(&1_699_999_999i64.to_le_bytes()); // prev_publish_time
+    data.extend_from_slice(&price.to_le_bytes()); // ema_price
+    data.extend_from_slice(&2_500_000u64.to_le_bytes()); // ema_conf
+    data.extend_from_slice(&250_000_000u64.to_le_bytes()); // posted_slot
+    // Accounts are sized for a partial verification level
+    data.resize(8 + 32 + 2 + 32 + 8 * 8 + 4, 0);
+    data
+}
+
+#[test]
+fn price_update_decodes_and_scales_to_micro_usd() {
+    let feed_id = [0xef; 32];
+
+    // $30.00 with an exponent of -8
+    let full = PythPriceUpdate::unpack(&price_update_account(&[1], feed_id, 3_000_000_000, -8)).unwrap();
+    assert_eq!(full.verification_level, PythVerificationLevel::Full);
+    assert_eq!(full.feed_id, feed_id);
+    assert_eq!(full.publish_time, 1_700_000_000);
+    assert_eq!(full.posted_slot, 250_000_000);
+    assert_eq!(full.usd_price(), Some((30_000_000, 25_000)));
+
+    let partial = PythPriceUpdate::unpack(&price_update_account(&[0, 5], feed_id, 3, 2)).unwrap();
+    assert_eq!(partial.verification_level, PythVerificationLevel::Partial { num_signatures: 5 });
+    assert_eq!(partial.usd_price(), Some((300_000_000, 250_000_000_000_000)));
+
+    // Non-positive prices and other account types are rejected
+    let negative = PythPriceUpdate::unpack(&price_update_account(&[1], feed_id, -1, -8)).unwrap();
+    assert_eq!(negative.usd_price(), None);
+    let mut legacy = price_update_account(&[1], feed_id, 3_000_000_000, -8);
+    legacy[0] ^= 0xff;
+    assert!(PythPriceUpdate::unpack(&legacy).is_err());
+}
+
+fn switchboard_source() -> OracleSource {
+    OracleSource {
+        pubkey: Pubkey::new_unique(),
+        oracle_type: OracleType::Switchboard,
+        is_active: true,
+        weight: 50,
+        max_deviation_bps: 500,
+        max_staleness_seconds: 900,
+        last_valid_price: 0,
+        last_update_timestamp: 0,
+        consecutive_failures: 0,
+        is_required: false,
+    }
+}
+
+#[test]
+fn duplicate_oracle_sources_are_rejected() {
+    let mut controller = MultiOracleController::new(Pubkey::new_unique(), "VCN/USD".to_string(), 1);
+    let source = switchboard_source();
+
+    assert!(!controller.has_oracle_source(&source.pubkey));
+    controller.add_oracle_source(source.clone()).unwrap();
+    assert!(controller.has_oracle_source(&source.pubkey));
+
+    let reweighted = OracleSource { weight: 80, ..source };
+    assert_eq!(
+        controller.add_oracle_source(reweighted),
+        Err(ProgramError::from(VCoinError::OracleSourceAlreadyExists))
+    );
+    assert_eq!(controller.oracle_sources.len(), 1);
+    assert_eq!(controller.oracle_sources[0].weight, 50);
+    assert_eq!(controller.health.active_oracles, 1);
+}
+
+#[test]
+fn program_created_controllers_fit_a_full_set_of_sources() {
+    let program_id = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let mut controller = MultiOracleController::new(authority, "VCN/USD".to_string(), 1);
+    for _ in 0..MAX_ORACLE_SOURCES {
+        controller.add_oracle_source(switchboard_source()).unwrap();
+    }
+    assert_eq!(
+        controller.add_oracle_source(switchboard_source()),
+        Err(ProgramError::from(VCoinError::OracleSourceLimitReached))
+    );
+    assert!(borsh::to_vec(&controller).unwrap().len() <= MultiOracleController::get_size(MAX_ORACLE_SOURCES));
+
+    // The instruction targets the asset's controller PDA
+    let instruction = VCoinInstruction::initialize_oracle_controller(&program_id, &authority, "VCN/USD".to_string(), 1).unwrap();
+    let (controller_address, _) = MultiOracleController::find_address(&program_id, "VCN/USD");
+    assert_eq!(instruction.accounts[1].pubkey, controller_address);
+    assert_ne!(controller_address, MultiOracleController::find_address(&program_id, "SOL/USD").0);
+}
+
+#[test]
+fn oracle_sources_can_be_rotated_out() {
+    let mut controller = MultiOracleController::new(Pubkey::new_unique(), "VCN/USD".to_string(), 2);
+    let (primary, backup) = (switchboard_source(), switchboard_source());
+    controller.add_oracle_source(primary.clone()).unwrap();
+    controller.add_oracle_source(backup.clone()).unwrap();
+
+    // Deactivating a source below the consensus minimum flags the controller
+    controller.update_oracle_source(&primary.pubkey, Some(false), None, None, None, None).unwrap();
+    assert_eq!(controller.health.active_oracles, 1);
+    assert!(controller.health.is_degraded);
+
+    // Invalid weights leave the source untouched
+    assert_eq!(
+        controller.update_oracle_source(&backup.pubkey, None, Some(101), Some(100), None, None),
+        Err(ProgramError::from(VCoinError::InvalidPriceOracleParams))
+    );
+    controller.update_oracle_source(&backup.pubkey, None, Some(80), None, Some(60), Some(true)).unwrap();
+    let updated = &controller.oracle_sources[1];
+    assert_eq!((updated.weight, updated.max_deviation_bps, updated.max_staleness_seconds), (80, 500, 60));
+    assert!(updated.is_required);
+
+    // Removing an inactive source keeps the active count
+    assert_eq!(controller.remove_oracle_source(&primary.pubkey).unwrap().pubkey, primary.pubkey);
+    assert_eq!((controller.health.total_oracles, controller.health.active_oracles), (1, 1));
+    assert_eq!(
+        controller.remove_oracle_source(&primary.pubkey),
+        Err(ProgramError::from(VCoinError::OracleSourceNotFound))
+    );
+    assert_eq!(
+        controller.update_oracle_source(&primary.pubkey, Some(true), None, None, None, None),
+        Err(ProgramError::from(VCoinError::OracleSourceNotFound))
+    );
+}
+
+#[test]
+fn consensus_builder_passes_active_sources_in_order() {
+    let program_id = Pubkey::new_unique();
+    let (caller, controller_key) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut controller = MultiOracleController::new(Pubkey::new_unique(), "VCN/USD".to_string(), 2);
+    let sources = [switchboard_source(), switchboard_source(), switchboard_source()];
+    for source in &sources {
+        controller.add_oracle_source(source.clone()).unwrap();
+    }
+    controller.update_oracle_source(&sources[1].pubkey, Some(false), None, None, None, None).unwrap();
+    assert_eq!(controller.consensus_accounts(), vec![sources[0].pubkey, sources[2].pubkey]);
+
+    let data = borsh::to_vec(&controller).unwrap();
+    let instruction = VCoinInstruction::update_oracle_consensus_for_controller(&program_id, &caller, &controller_key, &data).unwrap();
+    let keys: Vec<Pubkey> = instruction.accounts.iter().map(|account| account.pubkey).collect();
+    assert_eq!(keys, vec![caller, controller_key, sources[0].pubkey, sources[2].pubkey]);
+    assert!(instruction.accounts[0].is_signer && instruction.accounts[1].is_writable);
+    assert!(instruction.accounts[2..].iter().all(|account| !account.is_signer && !account.is_writable));
+
+    // Padding after the controller is ignored; an uninitialized account is refused
+    let mut padded = data.clone();
+    padded.resize(data.len() + 256, 0);
+    assert!(VCoinInstruction::update_oracle_consensus_for_controller(&program_id, &caller, &controller_key, &padded).is_ok());
+    controller.is_initialized = false;
+    let uninitialized = borsh::to_vec(&controller).unwrap();
+    assert!(VCoinInstruction::update_oracle_consensus_for_controller(&program_id, &caller, &controller_key, &uninitialized).is_err());
+}
+
+#[test]
+fn feed_registry_maps_asset_ids_to_controllers() {
+    let program_id = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let (registry_address, _) = OracleFeedRegistry::find_address(&program_id, &authority);
+    let (vcn, _) = OracleFeedRegistry::find_feed_address(&program_id, &registry_address, "VCN/USD");
+    let (sol, _) = OracleFeedRegistry::find_feed_address(&program_id, &registry_address, "SOL/USD");
+    assert_ne!(vcn, sol);
+
+    // Each authority's registry derives its own controllers
+    let (other_registry, _) = OracleFeedRegistry::find_address(&program_id, &Pubkey::new_unique());
+    assert_ne!(OracleFeedRegistry::find_feed_address(&program_id, &other_registry, "VCN/USD").0, vcn);
+
+    let mut registry = OracleFeedRegistry {
+        is_initialized: true,
+        authority,
+        feeds: vec![
+            ManagedFeed { asset_id: "VCN/USD".to_string(), controller: vcn },
+            ManagedFeed { asset_id: "SOL/USD".to_string(), controller: sol },
+        ],
+    };
+    assert_eq!(registry.lookup("SOL/USD"), Some(sol));
+    assert_eq!(registry.lookup("sol/usd"), None);
+
+    // A full registry of the longest asset ids fits the account
+    registry.feeds = (0..MAX_MANAGED_FEEDS)
+        .map(|i| ManagedFeed { asset_id: format!("{:>1$}", i, MAX_MANAGED_FEED_ASSET_ID_LEN), controller: sol })
+        .collect();
+    assert_eq!(borsh::to_vec(&registry).unwrap().len(), OracleFeedRegistry::get_size());
+}
+
+#[test]
+fn repeated_failures_deactivate_a_source() {
+    let mut controller = MultiOracleController::new(Pubkey::new_unique(), "VCN/USD".to_string(), 1);
+    let source = switchboard_source();
+    controller.add_oracle_source(source.clone()).unwrap();
+
+    for expected in 1..MAX_CONSECUTIVE_ORACLE_FAILURES {
+        assert_eq!(controller.record_oracle_failure(&source.pubkey), Ok(expected));
+        assert!(controller.oracle_sources[0].is_active);
+    }
+
+    // A valid price resets the streak
+    controller.record_oracle_price(&source.pubkey, 30_000, 1_700_000_000).unwrap();
+    assert_eq!(controller.record_oracle_failure(&source.pubkey), Ok(1));
+
+    for _ in 1..MAX_CONSECUTIVE_ORACLE_FAILURES {
+        controller.record_oracle_failure(&source.pubkey).unwrap();
+    }
+    assert!(!controller.oracle_sources[0].is_active);
+    assert!(controller.health.is_degraded);
+}
+
+#[test]
+fn quorum_policies_count_sources_or_weight() {
+    let mut controller = MultiOracleController::new(Pubkey::new_unique(), "VCN/USD".to_string(), 2);
+    // A required 60-weight primary and two optional 20-weight backups
+    controller.add_oracle_source(OracleSource { weight: 60, is_required: true, ..switchboard_source() }).unwrap();
+    controller.add_oracle_source(OracleSource { weight: 20, ..switchboard_source() }).unwrap();
+    controller.add_oracle_source(OracleSource { weight: 20, ..switchboard_source() }).unwrap();
+    let (primary, backup) = ((60, true), (20, false));
+
+    // Default: two sources, one of which must be the required primary
+    assert!(controller.quorum_met(&[primary, backup]));
+    assert!(!controller.quorum_met(&[backup, backup]));
+    assert!(!controller.quorum_met(&[primary]));
+
+    controller.quorum_policy = QuorumPolicy::NOfM { n: 2 };
+    assert!(controller.quorum_met(&[backup, backup]));
+    assert!(!controller.quorum_met(&[primary]));
+
+    controller.quorum_policy = QuorumPolicy::RequiredPlus { optional: 0 };
+    assert!(controller.quorum_met(&[primary]));
+    assert!(!controller.quorum_met(&[backup, backup]));
+
+    // Weight quorum: the primary alone, or both backups, is not enough
+    controller.quorum_policy = QuorumPolicy::MinWeight { min_weight: 70 };
+    assert!(controller.quorum_met(&[primary, backup]));
+    assert!(!controller.quorum_met(&[primary]));
+    assert!(!controller.quorum_met(&[backup, backup]));
+    assert!(!controller.quorum_met(&[]));
+
+    assert!(!QuorumPolicy::NOfM { n: 0 }.is_valid());
+    assert!(!QuorumPolicy::MinWeight { min_weight: 0 }.is_valid());
+}
+
+#[test]
+fn circuit_breaker_incidents_record_trigger_and_resolver() {
+    let authority = Pubkey::new_unique();
+    let mut controller = MultiOracleController::new(authority, "VCN/USD".to_string(), 2);
+    let start = 1_700_000_000;
+    let spike = CircuitBreakerTrigger::ExtremePriceChange { previous_price: 30_000, new_price: 45_000, change_bps: 5_000 };
+
+    controller.activate_circuit_breaker(spike, start);
+    assert_eq!(controller.circuit_breaker_reason.as_deref(), Some("Extreme price change detected (5000bps)"));
+    // Tripping again while active opens a second incident
+    let quorum = CircuitBreakerTrigger::QuorumNotMet { sources: 1, policy: QuorumPolicy::MinOracles, after_filtering: false };
+    controller.activate_circuit_breaker(quorum, start + 600);
+    assert_eq!(controller.circuit_breaker_incidents[0].duration(start + 900), 900);
+
+    // A reset closes every open incident
+    controller.deactivate_circuit_breaker(Some(authority), start + 3_600);
+    assert!(!controller.circuit_breaker_active);
+    for incident in &controller.circuit_breaker_incidents {
+        assert_eq!(incident.resolved_at, Some(start + 3_600));
+        assert_eq!(incident.resolved_by, Some(authority));
+    }
+    assert_eq!(controller.circuit_breaker_incidents[0].trigger, spike);
+    assert_eq!(controller.circuit_breaker_incidents[1].duration(start + 9_999), 3_000);
+
+    // Only the most recent incidents are kept, and they fit the account
+    for i in 0..MAX_CIRCUIT_BREAKER_INCIDENTS as i64 {
+        controller.activate_circuit_breaker(spike, start + 10_000 + i);
+        controller.deactivate_circuit_breaker(Some(authority), start + 10_000 + i);
+    }
+    assert_eq!(controller.circuit_breaker_incidents.len(), MAX_CIRCUIT_BREAKER_INCIDENTS);
+    assert_eq!(controller.circuit_breaker_incidents[0].activated_at, start + 10_000);
+    assert!(borsh::to_vec(&controller).unwrap().len() <= MultiOracleController::get_size(0));
+}
+
+#[test]
+fn repeated_trips_escalate_the_cooldown_until_recovery() {
+    let mut controller = MultiOracleController::new(Pubkey::new_unique(), "VCN/USD".to_string(), 2);
+    controller.circuit_breaker_cooldown = 600;
+    controller.circuit_breaker_max_cooldown = 3_000;
+    let start = 1_700_000_000;
+    let trigger = CircuitBreakerTrigger::QuorumNotMet { sources: 0, policy: QuorumPolicy::MinOracles, after_filtering: false };
+
+    // Without auto-recovery the breaker waits for a manual reset
+    controller.activate_circuit_breaker(trigger, start);
+    assert!(!controller.in_circuit_breaker_probation(start + 600));
+    controller.circuit_breaker_auto_recovery = true;
+    assert!(!controller.in_circuit_breaker_probation(start + 599));
+    assert!(controller.in_circuit_breaker_probation(start + 600));
+
+    // Failed probations double the cooldown up to the cap
+    let mut tripped_at = start;
+    for expected in [1_200, 2_400, 3_000, 3_000] {
+        tripped_at += controller.effective_circuit_breaker_cooldown() as i64;
+        controller.activate_circuit_breaker(trigger, tripped_at);
+        assert_eq!(controller.effective_circuit_breaker_cooldown(), expected);
+        assert!(!controller.in_circuit_breaker_probation(tripped_at + expected as i64 - 1));
+    }
+    assert_eq!(controller.circuit_breaker_trips, 5);
+
+    // A successful probation clears the breaker and the escalation
+    controller.deactivate_circuit_breaker(None, tripped_at + 3_000);
+    assert!(!controller.in_circuit_breaker_probation(tripped_at + 3_000));
+    assert_eq!(controller.effective_circuit_breaker_cooldown(), 600);
+    assert!(controller.circuit_breaker_incidents.iter().all(|incident| incident.resolved_by.is_none()));
+}
+
+#[test]
+fn staleness_policy_is_chosen_per_operation() {
+    let policy = StalenessPolicy { purchase: 60, supply_adjustment: 300, display: 3_600 };
+    assert!(policy.is_valid());
+    assert_eq!(policy.max_age(OracleOperation::Purchase), 60);
+    assert_eq!(policy.max_age(OracleOperation::SupplyAdjustment), 300);
+    assert_eq!(policy.max_age(OracleOperation::Display), 3_600);
+
+    // Only informational reads may fall back to emergency or cached prices
+    assert!(OracleOperation::Purchase.requires_regular_consensus());
+    assert!(OracleOperation::SupplyAdjustment.requires_regular_consensus());
+    assert!(!OracleOperation::Display.requires_regular_consensus());
+
+    assert!(!StalenessPolicy { purchase: 0, ..policy }.is_valid());
+    assert!(!StalenessPolicy { display: MAX_POLICY_STALENESS + 1, ..policy }.is_valid());
+}
+
+#[test]
+fn aggregation_strategies_under_a_poisoned_feed() {
+    // Four agreeing $1.00 feeds with tight intervals, one heavily weighted feed
+    // reporting $2.00 with a wide interval
+    let honest = |price| PriceSample { price, confidence: 500, weight: 20 };
+    let samples = [
+        honest(1_000_000),
+        PriceSample { price: 2_000_000, confidence: 100_000, weight: 80 },
+        honest(999_000),
+        honest(1_002_000),
+        honest(1_001_000),
+    ];
+
+    // The weighted mean follows the poisoned feed's weight halfway to its price
+    assert_eq!(AggregationStrategy::WeightedMean.aggregate(&samples), Some(1_500_250));
+    assert_eq!(AggregationStrategy::TrimmedMean { trim_bps: 0 }.aggregate(&samples), Some(1_200_400));
+    // The robust strategies stay with the honest majority
+    assert_eq!(AggregationStrategy::Median.aggregate(&samples), Some(1_001_000));
+    assert_eq!(AggregationStrategy::ConfidenceWeightedMedian.aggregate(&samples), Some(1_001_000));
+    assert_eq!(AggregationStrategy::TrimmedMean { trim_bps: 2_000 }.aggregate(&samples), Some(1_001_000));
+
+    // A tight interval outweighs a large weight in the confidence-weighted median
+    let confident = [
+        PriceSample { price: 1_000_000, confidence: 100, weight: 10 },
+        PriceSample { price: 1_040_000, confidence: 50_000, weight: 90 },
+    ];
+    assert_eq!(AggregationStrategy::ConfidenceWeightedMedian.aggregate(&confident), Some(1_000_000));
+    assert_eq!(AggregationStrategy::WeightedMean.aggregate(&confident), Some(1_036_000));
+
+    // Zero weights fall back to a simple average; no samples give no price
+    let unweighted = [PriceSample { weight: 0, ..confident[0] }, PriceSample { weight: 0, ..confident[1] }];
+    assert_eq!(AggregationStrategy::WeightedMean.aggregate(&unweighted), Some(1_020_000));
+    assert_eq!(AggregationStrategy::Median.aggregate(&[]), None);
+
+    assert_eq!(AggregationStrategy::default(), AggregationStrategy::WeightedMean);
+    assert!(AggregationStrategy::TrimmedMean { trim_bps: 4_999 }.is_valid());
+    assert!(!AggregationStrategy::TrimmedMean { trim_bps: 5_000 }.is_valid());
+}
+
+#[test]
+fn twap_weights_prices_by_how_long_they_held() {
+    const HOUR: i64 = 3_600;
+    let mut controller = MultiOracleController::new(Pubkey::new_unique(), "VCN/USD".to_string(), 1);
+    let start = 1_700_000_000;
+    assert_eq!(controller.get_twap(HOUR, start), None);
+
+    // $1.00 for three hours, then a one-hour spike to $2.00
+    controller.record_price(1_000_000, start);
+    controller.record_price(2_000_000, start + 3 * HOUR);
+    let now = start + 4 * HOUR;
+    assert_eq!(controller.get_twap(4 * HOUR, now), Some(1_250_000));
+    assert_eq!(controller.get_twap(2 * HOUR, now), Some(1_500_000));
+    assert_eq!(controller.get_twap(HOUR, now), Some(2_000_000));
+    // A window reaching before the first observation averages the covered part only
+    assert_eq!(controller.get_twap(8 * HOUR, now), Some(1_250_000));
+    // A price recorded just now has no weight yet
+    controller.record_price(9_000_000, now);
+    assert_eq!(controller.get_twap(4 * HOUR, now), Some(1_250_000));
+
+    // The ring keeps the latest PRICE_HISTORY_LEN observations
+    for hour in 5..(5 + PRICE_HISTORY_LEN as i64) {
+        controller.record_price(3_000_000, start + hour * HOUR);
+    }
+    assert_eq!(controller.price_history.len(), PRICE_HISTORY_LEN);
+    assert!(controller.price_history.iter().all(|observation| observation.price == 3_000_000));
+    assert_eq!(controller.get_twap(24 * HOUR, start + 60 * HOUR), Some(3_000_000));
+}
+
+#[test]
+fn ema_smoothing_damps_spot_moves() {
+    let mut controller = MultiOracleController::new(Pubkey::new_unique(), "VCN/USD".to_string(), 1);
+
+    // Without a previous consensus or with smoothing off the spot price is published
+    controller.ema_alpha_bps = 2_000;
+    assert_eq!(controller.smoothed_price(1_000_000), Some(1_000_000));
+    controller.last_consensus.price = 1_000_000;
+    controller.ema_alpha_bps = 0;
+    assert_eq!(controller.smoothed_price(2_000_000), Some(2_000_000));
+
+    // A 20% alpha moves the published price a fifth of the way to the spot price
+    controller.ema_alpha_bps = 2_000;
+    assert_eq!(controller.smoothed_price(2_000_000), Some(1_200_000));
+    assert_eq!(controller.smoothed_price(500_000), Some(900_000));
+
+    // Emergency and fallback prices are not averaged into the next consensus
+    controller.last_consensus.is_fallback_price = true;
+    assert_eq!(controller.smoothed_price(2_000_000), Some(2_000_000));
+}
+
+/// Ethereum-style signer (RedStone nodes, Chainlink DON) with the address derived from its key
+struct EthSigner {
+    key: libsecp256k1::SecretKey,
+    address: [u8; 20],
+}
+
+fn eth_signer(seed: u8) -> EthSigner {
+    let key = libsecp256k1::SecretKey::parse(&[seed; 32]).unwrap();
+    let public_key = libsecp256k1::PublicKey::from_secret_key(&key).serialize();
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&keccak::hash(&public_key[1..]).to_bytes()[12..]);
+    EthSigner { key, address }
+}
+
+/// Data package with one 32-byte value for `feed_id`, signed by `signer`
+fn redstone_package(signer: &EthSigner, feed_id: [u8; 32], value: u128, timestamp_ms: u64) -> Vec<u8> {
+    let mut package = feed_id.to_vec();
+    package.extend_from_slice(&[0u8; 16]);
+    package.extend_from_slice(&value.to_be_bytes());
+    package.extend_from_slice(&timestamp_ms.to_be_bytes()[2..]);
+    package.extend_from_slice(&32u32.to_be_bytes());
+    package.extend_from_slice(&1u32.to_be_bytes()[1..]);
+
+    let message = libsecp256k1::Message::parse(&keccak::hash(&package).to_bytes());
+    let (signature, recovery_id) = libsecp256k1::sign(&message, &signer.key);
+    package.extend_from_slice(&signature.serialize());
+    package.push(recovery_id.serialize() + 27);
+    package
+}
+
+fn redstone_payload(packages: &[Vec<u8>]) -> Vec<u8> {
+    let mut payload = packages.concat();
+    payload.extend_from_slice(&(packages.len() as u16).to_be_bytes());
+    payload.extend_from_slice(b"1.0.0#test");
+    payload.extend_from_slice(&10u32.to_be_bytes()[1..]);
+    payload.extend_from_slice(&[0x00, 0x00, 0x02, 0xed, 0x57, 0x01, 0x1e, 0x00, 0x00]);
+    payload
+}
+
+#[test]
+fn redstone_payload_needs_threshold_of_authorized_signers() {
+    let mut feed_id = [0u8; 32];
+    feed_id[..3].copy_from_slice(b"ETH");
+    let (alice, bob, mallory) = (eth_signer(1), eth_signer(2), eth_signer(3));
+    let config = RedstoneConfig { feed_id, signers: vec![alice.address, bob.address], signer_threshold: 2 };
+    assert!(config.is_valid());
+    let time = 1_700_000_000_000;
+
+    // $2000.10 and $2000.30 with 8 decimals; the unknown signer's package is ignored
+    let payload = redstone_payload(&[
+        redstone_package(&alice, feed_id, 200_010_000_000, time),
+        redstone_package(&mallory, feed_id, 900_000_000_000, time),
+        redstone_package(&bob, feed_id, 200_030_000_000, time),
+    ]);
+    assert_eq!(config.verify_payload(&payload), Ok(RedstonePrice {
+        price: 2_000_200_000,
+        confidence: 100_000,
+        timestamp: 1_700_000_000,
+    }));
+
+    // One signer reporting twice does not meet a threshold of two
+    let repeated = redstone_payload(&[
+        redstone_package(&alice, feed_id, 200_010_000_000, time),
+        redstone_package(&alice, feed_id, 200_010_000_000, time),
+    ]);
+    assert!(config.verify_payload(&repeated).is_err());
+
+    // A tampered value no longer recovers to the authorized signer
+    let mut tampered = payload.clone();
+    tampered[63] ^= 1;
+    assert!(config.verify_payload(&tampered).is_err());
+
+    // Packages signed at different times and foreign payloads are rejected
+    let mixed = redstone_payload(&[
+        redstone_package(&alice, feed_id, 200_010_000_000, time),
+        redstone_package(&bob, feed_id, 200_030_000_000, time + 1_000),
+    ]);
+    assert!(config.verify_payload(&mixed).is_err());
+    assert!(config.verify_payload(&payload[..payload.len() - 1]).is_err());
+
+    let duplicate_signers = RedstoneConfig { signers: vec![alice.address, alice.address], ..config };
+    assert!(!duplicate_signers.is_valid());
+}
+
+/// 32-byte big-endian ABI word
+fn abi_word(value: u128) -> Vec<u8> {
+    let mut word = vec![0u8; 16];
+    word.extend_from_slice(&value.to_be_bytes());
+    word
+}
+
+/// ABI-encoded v3 Data Streams report signed by `signers`
+fn streams_report(signers: &[&EthSigner], feed_id: [u8; 32], price: u128, observed_at: u128, expires_at: u128) -> Vec<u8> {
+    let context = [[0x0a; 32], [0x0b; 32], [0x0c; 32]].concat();
+    let mut blob = feed_id.to_vec();
+    for value in [observed_at, observed_at, 10, 20, expires_at, price, price - 10u128.pow(15), price + 10u128.pow(15)] {
+        blob.extend_from_slice(&abi_word(value));
+    }
+
+    let message_hash = keccak::hashv(&[&keccak::hash(&blob).to_bytes(), &context]).to_bytes();
+    let (mut rs, mut ss, mut vs) = (Vec::new(), Vec::new(), vec![0u8; 32]);
+    for (index, signer) in signers.iter().enumerate() {
+        let (signature, recovery_id) = libsecp256k1::sign(&libsecp256k1::Message::parse(&message_hash), &signer.key);
+        let signature = signature.serialize();
+        rs.extend_from_slice(&signature[..32]);
+        ss.extend_from_slice(&signature[32..]);
+        vs[index] = recovery_id.serialize();
+    }
+
+    let blob_offset = 7 * 32;
+    let rs_offset = blob_offset + 32 + blob.len();
+    let ss_offset = rs_offset + 32 + rs.len();
+    let mut report = context;
+    for offset in [blob_offset, rs_offset, ss_offset] {
+        report.extend_from_slice(&abi_word(offset as u128));
+    }
+    report.extend_from_slice(&vs);
+    for (data, length) in [(&blob, blob.len()), (&rs, signers.len()), (&ss, signers.len())] {
+        report.extend_from_slice(&abi_word(length as u128));
+        report.extend_from_slice(data);
+    }
+    report
+}
+
+#[test]
+fn chainlink_streams_report_needs_don_quorum() {
+    let mut feed_id = [0x77; 32];
+    feed_id[..2].copy_from_slice(&[0, 3]);
+    let (node_a, node_b, node_c, outsider) = (eth_signer(4), eth_signer(5), eth_signer(6), eth_signer(7));
+    let config = ChainlinkStreamsConfig {
+        feed_id,
+        signers: vec![node_a.address, node_b.address, node_c.address],
+        signer_threshold: 2,
+    };
+    assert!(config.is_valid());
+    let controller = Pubkey::new_unique();
+    let now = 1_700_000_100;
+    // $2000.20 with 18 decimals, a $0.001 spread either side
+    let price = 2_000_200_000_000_000_000_000;
+
+    let report = config
+        .verify_report(controller, &streams_report(&[&node_a, &node_c], feed_id, price, 1_700_000_090, 1_700_086_400), now)
+        .unwrap();
+    assert_eq!((report.controller, report.observations_timestamp, report.expires_at), (controller, 1_700_000_090, 1_700_086_400));
+    assert_eq!((report.price, report.bid, report.ask), (2_000_200_000, 2_000_199_000, 2_000_201_000));
+    assert_eq!(report.confidence(), 1_000);
+
+    // One DON signature plus an outsider's is not a quorum, nor is one node signing twice
+    let outsider_signed = streams_report(&[&node_a, &outsider], feed_id, price, 1_700_000_090, 1_700_086_400);
+    assert!(config.verify_report(controller, &outsider_signed, now).is_err());
+    let repeated = streams_report(&[&node_b, &node_b], feed_id, price, 1_700_000_090, 1_700_086_400);
+    assert!(config.verify_report(controller, &repeated, now).is_err());
+
+    // Expired reports, other feeds and tampered prices are rejected
+    let expired = streams_report(&[&node_a, &node_b], feed_id, price, 1_700_000_000, 1_700_000_050);
+    assert!(config.verify_report(controller, &expired, now).is_err());
+    let mut other_feed = feed_id;
+    other_feed[31] ^= 1;
+    let foreign = streams_report(&[&node_a, &node_b], other_feed, price, 1_700_000_090, 1_700_086_400);
+    assert!(config.verify_report(controller, &foreign, now).is_err());
+    let mut tampered = streams_report(&[&node_a, &node_b], feed_id, price, 1_700_000_090, 1_700_086_400);
+    tampered[7 * 32 + 32 + 6 * 32 + 31] ^= 1;
+    assert!(config.verify_report(controller, &tampered, now).is_err());
+
+    // Only v3 (crypto) feeds are accepted
+    let v2 = ChainlinkStreamsConfig { feed_id: [0x77; 32], ..config };
+    assert!(!v2.is_valid());
+}
+
+#[test]
+fn signed_price_message_binds_the_controller() {
+    let program_id = Pubkey::new_unique();
+    let controller = Pubkey::new_unique();
+
+    // controller || price || confidence || publish_time, little endian
+    let message = SignedPriceReport::message(&controller, 2_000_200_000, 100_000, 1_700_000_001);
+    assert_eq!(message.len(), 56);
+    assert_eq!(&message[..32], controller.as_ref());
+    assert_eq!(u64::from_le_bytes(message[32..40].try_into().unwrap()), 2_000_200_000);
+    assert_eq!(u64::from_le_bytes(message[40..48].try_into().unwrap()), 100_000);
+    assert_eq!(i64::from_le_bytes(message[48..56].try_into().unwrap()), 1_700_000_001);
+
+    // A signature for one controller cannot be replayed on another
+    let other = Pubkey::new_unique();
+    assert_ne!(SignedPriceReport::message(&other, 2_000_200_000, 100_000, 1_700_000_001), message);
+    assert_ne!(SignedPriceReport::find_address(&program_id, &other), SignedPriceReport::find_address(&program_id, &controller));
+
+    let report = SignedPriceReport {
+        is_initialized: true,
+        controller,
+        publisher: Pubkey::new_unique(),
+        price: 2_000_200_000,
+        confidence: 100_000,
+        publish_time: 1_700_000_001,
+        submitted_at: 1_700_000_002,
+    };
+    assert_eq!(borsh::to_vec(&report).unwrap().len(), SignedPriceReport::get_size());
+}
+
+/// The controller's account, sized to its current state
+fn controller_account(controller: &MultiOracleController) -> TestAccount {
+    TestAccount::program(Pubkey::new_unique(), controller, borsh::to_vec(controller).unwrap().len())
+}
+
+/// Register `oracle` with the controller as a source of `oracle_type`
+fn add_source(oracle_type: OracleType, oracle: TestAccount) -> (common::Outcome, MultiOracleController) {
+    let authority = Pubkey::new_unique();
+    let controller = MultiOracleController::new(authority, "VCN/USD".to_string(), 1);
+    let mut accounts = vec![
+        TestAccount::signer(authority).readonly(),
+        controller_account(&controller),
+        oracle.readonly(),
+    ];
+    let add = VCoinInstruction::AddOracleSource {
+        oracle_type,
+        weight: 50,
+        max_deviation_bps: 500,
+        max_staleness_seconds: 900,
+        is_required: false,
+        skip_if_exists: false,
+    };
+    let outcome = process(&mut accounts, &add);
+    (outcome, accounts[1].state())
+}
+
+/// Adding an oracle account of `oracle_type` owned by some other program is refused
+fn assert_foreign_owner_refused(oracle_type: OracleType) {
+    let oracle = TestAccount::new(Pubkey::new_unique(), Pubkey::new_unique(), vec![0; 512]);
+    let (outcome, controller) = add_source(oracle_type, oracle);
+    assert_eq!(outcome.result, Err(VCoinError::InvalidAccountOwner.into()));
+    assert!(outcome.logged("has an unexpected owner"), "{:?}", outcome.logs);
+    assert!(controller.oracle_sources.is_empty());
+}
+
+#[test]
+fn pyth_sources_must_be_owned_by_pyth() {
+    assert_foreign_owner_refused(OracleType::Pyth);
+}
+
+#[test]
+fn pyth_pull_sources_must_be_owned_by_the_pyth_receiver() {
+    assert_foreign_owner_refused(OracleType::PythPull);
+}
+
+#[test]
+fn switchboard_sources_must_be_owned_by_switchboard() {
+    assert_foreign_owner_refused(OracleType::Switchboard);
+}
+
+#[test]
+fn chainlink_sources_must_be_owned_by_chainlink() {
+    assert_foreign_owner_refused(OracleType::Chainlink);
+}
+
+#[test]
+fn chainlink_streams_sources_must_be_owned_by_this_program() {
+    assert_foreign_owner_refused(OracleType::ChainlinkStreams);
+}
+
+#[test]
+fn consensus_skips_a_source_whose_account_changed_owner() {
+    let caller = Pubkey::new_unique();
+    let mut controller = MultiOracleController::new(Pubkey::new_unique(), "VCN/USD".to_string(), 1);
+    let source = OracleSource { oracle_type: OracleType::Pyth, ..switchboard_source() };
+    controller.add_oracle_source(source.clone()).unwrap();
+    controller.last_consensus.price = 30_000;
+    controller.last_consensus.timestamp = common::NOW - 60;
+
+    // The account registered as Pyth is now owned by another program, so its
+    // price does not count and the last consensus is reused
+    let mut accounts = vec![
+        TestAccount::signer(caller),
+        controller_account(&controller),
+        TestAccount::new(source.pubkey, Pubkey::new_unique(), vec![0; 512]).readonly(),
+    ];
+    let outcome = process(&mut accounts, &VCoinInstruction::UpdateOracleConsensus);
+    assert_eq!(outcome.result, Ok(()));
+    assert!(outcome.logged("has an unexpected owner"), "{:?}", outcome.logs);
+    let controller: MultiOracleController = accounts[1].state();
+    assert_eq!(controller.oracle_sources[0].consecutive_failures, 1);
+    assert!(controller.last_consensus.is_fallback_price);
+    assert_eq!(controller.last_consensus.contributing_oracles, 0);
+}
+
+#[test]
+fn every_provider_read_checks_the_account_owner() {
+    let read = |oracle_type: OracleType, owner: Pubkey| {
+        let key = Pubkey::new_unique();
+        let (mut lamports, mut data) = (1_000_000, vec![0; 512]);
+        let account = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+        get_price_from_oracle(oracle_type, &account, common::NOW, None)
+    };
+    let wrong_owner = Err(ProgramError::from(VCoinError::InvalidAccountOwner));
+
+    for oracle_type in [
+        OracleType::Pyth,
+        OracleType::PythPull,
+        OracleType::Switchboard,
+        OracleType::Chainlink,
+        OracleType::ChainlinkStreams,
+        OracleType::SignedMessage,
+    ] {
+        assert_eq!(read(oracle_type.clone(), Pubkey::new_unique()), wrong_owner, "{:?}", oracle_type);
+    }
+
+    // Accounts this program stores get past the owner check to be decoded
+    assert_ne!(read(OracleType::ChainlinkStreams, vcoin_program::id()), wrong_owner);
+    assert_ne!(read(OracleType::SignedMessage, vcoin_program::id()), wrong_owner);
+}
@@ -0,0 +1,617 @@
+//! Presale state bookkeeping.
+
+mod common;
+
+use common::{presale, TestAccount};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey, rent::Rent, system_program};
+use vcoin_program::error::VCoinError;
+use vcoin_program::instruction::VCoinInstruction;
+use vcoin_program::state::*;
+
+const DAY: i64 = 86_400;
+
+fn error(e: VCoinError) -> Result<(), ProgramError> {
+    Err(e.into())
+}
+
+#[test]
+fn duplicate_and_excess_stablecoins_are_distinguished() {
+    let mut state = presale();
+    let usdc = Pubkey::new_unique();
+
+    assert_eq!(state.add_stablecoin_raw(usdc), Ok(()));
+    assert_eq!(state.add_stablecoin_raw(usdc), error(VCoinError::StablecoinAlreadySupported));
+
+    for _ in 1..10 {
+        state.add_stablecoin_raw(Pubkey::new_unique()).unwrap();
+    }
+    assert_eq!(state.add_stablecoin_raw(Pubkey::new_unique()), error(VCoinError::StablecoinLimitReached));
+    // A duplicate is still reported as such when the list is full
+    assert_eq!(state.add_stablecoin_raw(usdc), error(VCoinError::StablecoinAlreadySupported));
+    assert_eq!(state.allowed_stablecoins.len(), 10);
+}
+
+#[test]
+fn depegged_stablecoins_are_rejected_or_haircut() {
+    let mut guard = StablecoinPegGuard {
+        is_initialized: true,
+        presale: Pubkey::new_unique(),
+        stablecoin_mint: Pubkey::new_unique(),
+        oracle_controller: Pubkey::new_unique(),
+        band_bps: 200,
+        action: DepegAction::Reject,
+    };
+    assert_eq!(borsh::to_vec(&guard).unwrap().len(), StablecoinPegGuard::get_size());
+
+    // Within ±2% the stablecoin counts at face value
+    assert_eq!(guard.credited_amount(1_000_000_000, 980_000), Some(1_000_000_000));
+    assert_eq!(guard.credited_amount(1_000_000_000, 1_020_000), Some(1_000_000_000));
+    assert_eq!(StablecoinPegGuard::deviation_bps(970_000), 300);
+    assert_eq!(guard.credited_amount(1_000_000_000, 970_000), None);
+    assert_eq!(guard.credited_amount(1_000_000_000, 1_030_000), None);
+
+    // A haircut credits the market value below the band, and face value above it
+    guard.action = DepegAction::Haircut;
+    assert_eq!(guard.credited_amount(1_000_000_000, 970_000), Some(970_000_000));
+    assert_eq!(guard.credited_amount(1_000_000_000, 1_030_000), Some(1_000_000_000));
+}
+
+#[test]
+fn yield_deposits_leave_refunds_covered_and_recalls_split_out_the_yield() {
+    let mut state = presale();
+    let usdc = Pubkey::new_unique();
+    state.contributions.push(PresaleContribution {
+        buyer: Pubkey::new_unique(),
+        amount: 20_000_000,
+        dev_amount: 10_000_000,
+        locked_amount: 10_000_000,
+        stablecoin_type: StablecoinType::USDC,
+        stablecoin_mint: usdc,
+        refunded: false,
+        dev_refunded: false,
+        timestamp: state.start_time,
+        keeper_refund_destination: None,
+        last_purchase: PurchaseSnapshot::default(),
+    });
+
+    // Half of each contribution stays owed until the refund window closes
+    state.token_launched = true;
+    state.refund_period_end_timestamp = state.end_time + 30 * DAY;
+    assert_eq!(state.locked_treasury_liabilities(&usdc, state.end_time), Some(10_000_000));
+    assert_eq!(state.locked_treasury_liabilities(&usdc, state.end_time + 31 * DAY), Some(0));
+
+    let venue = YieldVenue {
+        is_initialized: true,
+        presale: Pubkey::new_unique(),
+        stablecoin_mint: usdc,
+        lending_program: Pubkey::new_unique(),
+        lending_market: Pubkey::new_unique(),
+        reserve: Pubkey::new_unique(),
+        collateral_account: Pubkey::new_unique(),
+        insurance_fund: Pubkey::new_unique(),
+        deposit_cap: 10_000_000,
+        deployed_principal: 8_000_000,
+        total_yield_routed: 0,
+        approved_at: 0,
+        active_at: YIELD_VENUE_REVIEW_DELAY,
+    };
+    assert_eq!(borsh::to_vec(&venue).unwrap().len(), YieldVenue::get_size());
+
+    // Principal follows the share of collateral redeemed; the rest is yield
+    assert_eq!(venue.split_recall(2_000_000, 8_000_000, 2_100_000), Some((2_000_000, 100_000)));
+    assert_eq!(venue.split_recall(8_000_000, 8_000_000, 8_400_000), Some((8_000_000, 400_000)));
+    assert_eq!(venue.split_recall(8_000_000, 8_000_000, 7_900_000), Some((8_000_000, 0)));
+    assert_eq!(venue.split_recall(0, 8_000_000, 0), None);
+    assert_eq!(venue.split_recall(9_000_000, 8_000_000, 0), None);
+
+    // A different reserve is a different market; the cap and fund are not
+    let mut other = venue.clone();
+    other.deposit_cap = 20_000_000;
+    other.insurance_fund = Pubkey::new_unique();
+    assert!(venue.same_market(&other));
+    other.reserve = Pubkey::new_unique();
+    assert!(!venue.same_market(&other));
+}
+
+#[test]
+fn default_stablecoins_follow_the_build_cluster() {
+    let mut state = presale();
+    for stablecoin_mint in stablecoin_mints::PRESALE_DEFAULTS {
+        state.add_stablecoin_raw(*stablecoin_mint).unwrap();
+    }
+
+    // One USDC and one USDT mint, both from the same cluster
+    let [usdc, usdt] = stablecoin_mints::PRESALE_DEFAULTS else { panic!("expected USDC and USDT") };
+    let cluster = stablecoin_mints::USDC.iter().position(|mint| mint == usdc).unwrap();
+    assert_eq!(stablecoin_mints::USDT[cluster], *usdt);
+    assert_eq!(cluster, usize::from(cfg!(feature = "devnet")));
+    assert_eq!(state.get_stablecoin_type_dynamic(usdc), Some(StablecoinType::USDC));
+    assert_eq!(state.get_stablecoin_type_dynamic(usdt), Some(StablecoinType::USDT));
+
+    // Other clusters' mints are still recognised once allowed
+    let testnet_usdt = stablecoin_mints::USDT[2];
+    assert_eq!(state.get_stablecoin_type_dynamic(&testnet_usdt), None);
+    state.add_stablecoin_raw(testnet_usdt).unwrap();
+    assert_eq!(state.get_stablecoin_type_dynamic(&testnet_usdt), Some(StablecoinType::USDT));
+
+    let other = Pubkey::new_unique();
+    state.add_stablecoin_raw(other).unwrap();
+    assert_eq!(state.get_stablecoin_type_dynamic(&other), Some(StablecoinType::OTHER));
+}
+
+#[test]
+fn buyer_capacity_follows_the_account_size() {
+    // Initialization and expansion size accounts from the same per-buyer figure
+    assert_eq!(PresaleState::get_size(), PresaleState::get_size_for_buyers(PRESALE_INITIAL_BUYER_CAPACITY));
+    for buyers in [0, PRESALE_INITIAL_BUYER_CAPACITY, 20_000, PRESALE_MAX_BUYER_CAPACITY] {
+        assert_eq!(PresaleState::buyer_capacity(PresaleState::get_size_for_buyers(buyers)), buyers);
+    }
+    let partial = PresaleState::get_size_for_buyers(100) + 1;
+    assert_eq!(PresaleState::buyer_capacity(partial), 100);
+
+    // Expansion is prompted at 90% and again once full
+    assert!(!PresaleState::buyer_capacity_running_low(13_499, PRESALE_INITIAL_BUYER_CAPACITY));
+    assert!(PresaleState::buyer_capacity_running_low(13_500, PRESALE_INITIAL_BUYER_CAPACITY));
+    assert!(!PresaleState::buyer_capacity_running_low(13_501, PRESALE_INITIAL_BUYER_CAPACITY));
+    assert!(PresaleState::buyer_capacity_running_low(PRESALE_INITIAL_BUYER_CAPACITY, PRESALE_INITIAL_BUYER_CAPACITY));
+}
+
+#[test]
+fn sale_analytics_aggregate_purchases_by_day() {
+    let start = 1_700_000_000 - 1_700_000_000 % DAY;
+    let mut analytics = SaleAnalytics::new(Pubkey::new_unique());
+    assert_eq!(borsh::to_vec(&analytics).unwrap().len(), SaleAnalytics::get_size());
+
+    analytics.record_purchase(start + 60, 1_000, true).unwrap();
+    analytics.record_purchase(start + 3_600, 500, false).unwrap();
+    analytics.record_purchase(start + DAY, 2_000, true).unwrap();
+    let history = analytics.history();
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0], SaleDay { day: start / DAY, usd_raised: 1_500, purchases: 2, new_buyers: 1 });
+    assert_eq!(history[1], SaleDay { day: start / DAY + 1, usd_raised: 2_000, purchases: 1, new_buyers: 1 });
+
+    // A day that wraps around the ring replaces the oldest one
+    analytics.record_purchase(start + SALE_ANALYTICS_DAYS as i64 * DAY, 7_000, false).unwrap();
+    let history = analytics.history();
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0].day, start / DAY + 1);
+    assert_eq!(history[1], SaleDay { day: start / DAY + SALE_ANALYTICS_DAYS as i64, usd_raised: 7_000, purchases: 1, new_buyers: 0 });
+    assert_eq!(borsh::to_vec(&analytics).unwrap().len(), SaleAnalytics::get_size());
+}
+
+#[test]
+fn fee_sponsorship_reimburses_claims_within_its_budget() {
+    let mut sponsorship = FeeSponsorship {
+        is_initialized: true,
+        authority: Pubkey::new_unique(),
+        mint: Pubkey::new_unique(),
+        keeper: Pubkey::new_unique(),
+        per_claim_lamports: 10_000,
+        budget_lamports: 25_000,
+        spent_lamports: 0,
+        claims_sponsored: 0,
+        bump: 255,
+    };
+    assert_eq!(borsh::to_vec(&sponsorship).unwrap().len(), FeeSponsorship::get_size());
+
+    // Each claim gets the per-claim cap until the budget runs short
+    for expected in [10_000, 10_000, 5_000, 0] {
+        let lamports = sponsorship.next_reimbursement();
+        assert_eq!(lamports, expected);
+        if lamports > 0 {
+            sponsorship.record_claim(lamports);
+        }
+    }
+    assert_eq!((sponsorship.spent_lamports, sponsorship.claims_sponsored), (25_000, 3));
+    assert_eq!(sponsorship.remaining_budget(), 0);
+}
+
+#[test]
+fn expanding_the_presale_account_makes_room_for_more_buyers() {
+    let mut state = presale();
+    state.num_buyers = 10;
+    let old_size = PresaleState::get_size_for_buyers(10);
+    let mut accounts = vec![
+        TestAccount::signer(state.authority),
+        TestAccount::program(Pubkey::new_unique(), &state, old_size),
+        TestAccount::new(system_program::ID, Pubkey::default(), Vec::new()).readonly(),
+        TestAccount::emergency_state(&state.mint, EmergencyMode::Normal),
+    ];
+    let lamports_before = accounts[1].lamports;
+
+    let outcome = common::process(&mut accounts, &VCoinInstruction::ExpandPresaleAccount { additional_buyers: 20 });
+    assert_eq!(outcome.result, Ok(()));
+    assert!(outcome.logged("Instruction: Expand Presale Account"));
+
+    // The account grows past its old size, the authority funds the extra rent and the state still loads
+    let new_size = PresaleState::get_size_for_buyers(30);
+    assert!(new_size > old_size);
+    assert_eq!(accounts[1].data.len(), new_size);
+    assert_eq!(PresaleState::buyer_capacity(accounts[1].data.len()), 30);
+    let rent = Rent::default();
+    assert_eq!(accounts[1].lamports - lamports_before, rent.minimum_balance(new_size) - rent.minimum_balance(old_size));
+    assert_eq!(accounts[1].state::<PresaleState>(), state);
+
+    // Only the presale authority can grow it
+    accounts[0] = TestAccount::signer(Pubkey::new_unique());
+    let outcome = common::process(&mut accounts, &VCoinInstruction::ExpandPresaleAccount { additional_buyers: 20 });
+    assert_eq!(outcome.result, error(VCoinError::Unauthorized));
+}
+
+#[test]
+fn guardians_open_dev_refunds_that_the_vault_pays_without_the_authority() {
+    let buyer = Pubkey::new_unique();
+    let usdc = Pubkey::new_unique();
+    let mut state = presale();
+    state.allowed_stablecoins.push(usdc);
+    state.buyer_pubkeys.push(buyer);
+    state.num_buyers = 1;
+    state.contributions.push(PresaleContribution {
+        buyer,
+        amount: 100_000_000,
+        dev_amount: 50_000_000,
+        locked_amount: 50_000_000,
+        stablecoin_type: StablecoinType::USDC,
+        stablecoin_mint: usdc,
+        refunded: false,
+        dev_refunded: false,
+        timestamp: common::NOW - DAY,
+        keeper_refund_destination: None,
+        last_purchase: PurchaseSnapshot::default(),
+    });
+    let guardians = [Pubkey::new_unique(), Pubkey::new_unique()];
+    let (bounty_address, bounty_bump) = BugBountyEscrow::find_address(&vcoin_program::id(), &state.mint);
+    let bounty = BugBountyEscrow {
+        is_initialized: true,
+        authority: state.authority,
+        mint: state.mint,
+        escrow: Pubkey::new_unique(),
+        guardians: guardians.to_vec(),
+        guardian_threshold: 2,
+        total_funded: 0,
+        total_committed: 0,
+        total_paid: 0,
+        num_awards: 0,
+        bump: bounty_bump,
+    };
+    let presale_key = Pubkey::new_unique();
+    let (vault_authority, vault_bump) =
+        Pubkey::find_program_address(&[b"dev_refund_vault", presale_key.as_ref()], &vcoin_program::id());
+    let mut claim_accounts = vec![
+        TestAccount::signer(buyer),
+        TestAccount::program(presale_key, &state, PresaleState::get_size_for_buyers(1)),
+        TestAccount::token(Pubkey::new_unique(), usdc, buyer, 0),
+        TestAccount::token(Pubkey::new_unique(), usdc, vault_authority, 1_000_000_000),
+        TestAccount::new(vault_authority, system_program::ID, Vec::new()).readonly(),
+        TestAccount::new(spl_token::ID, Pubkey::default(), Vec::new()).readonly(),
+        TestAccount::mint(usdc, 6).readonly(),
+        TestAccount::emergency_state(&state.mint, EmergencyMode::Normal),
+    ];
+
+    // Before the declaration the dev share stays with the treasury
+    let outcome = common::process(&mut claim_accounts, &VCoinInstruction::ClaimDevFundRefund);
+    assert_eq!(outcome.result, Err(ProgramError::InvalidArgument));
+    assert!(outcome.logged("Dev funds are not refundable"));
+
+    // A guardian quorum declares the forced refund, passing the vault's accounts
+    let mut declare_accounts = vec![
+        claim_accounts[1].clone(),
+        TestAccount::program(bounty_address, &bounty, BugBountyEscrow::get_size()).readonly(),
+        TestAccount::signer(guardians[0]),
+        TestAccount::signer(guardians[1]),
+        claim_accounts[3].clone().readonly(),
+    ];
+    let declare = VCoinInstruction::DeclareForcedRefund { reason_hash: [9; 32] };
+
+    // An underfunded vault does not stop the declaration, but its shortfall is
+    // reported; nor do token accounts held by anyone else count towards it
+    let mut underfunded = declare_accounts.clone();
+    underfunded[4] = TestAccount::token(Pubkey::new_unique(), usdc, vault_authority, 30_000_000).readonly();
+    underfunded.push(TestAccount::token(Pubkey::new_unique(), usdc, Pubkey::new_unique(), 1_000_000_000).readonly());
+    let outcome = common::process(&mut underfunded, &declare);
+    assert_eq!(outcome.result, Ok(()));
+    assert!(outcome.logged(&format!("Dev refund vault holds 30000000 of the 50000000 owed in {usdc}")));
+    assert!(outcome.logged("Dev refund vault is 20000000 short of the dev-treasury refunds owed"));
+
+    let outcome = common::process(&mut declare_accounts, &declare);
+    assert_eq!(outcome.result, Ok(()));
+    assert!(outcome.logged(&format!("Dev refund vault holds 1000000000 of the 50000000 owed in {usdc}")));
+    assert!(!outcome.logged("short of the dev-treasury refunds owed"));
+    let declared: PresaleState = declare_accounts[0].state();
+    assert!(declared.has_ended && declared.dev_funds_refundable);
+    assert_eq!(declared.forced_refund.map(|forced| forced.reason_hash), Some([9; 32]));
+    claim_accounts[1] = declare_accounts[0].clone();
+
+    // The vault must be the presale's own
+    let mut wrong_vault = claim_accounts.clone();
+    wrong_vault[4].key = Pubkey::new_unique();
+    let outcome = common::process(&mut wrong_vault, &VCoinInstruction::ClaimDevFundRefund);
+    assert_eq!(outcome.result, Err(ProgramError::InvalidArgument));
+    assert!(outcome.logged("Invalid dev refund vault authority"));
+
+    // The buyer claims the dev half straight away, signed for by the vault PDA
+    let outcome = common::process(&mut claim_accounts, &VCoinInstruction::ClaimDevFundRefund);
+    assert_eq!(outcome.result, Ok(()));
+    assert!(outcome.logged("Instruction: Claim Dev Fund Refund"));
+    let [transfer] = &outcome.invocations[..] else { panic!("expected one transfer") };
+    assert_eq!(transfer.transferred(), Some(50_000_000));
+    assert_eq!(transfer.instruction.accounts[3].pubkey, vault_authority);
+    assert_eq!(
+        transfer.signer_seeds,
+        vec![vec![b"dev_refund_vault".to_vec(), presale_key.to_bytes().to_vec(), vec![vault_bump]]],
+    );
+    assert!(claim_accounts[1].state::<PresaleState>().contributions[0].dev_refunded);
+
+    // And only once
+    let outcome = common::process(&mut claim_accounts, &VCoinInstruction::ClaimDevFundRefund);
+    assert_eq!(outcome.result, Err(ProgramError::InvalidArgument));
+    assert!(outcome.logged("Dev fund share already refunded"));
+}
+
+#[test]
+fn ending_and_finalizing_schedule_the_same_refund_windows() {
+    let mut state = presale();
+    state.total_usd_raised = state.soft_cap - 1;
+    let closed_at = state.end_time + DAY;
+    common::set_time(closed_at);
+
+    let mut windows = Vec::new();
+    for instruction in [VCoinInstruction::EndPresale, VCoinInstruction::FinalizePresale] {
+        let mut accounts = vec![
+            TestAccount::signer(Pubkey::new_unique()),
+            TestAccount::program(Pubkey::new_unique(), &state, PresaleState::get_size_for_buyers(1)),
+            TestAccount::emergency_state(&state.mint, EmergencyMode::Normal),
+        ];
+        let outcome = common::process(&mut accounts, &instruction);
+        assert_eq!(outcome.result, Ok(()), "{instruction:?}");
+        let closed: PresaleState = accounts[1].state();
+        assert!(closed.has_ended && !closed.soft_cap_reached && closed.dev_funds_refundable);
+        assert_eq!(closed.refund_available_timestamp, closed_at, "{instruction:?}");
+        windows.push((
+            closed.refund_available_timestamp,
+            closed.refund_period_end_timestamp,
+            closed.dev_refund_available_timestamp,
+            closed.dev_refund_period_end_timestamp,
+        ));
+    }
+    assert_eq!(windows[0], windows[1]);
+}
+
+#[test]
+fn odd_payments_are_refunded_exactly_as_they_were_split() {
+    let buyer = Pubkey::new_unique();
+    let usdc = Pubkey::new_unique();
+    let mut state = presale();
+    state.allowed_stablecoins.push(usdc);
+    let presale_key = Pubkey::new_unique();
+    let (locked_authority, _) = Pubkey::find_program_address(&[b"locked_treasury", presale_key.as_ref()], &vcoin_program::id());
+    let (vault_authority, _) = Pubkey::find_program_address(&[b"dev_refund_vault", presale_key.as_ref()], &vcoin_program::id());
+    let token_program = TestAccount::new(spl_token::ID, Pubkey::default(), Vec::new()).readonly();
+    let usdc_mint = TestAccount::mint(usdc, 6).readonly();
+    let buyer_usdc = TestAccount::token(Pubkey::new_unique(), usdc, buyer, 100_000_000);
+    let locked_treasury = TestAccount::token(Pubkey::new_unique(), usdc, locked_authority, 0);
+    let emergency_state = TestAccount::emergency_state(&state.mint, EmergencyMode::Normal);
+
+    // An odd payment leaves the extra base unit in the locked treasury
+    let mut accounts = vec![
+        TestAccount::signer(buyer),
+        TestAccount::program(presale_key, &state, PresaleState::get_size_for_buyers(1)),
+        TestAccount::mint(state.mint, 6),
+        TestAccount::token(Pubkey::new_unique(), state.mint, buyer, 0),
+        TestAccount::signer(Pubkey::new_unique()),
+        token_program.clone(),
+        buyer_usdc.clone(),
+        TestAccount::token(Pubkey::new_unique(), usdc, state.authority, 0),
+        locked_treasury.clone(),
+        token_program.clone(),
+        usdc_mint.clone(),
+        emergency_state.clone(),
+    ];
+    let outcome = common::process(&mut accounts, &VCoinInstruction::BuyTokensWithStablecoin { amount: 10_000_001 });
+    assert_eq!(outcome.result, Ok(()));
+    let transfers: Vec<_> = outcome.invocations.iter().filter_map(|invocation| invocation.transferred()).collect();
+    assert_eq!(transfers, vec![5_000_000, 5_000_001]);
+    let contribution = accounts[1].state::<PresaleState>().contributions[0].clone();
+    assert_eq!((contribution.dev_amount, contribution.locked_amount), (5_000_000, 5_000_001));
+
+    // Missing the soft cap opens both refunds, which pay back each half to the unit
+    let mut end_accounts = vec![TestAccount::signer(state.authority), accounts[1].clone(), emergency_state.clone()];
+    assert_eq!(common::process(&mut end_accounts, &VCoinInstruction::EndPresale).result, Ok(()));
+    let mut refund_accounts = vec![
+        TestAccount::signer(buyer),
+        end_accounts[1].clone(),
+        buyer_usdc,
+        locked_treasury,
+        TestAccount::new(locked_authority, system_program::ID, Vec::new()).readonly(),
+        token_program.clone(),
+        usdc_mint.clone(),
+        emergency_state,
+    ];
+    let outcome = common::process(&mut refund_accounts, &VCoinInstruction::ClaimRefund);
+    assert_eq!(outcome.result, Ok(()));
+    assert_eq!(outcome.invocations[0].transferred(), Some(5_000_001));
+
+    let ended: PresaleState = refund_accounts[1].state();
+    common::set_time(ended.dev_refund_available_timestamp);
+    refund_accounts[3] = TestAccount::token(Pubkey::new_unique(), usdc, vault_authority, 5_000_000);
+    refund_accounts[4] = TestAccount::new(vault_authority, system_program::ID, Vec::new()).readonly();
+    let outcome = common::process(&mut refund_accounts, &VCoinInstruction::ClaimDevFundRefund);
+    assert_eq!(outcome.result, Ok(()));
+    assert_eq!(outcome.invocations[0].transferred(), Some(5_000_000));
+}
+
+#[test]
+fn stablecoin_accounts_outside_the_token_programs_are_refused() {
+    let buyer = Pubkey::new_unique();
+    let usdc = Pubkey::new_unique();
+    let mut state = presale();
+    state.allowed_stablecoins.push(usdc);
+    let presale_key = Pubkey::new_unique();
+    let (locked_authority, _) = Pubkey::find_program_address(&[b"locked_treasury", presale_key.as_ref()], &vcoin_program::id());
+    let token_program = TestAccount::new(spl_token::ID, Pubkey::default(), Vec::new()).readonly();
+    let foreign = |account: TestAccount| TestAccount { owner: Pubkey::new_unique(), ..account };
+    let purchase_accounts = vec![
+        TestAccount::signer(buyer),
+        TestAccount::program(presale_key, &state, PresaleState::get_size_for_buyers(1)),
+        TestAccount::mint(state.mint, 6),
+        TestAccount::token(Pubkey::new_unique(), state.mint, buyer, 0),
+        TestAccount::signer(Pubkey::new_unique()),
+        token_program.clone(),
+        TestAccount::token(Pubkey::new_unique(), usdc, buyer, 100_000_000),
+        TestAccount::token(Pubkey::new_unique(), usdc, state.authority, 0),
+        TestAccount::token(Pubkey::new_unique(), usdc, locked_authority, 0),
+        token_program.clone(),
+        TestAccount::mint(usdc, 6).readonly(),
+        TestAccount::emergency_state(&state.mint, EmergencyMode::Normal),
+    ];
+    let buy = VCoinInstruction::BuyTokensWithStablecoin { amount: 10_000_000 };
+    let refused = |index: usize, account: TestAccount| {
+        let mut accounts = purchase_accounts.clone();
+        accounts[index] = account;
+        let outcome = common::process(&mut accounts, &buy);
+        assert_eq!(outcome.result, Err(ProgramError::IncorrectProgramId), "account {}: {:?}", index, outcome.logs);
+        assert!(outcome.invocations.is_empty());
+        outcome
+    };
+
+    // The buyer's payment, the stablecoin mint and the presale mint
+    for index in [6, 10, 2] {
+        let outcome = refused(index, foreign(purchase_accounts[index].clone()));
+        assert!(outcome.logged("is not owned by a token program"));
+    }
+    // A token program other than the one owning the payment
+    let token_2022 = TestAccount::new(spl_token_2022::ID, Pubkey::default(), Vec::new()).readonly();
+    assert!(refused(9, token_2022).logged("Token program mismatch"));
+
+    // Refunds only leave a locked treasury held by a token program
+    let mut accounts = purchase_accounts.clone();
+    assert_eq!(common::process(&mut accounts, &buy).result, Ok(()));
+    let mut end_accounts = vec![TestAccount::signer(state.authority), accounts[1].clone(), purchase_accounts[11].clone()];
+    assert_eq!(common::process(&mut end_accounts, &VCoinInstruction::EndPresale).result, Ok(()));
+    let mut refund_accounts = vec![
+        TestAccount::signer(buyer),
+        end_accounts[1].clone(),
+        purchase_accounts[6].clone(),
+        foreign(purchase_accounts[8].clone()),
+        TestAccount::new(locked_authority, system_program::ID, Vec::new()).readonly(),
+        token_program,
+        purchase_accounts[10].clone(),
+        purchase_accounts[11].clone(),
+    ];
+    let outcome = common::process(&mut refund_accounts, &VCoinInstruction::ClaimRefund);
+    assert_eq!(outcome.result, Err(ProgramError::IncorrectProgramId));
+    assert!(outcome.logged("is not owned by a token program"));
+    assert!(outcome.invocations.is_empty());
+}
+
+#[test]
+fn purchases_and_closing_answer_to_the_presale_mints_emergency_state() {
+    let buyer = Pubkey::new_unique();
+    let usdc = Pubkey::new_unique();
+    let mut state = presale();
+    state.allowed_stablecoins.push(usdc);
+    let presale_key = Pubkey::new_unique();
+    let (locked_authority, _) = Pubkey::find_program_address(&[b"locked_treasury", presale_key.as_ref()], &vcoin_program::id());
+    let token_program = TestAccount::new(spl_token::ID, Pubkey::default(), Vec::new()).readonly();
+    let purchase_accounts = vec![
+        TestAccount::signer(buyer),
+        TestAccount::program(presale_key, &state, PresaleState::get_size_for_buyers(1)),
+        TestAccount::mint(state.mint, 6),
+        TestAccount::token(Pubkey::new_unique(), state.mint, buyer, 0),
+        TestAccount::signer(Pubkey::new_unique()),
+        token_program.clone(),
+        TestAccount::token(Pubkey::new_unique(), usdc, buyer, 100_000_000),
+        TestAccount::token(Pubkey::new_unique(), usdc, state.authority, 0),
+        TestAccount::token(Pubkey::new_unique(), usdc, locked_authority, 0),
+        token_program,
+        TestAccount::mint(usdc, 6).readonly(),
+        TestAccount::emergency_state(&state.mint, EmergencyMode::Normal),
+    ];
+    let buy = VCoinInstruction::BuyTokensWithStablecoin { amount: 10_000_000 };
+    let buy_with = |emergency_state: TestAccount| {
+        let mut accounts = purchase_accounts.clone();
+        accounts[11] = emergency_state;
+        let outcome = common::process(&mut accounts, &buy);
+        assert!(outcome.invocations.is_empty());
+        outcome
+    };
+
+    // A paused or maintained mint sells nothing
+    for mode in [EmergencyMode::Paused, EmergencyMode::Maintenance] {
+        let outcome = buy_with(TestAccount::emergency_state(&state.mint, mode.clone()));
+        assert_eq!(outcome.result, error(VCoinError::BlockedByEmergencyMode), "{mode:?}");
+    }
+
+    // Nor does another mint's state in normal mode stand in for the paused one
+    let other_mint = TestAccount::emergency_state(&Pubkey::new_unique(), EmergencyMode::Normal);
+    let outcome = buy_with(other_mint.clone());
+    assert_eq!(outcome.result, Err(ProgramError::InvalidSeeds));
+    assert!(outcome.logged("is not the emergency state of mint"));
+
+    // The same holds for the authority closing the sale
+    let mut end_accounts = vec![TestAccount::signer(state.authority), purchase_accounts[1].clone(), other_mint];
+    assert_eq!(common::process(&mut end_accounts, &VCoinInstruction::EndPresale).result, Err(ProgramError::InvalidSeeds));
+    end_accounts[2] = TestAccount::emergency_state(&state.mint, EmergencyMode::Paused);
+    let outcome = common::process(&mut end_accounts, &VCoinInstruction::EndPresale);
+    assert_eq!(outcome.result, error(VCoinError::BlockedByEmergencyMode));
+    assert!(!end_accounts[1].state::<PresaleState>().has_ended);
+
+    end_accounts[2] = purchase_accounts[11].clone();
+    assert_eq!(common::process(&mut end_accounts, &VCoinInstruction::EndPresale).result, Ok(()));
+}
+
+#[test]
+fn keepers_refund_opted_in_buyers_without_their_signature() {
+    let buyer = Pubkey::new_unique();
+    let usdc = Pubkey::new_unique();
+    let buyer_usdc = Pubkey::new_unique();
+    let mut state = presale();
+    state.allowed_stablecoins.push(usdc);
+    state.has_ended = true;
+    state.buyer_pubkeys.push(buyer);
+    state.num_buyers = 1;
+    state.contributions.push(PresaleContribution {
+        buyer,
+        amount: 100_000_000,
+        dev_amount: 50_000_000,
+        locked_amount: 50_000_000,
+        stablecoin_type: StablecoinType::USDC,
+        stablecoin_mint: usdc,
+        refunded: false,
+        dev_refunded: false,
+        timestamp: common::NOW - DAY,
+        keeper_refund_destination: Some(buyer_usdc),
+        last_purchase: PurchaseSnapshot::default(),
+    });
+    let presale_key = Pubkey::new_unique();
+    let (locked_authority, _) = Pubkey::find_program_address(&[b"locked_treasury", presale_key.as_ref()], &vcoin_program::id());
+    let refund_accounts = vec![
+        TestAccount::new(buyer, system_program::ID, Vec::new()).readonly(),
+        TestAccount::program(presale_key, &state, PresaleState::get_size_for_buyers(1)),
+        TestAccount::token(buyer_usdc, usdc, buyer, 0),
+        TestAccount::token(Pubkey::new_unique(), usdc, locked_authority, 50_000_000),
+        TestAccount::new(locked_authority, system_program::ID, Vec::new()).readonly(),
+        TestAccount::new(spl_token::ID, Pubkey::default(), Vec::new()).readonly(),
+        TestAccount::mint(usdc, 6).readonly(),
+        TestAccount::emergency_state(&state.mint, EmergencyMode::Normal),
+    ];
+
+    // Anywhere but the recorded destination still needs the buyer's signature
+    let mut accounts = refund_accounts.clone();
+    accounts[2] = TestAccount::token(Pubkey::new_unique(), usdc, Pubkey::new_unique(), 0);
+    let outcome = common::process(&mut accounts, &VCoinInstruction::ClaimRefund);
+    assert_eq!(outcome.result, error(VCoinError::Unauthorized));
+    assert!(outcome.logged("Buyer must sign transaction"));
+    assert!(outcome.invocations.is_empty());
+    assert!(!accounts[1].state::<PresaleState>().contributions[0].refunded);
+
+    // The recorded destination gets the refund whoever submits it
+    let mut accounts = refund_accounts;
+    let outcome = common::process(&mut accounts, &VCoinInstruction::ClaimRefund);
+    assert_eq!(outcome.result, Ok(()));
+    assert!(outcome.logged("Keeper-triggered refund to the buyer's recorded account"));
+    let [transfer] = &outcome.invocations[..] else { panic!("expected one transfer") };
+    assert_eq!(transfer.transferred(), Some(50_000_000));
+    assert_eq!(transfer.instruction.accounts[2].pubkey, buyer_usdc);
+    assert!(accounts[1].state::<PresaleState>().contributions[0].refunded);
+}
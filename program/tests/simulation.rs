@@ -0,0 +1,333 @@
+//! Scripted clocks and prices replaying bull and bear years through the
+//! autonomous supply rules.
+
+use solana_program::pubkey::Pubkey;
+use vcoin_program::state::{
+    AutonomousSupplyController, BurnKind, BurnLog, BurnRecord, PendingSupplyParameters, SimulationState, SupplyActionLedger, SupplyActionRecord, SupplyDistribution,
+    SupplyOperation, SupplyOracleHealth, SupplyParameters, SupplyPriceSource, SupplyRateLimit, MAX_SIMULATED_PRICES,
+    MAX_BURN_LOG_RECORDS, MAX_SUPPLY_ACTION_RECORDS, MAX_SUPPLY_RATE_BPS, SIMULATED_PRICE_FEED_SIZE, SUPPLY_KEEPER_REWARD_BPS, SUPPLY_PARAMETERS_TIMELOCK,
+};
+
+const START: i64 = 1_700_000_000;
+const YEAR: u32 = 31_536_000;
+
+fn simulation(prices: Vec<u64>) -> SimulationState {
+    SimulationState {
+        is_initialized: true,
+        authority: Pubkey::new_unique(),
+        unix_timestamp: START,
+        prices,
+        next_price: 0,
+        price_feed: Pubkey::new_unique(),
+        bump: 255,
+    }
+}
+
+fn supply_controller() -> AutonomousSupplyController {
+    AutonomousSupplyController {
+        is_initialized: true,
+        mint: Pubkey::new_unique(),
+        price_oracle: Pubkey::new_unique(),
+        initial_price: 30_000,
+        year_start_price: 30_000,
+        current_price: 30_000,
+        last_price_update: START,
+        year_start_timestamp: START,
+        last_mint_timestamp: 0,
+        last_burn_timestamp: 0,
+        current_supply: 2_000_000_000_000_000,
+        token_decimals: 6,
+        min_supply: 1_000_000_000_000_000,
+        high_supply_threshold: 5_000_000_000_000_000,
+        max_supply: 10_000_000_000_000_000,
+        mint_authority: Pubkey::new_unique(),
+        mint_authority_bump: 255,
+        burn_treasury: Pubkey::new_unique(),
+        burn_treasury_bump: 255,
+        min_growth_for_mint_bps: 500,
+        min_decline_for_burn_bps: 500,
+        medium_growth_mint_rate_bps: 500,
+        high_growth_mint_rate_bps: 1_000,
+        medium_decline_burn_rate_bps: 500,
+        high_decline_burn_rate_bps: 1_000,
+        high_growth_threshold_bps: 1_000,
+        high_decline_threshold_bps: 1_000,
+        extreme_growth_threshold_bps: 3_000,
+        extreme_decline_threshold_bps: 3_000,
+        post_cap_mint_rate_bps: 200,
+        post_cap_burn_rate_bps: 200,
+        price_source: SupplyPriceSource::MultiOracleController,
+        authority: Pubkey::new_unique(),
+        pending_parameters: None,
+        rate_limit: SupplyRateLimit::new(YEAR as i64, 400_000_000_000_000, 1, START),
+        distribution: None,
+        mint_cooldown_seconds: 86_400,
+        burn_cooldown_seconds: 86_400,
+        fees_swept_to_burn: 0,
+    }
+}
+
+/// Supply after each yearly evaluation of the scripted prices
+fn replay(prices: Vec<u64>) -> Vec<u64> {
+    let mut simulation = simulation(prices);
+    let mut controller = supply_controller();
+    let mut supplies = Vec::new();
+
+    while let Some(price) = simulation.step(YEAR) {
+        controller.update_price(price, simulation.unix_timestamp);
+        assert!(controller.is_annual_evaluation_time(simulation.unix_timestamp));
+
+        let minted = controller.calculate_mint_amount().unwrap();
+        let burned = controller.calculate_burn_amount().unwrap();
+        for (operation, amount) in [(SupplyOperation::Mint, minted), (SupplyOperation::Burn, burned)] {
+            if amount > 0 {
+                assert!(controller.rate_limit.remaining(operation, simulation.unix_timestamp) >= amount);
+                controller.rate_limit.record(operation, amount);
+            }
+        }
+        controller.current_supply = controller.current_supply + minted - burned;
+        supplies.push(controller.current_supply);
+
+        controller.start_new_year_period(simulation.unix_timestamp);
+    }
+
+    supplies
+}
+
+#[test]
+fn bull_and_bear_years_replay_identically() {
+    // +20% (high-rate mint), -25% (high-rate burn), +2% (no change)
+    let prices = vec![36_000, 27_000, 27_540];
+    let supplies = replay(prices.clone());
+
+    assert_eq!(supplies, vec![2_200_000_000_000_000, 1_980_000_000_000_000, 1_980_000_000_000_000]);
+    assert_eq!(replay(prices), supplies);
+}
+
+#[test]
+fn steps_advance_the_clock_past_the_end_of_the_script() {
+    let mut simulation = simulation(vec![36_000]);
+
+    assert_eq!(simulation.step(60), Some(36_000));
+    assert_eq!(simulation.unix_timestamp, START + 60);
+
+    // The feed uses the Custom oracle layout: price, confidence, publish time
+    let feed = simulation.price_feed_data(36_000);
+    assert_eq!(feed.len(), SIMULATED_PRICE_FEED_SIZE);
+    assert_eq!(u64::from_le_bytes(feed[0..8].try_into().unwrap()), 36_000);
+    assert_eq!(u64::from_le_bytes(feed[8..16].try_into().unwrap()), 0);
+    assert_eq!(i64::from_le_bytes(feed[16..24].try_into().unwrap()), START + 60);
+
+    assert_eq!(simulation.step(60), None);
+    assert_eq!(simulation.unix_timestamp, START + 120);
+    assert_eq!(simulation.next_price, 1);
+
+    // The account is sized for a full script
+    simulation.prices = vec![u64::MAX; MAX_SIMULATED_PRICES];
+    assert_eq!(borsh::to_vec(&simulation).unwrap().len(), SimulationState::get_size());
+}
+
+#[test]
+fn queued_parameters_take_over_once_the_timelock_passes() {
+    let mut controller = supply_controller();
+    let current = controller.parameters();
+    assert!(current.is_valid());
+
+    // Mint 20% from 15% growth instead of 10% from 10%
+    let aggressive = SupplyParameters {
+        high_growth_threshold_bps: 1_500,
+        high_growth_mint_rate_bps: MAX_SUPPLY_RATE_BPS,
+        ..current
+    };
+    assert!(aggressive.is_valid());
+    assert!(!SupplyParameters { high_growth_mint_rate_bps: MAX_SUPPLY_RATE_BPS + 1, ..current }.is_valid());
+    assert!(!SupplyParameters { min_growth_for_mint_bps: 1_200, ..current }.is_valid());
+    assert!(!SupplyParameters { extreme_decline_threshold_bps: 10_001, ..current }.is_valid());
+
+    let effective_at = START + SUPPLY_PARAMETERS_TIMELOCK;
+    controller.pending_parameters = Some(PendingSupplyParameters { parameters: aggressive, effective_at });
+    assert_eq!(controller.apply_due_parameters(effective_at - 1), None);
+    assert_eq!(controller.parameters(), current);
+
+    assert_eq!(controller.apply_due_parameters(effective_at), Some(aggressive));
+    assert_eq!(controller.parameters(), aggressive);
+    assert_eq!(controller.pending_parameters, None);
+
+    // +20% growth now mints at the new high rate
+    controller.update_price(36_000, effective_at);
+    assert_eq!(controller.calculate_mint_amount(), Some(400_000_000_000_000));
+
+    // Accounts are sized for a queued parameter set
+    controller.pending_parameters = Some(PendingSupplyParameters { parameters: current, effective_at });
+    assert!(borsh::to_vec(&controller).unwrap().len() <= AutonomousSupplyController::get_size());
+}
+
+#[test]
+fn mints_stop_at_the_maximum_supply() {
+    let mut controller = supply_controller();
+    controller.max_supply = 2_150_000_000_000_000;
+    controller.update_price(36_000, START + 60);
+
+    // +20% would mint 10% of 2B, but only 150M fit under the cap
+    assert_eq!(controller.mint_headroom(), 150_000_000_000_000);
+    assert_eq!(controller.calculate_mint_amount(), Some(150_000_000_000_000));
+
+    controller.current_supply = controller.max_supply;
+    assert_eq!(controller.mint_headroom(), 0);
+    assert_eq!(controller.calculate_mint_amount(), Some(0));
+}
+
+#[test]
+fn rate_limits_cap_each_window() {
+    let mut limit = SupplyRateLimit::new(86_400, 1_000, 2, START);
+    assert!(limit.is_valid());
+    assert!(!SupplyRateLimit::new(60, 1_000, 2, START).is_valid());
+    assert!(!SupplyRateLimit::new(86_400, 1_000, 0, START).is_valid());
+
+    // Mints and burns have separate amounts but share the operation count
+    assert_eq!(limit.remaining(SupplyOperation::Mint, START), 1_000);
+    limit.record(SupplyOperation::Mint, 700);
+    assert_eq!(limit.remaining(SupplyOperation::Mint, START + 60), 300);
+    assert_eq!(limit.remaining(SupplyOperation::Burn, START + 60), 1_000);
+    limit.record(SupplyOperation::Burn, 100);
+    assert_eq!(limit.remaining(SupplyOperation::Burn, START + 120), 0);
+
+    // The next window starts empty
+    assert_eq!(limit.remaining(SupplyOperation::Mint, START + 86_400), 1_000);
+    assert_eq!(limit.window_start, START + 86_400);
+    assert_eq!((limit.minted_this_epoch, limit.burned_this_epoch, limit.operations_this_epoch), (0, 0, 0));
+}
+
+#[test]
+fn mints_and_burns_cool_down_independently() {
+    let mut controller = supply_controller();
+    controller.burn_cooldown_seconds = 3_600;
+
+    // Nothing has happened yet, so neither operation is cooling down
+    assert_eq!(controller.cooldown_ends_at(SupplyOperation::Mint), None);
+    assert!(!controller.is_cooling_down(SupplyOperation::Burn, START));
+
+    // A mint only delays the next mint
+    controller.last_mint_timestamp = START;
+    assert_eq!(controller.cooldown_ends_at(SupplyOperation::Mint), Some(START + 86_400));
+    assert!(controller.is_cooling_down(SupplyOperation::Mint, START + 86_399));
+    assert!(!controller.is_cooling_down(SupplyOperation::Mint, START + 86_400));
+    assert!(!controller.is_cooling_down(SupplyOperation::Burn, START + 60));
+
+    controller.last_burn_timestamp = START + 60;
+    assert!(controller.is_cooling_down(SupplyOperation::Burn, START + 3_659));
+    assert!(!controller.is_cooling_down(SupplyOperation::Burn, START + 3_660));
+
+    // A zero cooldown never blocks
+    controller.mint_cooldown_seconds = 0;
+    assert!(!controller.is_cooling_down(SupplyOperation::Mint, START));
+}
+
+#[test]
+fn keepers_earn_a_sliver_of_each_cranked_action() {
+    // A +20% year mints 10% of supply; the keeper gets its share of that
+    let mut controller = supply_controller();
+    controller.update_price(36_000, START + YEAR as i64);
+    let minted = controller.calculate_mint_amount().unwrap();
+    assert_eq!(minted, 200_000_000_000_000);
+    assert_eq!(AutonomousSupplyController::keeper_reward(minted), Some(minted / 10_000 * u64::from(SUPPLY_KEEPER_REWARD_BPS)));
+
+    // Dust actions round the reward down to nothing
+    assert_eq!(AutonomousSupplyController::keeper_reward(9_999 / u64::from(SUPPLY_KEEPER_REWARD_BPS)), Some(0));
+}
+
+#[test]
+fn distributions_split_the_treasury_by_share() {
+    let mut distribution = SupplyDistribution {
+        staking_rewards: Pubkey::new_unique(),
+        staking_rewards_bps: 3_333,
+        liquidity: Pubkey::new_unique(),
+        liquidity_bps: 3_333,
+        operations: Pubkey::new_unique(),
+        operations_bps: 3_334,
+    };
+    assert!(distribution.is_valid());
+
+    // Operations takes the rounding remainder, so the whole balance is paid out
+    assert_eq!(distribution.split(100), Some([33, 33, 34]));
+    assert_eq!(distribution.split(1), Some([0, 0, 1]));
+
+    distribution.operations_bps = 3_333;
+    assert!(!distribution.is_valid());
+    distribution.operations_bps = u16::MAX;
+    assert!(!distribution.is_valid());
+
+    // Controllers are sized for a distribution
+    let mut controller = supply_controller();
+    controller.distribution = Some(distribution);
+    controller.pending_parameters = Some(PendingSupplyParameters { parameters: controller.parameters(), effective_at: START });
+    assert!(borsh::to_vec(&controller).unwrap().len() <= AutonomousSupplyController::get_size());
+}
+
+#[test]
+fn the_ledger_keeps_the_latest_actions_oldest_first() {
+    let mut ledger = SupplyActionLedger::new(Pubkey::new_unique());
+    let action = |year: i64| SupplyActionRecord {
+        timestamp: START + year * YEAR as i64,
+        operation: if year % 2 == 0 { SupplyOperation::Mint } else { SupplyOperation::Burn },
+        amount: u64::MAX,
+        trigger_price: u64::MAX,
+        year_start_price: u64::MAX,
+        supply_after: u64::MAX,
+        oracle_health: Some(SupplyOracleHealth {
+            health_score: 100,
+            contributing_oracles: u8::MAX,
+            confidence: u64::MAX,
+            is_emergency_price: true,
+        }),
+    };
+
+    for year in 0..3 {
+        ledger.record(action(year));
+    }
+    assert_eq!(ledger.history(), vec![action(0), action(1), action(2)]);
+
+    // Once full, each action replaces the oldest
+    let recorded = MAX_SUPPLY_ACTION_RECORDS as i64 + 5;
+    for year in 3..recorded {
+        ledger.record(action(year));
+    }
+    assert_eq!(ledger.total_actions, recorded as u64);
+    assert_eq!(ledger.records.len(), MAX_SUPPLY_ACTION_RECORDS);
+    let history = ledger.history();
+    assert_eq!(history.first(), Some(&action(5)));
+    assert_eq!(history.last(), Some(&action(recorded - 1)));
+    assert!(history.windows(2).all(|pair| pair[0].timestamp < pair[1].timestamp));
+
+    // The account is sized for a full ledger
+    assert_eq!(borsh::to_vec(&ledger).unwrap().len(), SupplyActionLedger::get_size());
+}
+
+#[test]
+fn the_burn_log_totals_every_burn_it_has_seen() {
+    let mut log = BurnLog::new(Pubkey::new_unique());
+    let source = Pubkey::new_unique();
+    let burn = |n: u64| BurnRecord {
+        timestamp: START + n as i64,
+        slot: n,
+        kind: if n % 3 == 0 { BurnKind::Manual } else { BurnKind::Autonomous },
+        source,
+        amount: 1_000 + n,
+        price: u64::MAX,
+    };
+
+    // Totals keep counting after the oldest records are overwritten
+    let recorded = MAX_BURN_LOG_RECORDS as u64 + 10;
+    for n in 0..recorded {
+        log.record(burn(n));
+    }
+    assert_eq!(log.total_burns, recorded);
+    assert_eq!(log.total_burned, (0..recorded).map(|n| 1_000 + n).sum::<u64>());
+    assert_eq!(log.records.len(), MAX_BURN_LOG_RECORDS);
+    let history = log.history();
+    assert_eq!(history.first(), Some(&burn(10)));
+    assert_eq!(history.last(), Some(&burn(recorded - 1)));
+
+    // The account is sized for a full log
+    assert_eq!(borsh::to_vec(&log).unwrap().len(), BurnLog::get_size());
+}
@@ -0,0 +1,293 @@
+//! Vesting schedule math for shared and per-beneficiary terms.
+
+mod common;
+
+use common::{process, TestAccount};
+use solana_program::pubkey::Pubkey;
+use vcoin_program::instruction::VCoinInstruction;
+use vcoin_program::state::{EmergencyMode, VestingGrant, VestingPause, VestingSchedule, VestingState};
+
+const DAY: i64 = 86_400;
+
+fn team_schedule() -> VestingSchedule {
+    // One-year cliff, four-year vest, monthly unlocks
+    VestingSchedule {
+        start_time: 1_700_000_000,
+        cliff_duration: 360 * DAY,
+        duration: 1_440 * DAY,
+        release_interval: 30 * DAY,
+    }
+}
+
+fn monthly_pool(total_tokens: u64, num_releases: u8) -> VestingState {
+    VestingState {
+        is_initialized: true,
+        authority: Pubkey::new_unique(),
+        mint: Pubkey::new_unique(),
+        escrow: Pubkey::new_unique(),
+        total_tokens,
+        total_allocated: total_tokens,
+        total_released: 0,
+        start_time: 1_700_000_000,
+        release_interval: 30 * DAY,
+        num_releases,
+        num_beneficiaries: 0,
+        crank_tip_lamports: 0,
+        receipts_transferable: false,
+        pause: VestingPause::default(),
+    }
+}
+
+fn beneficiary(schedule: VestingSchedule) -> VestingGrant {
+    VestingGrant {
+        is_initialized: true,
+        vesting: Pubkey::new_unique(),
+        beneficiary: Pubkey::new_unique(),
+        total_amount: 48_000,
+        released_amount: 0,
+        revoked_at: None,
+        schedule: Some(schedule),
+        pending_beneficiary: None,
+        destination: Pubkey::new_unique(),
+        receipt_mint: None,
+        receipt_transferable: false,
+        pause: VestingPause::default(),
+        last_release_time: 0,
+    }
+}
+
+#[test]
+fn custom_schedule_respects_cliff_and_intervals() {
+    let schedule = team_schedule();
+    let start = schedule.start_time;
+    assert!(schedule.is_valid());
+
+    assert_eq!(schedule.vested_amount(48_000, start - DAY), Ok(0));
+    assert_eq!(schedule.vested_amount(48_000, start + 359 * DAY), Ok(0));
+    // At the cliff twelve monthly intervals unlock at once
+    assert_eq!(schedule.vested_amount(48_000, start + 360 * DAY), Ok(12_000));
+    // Partial intervals do not count
+    assert_eq!(schedule.vested_amount(48_000, start + 389 * DAY), Ok(12_000));
+    assert_eq!(schedule.vested_amount(48_000, start + 390 * DAY), Ok(13_000));
+    assert_eq!(schedule.vested_amount(48_000, start + 2_000 * DAY), Ok(48_000));
+
+    let invalid = VestingSchedule { cliff_duration: 2_000 * DAY, ..schedule };
+    assert!(!invalid.is_valid());
+}
+
+#[test]
+fn revoked_grant_stops_vesting() {
+    let schedule = team_schedule();
+    let mut grant = beneficiary(schedule);
+    grant.revoked_at = Some(schedule.start_time + 400 * DAY);
+
+    let later = schedule.start_time + 1_000 * DAY;
+    assert_eq!(grant.vested_amount(later, &schedule), Ok(13_000));
+    assert_eq!(grant.calculate_released_amount(later, &schedule), Ok(13_000));
+}
+
+#[test]
+fn schedule_preview_lists_unlock_tranches() {
+    let mut advisor = beneficiary(VestingSchedule {
+        start_time: 1_700_000_000,
+        cliff_duration: 100 * DAY,
+        duration: 300 * DAY,
+        release_interval: 60 * DAY,
+    });
+    advisor.total_amount = 3_000;
+    let mut investor = beneficiary(team_schedule());
+    investor.schedule = None;
+    investor.total_amount = 1_000;
+
+    let vesting = VestingState {
+        is_initialized: true,
+        authority: Pubkey::new_unique(),
+        mint: Pubkey::new_unique(),
+        escrow: Pubkey::new_unique(),
+        total_tokens: 4_000,
+        total_allocated: 4_000,
+        total_released: 0,
+        start_time: 1_700_000_000,
+        release_interval: 30 * DAY,
+        num_releases: 4,
+        num_beneficiaries: 2,
+        crank_tip_lamports: 0,
+        receipts_transferable: false,
+        pause: VestingPause::default(),
+    };
+    let shared = vesting.shared_schedule();
+    let start = vesting.start_time;
+
+    // Shared schedule: four equal monthly releases
+    assert_eq!(investor.schedule_preview(&shared), Ok(vec![
+        (start + 30 * DAY, 250),
+        (start + 60 * DAY, 250),
+        (start + 90 * DAY, 250),
+        (start + 120 * DAY, 250),
+    ]));
+
+    // Custom schedule: the cliff falls between intervals and unlocks the first
+    // whole interval; the tranches add up to the full grant
+    assert_eq!(advisor.schedule_preview(&shared), Ok(vec![
+        (start + 100 * DAY, 600),
+        (start + 120 * DAY, 600),
+        (start + 180 * DAY, 600),
+        (start + 240 * DAY, 600),
+        (start + 300 * DAY, 600),
+    ]));
+
+    // Grants vest from their own account, so the vesting state keeps a fixed size
+    let paused = VestingPause { paused_at: Some(start), paused_duration: 0 };
+    let largest_state = VestingState { pause: paused, ..vesting };
+    assert_eq!(borsh::to_vec(&largest_state).unwrap().len(), VestingState::get_size());
+    let largest_grant = VestingGrant {
+        revoked_at: Some(start),
+        pending_beneficiary: Some(Pubkey::new_unique()),
+        receipt_mint: Some(Pubkey::new_unique()),
+        pause: paused,
+        ..advisor
+    };
+    assert_eq!(borsh::to_vec(&largest_grant).unwrap().len(), VestingGrant::get_size());
+}
+
+#[test]
+fn pauses_shift_later_unlocks() {
+    let schedule = team_schedule();
+    let start = schedule.start_time;
+    let mut grant = beneficiary(schedule);
+    let mut vesting_pause = VestingPause::default();
+
+    // Pause the whole account for 50 days, 10 days before the cliff
+    vesting_pause.paused_at = Some(start + 350 * DAY);
+    assert_eq!(vesting_pause.accrual_time(start + 380 * DAY), start + 350 * DAY);
+    assert_eq!(vesting_pause.resume(start + 400 * DAY), Some(50 * DAY));
+    assert_eq!(vesting_pause.accrual_time(start + 405 * DAY), start + 355 * DAY);
+    assert_eq!(vesting_pause.resume(start + 405 * DAY), None);
+
+    // A grant pause is timed on the account's clock: 30 more days
+    let pool_time = vesting_pause.accrual_time(start + 405 * DAY);
+    grant.pause.paused_at = Some(pool_time);
+    grant.pause.resume(pool_time + 30 * DAY);
+
+    // The cliff now lands 80 days late for this grant
+    let accrual = |time: i64| grant.pause.accrual_time(vesting_pause.accrual_time(time));
+    assert_eq!(grant.vested_amount(accrual(start + 439 * DAY), &schedule), Ok(0));
+    assert_eq!(grant.vested_amount(accrual(start + 440 * DAY), &schedule), Ok(12_000));
+}
+
+#[test]
+fn claimable_amount_excludes_released_tokens() {
+    let mut grant = beneficiary(team_schedule());
+    let vesting = monthly_pool(48_000, 1);
+    let start = team_schedule().start_time;
+
+    assert_eq!(vesting.claimable_at(start + 390 * DAY, &grant), Ok(13_000));
+    grant.released_amount = 12_000;
+    assert_eq!(vesting.claimable_at(start + 390 * DAY, &grant), Ok(1_000));
+
+    // Paused accounts stop accruing, but vested tokens stay claimable
+    let paused = VestingState {
+        pause: VestingPause { paused_at: Some(start + 390 * DAY), paused_duration: 0 },
+        ..vesting
+    };
+    assert_eq!(paused.claimable_at(start + 500 * DAY, &grant), Ok(1_000));
+}
+
+#[test]
+fn interleaved_releases_are_tracked_per_grant() {
+    // Three grants on the shared schedule: four monthly releases
+    let mut vesting = monthly_pool(9_000, 4);
+    let month = |n: i64| vesting.start_time + n * 30 * DAY;
+    let (t1, t2, t3, t4) = (month(1), month(2), month(3), month(4));
+    let mut grants: Vec<VestingGrant> = [4_000, 4_000, 1_000]
+        .into_iter()
+        .map(|amount| {
+            let mut grant = beneficiary(team_schedule());
+            grant.schedule = None;
+            grant.total_amount = amount;
+            grant
+        })
+        .collect();
+
+    // Month 1: only the first beneficiary releases
+    let claimable = vesting.claimable_at(t1, &grants[0]).unwrap();
+    assert_eq!(claimable, 1_000);
+    vesting.record_release(&mut grants[0], claimable, t1).unwrap();
+
+    // Month 2: the second beneficiary gets both months despite the earlier release
+    // for someone else; the first only gets the new month
+    assert_eq!(vesting.claimable_at(t2, &grants[1]), Ok(2_000));
+    vesting.record_release(&mut grants[1], 2_000, t2).unwrap();
+    assert_eq!(vesting.claimable_at(t2, &grants[0]), Ok(1_000));
+    vesting.record_release(&mut grants[0], 400, t2).unwrap();
+
+    // Releasing twice at the same time gives nothing more than what is left
+    assert_eq!(vesting.claimable_at(t2, &grants[0]), Ok(600));
+    assert!(vesting.record_release(&mut grants[0], 601, t2).is_err());
+    assert_eq!(vesting.claimable_at(t2, &grants[1]), Ok(0));
+
+    // Month 3: the third beneficiary releases for the first time
+    assert_eq!(vesting.claimable_at(t3, &grants[2]), Ok(750));
+    vesting.record_release(&mut grants[2], 750, t3).unwrap();
+
+    // After the schedule ends everyone drains exactly their grant
+    for grant in grants.iter_mut() {
+        let claimable = vesting.claimable_at(t4, grant).unwrap();
+        vesting.record_release(grant, claimable, t4).unwrap();
+        assert_eq!(grant.released_amount, grant.total_amount);
+        assert_eq!(grant.last_release_time, t4);
+    }
+    assert_eq!(vesting.total_released, 9_000);
+    assert_eq!(vesting.outstanding_allocation(), Some(0));
+}
+
+#[test]
+fn third_party_cranks_release_everything_for_a_single_tip() {
+    const TIP: u64 = 5_000;
+    let program_id = vcoin_program::id();
+    let vesting_key = Pubkey::new_unique();
+    let vesting = VestingState { crank_tip_lamports: TIP, ..monthly_pool(48_000, 4) };
+    let mut grant = beneficiary(team_schedule());
+    grant.schedule = None;
+    grant.vesting = vesting_key;
+    let (grant_key, _) = VestingGrant::find_address(&program_id, &vesting_key, &grant.beneficiary);
+    let (escrow_authority, _) = VestingState::find_escrow_authority(&program_id, &vesting_key);
+    let cranker = Pubkey::new_unique();
+    let mut accounts = vec![
+        TestAccount::signer(cranker),
+        TestAccount::program(vesting_key, &vesting, borsh::to_vec(&vesting).unwrap().len()),
+        TestAccount::program(grant_key, &grant, borsh::to_vec(&grant).unwrap().len()),
+        TestAccount::mint(vesting.mint, 6),
+        TestAccount::token(grant.destination, vesting.mint, grant.beneficiary, 0),
+        TestAccount::new(spl_token::ID, Pubkey::default(), Vec::new()).readonly(),
+        TestAccount::token(vesting.escrow, vesting.mint, escrow_authority, 48_000),
+        TestAccount::new(escrow_authority, solana_program::system_program::ID, Vec::new()).readonly(),
+        TestAccount::emergency_state(&vesting.mint, EmergencyMode::Normal),
+    ];
+    // Two of four monthly releases have vested
+    common::set_time(vesting.start_time + 60 * DAY);
+    let release_one = VCoinInstruction::ReleaseVestedTokens { beneficiary: grant.beneficiary, max_amount: Some(1) };
+
+    // A third party asking for one base unit releases all that has vested, once tipped
+    let cranker_lamports = accounts[0].lamports;
+    let outcome = process(&mut accounts, &release_one);
+    assert_eq!(outcome.result, Ok(()));
+    assert_eq!(outcome.invocations[0].transferred(), Some(24_000));
+    assert_eq!(accounts[0].lamports, cranker_lamports + TIP);
+
+    // Cranking again finds nothing to release and earns nothing
+    let outcome = process(&mut accounts, &release_one);
+    assert_eq!(outcome.result, Ok(()));
+    assert!(outcome.invocations.is_empty());
+    assert_eq!(accounts[0].lamports, cranker_lamports + TIP);
+
+    // The beneficiary may still release part of the next tranche, untipped
+    common::set_time(vesting.start_time + 90 * DAY);
+    accounts[0] = TestAccount::signer(grant.beneficiary);
+    let beneficiary_lamports = accounts[0].lamports;
+    let outcome = process(&mut accounts, &release_one);
+    assert_eq!(outcome.result, Ok(()));
+    assert_eq!(outcome.invocations[0].transferred(), Some(1));
+    assert_eq!(accounts[0].lamports, beneficiary_lamports);
+    assert_eq!(accounts[2].state::<VestingGrant>().released_amount, 24_001);
+}